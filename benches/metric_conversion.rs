@@ -1,5 +1,7 @@
 mod common;
 
+use std::hint::black_box;
+
 use common::{LARGE, MEDIUM, SMALL, TINY};
 use criterion::{Bencher, Criterion, criterion_group, criterion_main};
 use crop::Rope;
@@ -10,7 +12,7 @@ fn line_of_byte(c: &mut Criterion) {
         let r = Rope::from(s);
         let mut byte_offsets = (0..=r.byte_len()).cycle();
         bench.iter(|| {
-            let _ = r.line_of_byte(byte_offsets.next().unwrap());
+            black_box(r.line_of_byte(black_box(byte_offsets.next().unwrap())));
         });
     }
 
@@ -28,7 +30,7 @@ fn byte_of_line(c: &mut Criterion) {
         let r = Rope::from(s);
         let mut line_offsets = (0..=r.line_len()).cycle();
         bench.iter(|| {
-            let _ = r.byte_of_line(line_offsets.next().unwrap());
+            black_box(r.byte_of_line(black_box(line_offsets.next().unwrap())));
         });
     }
 
@@ -40,5 +42,33 @@ fn byte_of_line(c: &mut Criterion) {
     group.bench_function("large", |b| bench(b, LARGE));
 }
 
-criterion_group!(benches, byte_of_line, line_of_byte);
+/// Looks up lines at pseudo-random offsets in a ~100MB document, so that most
+/// lookups have to descend through nodes that are not in the CPU cache.
+fn byte_of_line_long_document(c: &mut Criterion) {
+    let r = Rope::from(LARGE.repeat(64));
+
+    let line_len = r.line_len();
+
+    // A simple LCG is enough to scatter the lookups across the whole rope.
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+
+    let mut group = c.benchmark_group("byte_of_line_long_document");
+
+    group.bench_function("random", |b| {
+        b.iter(|| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let line = (state >> 33) as usize % line_len;
+            black_box(r.byte_of_line(black_box(line)));
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    byte_of_line,
+    byte_of_line_long_document,
+    line_of_byte
+);
 criterion_main!(benches);
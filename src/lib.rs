@@ -7,12 +7,22 @@
 //! complexity of inserting, deleting or replacing a piece of text is always
 //! logarithmic in the size of the `Rope`.
 //!
-//! The crate has a relatively straightforward API. There are 3 structs to be
+//! The crate has a relatively straightforward API. There are 10 structs to be
 //! aware of:
 //!
 //! - [`Rope`]: the star of the crate;
 //! - [`RopeSlice`]: an immutable slice of a `Rope`;
-//! - [`RopeBuilder`]: an incremental `Rope` builder.
+//! - [`RopeBuilder`]: an incremental `Rope` builder;
+//! - [`RopeChain`]: a read-only view over several `Rope`s and `RopeSlice`s
+//!   concatenated together;
+//! - [`WeakRope`]: a weak handle to a `Rope` that doesn't keep it alive;
+//! - [`RopeSnapshot`]: an immutable snapshot of a `Rope` that can be handed to
+//!   other threads;
+//! - [`FrozenRope`]: a packed, read-only snapshot of a `Rope`;
+//! - [`ByteRope`] and [`ByteRopeSlice`]: a rope of arbitrary bytes that don't
+//!   have to be valid UTF-8, and an immutable slice of it;
+//! - [`History`]: a `Rope` that records its edits so that they can be undone
+//!   and redone.
 //!
 //! plus the [`iter`] module which contains iterators over `Rope`s and
 //! `RopeSlice`s. That's it.
@@ -134,10 +144,101 @@
 //!   on `Rope`s and `RopeSlice`s such as the
 //!   [`Graphemes`](crate::iter::Graphemes) iterator and others;
 //!
+//! - `char-metric` (disabled by default): makes the `Rope` and `RopeSlice`
+//!   track the number of `char`s they contain, allowing them to efficiently
+//!   convert char offsets to and from byte offsets in logarithmic time;
+//!
 //! - `utf16-metric` (disabled by default): makes the `Rope` and `RopeSlice`
 //!   track the UTF-16 code units they'd have if their content was stored as
 //!   UTF-16 instead of UTF-8, allowing them to efficiently convert UTF-16
-//!   code unit offsets to and from byte offsets in logarithmic time.
+//!   code unit offsets to and from byte offsets in logarithmic time;
+//!
+//! - `pool` (disabled by default): recycles the buffers of freed leaves via a
+//!   thread-local free list, reducing allocator traffic during heavy editing.
+//!   See the [`pool`](crate::pool) module for more infos;
+//!
+//! - `display-width` (disabled by default): enables APIs that measure text in
+//!   terminal columns, like the [`WrapPoints`](crate::iter::WrapPoints)
+//!   iterator used to soft-wrap lines;
+//!
+//! - `width-metric` (disabled by default): makes the `Rope` and `RopeSlice`
+//!   track the number of terminal columns their content takes up, allowing
+//!   them to efficiently convert column offsets to and from byte offsets in
+//!   logarithmic time. Implies `display-width`;
+//!
+//! - `regex` (disabled by default): enables regex search over `Rope`s and
+//!   `RopeSlice`s via the [`RegexFindIter`](crate::iter::RegexFindIter)
+//!   iterator, which runs a lazy DFA from the `regex-automata` crate directly
+//!   over their chunks. The regex type is re-exported as `crop::Regex`, and
+//!   `&Regex`es can be used as a [`Pattern`](crate::pattern::Pattern).
+//!
+//! - `rayon` (disabled by default): enables the
+//!   [`ParChunks`](crate::iter::ParChunks) and
+//!   [`ParLines`](crate::iter::ParLines) parallel iterators, which split the
+//!   work between threads using the `rayon` crate;
+//!
+//! - `debug-viz` (disabled by default): enables
+//!   [`Rope::to_dot()`](crate::Rope::to_dot()), which exports the B-tree
+//!   backing a `Rope` to the Graphviz DOT language for debugging;
+//!
+//! - `validate` (disabled by default): enables
+//!   [`Rope::validate()`](crate::Rope::validate()) and
+//!   [`RopeSlice::validate()`](crate::RopeSlice::validate()), which check the
+//!   internal invariants of the B-tree and panic if they're broken. Useful
+//!   in fuzzers and property tests of downstream crates;
+//!
+//! - `arbitrary` (disabled by default): implements
+//!   [`Arbitrary`](::arbitrary::Arbitrary) for `Rope` and for the
+//!   [`EditOp`](crate::arbitrary::EditOp) enum, to generate structured
+//!   inputs in fuzz targets;
+//!
+//! - `proptest` (disabled by default): enables the [`proptest`] module,
+//!   which contains strategies generating `Rope`s with varied chunk layouts
+//!   for property tests;
+//!
+//! - `quickcheck` (disabled by default): implements `Arbitrary` from the
+//!   `quickcheck` crate for `Rope`, with shrinking towards shorter text and
+//!   simpler chunk layouts;
+//!
+//! - `rkyv` (disabled by default): implements `Archive`, `Serialize` and
+//!   `Deserialize` from the `rkyv` crate for `Rope`. The archived text is an
+//!   [`ArchivedRope`](crate::ArchivedRope) whose chunks can be read directly
+//!   from the archive;
+//!
+//! - `ropey-compat` (disabled by default): enables the
+//!   [`compat::ropey`](crate::compat::ropey) module, which wraps crop's
+//!   `Rope` in a type exposing `ropey`'s method names to ease migrating from
+//!   it. Implies `char-metric`;
+//!
+//! - `encoding` (disabled by default): enables
+//!   [`Rope::from_bytes_with_encoding()`](crate::Rope::from_bytes_with_encoding()),
+//!   which decodes UTF-16 (with byte order mark detection), Latin-1 and
+//!   Windows-1252 text directly into a `Rope`. See the
+//!   [`encoding`](crate::encoding) module for more infos;
+//!
+//! - `unicode-normalization` (disabled by default): enables
+//!   [`Rope::is_nfc()`](crate::Rope::is_nfc()),
+//!   [`Rope::to_nfc()`](crate::Rope::to_nfc()) and
+//!   [`Rope::to_nfd()`](crate::Rope::to_nfd()) (and their `RopeSlice`
+//!   counterparts), which check and convert the Unicode normalization form
+//!   of the text using the `unicode-normalization` crate;
+//!
+//! - `max-line-len` (disabled by default): makes every node of the B-tree
+//!   track the length of the longest line it contains, allowing
+//!   [`Rope::max_line_len()`](crate::Rope::max_line_len()) to run in
+//!   constant time and
+//!   [`RopeSlice::max_line_len()`](crate::RopeSlice::max_line_len()) in
+//!   logarithmic time instead of scanning every line;
+//!
+//! - `word-count` (disabled by default): makes every node of the B-tree
+//!   track the number of whitespace-delimited words it contains, allowing
+//!   [`Rope::word_len()`](crate::Rope::word_len()) to run in constant time
+//!   and [`RopeSlice::word_len()`](crate::RopeSlice::word_len()) in
+//!   logarithmic time.
+//!
+//! Both of the last two features make edits slower: every leaf touched by an
+//! edit is rescanned to recompute what it tracks, and every internal node on
+//! the path from it to the root recombines the data of all its children.
 
 #![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![allow(clippy::explicit_auto_deref)]
@@ -152,6 +253,60 @@
 
 extern crate alloc;
 
+#[cfg_attr(docsrs, doc(cfg(feature = "arbitrary")))]
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary {
+    //! Support for generating [`Rope`](crate::Rope)s and edits to them with
+    //! the [`arbitrary`](https://docs.rs/arbitrary) crate.
+    //!
+    //! This is mostly useful to write structure-aware fuzz targets with
+    //! `cargo fuzz`: the `Rope`s are built by a sequence of insertions
+    //! instead of from a single string, and the [`EditOp`]s can be applied
+    //! to any `Rope` without having to check their offsets first.
+
+    pub use crate::rope::arbitrary::*;
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "ropey-compat")))]
+#[cfg(feature = "ropey-compat")]
+pub mod compat {
+    //! Compatibility layers exposing the APIs of other rope crates.
+
+    pub mod ropey {
+        //! A [`Rope`] and [`RopeSlice`] with the method names of the
+        //! [`ropey`](https://docs.rs/ropey/1) crate, implemented on top of
+        //! crop's own [`Rope`](crate::Rope).
+        //!
+        //! Code written against ropey can usually be ported by changing its
+        //! imports to this module, and then moved over to crop's API
+        //! gradually via [`Rope::as_crop()`] and [`Rope::into_crop()`].
+        //!
+        //! Only the most commonly used methods are provided, and lines are
+        //! only broken on `'\n'` (and `"\r\n"`), while ropey also recognizes
+        //! the other Unicode line breaks by default.
+
+        pub use crate::rope::ropey_compat::*;
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "encoding")))]
+#[cfg(feature = "encoding")]
+pub mod encoding {
+    //! Decoding [`Rope`](crate::Rope)s from text encodings other than UTF-8.
+    //!
+    //! Files created on Windows are often saved as UTF-16, and a byte order
+    //! mark at their start tells which byte order their code units are
+    //! stored in. [`Encoding::detect()`] reads it, and
+    //! [`Rope::from_bytes_with_encoding()`](crate::Rope::from_bytes_with_encoding())
+    //! then transcodes the rest of the bytes to UTF-8.
+    //!
+    //! Legacy single-byte encodings like [Latin-1](Encoding::Latin1) and
+    //! [Windows-1252](Encoding::Windows1252) can also be decoded. They don't
+    //! have a byte order mark, and every byte sequence is valid in them.
+
+    pub use crate::rope::encoding::*;
+}
+
 pub mod iter {
     //! Iterators over [`Rope`](crate::Rope)s and
     //! [`RopeSlice`](crate::RopeSlice)s.
@@ -159,20 +314,104 @@ pub mod iter {
     pub use crate::rope::iterators::*;
 }
 
+pub mod pattern {
+    //! Patterns searched for in [`Rope`](crate::Rope)s and
+    //! [`RopeSlice`](crate::RopeSlice)s.
+    //!
+    //! Every search method, from `find()` and `find_iter()` to `split()`,
+    //! `trim_matches()` and `replace_all()`, accepts a [`Pattern`], which can
+    //! be a `char`, a string, a set of `char`s, a `char` predicate or, with
+    //! the `regex` feature, a `&Regex`.
+
+    pub use crate::rope::pattern::*;
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "pool")))]
+#[cfg(feature = "pool")]
+pub mod pool {
+    //! A thread-local pool of leaf buffers.
+    //!
+    //! The text of a [`Rope`](crate::Rope) is stored in fixed-size buffers,
+    //! one per leaf of its B-tree. Heavy editing sessions can allocate and
+    //! free many of them, so when the `pool` feature is enabled the freed
+    //! buffers are kept in bounded, per-thread free lists, one for each buffer
+    //! size, and reused the next time a leaf of the same size needs to be
+    //! allocated.
+    //!
+    //! The pool is shared by all the ropes on the same thread. Buffers freed
+    //! on a thread are always given back to that thread's pool, regardless of
+    //! which thread allocated them.
+    //!
+    //! Only the text buffers of leaves are pooled. The internal nodes of the
+    //! tree and the reference-counted allocations wrapping every node are
+    //! still allocated and freed through the global allocator.
+
+    pub use crate::rope::pool::*;
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+#[cfg(feature = "proptest")]
+pub mod proptest {
+    //! [`proptest`](https://docs.rs/proptest) strategies generating
+    //! [`Rope`](crate::Rope)s.
+    //!
+    //! A `Rope` built from a single string always has its chunks packed as
+    //! tightly as possible and all its gaps at the end of their chunks. The
+    //! strategies in this module build `Rope`s through a sequence of edits
+    //! instead, so that property tests also exercise the chunk layouts
+    //! produced by real editing sessions.
+
+    pub use crate::rope::proptest::*;
+}
+
 mod rope;
 
 #[doc(hidden)]
 pub mod tree;
 
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+#[cfg(feature = "regex")]
+pub use regex_automata::hybrid::regex::Regex;
+#[cfg_attr(docsrs, doc(cfg(feature = "rkyv")))]
+#[cfg(feature = "rkyv")]
+pub use rope::ArchivedRope;
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use rope::RopeReader;
+pub use rope::metrics::ChunkSummary;
+pub use rope::{
+    ByteRope,
+    ByteRopeSlice,
+    Change,
+    Conflict,
+    Cursor,
+    Delta,
+    Edit,
+    Error,
+    FrozenRope,
+    Gravity,
+    History,
+    IntervalMap,
+    LineEnding,
+    LineEndings,
+    MarkerId,
+    MarkerSet,
+    MemUsage,
+    Position,
+    PositionEncoding,
+    Rope,
+    RopeBuilder,
+    RopeChain,
+    RopeSlice,
+    RopeSnapshot,
+    TreeStats,
+    Utf8Error,
+    WeakRope,
+};
 // These are not part of the public API, we only export them to be able to run
 // doctests.
-pub use rope::{Rope, RopeBuilder, RopeSlice};
 #[doc(hidden)]
-pub use rope::{
-    gap_buffer::GapBuffer,
-    gap_slice::GapSlice,
-    metrics::ChunkSummary,
-};
+pub use rope::{gap_buffer::GapBuffer, gap_slice::GapSlice};
 
 #[inline]
 pub(crate) fn range_bounds_to_start_end<T, B>(
@@ -0,0 +1,258 @@
+//! This module exports the [`ChunkAggregate`] struct, which the leaves of a
+//! [`Rope`](crate::Rope) store next to their [`ChunkSummary`].
+//!
+//! [`ChunkSummary`]: super::metrics::ChunkSummary
+
+use crate::tree::Aggregate;
+
+/// Data about a piece of text that, unlike the fields of a
+/// [`ChunkSummary`](super::metrics::ChunkSummary), can't be subtracted out
+/// of the data of a larger piece of text containing it.
+///
+/// Each field is gated behind the feature that needs it, so with none of
+/// them enabled this is a zero-sized type and the nodes of the B-tree don't
+/// pay for it.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct ChunkAggregate {
+    #[cfg(feature = "max-line-len")]
+    pub(super) line_lengths: LineLengths,
+    #[cfg(feature = "word-count")]
+    pub(super) word_count: WordCount,
+}
+
+impl From<&str> for ChunkAggregate {
+    #[cfg_attr(
+        not(any(feature = "max-line-len", feature = "word-count")),
+        allow(unused_variables)
+    )]
+    #[inline]
+    fn from(s: &str) -> Self {
+        Self {
+            #[cfg(feature = "max-line-len")]
+            line_lengths: LineLengths::from(s),
+            #[cfg(feature = "word-count")]
+            word_count: WordCount::from(s),
+        }
+    }
+}
+
+impl Aggregate for ChunkAggregate {
+    #[cfg_attr(
+        not(any(feature = "max-line-len", feature = "word-count")),
+        allow(unused_variables)
+    )]
+    #[inline]
+    fn append(&mut self, next: &Self) {
+        #[cfg(feature = "max-line-len")]
+        self.line_lengths.append(&next.line_lengths);
+        #[cfg(feature = "word-count")]
+        self.word_count.append(&next.word_count);
+    }
+}
+
+/// The lengths of the lines of a piece of text, stored so that the length of
+/// the longest line can be computed by only looking at the text's first and
+/// last line when two pieces of text are concatenated.
+///
+/// All the lengths are in bytes and, like the lines yielded by
+/// [`Rope::lines()`](crate::Rope::lines()), don't include the `"\n"` or
+/// `"\r\n"` terminating them.
+#[cfg(feature = "max-line-len")]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub(super) struct LineLengths {
+    /// The number of bytes before the first line feed, or the length of the
+    /// text if it doesn't contain any. This includes the carriage return
+    /// before the line feed, if there is one.
+    first: usize,
+
+    /// The number of bytes after the last line feed, or the length of the
+    /// text if it doesn't contain any.
+    last: usize,
+
+    /// The length of the longest line that is both started and terminated by
+    /// a line feed in the text.
+    longest_inner: usize,
+
+    /// Whether the text contains at least one line feed.
+    has_line_break: bool,
+
+    /// Whether the first line feed is preceded by a carriage return in the
+    /// text.
+    first_ends_in_cr: bool,
+
+    /// Whether the last byte of the text is a carriage return.
+    ends_in_cr: bool,
+}
+
+#[cfg(feature = "max-line-len")]
+impl From<&str> for LineLengths {
+    #[inline]
+    fn from(s: &str) -> Self {
+        let ends_in_cr = s.ends_with('\r');
+
+        let Some(first_lf) = s.find('\n') else {
+            return Self {
+                first: s.len(),
+                last: s.len(),
+                ends_in_cr,
+                ..Self::default()
+            };
+        };
+
+        let last_lf = s.rfind('\n').unwrap_or(first_lf);
+
+        let longest_inner = s[first_lf + 1..last_lf.max(first_lf + 1)]
+            .split('\n')
+            .map(|line| line.len() - line.ends_with('\r') as usize)
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            first: first_lf,
+            last: s.len() - last_lf - 1,
+            longest_inner,
+            has_line_break: true,
+            first_ends_in_cr: s[..first_lf].ends_with('\r'),
+            ends_in_cr,
+        }
+    }
+}
+
+#[cfg(feature = "max-line-len")]
+impl LineLengths {
+    #[inline]
+    fn append(&mut self, next: &Self) {
+        if next.is_empty() {
+            return;
+        }
+
+        if self.is_empty() {
+            *self = *next;
+            return;
+        }
+
+        // Whether the first line feed of `next` is preceded by a carriage
+        // return, which can be the last byte of `self` if `next` starts with
+        // the line feed.
+        let next_first_ends_in_cr = if next.first > 0 {
+            next.first_ends_in_cr
+        } else {
+            self.ends_in_cr
+        };
+
+        match (self.has_line_break, next.has_line_break) {
+            (false, false) => {
+                self.first += next.first;
+                self.last = self.first;
+            },
+
+            (false, true) => {
+                self.first += next.first;
+                self.first_ends_in_cr = next_first_ends_in_cr;
+                self.last = next.last;
+                self.longest_inner = next.longest_inner;
+                self.has_line_break = true;
+            },
+
+            (true, false) => {
+                self.last += next.first;
+            },
+
+            (true, true) => {
+                // The line between the last line feed of `self` and the
+                // first one of `next`.
+                let middle =
+                    self.last + next.first - next_first_ends_in_cr as usize;
+
+                self.longest_inner =
+                    self.longest_inner.max(next.longest_inner).max(middle);
+
+                self.last = next.last;
+            },
+        }
+
+        self.ends_in_cr = next.ends_in_cr;
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        !self.has_line_break && self.first == 0
+    }
+
+    /// Returns the length of the longest line in the text.
+    #[inline]
+    pub(super) fn max_line_len(&self) -> usize {
+        if !self.has_line_break {
+            return self.first;
+        }
+
+        (self.first - self.first_ends_in_cr as usize)
+            .max(self.longest_inner)
+            .max(self.last)
+    }
+}
+
+/// The number of whitespace-delimited words in a piece of text, i.e. the
+/// number of items yielded by [`str::split_whitespace()`].
+///
+/// A word can span the boundary between two pieces of text, so we also need
+/// to know whether the text starts and ends in the middle of a word to avoid
+/// counting it twice when the two are concatenated.
+#[cfg(feature = "word-count")]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub(super) struct WordCount {
+    /// The number of words in the text, including the ones cut by its start
+    /// or its end.
+    words: usize,
+
+    /// Whether the first char of the text isn't whitespace.
+    starts_in_word: bool,
+
+    /// Whether the last char of the text isn't whitespace.
+    ends_in_word: bool,
+
+    /// Whether the text contains at least one char.
+    has_chars: bool,
+}
+
+#[cfg(feature = "word-count")]
+impl From<&str> for WordCount {
+    #[inline]
+    fn from(s: &str) -> Self {
+        let is_word_char = |ch: char| !ch.is_whitespace();
+
+        Self {
+            words: s.split_whitespace().count(),
+            starts_in_word: s.chars().next().is_some_and(is_word_char),
+            ends_in_word: s.chars().next_back().is_some_and(is_word_char),
+            has_chars: !s.is_empty(),
+        }
+    }
+}
+
+#[cfg(feature = "word-count")]
+impl WordCount {
+    #[inline]
+    fn append(&mut self, next: &Self) {
+        if !next.has_chars {
+            return;
+        }
+
+        if !self.has_chars {
+            *self = *next;
+            return;
+        }
+
+        // A word spanning the boundary was counted once on each side.
+        let spanning = self.ends_in_word && next.starts_in_word;
+
+        self.words += next.words - spanning as usize;
+        self.ends_in_word = next.ends_in_word;
+    }
+
+    /// Returns the number of words in the text.
+    #[inline]
+    pub(super) fn words(&self) -> usize {
+        self.words
+    }
+}
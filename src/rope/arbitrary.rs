@@ -0,0 +1,138 @@
+use alloc::string::String;
+use core::ops::Range;
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::Rope;
+use crate::Change;
+
+/// An edit to a [`Rope`] which can be generated by
+/// [`Arbitrary`](::arbitrary::Arbitrary).
+///
+/// The byte offsets of an arbitrary `EditOp` aren't related to any
+/// particular `Rope`, so instead of panicking when they're out of bounds or
+/// not on a code point boundary [`apply()`](Self::apply()) first wraps them
+/// around the length of the `Rope` and then moves them to the next code point
+/// boundary. Every `EditOp` can therefore be applied to every `Rope`.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::Rope;
+/// # use crop::arbitrary::EditOp;
+/// #
+/// let mut r = Rope::from("Hello World!");
+///
+/// EditOp::Insert { byte_offset: 5, text: ",".into() }.apply(&mut r);
+/// EditOp::Delete { byte_range: 12..13 }.apply(&mut r);
+///
+/// // 20 is out of bounds, so it wraps around to 20 % (12 + 1) = 7.
+/// EditOp::Replace { byte_range: 20..12, text: "Earth".into() }.apply(&mut r);
+///
+/// assert_eq!(r, "Hello, Earth");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EditOp {
+    /// Inserts the text at the given byte offset.
+    Insert {
+        /// Where to insert the text.
+        byte_offset: usize,
+
+        /// The text to insert.
+        text: String,
+    },
+
+    /// Deletes the text in the given byte range.
+    Delete {
+        /// The range to delete. Its start and end are swapped if the former
+        /// is greater than the latter.
+        byte_range: Range<usize>,
+    },
+
+    /// Replaces the text in the given byte range with some other text.
+    Replace {
+        /// The range to replace. Its start and end are swapped if the former
+        /// is greater than the latter.
+        byte_range: Range<usize>,
+
+        /// The replacement text.
+        text: String,
+    },
+}
+
+impl EditOp {
+    /// Applies the edit to the `Rope`, after adjusting its byte offsets to
+    /// always fall on code point boundaries within the `Rope`.
+    #[inline]
+    pub fn apply(&self, rope: &mut Rope) -> Change {
+        match self {
+            Self::Insert { byte_offset, text } => {
+                let byte_offset = adjust_offset(rope, *byte_offset);
+                rope.insert(byte_offset, text)
+            },
+
+            Self::Delete { byte_range } => {
+                rope.delete(adjust_range(rope, byte_range.clone()))
+            },
+
+            Self::Replace { byte_range, text } => {
+                rope.replace(adjust_range(rope, byte_range.clone()), text)
+            },
+        }
+    }
+}
+
+/// Wraps the byte offset around the length of the `Rope` and moves it to the
+/// next code point boundary.
+#[inline]
+fn adjust_offset(rope: &Rope, byte_offset: usize) -> usize {
+    let mut byte_offset = byte_offset % (rope.byte_len() + 1);
+
+    while !rope.is_char_boundary(byte_offset) {
+        byte_offset += 1;
+    }
+
+    byte_offset
+}
+
+#[inline]
+fn adjust_range(rope: &Rope, byte_range: Range<usize>) -> Range<usize> {
+    let start = adjust_offset(rope, byte_range.start);
+    let end = adjust_offset(rope, byte_range.end);
+    start.min(end)..start.max(end)
+}
+
+impl<'a> Arbitrary<'a> for EditOp {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.choose_index(3)? {
+            0 => Self::Insert {
+                byte_offset: u.arbitrary()?,
+                text: u.arbitrary()?,
+            },
+            1 => Self::Delete { byte_range: u.arbitrary()? },
+            _ => Self::Replace {
+                byte_range: u.arbitrary()?,
+                text: u.arbitrary()?,
+            },
+        })
+    }
+}
+
+/// An arbitrary `Rope` is built by inserting a sequence of arbitrary strings
+/// at arbitrary offsets, so that its chunks aren't all packed to the brim
+/// and their gaps are scattered around instead of being all at the end.
+impl<'a> Arbitrary<'a> for Rope {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut rope = Rope::new();
+
+        for insertion in u.arbitrary_iter::<(usize, &'a str)>()? {
+            let (byte_offset, text) = insertion?;
+            let byte_offset = adjust_offset(&rope, byte_offset);
+            rope.insert(byte_offset, text);
+        }
+
+        Ok(rope)
+    }
+}
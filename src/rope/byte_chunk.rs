@@ -0,0 +1,334 @@
+use alloc::vec::Vec;
+use core::ops::{Add, AddAssign, RangeBounds, Sub, SubAssign};
+
+use crate::range_bounds_to_start_end;
+use crate::tree::{
+    Aggregated,
+    AsSlice,
+    BalancedLeaf,
+    BaseMeasured,
+    Metric,
+    ReplaceableLeaf,
+    SlicingMetric,
+    Summarize,
+};
+
+#[cfg(any(test, feature = "small_chunks"))]
+pub(super) const BYTE_CHUNK_MAX_BYTES: usize = 4;
+
+#[cfg(fuzzing)]
+pub(super) const BYTE_CHUNK_MAX_BYTES: usize = 16;
+
+#[cfg(not(any(test, fuzzing, feature = "small_chunks")))]
+pub(super) const BYTE_CHUNK_MAX_BYTES: usize = 2048;
+
+/// A leaf of a [`ByteRope`](super::ByteRope).
+///
+/// Unlike the [`GapBuffer`](super::gap_buffer::GapBuffer)s used by `Rope`s,
+/// its bytes don't have to be valid UTF-8, and its only metric is their
+/// number.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub(super) struct ByteChunk {
+    bytes: Vec<u8>,
+}
+
+impl core::fmt::Debug for ByteChunk {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.bytes, f)
+    }
+}
+
+impl From<&[u8]> for ByteChunk {
+    #[inline]
+    fn from(bytes: &[u8]) -> Self {
+        let mut chunk = Self::with_capacity();
+        chunk.bytes.extend_from_slice(bytes);
+        chunk
+    }
+}
+
+impl ByteChunk {
+    /// The minimum number of bytes a chunk can hold without being
+    /// underfilled.
+    #[inline]
+    pub(super) const fn min_bytes() -> usize {
+        BYTE_CHUNK_MAX_BYTES / 4
+    }
+
+    #[inline]
+    fn with_capacity() -> Self {
+        Self { bytes: Vec::with_capacity(BYTE_CHUNK_MAX_BYTES) }
+    }
+}
+
+impl Summarize for ByteChunk {
+    type Summary = usize;
+
+    #[inline]
+    fn summarize(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+impl Summarize for &[u8] {
+    type Summary = usize;
+
+    #[inline]
+    fn summarize(&self) -> usize {
+        self.len()
+    }
+}
+
+impl BaseMeasured for ByteChunk {
+    type BaseMetric = RawByteMetric;
+}
+
+impl AsSlice for ByteChunk {
+    type Slice<'a> = &'a [u8];
+
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Aggregated for ByteChunk {
+    type Aggregate = ();
+
+    #[inline]
+    fn aggregate(_: &[u8]) {}
+}
+
+impl BalancedLeaf for ByteChunk {
+    #[inline]
+    fn is_underfilled(&self, &len: &usize) -> bool {
+        len < Self::min_bytes()
+    }
+
+    #[inline]
+    fn balance_leaves(
+        (left, left_len): (&mut Self, &mut usize),
+        (right, right_len): (&mut Self, &mut usize),
+    ) {
+        // The two leaves can be combined in a single chunk.
+        if left.bytes.len() + right.bytes.len() <= BYTE_CHUNK_MAX_BYTES {
+            left.bytes.append(&mut right.bytes);
+        }
+        // The left side is underfilled => take bytes from the right side.
+        else if left.bytes.len() < Self::min_bytes() {
+            let missing_left = Self::min_bytes() - left.bytes.len();
+            left.bytes.extend(right.bytes.drain(..missing_left));
+        }
+        // The right side is underfilled => take bytes from the left side.
+        else if right.bytes.len() < Self::min_bytes() {
+            let missing_right = Self::min_bytes() - right.bytes.len();
+            let split = left.bytes.len() - missing_right;
+            right.bytes.splice(..0, left.bytes.drain(split..));
+        }
+
+        *left_len = left.bytes.len();
+        *right_len = right.bytes.len();
+    }
+}
+
+impl ReplaceableLeaf<RawByteMetric> for ByteChunk {
+    type Replacement<'a> = &'a [u8];
+
+    type ExtraLeaves = alloc::vec::IntoIter<Self>;
+
+    #[inline]
+    fn replace<R>(
+        &mut self,
+        len: &mut usize,
+        range: R,
+        replacement: &[u8],
+    ) -> Option<Self::ExtraLeaves>
+    where
+        R: RangeBounds<RawByteMetric>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(range, 0, self.bytes.len());
+
+        debug_assert!(start <= end);
+        debug_assert!(end <= self.bytes.len());
+
+        if self.bytes.len() - (end - start) + replacement.len()
+            <= BYTE_CHUNK_MAX_BYTES
+        {
+            self.bytes.splice(start..end, replacement.iter().copied());
+            *len = self.bytes.len();
+            return None;
+        }
+
+        let rest = self.bytes.split_off(end);
+        self.bytes.truncate(start);
+
+        let mut segments = Segmenter::new([&self.bytes, replacement, &rest]);
+
+        let first = segments.next().unwrap();
+        let extras = segments.collect::<Vec<_>>();
+
+        *self = first;
+        *len = self.bytes.len();
+
+        Some(extras.into_iter())
+    }
+
+    #[inline]
+    fn remove_up_to(&mut self, len: &mut usize, up_to: RawByteMetric) {
+        self.bytes.drain(..up_to.0);
+        *len = self.bytes.len();
+    }
+}
+
+/// Segments the concatenation of a bunch of byte slices into [`ByteChunk`]s
+/// with at least [`ByteChunk::min_bytes()`] bytes.
+///
+/// The only exception is if the total is shorter than
+/// [`ByteChunk::min_bytes()`], in which case this will only yield a single
+/// chunk with all the bytes.
+pub(super) struct Segmenter<'a, const N: usize> {
+    segments: [&'a [u8]; N],
+    remaining: usize,
+}
+
+impl<'a, const N: usize> Segmenter<'a, N> {
+    #[inline]
+    pub(super) fn new(segments: [&'a [u8]; N]) -> Self {
+        let remaining = segments.iter().map(|s| s.len()).sum();
+        Self { segments, remaining }
+    }
+}
+
+impl<const N: usize> Iterator for Segmenter<'_, N> {
+    type Item = ByteChunk;
+
+    #[inline]
+    fn next(&mut self) -> Option<ByteChunk> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let min = ByteChunk::min_bytes();
+
+        let mut chunk_len = if self.remaining <= BYTE_CHUNK_MAX_BYTES {
+            self.remaining
+        } else if self.remaining - BYTE_CHUNK_MAX_BYTES >= min {
+            BYTE_CHUNK_MAX_BYTES
+        } else {
+            // Take `chunk_len` such that `remaining - chunk_len = min`.
+            self.remaining - min
+        };
+
+        self.remaining -= chunk_len;
+
+        let mut chunk = ByteChunk::with_capacity();
+
+        for segment in &mut self.segments {
+            let (left, right) = segment.split_at(chunk_len.min(segment.len()));
+            chunk.bytes.extend_from_slice(left);
+            chunk_len -= left.len();
+            *segment = right;
+            if chunk_len == 0 {
+                break;
+            }
+        }
+
+        Some(chunk)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) struct RawByteMetric(pub(super) usize);
+
+impl Add for RawByteMetric {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl Sub for RawByteMetric {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl AddAssign for RawByteMetric {
+    #[inline]
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0
+    }
+}
+
+impl SubAssign for RawByteMetric {
+    #[inline]
+    fn sub_assign(&mut self, other: Self) {
+        self.0 -= other.0
+    }
+}
+
+impl Add<usize> for RawByteMetric {
+    type Output = usize;
+
+    #[inline]
+    fn add(self, other: usize) -> usize {
+        self.0 + other
+    }
+}
+
+impl From<RawByteMetric> for usize {
+    #[inline]
+    fn from(RawByteMetric(n): RawByteMetric) -> usize {
+        n
+    }
+}
+
+impl Metric<usize> for RawByteMetric {
+    #[inline]
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    #[inline]
+    fn one() -> Self {
+        Self(1)
+    }
+
+    #[inline]
+    fn measure(&len: &usize) -> Self {
+        Self(len)
+    }
+}
+
+impl SlicingMetric<ByteChunk> for RawByteMetric {
+    #[inline]
+    fn slice_up_to<'a>(
+        chunk: &'a [u8],
+        Self(up_to): Self,
+        _: &usize,
+    ) -> (&'a [u8], usize)
+    where
+        'a: 'a,
+    {
+        (&chunk[..up_to], up_to)
+    }
+
+    #[inline]
+    fn slice_from<'a>(
+        chunk: &'a [u8],
+        Self(from): Self,
+        &len: &usize,
+    ) -> (&'a [u8], usize)
+    where
+        'a: 'a,
+    {
+        (&chunk[from..], len - from)
+    }
+}
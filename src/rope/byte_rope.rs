@@ -0,0 +1,461 @@
+use alloc::vec::Vec;
+use core::ops::RangeBounds;
+
+use super::byte_chunk::{ByteChunk, RawByteMetric, Segmenter};
+use super::iterators::{ByteRopeBytes, ByteRopeChunks};
+use super::utils::panic_messages as panic;
+use super::{ByteRopeSlice, Change, Rope};
+use crate::range_bounds_to_start_end;
+use crate::tree::Tree;
+
+/// A rope of arbitrary bytes.
+///
+/// A `ByteRope` is backed by the same B-tree as a [`Rope`], so inserting,
+/// deleting and slicing all run in O(log n), but its contents don't have to
+/// be valid UTF-8. This makes it suitable for hex editors, binary patching
+/// tools and other programs editing data that isn't text.
+///
+/// Since bytes don't have code point boundaries or lines, all the offsets
+/// taken by its methods are byte offsets, and any of them is valid as long
+/// as it's in bounds.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::ByteRope;
+/// #
+/// let mut r = ByteRope::from(&[0xde, 0xad, 0xbe, 0xef][..]);
+///
+/// r.insert(2, [0x00, 0xff]);
+/// assert_eq!(r, [0xde, 0xad, 0x00, 0xff, 0xbe, 0xef]);
+///
+/// r.delete(..2);
+/// assert_eq!(r.byte_slice(2..), [0xbe, 0xef]);
+/// ```
+#[derive(Clone, Default)]
+pub struct ByteRope {
+    pub(super) tree: Tree<{ Rope::arity() }, ByteChunk>,
+}
+
+impl ByteRope {
+    #[doc(hidden)]
+    pub fn assert_invariants(&self) {
+        self.tree.assert_invariants();
+
+        let leaves = self.tree.leaves();
+
+        if leaves.len() == 1 {
+            return;
+        }
+
+        for chunk in leaves {
+            assert!(
+                chunk.len() >= ByteChunk::min_bytes(),
+                "The chunk {:?} was supposed to contain at least {} bytes \
+                 but actually contains {}",
+                chunk,
+                ByteChunk::min_bytes(),
+                chunk.len()
+            );
+        }
+    }
+
+    /// Returns the byte at `byte_index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds (i.e. greater than or equal to
+    /// [`len()`](Self::len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&[1, 2, 3][..]);
+    ///
+    /// assert_eq!(r.byte(1), 2);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte(&self, byte_index: usize) -> u8 {
+        if byte_index >= self.len() {
+            panic::byte_index_out_of_bounds(byte_index, self.len());
+        }
+
+        let (chunk, RawByteMetric(chunk_byte_offset)) =
+            self.tree.leaf_at_measure(RawByteMetric(byte_index + 1));
+
+        chunk[byte_index - chunk_byte_offset]
+    }
+
+    /// Returns an immutable slice of the `ByteRope` in the specified byte
+    /// range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`len()`](Self::len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&[1, 2, 3, 4][..]);
+    ///
+    /// assert_eq!(r.byte_slice(1..3), [2, 3]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte_slice<R>(&self, byte_range: R) -> ByteRopeSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.len() {
+            panic::byte_offset_out_of_bounds(end, self.len());
+        }
+
+        ByteRopeSlice {
+            tree_slice: self
+                .tree
+                .slice(RawByteMetric(start)..RawByteMetric(end)),
+        }
+    }
+
+    /// Returns an iterator over the bytes of this `ByteRope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&[1, 2, 3][..]);
+    ///
+    /// assert_eq!(r.bytes().rev().collect::<Vec<_>>(), [3, 2, 1]);
+    /// ```
+    #[inline]
+    pub fn bytes(&self) -> ByteRopeBytes<'_> {
+        ByteRopeBytes::new(self.chunks(), self.len())
+    }
+
+    /// Returns an iterator over the chunks of this `ByteRope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&[1, 2, 3][..]);
+    ///
+    /// let bytes = r.chunks().flatten().copied().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(bytes, [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn chunks(&self) -> ByteRopeChunks<'_> {
+        ByteRopeChunks { leaves: self.tree.leaves() }
+    }
+
+    /// Deletes the bytes in the specified byte range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`len()`](Self::len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let mut r = ByteRope::from(&[1, 2, 3, 4][..]);
+    ///
+    /// r.delete(1..3);
+    /// assert_eq!(r, [1, 4]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn delete<R>(&mut self, byte_range: R) -> Change
+    where
+        R: RangeBounds<usize>,
+    {
+        self.replace(byte_range, [])
+    }
+
+    /// Returns the byte at `byte_index`, or `None` if the index is out of
+    /// bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&[1, 2, 3][..]);
+    ///
+    /// assert_eq!(r.get_byte(2), Some(3));
+    /// assert_eq!(r.get_byte(3), None);
+    /// ```
+    #[inline]
+    pub fn get_byte(&self, byte_index: usize) -> Option<u8> {
+        (byte_index < self.len()).then(|| self.byte(byte_index))
+    }
+
+    /// Inserts `bytes` at `byte_offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`len()`](Self::len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let mut r = ByteRope::from(&[1, 4][..]);
+    ///
+    /// r.insert(1, [2, 3]);
+    /// assert_eq!(r, [1, 2, 3, 4]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn insert<T>(&mut self, byte_offset: usize, bytes: T) -> Change
+    where
+        T: AsRef<[u8]>,
+    {
+        self.replace(byte_offset..byte_offset, bytes)
+    }
+
+    /// Returns `true` if the `ByteRope` doesn't contain any bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let mut r = ByteRope::from(&[1, 2][..]);
+    /// assert!(!r.is_empty());
+    ///
+    /// r.delete(..);
+    /// assert!(r.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of bytes in the `ByteRope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let r = ByteRope::from(vec![0; 100]);
+    ///
+    /// assert_eq!(r.len(), 100);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        *self.tree.summary()
+    }
+
+    /// Creates a new, empty `ByteRope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let r = ByteRope::new();
+    ///
+    /// assert!(r.is_empty());
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the bytes in the specified byte range with `bytes`.
+    ///
+    /// Returns a [`Change`] describing the edit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`len()`](Self::len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let mut r = ByteRope::from(&[1, 2, 3][..]);
+    ///
+    /// r.replace(1..2, [0xaa, 0xbb]);
+    /// assert_eq!(r, [1, 0xaa, 0xbb, 3]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn replace<R, T>(&mut self, byte_range: R, bytes: T) -> Change
+    where
+        R: RangeBounds<usize>,
+        T: AsRef<[u8]>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.len() {
+            panic::byte_offset_out_of_bounds(end, self.len());
+        }
+
+        let bytes = bytes.as_ref();
+
+        self.tree.replace(RawByteMetric(start)..RawByteMetric(end), bytes);
+
+        Change { byte_range: start..end, inserted_len: bytes.len() }
+    }
+
+    /// Splits the `ByteRope` at `byte_offset`, returning the bytes after it
+    /// as a new `ByteRope`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`len()`](Self::len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let mut r = ByteRope::from(&[1, 2, 3][..]);
+    ///
+    /// let rest = r.split_off(1);
+    /// assert_eq!(r, [1]);
+    /// assert_eq!(rest, [2, 3]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn split_off(&mut self, byte_offset: usize) -> Self {
+        let split = Self::from(self.byte_slice(byte_offset..));
+        self.delete(byte_offset..);
+        split
+    }
+
+    /// Copies the contents of the `ByteRope` into a `Vec<u8>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&[1, 2, 3][..]);
+    ///
+    /// assert_eq!(r.to_vec(), [1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.byte_slice(..).to_vec()
+    }
+}
+
+impl core::fmt::Debug for ByteRope {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("ByteRope(")?;
+        f.debug_list().entries(self.bytes()).finish()?;
+        f.write_str(")")
+    }
+}
+
+impl From<&[u8]> for ByteRope {
+    #[inline]
+    fn from(bytes: &[u8]) -> Self {
+        Self { tree: Tree::from_leaves(Segmenter::new([bytes])) }
+    }
+}
+
+impl From<Vec<u8>> for ByteRope {
+    #[inline]
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::from(&*bytes)
+    }
+}
+
+impl From<ByteRopeSlice<'_>> for ByteRope {
+    #[inline]
+    fn from(slice: ByteRopeSlice<'_>) -> Self {
+        Self { tree: Tree::from(slice.tree_slice) }
+    }
+}
+
+impl From<ByteRope> for Vec<u8> {
+    #[inline]
+    fn from(rope: ByteRope) -> Self {
+        rope.to_vec()
+    }
+}
+
+impl PartialEq<ByteRope> for ByteRope {
+    #[inline]
+    fn eq(&self, rhs: &ByteRope) -> bool {
+        self.byte_slice(..) == rhs.byte_slice(..)
+    }
+}
+
+impl Eq for ByteRope {}
+
+impl PartialEq<[u8]> for ByteRope {
+    #[inline]
+    fn eq(&self, rhs: &[u8]) -> bool {
+        self.byte_slice(..) == *rhs
+    }
+}
+
+impl PartialEq<&[u8]> for ByteRope {
+    #[inline]
+    fn eq(&self, rhs: &&[u8]) -> bool {
+        self == *rhs
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for ByteRope {
+    #[inline]
+    fn eq(&self, rhs: &[u8; N]) -> bool {
+        self == &rhs[..]
+    }
+}
+
+impl PartialEq<Vec<u8>> for ByteRope {
+    #[inline]
+    fn eq(&self, rhs: &Vec<u8>) -> bool {
+        self == &**rhs
+    }
+}
+
+impl PartialEq<ByteRope> for [u8] {
+    #[inline]
+    fn eq(&self, rhs: &ByteRope) -> bool {
+        rhs == self
+    }
+}
+
+impl PartialEq<ByteRope> for &[u8] {
+    #[inline]
+    fn eq(&self, rhs: &ByteRope) -> bool {
+        rhs == *self
+    }
+}
@@ -0,0 +1,214 @@
+use alloc::vec::Vec;
+use core::ops::RangeBounds;
+
+use super::byte_chunk::{ByteChunk, RawByteMetric};
+use super::iterators::{ByteRopeBytes, ByteRopeChunks};
+use super::utils::panic_messages as panic;
+use super::{ByteRope, Rope};
+use crate::range_bounds_to_start_end;
+use crate::tree::TreeSlice;
+
+/// An immutable slice of a [`ByteRope`](crate::ByteRope).
+#[derive(Copy, Clone)]
+pub struct ByteRopeSlice<'a> {
+    pub(super) tree_slice: TreeSlice<'a, { Rope::arity() }, ByteChunk>,
+}
+
+impl<'a> ByteRopeSlice<'a> {
+    /// Returns the byte at `byte_index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds (i.e. greater than or equal to
+    /// [`len()`](Self::len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&[1, 2, 3, 4][..]);
+    /// let s = r.byte_slice(2..);
+    ///
+    /// assert_eq!(s.byte(0), 3);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte(&self, byte_index: usize) -> u8 {
+        if byte_index >= self.len() {
+            panic::byte_index_out_of_bounds(byte_index, self.len());
+        }
+
+        let (chunk, RawByteMetric(chunk_byte_offset)) =
+            self.tree_slice.leaf_at_measure(RawByteMetric(byte_index + 1));
+
+        chunk[byte_index - chunk_byte_offset]
+    }
+
+    /// Returns a sub-slice of this `ByteRopeSlice` in the specified byte
+    /// range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`len()`](Self::len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&[1, 2, 3, 4][..]);
+    /// let s = r.byte_slice(1..);
+    ///
+    /// assert_eq!(s.byte_slice(..2), [2, 3]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte_slice<R>(self, byte_range: R) -> ByteRopeSlice<'a>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.len() {
+            panic::byte_offset_out_of_bounds(end, self.len());
+        }
+
+        Self {
+            tree_slice: self
+                .tree_slice
+                .slice(RawByteMetric(start)..RawByteMetric(end)),
+        }
+    }
+
+    /// Returns an iterator over the bytes of this `ByteRopeSlice`.
+    #[inline]
+    pub fn bytes(&self) -> ByteRopeBytes<'a> {
+        ByteRopeBytes::new(self.chunks(), self.len())
+    }
+
+    /// Returns an iterator over the chunks of this `ByteRopeSlice`.
+    #[inline]
+    pub fn chunks(&self) -> ByteRopeChunks<'a> {
+        ByteRopeChunks { leaves: self.tree_slice.leaves() }
+    }
+
+    /// Returns the byte at `byte_index`, or `None` if the index is out of
+    /// bounds.
+    #[inline]
+    pub fn get_byte(&self, byte_index: usize) -> Option<u8> {
+        (byte_index < self.len()).then(|| self.byte(byte_index))
+    }
+
+    /// Returns `true` if the `ByteRopeSlice` doesn't contain any bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of bytes in the `ByteRopeSlice`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        *self.tree_slice.summary()
+    }
+
+    /// Copies the contents of the `ByteRopeSlice` into a `Vec<u8>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::ByteRope;
+    /// #
+    /// let r = ByteRope::from(&[1, 2, 3][..]);
+    ///
+    /// assert_eq!(r.byte_slice(1..).to_vec(), [2, 3]);
+    /// ```
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut vec = Vec::with_capacity(self.len());
+        for chunk in self.chunks() {
+            vec.extend_from_slice(chunk);
+        }
+        vec
+    }
+}
+
+impl core::fmt::Debug for ByteRopeSlice<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("ByteRopeSlice(")?;
+        f.debug_list().entries(self.bytes()).finish()?;
+        f.write_str(")")
+    }
+}
+
+impl PartialEq<ByteRopeSlice<'_>> for ByteRopeSlice<'_> {
+    #[inline]
+    fn eq(&self, rhs: &ByteRopeSlice<'_>) -> bool {
+        self.len() == rhs.len() && self.bytes().eq(rhs.bytes())
+    }
+}
+
+impl Eq for ByteRopeSlice<'_> {}
+
+impl PartialEq<[u8]> for ByteRopeSlice<'_> {
+    #[inline]
+    fn eq(&self, rhs: &[u8]) -> bool {
+        if self.len() != rhs.len() {
+            return false;
+        }
+
+        let mut rhs = rhs;
+
+        for chunk in self.chunks() {
+            let (left, right) = rhs.split_at(chunk.len());
+            if chunk != left {
+                return false;
+            }
+            rhs = right;
+        }
+
+        true
+    }
+}
+
+impl PartialEq<&[u8]> for ByteRopeSlice<'_> {
+    #[inline]
+    fn eq(&self, rhs: &&[u8]) -> bool {
+        self == *rhs
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for ByteRopeSlice<'_> {
+    #[inline]
+    fn eq(&self, rhs: &[u8; N]) -> bool {
+        self == &rhs[..]
+    }
+}
+
+impl PartialEq<Vec<u8>> for ByteRopeSlice<'_> {
+    #[inline]
+    fn eq(&self, rhs: &Vec<u8>) -> bool {
+        self == &**rhs
+    }
+}
+
+impl PartialEq<ByteRope> for ByteRopeSlice<'_> {
+    #[inline]
+    fn eq(&self, rhs: &ByteRope) -> bool {
+        *self == rhs.byte_slice(..)
+    }
+}
+
+impl PartialEq<ByteRopeSlice<'_>> for ByteRope {
+    #[inline]
+    fn eq(&self, rhs: &ByteRopeSlice<'_>) -> bool {
+        self.byte_slice(..) == *rhs
+    }
+}
@@ -0,0 +1,106 @@
+use alloc::string::String;
+
+use super::{Rope, RopeBuilder, RopeSlice};
+
+/// Returns whether the text of the slice is equal to `other` once both are
+/// case-folded with [`fold_char()`].
+#[inline]
+pub(super) fn eq_ignore_case(slice: RopeSlice<'_>, other: &str) -> bool {
+    if slice == other {
+        return true;
+    }
+
+    slice.chars().map(fold_char).eq(other.chars().map(fold_char))
+}
+
+/// Maps a char to its simple case folding, i.e. to the single char all the
+/// chars that only differ from it in case are mapped to.
+///
+/// `core` doesn't expose the Unicode case folding tables, so this is derived
+/// from the case mappings of [`char::to_uppercase()`] and
+/// [`char::to_lowercase()`]: lowercasing the uppercase form of a char gives
+/// its simple case folding for all the chars with a single-char mapping
+/// (e.g. `'ς'`, `'ſ'` and the Kelvin sign `'\u{212A}'` are folded to `'σ'`,
+/// `'s'` and `'k'`). Chars whose mappings expand to several chars, like
+/// `'ß'` and `'İ'`, are left as they are.
+#[inline]
+pub(super) fn fold_char(ch: char) -> char {
+    if ch.is_ascii() {
+        return ch.to_ascii_lowercase();
+    }
+
+    // The dotless i only folds to the dotted one in Turkic languages.
+    if ch == 'ı' {
+        return ch;
+    }
+
+    let mut upper = ch.to_uppercase();
+
+    let (Some(upper), None) = (upper.next(), upper.next()) else {
+        return ch;
+    };
+
+    let mut lower = upper.to_lowercase();
+
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => ch,
+    }
+}
+
+/// Returns a new `Rope` with the lowercase equivalent of the text of the
+/// slice, as returned by [`str::to_lowercase()`].
+#[inline]
+pub(super) fn to_lowercase(slice: RopeSlice<'_>) -> Rope {
+    convert_case(slice, str::to_lowercase)
+}
+
+/// Returns a new `Rope` with the uppercase equivalent of the text of the
+/// slice, as returned by [`str::to_uppercase()`].
+#[inline]
+pub(super) fn to_uppercase(slice: RopeSlice<'_>) -> Rope {
+    convert_case(slice, str::to_uppercase)
+}
+
+/// Converts the text of the slice with `convert`, appending the result to a
+/// `RopeBuilder` a chunk at a time.
+///
+/// The case mapping of a char can depend on the chars around it (e.g. a
+/// `'Σ'` is lowercased to `'ς'` at the end of a word and to `'σ'`
+/// elsewhere), so we can't just convert every chunk on its own. However,
+/// ASCII whitespace is neither cased nor case-ignorable, so that context
+/// never extends past it. We can then split the text right after every
+/// ASCII whitespace, and only have to buffer the text between two of them
+/// when it spans more than one chunk.
+#[inline]
+fn convert_case(slice: RopeSlice<'_>, convert: fn(&str) -> String) -> Rope {
+    let mut builder = RopeBuilder::new();
+
+    // The text after the last ASCII whitespace we've seen.
+    let mut pending = String::new();
+
+    for chunk in slice.chunks() {
+        let Some(last_whitespace) =
+            chunk.rfind(|ch: char| ch.is_ascii_whitespace())
+        else {
+            pending.push_str(chunk);
+            continue;
+        };
+
+        let (head, tail) = chunk.split_at(last_whitespace + 1);
+
+        if pending.is_empty() {
+            builder.append(convert(head));
+        } else {
+            pending.push_str(head);
+            builder.append(convert(&pending));
+            pending.clear();
+        }
+
+        pending.push_str(tail);
+    }
+
+    builder.append(convert(&pending));
+
+    builder.build()
+}
@@ -0,0 +1,281 @@
+use super::gap_slice::GapSlice;
+use super::metrics::ByteMetric;
+use super::rope::RopeChunk;
+use super::utils::panic_messages as panic;
+use super::{Rope, RopeSlice};
+use crate::tree::LeafCursor;
+
+/// A cursor over the text of a [`Rope`] or [`RopeSlice`] which can be moved
+/// back and forth one char at a time.
+///
+/// Unlike calling methods like [`byte()`](Rope::byte()) or
+/// [`byte_slice()`](Rope::byte_slice()) at increasing offsets, which have to
+/// descend the B-tree from its root every time, a cursor remembers the path
+/// to the chunk it's currently in. Moving it to a nearby offset is therefore
+/// O(1) as long as it stays in the same chunk, and amortized O(1) when it
+/// crosses into one of its neighbors.
+///
+/// This struct is created by the `cursor` method on
+/// [`Rope`](Rope::cursor()) and [`RopeSlice`](RopeSlice::cursor()). See
+/// their documentation for more.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::Rope;
+/// #
+/// let r = Rope::from("Hello 🌎!");
+///
+/// let mut cursor = r.cursor(6);
+///
+/// assert_eq!(cursor.next_char(), Some('🌎'));
+/// assert_eq!(cursor.byte_offset(), 10);
+///
+/// assert_eq!(cursor.prev_char(), Some('🌎'));
+/// assert_eq!(cursor.prev_char(), Some(' '));
+/// assert_eq!(cursor.byte_offset(), 5);
+///
+/// cursor.seek(r.byte_len());
+/// assert_eq!(cursor.next_char(), None);
+/// assert_eq!(cursor.prev_char(), Some('!'));
+/// ```
+#[derive(Clone)]
+pub struct Cursor<'a> {
+    leaves: LeafCursor<'a, { Rope::arity() }, RopeChunk>,
+
+    /// The byte offsets of the start and end of the `Rope` or `RopeSlice`
+    /// we're moving over, relative to the root of its tree.
+    start: usize,
+    end: usize,
+
+    /// The chunk the cursor is currently in, clamped to `start..end`. Each
+    /// leaf is made of two chunks, the ones before and after its gap.
+    chunk: &'a str,
+
+    /// The byte offset of the start of `chunk`, relative to the root.
+    chunk_start: usize,
+
+    /// Whether `chunk` is the right chunk of the current leaf.
+    is_right: bool,
+
+    /// The byte offset of the cursor, relative to the root.
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// Returns the byte offset of the cursor.
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.offset - self.start
+    }
+
+    /// Returns the chunk the cursor is currently in, together with the byte
+    /// offset of its start.
+    ///
+    /// When the cursor is on the boundary between two chunks this can return
+    /// either of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello World!");
+    ///
+    /// let cursor = r.cursor(6);
+    /// let (chunk, chunk_start) = cursor.chunk();
+    ///
+    /// let chunk_end = chunk_start + chunk.len();
+    ///
+    /// assert!((chunk_start..=chunk_end).contains(&cursor.byte_offset()));
+    /// assert_eq!(r.byte_slice(chunk_start..chunk_end), chunk);
+    /// ```
+    #[inline]
+    pub fn chunk(&self) -> (&'a str, usize) {
+        (self.chunk, self.chunk_start - self.start)
+    }
+
+    #[inline]
+    fn chunk_end(&self) -> usize {
+        self.chunk_start + self.chunk.len()
+    }
+
+    /// Loads the left or right chunk of the current leaf, clamping it to the
+    /// range of the `Rope` or `RopeSlice`.
+    #[inline]
+    fn load_chunk(&mut self, is_right: bool) {
+        let leaf: GapSlice<'a> = self.leaves.leaf();
+
+        let ByteMetric(leaf_start) = self.leaves.offset();
+
+        let (chunk, chunk_start) = if is_right {
+            (leaf.right_chunk(), leaf_start + leaf.len_left())
+        } else {
+            (leaf.left_chunk(), leaf_start)
+        };
+
+        let start = self.start.clamp(chunk_start, chunk_start + chunk.len());
+        let end = self.end.clamp(start, chunk_start + chunk.len());
+
+        self.chunk = &chunk[start - chunk_start..end - chunk_start];
+        self.chunk_start = start;
+        self.is_right = is_right;
+    }
+
+    #[inline]
+    pub(super) fn new(slice: RopeSlice<'a>, byte_offset: usize) -> Self {
+        let ByteMetric(start) = slice.tree_slice.base_offset();
+
+        let mut cursor = Self {
+            leaves: LeafCursor::from(&slice.tree_slice),
+            start,
+            end: start + slice.byte_len(),
+            chunk: "",
+            chunk_start: start,
+            is_right: false,
+            offset: start,
+        };
+
+        cursor.seek(byte_offset);
+
+        cursor
+    }
+
+    /// Moves the cursor to the start of the next non-empty chunk.
+    ///
+    /// There must be one, i.e. the end of the current chunk must come before
+    /// the end of the text.
+    #[inline]
+    fn next_chunk(&mut self) {
+        debug_assert!(self.chunk_end() < self.end);
+
+        loop {
+            if self.is_right {
+                let has_next = self.leaves.next_leaf();
+                debug_assert!(has_next);
+                self.load_chunk(false);
+            } else {
+                self.load_chunk(true);
+            }
+
+            if !self.chunk.is_empty() {
+                break;
+            }
+        }
+
+        self.offset = self.chunk_start;
+    }
+
+    /// Returns the char after the cursor and moves the cursor past it, or
+    /// returns `None` if the cursor is at the end of the text.
+    #[inline]
+    pub fn next_char(&mut self) -> Option<char> {
+        if self.offset == self.chunk_end() {
+            if self.offset == self.end {
+                return None;
+            }
+            self.next_chunk();
+        }
+
+        let ch =
+            self.chunk[self.offset - self.chunk_start..].chars().next()?;
+        self.offset += ch.len_utf8();
+        Some(ch)
+    }
+
+    /// Returns the char before the cursor and moves the cursor before it, or
+    /// returns `None` if the cursor is at the start of the text.
+    #[inline]
+    pub fn prev_char(&mut self) -> Option<char> {
+        if self.offset == self.chunk_start {
+            if self.offset == self.start {
+                return None;
+            }
+            self.prev_chunk();
+        }
+
+        let ch = self.chunk[..self.offset - self.chunk_start]
+            .chars()
+            .next_back()?;
+        self.offset -= ch.len_utf8();
+        Some(ch)
+    }
+
+    /// Moves the cursor to the end of the previous non-empty chunk.
+    ///
+    /// There must be one, i.e. the start of the current chunk must come after
+    /// the start of the text.
+    #[inline]
+    fn prev_chunk(&mut self) {
+        debug_assert!(self.chunk_start > self.start);
+
+        loop {
+            if self.is_right {
+                self.load_chunk(false);
+            } else {
+                let has_previous = self.leaves.previous_leaf();
+                debug_assert!(has_previous);
+                self.load_chunk(true);
+            }
+
+            if !self.chunk.is_empty() {
+                break;
+            }
+        }
+
+        self.offset = self.chunk_end();
+    }
+
+    /// Moves the cursor to the given byte offset.
+    ///
+    /// This is O(log n) in the length of the text since it has to descend the
+    /// B-tree from its root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than the byte
+    /// length of the text) or if it doesn't lie on a code point boundary.
+    #[track_caller]
+    #[inline]
+    pub fn seek(&mut self, byte_offset: usize) {
+        let byte_len = self.end - self.start;
+
+        if byte_offset > byte_len {
+            panic::byte_offset_out_of_bounds(byte_offset, byte_len);
+        }
+
+        let offset = self.start + byte_offset;
+
+        self.leaves.seek(ByteMetric(offset));
+
+        let ByteMetric(leaf_start) = self.leaves.offset();
+        let len_left = self.leaves.leaf().len_left();
+        self.load_chunk(offset >= leaf_start + len_left);
+
+        // If the offset is at the end of a chunk, e.g. because it's at the end
+        // of the text or right before an empty chunk, we move back to the
+        // chunk ending at the offset so that `chunk()` returns something
+        // useful.
+        while self.chunk.is_empty() && self.chunk_start > self.start {
+            self.prev_chunk();
+        }
+
+        let offset_in_chunk = offset - self.chunk_start;
+
+        if !self.chunk.is_char_boundary(offset_in_chunk) {
+            panic::byte_offset_not_char_boundary(self.chunk, offset_in_chunk);
+        }
+
+        self.offset = offset;
+    }
+}
+
+impl core::fmt::Debug for Cursor<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Cursor")
+            .field("byte_offset", &self.byte_offset())
+            .field("chunk", &self.chunk)
+            .finish()
+    }
+}
@@ -0,0 +1,571 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::{Range, RangeBounds};
+
+use super::utils::panic_messages as panic;
+use crate::range_bounds_to_start_end;
+
+/// A set of edits that can be applied to a [`Rope`](crate::Rope) with
+/// [`Rope::apply_delta()`](crate::Rope::apply_delta()).
+///
+/// A `Delta` is built by calling [`insert()`](Self::insert()),
+/// [`delete()`](Self::delete()) and [`replace()`](Self::replace()) on it
+/// exactly like they would be called on the text it's meant to be applied to,
+/// i.e. with every edit's offsets referring to the text as it is after all
+/// the previous edits.
+///
+/// Internally the edits are kept normalized as a sorted list of
+/// non-overlapping [`Edit`]s whose byte ranges refer to the original text,
+/// with edits that touch or overlap each other merged together. This makes
+/// the size of a `Delta` proportional to the size of the changes it describes
+/// rather than to the number of calls that built it.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::{Delta, Rope};
+/// #
+/// let mut delta = Delta::new();
+///
+/// delta.insert(0, "Hello ");
+/// delta.replace(6..11, "Earth");
+/// delta.insert(11, " 🌎");
+///
+/// let mut r = Rope::from("World!");
+/// r.apply_delta(&delta);
+///
+/// assert_eq!(r, "Hello Earth 🌎!");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Delta {
+    edits: Vec<Edit>,
+}
+
+/// A single edit in a [`Delta`], replacing a byte range of the original text
+/// with some other text.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Edit {
+    /// The byte range of the original text being replaced.
+    pub byte_range: Range<usize>,
+
+    /// The text the byte range is replaced with.
+    pub text: String,
+}
+
+/// A description of a single edit made to a [`Rope`](crate::Rope), returned
+/// by its editing methods like [`insert()`](crate::Rope::insert()),
+/// [`delete()`](crate::Rope::delete()) and
+/// [`replace()`](crate::Rope::replace()).
+///
+/// This can be forwarded to the other data structures that need to know what
+/// changed in the `Rope`, like a [`MarkerSet`](crate::MarkerSet) or an
+/// [`IntervalMap`](crate::IntervalMap), without having to duplicate the
+/// offset computations at every call site.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::{Change, Gravity, MarkerSet, Rope};
+/// #
+/// let mut r = Rope::from("Hello World!");
+///
+/// let mut markers = MarkerSet::new();
+/// let bang = markers.add(11, Gravity::Right);
+///
+/// let change = r.replace(6..11, "Earth 🌎");
+/// assert_eq!(change, Change { byte_range: 6..11, inserted_len: 10 });
+/// assert_eq!(r.byte_slice(change.inserted_byte_range()), "Earth 🌎");
+///
+/// markers.apply_change(&change);
+/// assert_eq!(r.byte_slice(markers.get(bang).unwrap()..), "!");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Change {
+    /// The byte range of the `Rope` before the edit that was replaced.
+    pub byte_range: Range<usize>,
+
+    /// The length of the text that replaced the byte range.
+    pub inserted_len: usize,
+}
+
+impl Change {
+    /// Returns the byte range of the `Rope` after the edit containing the
+    /// inserted text.
+    #[inline]
+    pub fn inserted_byte_range(&self) -> Range<usize> {
+        self.byte_range.start..self.byte_range.start + self.inserted_len
+    }
+}
+
+impl Delta {
+    /// Returns a `Delta` with the same effect as applying this one followed
+    /// by `other`, whose offsets refer to the text as it is after this
+    /// `Delta` has been applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the ends of `other`'s edits falls inside a multi-byte
+    /// code point of the text inserted by this `Delta`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Delta, Rope};
+    /// #
+    /// let mut first = Delta::new();
+    /// first.insert(0, "Hello ");
+    ///
+    /// let mut second = Delta::new();
+    /// second.replace(6..11, "Earth");
+    ///
+    /// let mut r = Rope::from("World!");
+    /// r.apply_delta(&first.compose(&second));
+    ///
+    /// assert_eq!(r, "Hello Earth!");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn compose(&self, other: &Delta) -> Delta {
+        let mut composed = self.clone();
+
+        // Going back to front, the offsets of every edit of `other` refer to
+        // the text as it is after the ones we've already recorded.
+        for edit in other.edits.iter().rev() {
+            composed.replace_inner(
+                edit.byte_range.start,
+                edit.byte_range.end,
+                &edit.text,
+            );
+        }
+
+        composed
+    }
+
+    /// Records the deletion of the given byte range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the
+    /// range has an unbounded end.
+    #[track_caller]
+    #[inline]
+    pub fn delete<R>(&mut self, byte_range: R) -> &mut Self
+    where
+        R: RangeBounds<usize>,
+    {
+        self.replace(byte_range, "")
+    }
+
+    /// Returns the edits making up the `Delta`, sorted by their start and
+    /// with non-overlapping byte ranges referring to the original text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Delta, Edit};
+    /// #
+    /// let mut delta = Delta::new();
+    ///
+    /// delta.insert(0, "foo");
+    /// delta.delete(5..8);
+    ///
+    /// assert_eq!(
+    ///     delta.edits(),
+    ///     [
+    ///         Edit { byte_range: 0..0, text: "foo".to_owned() },
+    ///         Edit { byte_range: 2..5, text: String::new() },
+    ///     ]
+    /// );
+    /// ```
+    #[inline]
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+
+    /// Records the insertion of `text` at the given byte offset.
+    #[inline]
+    pub fn insert<T>(&mut self, byte_offset: usize, text: T) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        self.replace(byte_offset..byte_offset, text)
+    }
+
+    /// Creates a `Delta` from a list of edits which are already sorted and
+    /// don't touch or overlap each other.
+    #[inline]
+    pub(super) fn from_edits(edits: Vec<Edit>) -> Self {
+        debug_assert!(edits.windows(2).all(|pair| {
+            pair[0].byte_range.end < pair[1].byte_range.start
+        }));
+
+        Self { edits }
+    }
+
+    /// Returns `true` if the `Delta` doesn't contain any edits.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Creates a new, empty `Delta`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the replacement of the given byte range with `text`.
+    ///
+    /// The byte range refers to the text as it is after all the edits
+    /// previously recorded in the `Delta`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, if the range
+    /// has an unbounded end, or if either of its ends falls inside a
+    /// multi-byte code point of a previously recorded edit's text.
+    #[track_caller]
+    #[inline]
+    pub fn replace<R, T>(&mut self, byte_range: R, text: T) -> &mut Self
+    where
+        R: RangeBounds<usize>,
+        T: AsRef<str>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, usize::MAX);
+
+        assert!(end < usize::MAX, "the byte range must have a bounded end");
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        self.replace_inner(start, end, text.as_ref());
+
+        self
+    }
+
+    #[track_caller]
+    #[inline]
+    fn replace_inner(&mut self, start: usize, end: usize, text: &str) {
+        // The number of bytes added and removed by the edits before the
+        // current one, which are used to map an offset in the edited text
+        // falling outside of any edit back to the original text.
+        let mut added = 0;
+        let mut removed = 0;
+
+        // Skip the edits ending strictly before the start of the new one.
+        let mut first = 0;
+
+        while let Some(edit) = self.edits.get(first) {
+            if edit.new_start(added, removed) + edit.text.len() >= start {
+                break;
+            }
+            added += edit.text.len();
+            removed += edit.byte_range.len();
+            first += 1;
+        }
+
+        let mut replacement = String::new();
+
+        let original_start = match self.edits.get(first) {
+            Some(edit) if edit.new_start(added, removed) <= start => {
+                let offset = start - edit.new_start(added, removed);
+                replacement.push_str(&edit.text[..offset]);
+                edit.byte_range.start
+            },
+            _ => start + removed - added,
+        };
+
+        replacement.push_str(text);
+
+        // Find all the edits touching or overlapping the new one, which will
+        // be merged with it.
+        let mut last = first;
+
+        let mut last_suffix = None;
+
+        while let Some(edit) = self.edits.get(last) {
+            let new_start = edit.new_start(added, removed);
+
+            if new_start > end {
+                break;
+            }
+
+            last_suffix =
+                (end < new_start + edit.text.len()).then(|| end - new_start);
+
+            added += edit.text.len();
+            removed += edit.byte_range.len();
+            last += 1;
+        }
+
+        let original_end = match last_suffix {
+            Some(offset) => {
+                let edit = &self.edits[last - 1];
+                replacement.push_str(&edit.text[offset..]);
+                edit.byte_range.end
+            },
+            None => end + removed - added,
+        };
+
+        let merged =
+            if original_start == original_end && replacement.is_empty() {
+                None
+            } else {
+                Some(Edit {
+                    byte_range: original_start..original_end,
+                    text: replacement,
+                })
+            };
+
+        self.edits.splice(first..last, merged);
+    }
+
+    /// Rebases two `Delta`s made concurrently on top of the same text
+    /// against each other, returning a `(self', other')` pair such that
+    /// applying `self` followed by `other'` results in the same text as
+    /// applying `other` followed by `self'`.
+    ///
+    /// This is the building block of operational transformation: a server
+    /// receiving an edit made against an older version of a document can
+    /// rebase it on top of the edits it has accepted since, and a client can
+    /// do the same with the edits it receives from the server.
+    ///
+    /// Text deleted by both `Delta`s is only deleted once, while text inserted
+    /// by one of them inside a range deleted by the other is kept. When both
+    /// insert text at the same offset, the text inserted by `self` ends up
+    /// first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Delta, Rope};
+    /// #
+    /// let r = Rope::from("Hello World!");
+    ///
+    /// let mut ours = Delta::new();
+    /// ours.replace(6..11, "Earth");
+    ///
+    /// let mut theirs = Delta::new();
+    /// theirs.insert(5, ",").insert(12, "!!");
+    ///
+    /// let (ours_rebased, theirs_rebased) = ours.transform(&theirs);
+    ///
+    /// let mut a = r.clone();
+    /// a.apply_delta(&ours);
+    /// a.apply_delta(&theirs_rebased);
+    ///
+    /// let mut b = r.clone();
+    /// b.apply_delta(&theirs);
+    /// b.apply_delta(&ours_rebased);
+    ///
+    /// assert_eq!(a, "Hello, Earth!!!");
+    /// assert_eq!(a, b);
+    /// ```
+    #[inline]
+    pub fn transform(&self, other: &Delta) -> (Delta, Delta) {
+        let mut ours = Ops::new(self);
+        let mut theirs = Ops::new(other);
+
+        let mut ours_rebased = DeltaBuilder::default();
+        let mut theirs_rebased = DeltaBuilder::default();
+
+        loop {
+            match (ours.peek(), theirs.peek()) {
+                (Op::Insert(text), _) => {
+                    ours_rebased.insert(text);
+                    theirs_rebased.retain(text.len());
+                    ours.next_op();
+                },
+
+                (_, Op::Insert(text)) => {
+                    ours_rebased.retain(text.len());
+                    theirs_rebased.insert(text);
+                    theirs.next_op();
+                },
+
+                (Op::Retain(usize::MAX), Op::Retain(usize::MAX)) => break,
+
+                (ours_op, theirs_op) => {
+                    let len = ours_op.len().min(theirs_op.len());
+
+                    match (ours_op, theirs_op) {
+                        (Op::Retain(_), Op::Retain(_)) => {
+                            ours_rebased.retain(len);
+                            theirs_rebased.retain(len);
+                        },
+                        (Op::Delete(_), Op::Retain(_)) => {
+                            ours_rebased.delete(len);
+                        },
+                        (Op::Retain(_), Op::Delete(_)) => {
+                            theirs_rebased.delete(len);
+                        },
+                        // Both deleted the same text.
+                        _ => {},
+                    }
+
+                    ours.advance(len);
+                    theirs.advance(len);
+                },
+            }
+        }
+
+        (ours_rebased.build(), theirs_rebased.build())
+    }
+}
+
+impl Edit {
+    /// Returns the start of the edit in the edited text, given the number of
+    /// bytes added and removed by the edits before it.
+    #[inline]
+    fn new_start(&self, added: usize, removed: usize) -> usize {
+        self.byte_range.start + added - removed
+    }
+}
+
+/// A single operation of a [`Delta`] seen as a sequence of operations to
+/// perform while walking over the text it's applied to, which is what
+/// [`Delta::transform()`] works with.
+#[derive(Clone, Copy)]
+enum Op<'a> {
+    /// Keep the next `n` bytes, or the rest of the text if `n` is
+    /// `usize::MAX`.
+    Retain(usize),
+
+    /// Delete the next `n` bytes.
+    Delete(usize),
+
+    /// Insert some text.
+    Insert(&'a str),
+}
+
+impl Op<'_> {
+    /// Returns the number of bytes of the original text this operation walks
+    /// over.
+    #[inline]
+    fn len(&self) -> usize {
+        match *self {
+            Op::Retain(len) | Op::Delete(len) => len,
+            Op::Insert(_) => 0,
+        }
+    }
+}
+
+/// An iterator-like cursor over the [`Op`]s of a [`Delta`], which can consume
+/// a retain or a delete only partially.
+struct Ops<'a> {
+    edits: core::slice::Iter<'a, Edit>,
+
+    /// The offset in the original text of the end of the last edit.
+    offset: usize,
+
+    /// The operations of the current edit, back to front.
+    pending: Vec<Op<'a>>,
+}
+
+impl<'a> Ops<'a> {
+    #[inline]
+    fn new(delta: &'a Delta) -> Self {
+        let mut ops =
+            Self { edits: delta.edits.iter(), offset: 0, pending: Vec::new() };
+        ops.next_op();
+        ops
+    }
+
+    /// Consumes `len` bytes of the current retain or delete.
+    #[inline]
+    fn advance(&mut self, len: usize) {
+        match self.pending.last_mut() {
+            Some(Op::Retain(usize::MAX)) => {},
+            Some(Op::Retain(n) | Op::Delete(n)) if *n > len => *n -= len,
+            _ => self.next_op(),
+        }
+    }
+
+    /// Moves to the next operation, which is an endless retain once all the
+    /// edits have been consumed.
+    #[inline]
+    fn next_op(&mut self) {
+        self.pending.pop();
+
+        if !self.pending.is_empty() {
+            return;
+        }
+
+        let Some(edit) = self.edits.next() else {
+            self.pending.push(Op::Retain(usize::MAX));
+            return;
+        };
+
+        if !edit.byte_range.is_empty() {
+            self.pending.push(Op::Delete(edit.byte_range.len()));
+        }
+
+        if !edit.text.is_empty() {
+            self.pending.push(Op::Insert(&edit.text));
+        }
+
+        if edit.byte_range.start > self.offset {
+            self.pending.push(Op::Retain(edit.byte_range.start - self.offset));
+        }
+
+        self.offset = edit.byte_range.end;
+    }
+
+    #[inline]
+    fn peek(&self) -> Op<'a> {
+        *self.pending.last().unwrap()
+    }
+}
+
+/// Builds a [`Delta`] from a sequence of [`Op`]s.
+#[derive(Default)]
+struct DeltaBuilder {
+    edits: Vec<Edit>,
+
+    /// The current offset in the original text.
+    offset: usize,
+
+    /// Whether the last edit ends at `offset`, in which case new deletions
+    /// and insertions are merged into it.
+    is_in_edit: bool,
+}
+
+impl DeltaBuilder {
+    #[inline]
+    fn build(self) -> Delta {
+        Delta::from_edits(self.edits)
+    }
+
+    #[inline]
+    fn current_edit(&mut self) -> &mut Edit {
+        if !self.is_in_edit {
+            self.edits.push(Edit {
+                byte_range: self.offset..self.offset,
+                text: String::new(),
+            });
+            self.is_in_edit = true;
+        }
+        self.edits.last_mut().unwrap()
+    }
+
+    #[inline]
+    fn delete(&mut self, len: usize) {
+        self.current_edit().byte_range.end += len;
+        self.offset += len;
+    }
+
+    #[inline]
+    fn insert(&mut self, text: &str) {
+        self.current_edit().text.push_str(text);
+    }
+
+    #[inline]
+    fn retain(&mut self, len: usize) {
+        if len > 0 {
+            self.offset += len;
+            self.is_in_edit = false;
+        }
+    }
+}
@@ -0,0 +1,425 @@
+//! This module contains the logic used to implement [`Rope::diff()`].
+//!
+//! The diff is computed in two steps. First, the common prefix and suffix of
+//! the two `Rope`s are skipped, starting from the nodes their trees share
+//! with each other and then comparing the bytes that follow. Then, the
+//! remaining middle sections are diffed char by char using the linear space
+//! variant of Myers' algorithm described in "An O(ND) Difference Algorithm
+//! and Its Variations".
+//!
+//! Like in most diff implementations, the search for the middle snake gives
+//! up after a number of edits proportional to the square root of the input
+//! length, in which case the whole range being diffed is replaced at once.
+//! This keeps diffing two unrelated texts from taking quadratic time.
+
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::{Delta, Edit, Rope, RopeSlice};
+
+#[inline]
+pub(super) fn diff(old: &Rope, new: &Rope) -> Delta {
+    let old_len = old.byte_len();
+    let new_len = new.byte_len();
+
+    let mut prefix = usize::from(old.tree.shared_prefix(&new.tree));
+
+    prefix +=
+        common_prefix_len(old.byte_slice(prefix..), new.byte_slice(prefix..));
+
+    while !old.is_char_boundary(prefix) {
+        prefix -= 1;
+    }
+
+    let max_suffix = old_len.min(new_len) - prefix;
+
+    let mut suffix =
+        usize::from(old.tree.shared_suffix(&new.tree)).min(max_suffix);
+
+    if suffix < max_suffix {
+        suffix += common_suffix_len(
+            old.byte_slice(prefix..old_len - suffix),
+            new.byte_slice(prefix..new_len - suffix),
+        );
+    }
+
+    while !old.is_char_boundary(old_len - suffix) {
+        suffix -= 1;
+    }
+
+    let old_middle = old.byte_slice(prefix..old_len - suffix);
+    let new_middle = new.byte_slice(prefix..new_len - suffix);
+
+    let old_chars = old_middle.chars().collect::<Vec<_>>();
+    let new_chars = new_middle.chars().collect::<Vec<_>>();
+
+    let ops = Myers::new(&old_chars, &new_chars).diff();
+
+    let old_offsets = byte_offsets(&old_chars);
+    let new_offsets = byte_offsets(&new_chars);
+
+    let new_middle = new_middle.to_string();
+
+    let edits = ops
+        .into_iter()
+        .map(|Op { old, new }| Edit {
+            byte_range: prefix + old_offsets[old.start]
+                ..prefix + old_offsets[old.end],
+            text: new_middle[new_offsets[new.start]..new_offsets[new.end]]
+                .into(),
+        })
+        .collect();
+
+    Delta::from_edits(edits)
+}
+
+/// A range of chars in the old text that should be replaced with a range of
+/// chars in the new text.
+struct Op {
+    old: Range<usize>,
+    new: Range<usize>,
+}
+
+/// Returns the byte offset of every char in `chars`, followed by the total
+/// byte length.
+#[inline]
+fn byte_offsets(chars: &[char]) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(chars.len() + 1);
+
+    let mut offset = 0;
+
+    for ch in chars {
+        offsets.push(offset);
+        offset += ch.len_utf8();
+    }
+
+    offsets.push(offset);
+
+    offsets
+}
+
+/// Returns the length of the longest common prefix of the two slices, in
+/// bytes.
+#[inline]
+fn common_prefix_len(left: RopeSlice<'_>, right: RopeSlice<'_>) -> usize {
+    let mut left_chunks = left.chunks();
+    let mut right_chunks = right.chunks();
+
+    let mut left_chunk: &[u8] = &[];
+    let mut right_chunk: &[u8] = &[];
+
+    let mut len = 0;
+
+    loop {
+        if left_chunk.is_empty() {
+            let Some(chunk) = left_chunks.next() else { return len };
+            left_chunk = chunk.as_bytes();
+        }
+
+        if right_chunk.is_empty() {
+            let Some(chunk) = right_chunks.next() else { return len };
+            right_chunk = chunk.as_bytes();
+        }
+
+        let max = left_chunk.len().min(right_chunk.len());
+
+        let common = left_chunk[..max]
+            .iter()
+            .zip(&right_chunk[..max])
+            .take_while(|(l, r)| l == r)
+            .count();
+
+        len += common;
+
+        if common < max {
+            return len;
+        }
+
+        left_chunk = &left_chunk[max..];
+        right_chunk = &right_chunk[max..];
+    }
+}
+
+/// Returns the length of the longest common suffix of the two slices, in
+/// bytes.
+#[inline]
+fn common_suffix_len(left: RopeSlice<'_>, right: RopeSlice<'_>) -> usize {
+    let mut left_chunks = left.chunks();
+    let mut right_chunks = right.chunks();
+
+    let mut left_chunk: &[u8] = &[];
+    let mut right_chunk: &[u8] = &[];
+
+    let mut len = 0;
+
+    loop {
+        if left_chunk.is_empty() {
+            let Some(chunk) = left_chunks.next_back() else { return len };
+            left_chunk = chunk.as_bytes();
+        }
+
+        if right_chunk.is_empty() {
+            let Some(chunk) = right_chunks.next_back() else { return len };
+            right_chunk = chunk.as_bytes();
+        }
+
+        let max = left_chunk.len().min(right_chunk.len());
+
+        let common = left_chunk[left_chunk.len() - max..]
+            .iter()
+            .rev()
+            .zip(right_chunk[right_chunk.len() - max..].iter().rev())
+            .take_while(|(l, r)| l == r)
+            .count();
+
+        len += common;
+
+        if common < max {
+            return len;
+        }
+
+        left_chunk = &left_chunk[..left_chunk.len() - max];
+        right_chunk = &right_chunk[..right_chunk.len() - max];
+    }
+}
+
+/// The state of a diff between two sequences of chars.
+struct Myers<'a> {
+    old: &'a [char],
+    new: &'a [char],
+
+    /// The furthest reaching x coordinates of the forward search.
+    forward: V,
+
+    /// The furthest reaching x coordinates of the backward search.
+    backward: V,
+
+    /// The maximum number of edits the searches for a middle snake can try
+    /// before giving up, which bounds the time spent diffing two very
+    /// different texts at the cost of a non-minimal diff.
+    max_cost: usize,
+
+    ops: Vec<Op>,
+}
+
+impl<'a> Myers<'a> {
+    #[inline]
+    fn new(old: &'a [char], new: &'a [char]) -> Self {
+        let max_cost = (old.len() + new.len()).isqrt().max(256);
+
+        let max_d = max_d(old.len(), new.len()).min(max_cost);
+
+        Self {
+            old,
+            new,
+            forward: V::new(max_d),
+            backward: V::new(max_d),
+            max_cost,
+            ops: Vec::new(),
+        }
+    }
+
+    /// Returns the ops turning the old chars into the new ones, with the
+    /// adjacent ones merged together.
+    #[inline]
+    fn diff(mut self) -> Vec<Op> {
+        self.conquer(0..self.old.len(), 0..self.new.len());
+        self.ops
+    }
+
+    fn conquer(
+        &mut self,
+        mut old_range: Range<usize>,
+        mut new_range: Range<usize>,
+    ) {
+        let prefix = self.old[old_range.clone()]
+            .iter()
+            .zip(&self.new[new_range.clone()])
+            .take_while(|(o, n)| o == n)
+            .count();
+
+        old_range.start += prefix;
+        new_range.start += prefix;
+
+        let suffix = self.old[old_range.clone()]
+            .iter()
+            .rev()
+            .zip(self.new[new_range.clone()].iter().rev())
+            .take_while(|(o, n)| o == n)
+            .count();
+
+        old_range.end -= suffix;
+        new_range.end -= suffix;
+
+        if old_range.is_empty() && new_range.is_empty() {
+            return;
+        }
+
+        if old_range.is_empty() || new_range.is_empty() {
+            self.push_op(old_range, new_range);
+            return;
+        }
+
+        match self.middle_snake(old_range.clone(), new_range.clone()) {
+            Some((x, y)) => {
+                self.conquer(old_range.start..x, new_range.start..y);
+                self.conquer(x..old_range.end, y..new_range.end);
+            },
+
+            None => self.push_op(old_range, new_range),
+        }
+    }
+
+    /// Returns the start of the middle snake of an optimal path between the
+    /// two ranges, which splits the problem in two halves with roughly the
+    /// same number of edits, or `None` if finding it would take more than
+    /// `max_cost` edits.
+    ///
+    /// Both ranges must be non-empty and they mustn't share a common prefix
+    /// or suffix.
+    #[inline]
+    fn middle_snake(
+        &mut self,
+        old_range: Range<usize>,
+        new_range: Range<usize>,
+    ) -> Option<(usize, usize)> {
+        let n = old_range.len();
+        let m = new_range.len();
+
+        let delta = n as isize - m as isize;
+        let is_odd = delta & 1 == 1;
+
+        let forward = &mut self.forward;
+        let backward = &mut self.backward;
+
+        forward[1] = 0;
+        backward[1] = 0;
+
+        let old = &self.old[old_range.clone()];
+        let new = &self.new[new_range.clone()];
+
+        for d in 0..max_d(n, m).min(self.max_cost) as isize {
+            for k in (-d..=d).rev().step_by(2) {
+                let mut x = if k == -d
+                    || (k != d && forward[k - 1] < forward[k + 1])
+                {
+                    forward[k + 1]
+                } else {
+                    forward[k - 1] + 1
+                };
+
+                let y = (x as isize - k) as usize;
+
+                let (x0, y0) = (x, y);
+
+                if x < n && y < m {
+                    x += old[x..]
+                        .iter()
+                        .zip(&new[y..])
+                        .take_while(|(o, n)| o == n)
+                        .count();
+                }
+
+                forward[k] = x;
+
+                if is_odd
+                    && (k - delta).abs() < d
+                    && forward[k] + backward[-(k - delta)] >= n
+                {
+                    return Some((old_range.start + x0, new_range.start + y0));
+                }
+            }
+
+            for k in (-d..=d).rev().step_by(2) {
+                let mut x = if k == -d
+                    || (k != d && backward[k - 1] < backward[k + 1])
+                {
+                    backward[k + 1]
+                } else {
+                    backward[k - 1] + 1
+                };
+
+                let mut y = (x as isize - k) as usize;
+
+                if x < n && y < m {
+                    let advance = old[..n - x]
+                        .iter()
+                        .rev()
+                        .zip(new[..m - y].iter().rev())
+                        .take_while(|(o, n)| o == n)
+                        .count();
+
+                    x += advance;
+                    y += advance;
+                }
+
+                backward[k] = x;
+
+                if !is_odd
+                    && (k - delta).abs() <= d
+                    && backward[k] + forward[-(k - delta)] >= n
+                {
+                    return Some((
+                        old_range.start + n - x,
+                        new_range.start + m - y,
+                    ));
+                }
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn push_op(&mut self, old: Range<usize>, new: Range<usize>) {
+        if let Some(last) = self.ops.last_mut() {
+            if last.old.end == old.start && last.new.end == new.start {
+                last.old.end = old.end;
+                last.new.end = new.end;
+                return;
+            }
+        }
+
+        self.ops.push(Op { old, new });
+    }
+}
+
+/// The furthest reaching x coordinate on each diagonal k of the edit graph,
+/// indexed by k.
+struct V {
+    offset: isize,
+    xs: Vec<usize>,
+}
+
+impl V {
+    #[inline]
+    fn new(max_d: usize) -> Self {
+        Self { offset: max_d as isize, xs: vec![0; 2 * max_d] }
+    }
+}
+
+impl core::ops::Index<isize> for V {
+    type Output = usize;
+
+    #[inline]
+    fn index(&self, k: isize) -> &usize {
+        &self.xs[(k + self.offset) as usize]
+    }
+}
+
+impl core::ops::IndexMut<isize> for V {
+    #[inline]
+    fn index_mut(&mut self, k: isize) -> &mut usize {
+        &mut self.xs[(k + self.offset) as usize]
+    }
+}
+
+/// The maximum number of edits needed to turn a sequence of length `n` into
+/// one of length `m`, halved since the forward and backward searches meet in
+/// the middle.
+#[inline]
+fn max_d(n: usize, m: usize) -> usize {
+    (n + m).div_ceil(2) + 1
+}
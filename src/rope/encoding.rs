@@ -0,0 +1,270 @@
+use alloc::string::String;
+
+use super::{Rope, RopeBuilder};
+
+/// The number of bytes decoded text is buffered in before being appended to
+/// the [`RopeBuilder`].
+const BUFFER_BYTES: usize = 1024;
+
+/// A text encoding [`Rope`]s can be decoded from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// UTF-8.
+    Utf8,
+
+    /// UTF-16 with its code units stored in little-endian byte order.
+    Utf16Le,
+
+    /// UTF-16 with its code units stored in big-endian byte order.
+    Utf16Be,
+
+    /// ISO-8859-1, which maps every byte to the code point with the same
+    /// value.
+    Latin1,
+
+    /// Windows-1252, which is the same as [`Latin1`](Self::Latin1) except
+    /// for bytes in the `0x80..=0x9F` range being mapped to printable
+    /// characters like `€` and `“` instead of C1 control codes.
+    ///
+    /// The five bytes left undefined by the encoding (`0x81`, `0x8D`,
+    /// `0x8F`, `0x90` and `0x9D`) are mapped to their C1 control codes,
+    /// like browsers do.
+    Windows1252,
+}
+
+impl Encoding {
+    /// Returns the byte order mark of this encoding, or an empty slice if it
+    /// doesn't have one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::encoding::Encoding;
+    /// #
+    /// assert_eq!(Encoding::Utf8.bom(), b"\xEF\xBB\xBF");
+    /// assert_eq!(Encoding::Utf16Le.bom(), b"\xFF\xFE");
+    /// assert!(Encoding::Latin1.bom().is_empty());
+    /// ```
+    #[inline]
+    pub fn bom(&self) -> &'static [u8] {
+        match self {
+            Self::Utf8 => b"\xEF\xBB\xBF",
+            Self::Utf16Le => b"\xFF\xFE",
+            Self::Utf16Be => b"\xFE\xFF",
+            Self::Latin1 | Self::Windows1252 => b"",
+        }
+    }
+
+    /// Detects the encoding of `bytes` from their byte order mark, falling
+    /// back to UTF-8 if they don't start with one.
+    ///
+    /// Single-byte encodings like [`Latin1`](Self::Latin1) can't be detected
+    /// this way, and have to be picked explicitly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::encoding::Encoding;
+    /// #
+    /// assert_eq!(Encoding::detect(b"\xFE\xFF\x00a"), Encoding::Utf16Be);
+    /// assert_eq!(Encoding::detect(b"abc"), Encoding::Utf8);
+    /// ```
+    #[inline]
+    pub fn detect(bytes: &[u8]) -> Self {
+        Self::from_bom(bytes).unwrap_or(Self::Utf8)
+    }
+
+    /// Returns the encoding whose byte order mark `bytes` start with, if
+    /// any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::encoding::Encoding;
+    /// #
+    /// let bytes = b"\xFF\xFEa\x00";
+    ///
+    /// assert_eq!(Encoding::from_bom(bytes), Some(Encoding::Utf16Le));
+    /// assert_eq!(Encoding::from_bom(b"abc"), None);
+    /// ```
+    #[inline]
+    pub fn from_bom(bytes: &[u8]) -> Option<Self> {
+        [Self::Utf8, Self::Utf16Le, Self::Utf16Be]
+            .into_iter()
+            .find(|encoding| bytes.starts_with(encoding.bom()))
+    }
+}
+
+impl core::fmt::Display for Encoding {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+            Self::Latin1 => "ISO-8859-1",
+            Self::Windows1252 => "Windows-1252",
+        })
+    }
+}
+
+/// The error returned when decoding a [`Rope`] from bytes that aren't valid
+/// in the given [`Encoding`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    encoding: Encoding,
+    byte_offset: usize,
+}
+
+impl DecodeError {
+    /// Returns the byte offset of the first invalid sequence, counted from
+    /// the start of the input (including its byte order mark, if any).
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    /// Returns the encoding the bytes were being decoded from.
+    #[inline]
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+}
+
+impl core::fmt::Display for DecodeError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "invalid {} at byte {}", self.encoding, self.byte_offset)
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+#[inline]
+pub(super) fn decode(
+    bytes: &[u8],
+    encoding: Encoding,
+) -> Result<Rope, DecodeError> {
+    let bom_len = if bytes.starts_with(encoding.bom()) {
+        encoding.bom().len()
+    } else {
+        0
+    };
+
+    let err = |byte_offset| DecodeError { encoding, byte_offset };
+
+    match encoding {
+        Encoding::Utf8 => core::str::from_utf8(&bytes[bom_len..])
+            .map(|text| {
+                let mut rope = Rope::from(text);
+                rope.has_bom = bom_len > 0;
+                rope
+            })
+            .map_err(|e| err(bom_len + e.valid_up_to())),
+
+        Encoding::Utf16Le => {
+            decode_utf16(&bytes[bom_len..], u16::from_le_bytes)
+                .map_err(|offset| err(bom_len + offset))
+        },
+
+        Encoding::Utf16Be => {
+            decode_utf16(&bytes[bom_len..], u16::from_be_bytes)
+                .map_err(|offset| err(bom_len + offset))
+        },
+
+        Encoding::Latin1 => Ok(decode_single_byte(bytes, char::from)),
+
+        Encoding::Windows1252 => {
+            Ok(decode_single_byte(bytes, windows_1252_to_char))
+        },
+    }
+}
+
+/// The characters the bytes in the `0x80..=0x9F` range are mapped to in
+/// Windows-1252. Every other byte is mapped to the code point with the same
+/// value.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}',
+    '\u{2020}', '\u{2021}', '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}',
+    '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}', '\u{90}', '\u{2018}',
+    '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}',
+    '\u{17E}', '\u{178}',
+];
+
+#[inline]
+fn windows_1252_to_char(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => WINDOWS_1252_HIGH[(byte - 0x80) as usize],
+        _ => char::from(byte),
+    }
+}
+
+/// Decodes the text of a single-byte encoding into a `Rope`, mapping each
+/// byte to a character with `to_char`.
+#[inline]
+fn decode_single_byte(bytes: &[u8], to_char: fn(u8) -> char) -> Rope {
+    let chars = bytes.iter().map(|&byte| Ok(to_char(byte)));
+    let Ok(rope) = build_from_chars::<core::convert::Infallible>(chars);
+    rope
+}
+
+/// Decodes UTF-16 text into a `Rope`, returning the byte offset of the first
+/// unpaired surrogate or of the trailing byte of an input with an odd length
+/// on error.
+#[inline]
+fn decode_utf16(
+    bytes: &[u8],
+    to_code_unit: fn([u8; 2]) -> u16,
+) -> Result<Rope, usize> {
+    let code_units = bytes.chunks_exact(2).map(|b| to_code_unit([b[0], b[1]]));
+
+    let mut byte_offset = 0;
+
+    let chars = core::char::decode_utf16(code_units).map(|ch| {
+        let ch = ch.map_err(|_| byte_offset);
+        if let Ok(ch) = ch {
+            byte_offset += 2 * ch.len_utf16();
+        }
+        ch
+    });
+
+    let rope = build_from_chars(chars)?;
+
+    if bytes.len() % 2 != 0 {
+        return Err(bytes.len() - 1);
+    }
+
+    Ok(rope)
+}
+
+/// Builds a `Rope` from a sequence of decoded characters, stopping at the
+/// first error.
+///
+/// The characters are buffered [`BUFFER_BYTES`] at a time before being
+/// appended to a [`RopeBuilder`], so the whole text is never collected in a
+/// single `String`.
+#[inline]
+fn build_from_chars<E>(
+    chars: impl Iterator<Item = Result<char, E>>,
+) -> Result<Rope, E> {
+    let mut builder = RopeBuilder::new();
+
+    let mut buffer = String::with_capacity(BUFFER_BYTES);
+
+    for ch in chars {
+        let ch = ch?;
+
+        if buffer.len() + ch.len_utf8() > BUFFER_BYTES {
+            builder.append(&buffer);
+            buffer.clear();
+        }
+
+        buffer.push(ch);
+    }
+
+    builder.append(&buffer);
+
+    Ok(builder.build())
+}
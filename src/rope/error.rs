@@ -0,0 +1,274 @@
+use core::ops::RangeBounds;
+
+use super::RopeSlice;
+use crate::range_bounds_to_start_end;
+
+/// The error returned by the `try_*` methods of [`Rope`](crate::Rope) and
+/// [`RopeSlice`] when they're given invalid offsets or indexes, or when an
+/// edit would exceed the depth limit of a `Rope`.
+///
+/// Each variant corresponds to one of the conditions that would make the
+/// panicking version of the same method panic.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The byte offset doesn't lie on a code point boundary.
+    ByteOffsetNotCharBoundary {
+        /// The byte offset.
+        byte_offset: usize,
+    },
+
+    /// The byte offset is greater than the byte length.
+    ByteOffsetOutOfBounds {
+        /// The byte offset.
+        byte_offset: usize,
+
+        /// The byte length of the `Rope` or `RopeSlice`.
+        byte_len: usize,
+    },
+
+    /// The start of a byte range is greater than its end.
+    ByteStartAfterEnd {
+        /// The start of the byte range.
+        byte_start: usize,
+
+        /// The end of the byte range.
+        byte_end: usize,
+    },
+
+    /// The edit would make the `Rope` longer than its depth limit allows.
+    ///
+    /// See [`Rope::set_depth_limit()`](crate::Rope::set_depth_limit()).
+    DepthLimitExceeded {
+        /// The byte length the `Rope` would have after the edit.
+        byte_len: usize,
+
+        /// The maximum byte length allowed by the depth limit.
+        max_byte_len: usize,
+    },
+
+    /// The line index is greater than or equal to the number of lines.
+    LineIndexOutOfBounds {
+        /// The line index.
+        line_index: usize,
+
+        /// The number of lines in the `Rope` or `RopeSlice`.
+        line_len: usize,
+    },
+
+    /// The line offset is greater than the number of lines.
+    LineOffsetOutOfBounds {
+        /// The line offset.
+        line_offset: usize,
+
+        /// The number of lines in the `Rope` or `RopeSlice`.
+        line_len: usize,
+    },
+
+    /// The start of a line range is greater than its end.
+    LineStartAfterEnd {
+        /// The start of the line range.
+        line_start: usize,
+
+        /// The end of the line range.
+        line_end: usize,
+    },
+}
+
+impl core::fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match *self {
+            Self::ByteOffsetNotCharBoundary { byte_offset } => {
+                write!(f, "byte offset {byte_offset} is not a char boundary")
+            },
+
+            Self::ByteOffsetOutOfBounds { byte_offset, byte_len } => write!(
+                f,
+                "byte offset out of bounds: the offset is {byte_offset} but \
+                 the length is {byte_len}"
+            ),
+
+            Self::ByteStartAfterEnd { byte_start, byte_end } => write!(
+                f,
+                "byte start after end: the start is {byte_start} but the end \
+                 is {byte_end}"
+            ),
+
+            Self::DepthLimitExceeded { byte_len, max_byte_len } => write!(
+                f,
+                "depth limit exceeded: the length would be {byte_len} but \
+                 the depth limit only allows {max_byte_len}"
+            ),
+
+            Self::LineIndexOutOfBounds { line_index, line_len } => write!(
+                f,
+                "line index out of bounds: the index is {line_index} but the \
+                 length is {line_len}"
+            ),
+
+            Self::LineOffsetOutOfBounds { line_offset, line_len } => write!(
+                f,
+                "line offset out of bounds: the offset is {line_offset} but \
+                 the length is {line_len}"
+            ),
+
+            Self::LineStartAfterEnd { line_start, line_end } => write!(
+                f,
+                "line start after end: the start is {line_start} but the end \
+                 is {line_end}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// The error returned when creating a [`Rope`](crate::Rope) from bytes that
+/// aren't valid UTF-8.
+///
+/// Unlike [`core::str::Utf8Error`], whose position is only meaningful
+/// relative to the slice that was being checked, the byte offset of this
+/// error is always relative to the start of the whole input.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Utf8Error {
+    byte_offset: usize,
+    invalid_len: Option<usize>,
+}
+
+impl Utf8Error {
+    /// Returns the byte offset of the start of the invalid sequence, which is
+    /// also the length of the longest prefix of the input that's valid UTF-8.
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    /// Returns the length in bytes of the invalid sequence, or `None` if the
+    /// input ended in the middle of a code point.
+    #[inline]
+    pub fn invalid_len(&self) -> Option<usize> {
+        self.invalid_len
+    }
+
+    #[inline]
+    pub(super) fn new(err: core::str::Utf8Error) -> Self {
+        Self { byte_offset: err.valid_up_to(), invalid_len: err.error_len() }
+    }
+}
+
+impl core::fmt::Display for Utf8Error {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.invalid_len {
+            Some(len) => write!(
+                f,
+                "invalid UTF-8 at byte {}: sequence of {len} bytes is not a \
+                 code point",
+                self.byte_offset
+            ),
+
+            None => write!(
+                f,
+                "invalid UTF-8 at byte {}: input ends in the middle of a \
+                 code point",
+                self.byte_offset
+            ),
+        }
+    }
+}
+
+impl core::error::Error for Utf8Error {}
+
+/// Checks that the byte offset is within the bounds of the slice and that it
+/// lies on a code point boundary.
+#[inline]
+pub(super) fn check_byte_offset(
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+) -> Result<(), Error> {
+    if byte_offset > slice.byte_len() {
+        return Err(Error::ByteOffsetOutOfBounds {
+            byte_offset,
+            byte_len: slice.byte_len(),
+        });
+    }
+
+    if !slice.is_char_boundary(byte_offset) {
+        return Err(Error::ByteOffsetNotCharBoundary { byte_offset });
+    }
+
+    Ok(())
+}
+
+/// Checks that the byte range is valid for the slice, returning its start and
+/// end if it is.
+#[inline]
+pub(super) fn check_byte_range<R>(
+    slice: RopeSlice<'_>,
+    byte_range: R,
+) -> Result<(usize, usize), Error>
+where
+    R: RangeBounds<usize>,
+{
+    let (start, end) =
+        range_bounds_to_start_end(byte_range, 0, slice.byte_len());
+
+    if start > end {
+        return Err(Error::ByteStartAfterEnd {
+            byte_start: start,
+            byte_end: end,
+        });
+    }
+
+    check_byte_offset(slice, end)?;
+    check_byte_offset(slice, start)?;
+
+    Ok((start, end))
+}
+
+/// Checks that the line index is within the bounds of the slice.
+#[inline]
+pub(super) fn check_line_index(
+    slice: RopeSlice<'_>,
+    line_index: usize,
+) -> Result<(), Error> {
+    if line_index >= slice.line_len() {
+        return Err(Error::LineIndexOutOfBounds {
+            line_index,
+            line_len: slice.line_len(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that the line range is valid for the slice, returning its start and
+/// end if it is.
+#[inline]
+pub(super) fn check_line_range<R>(
+    slice: RopeSlice<'_>,
+    line_range: R,
+) -> Result<(usize, usize), Error>
+where
+    R: RangeBounds<usize>,
+{
+    let (start, end) =
+        range_bounds_to_start_end(line_range, 0, slice.line_len());
+
+    if start > end {
+        return Err(Error::LineStartAfterEnd {
+            line_start: start,
+            line_end: end,
+        });
+    }
+
+    if end > slice.line_len() {
+        return Err(Error::LineOffsetOutOfBounds {
+            line_offset: end,
+            line_len: slice.line_len(),
+        });
+    }
+
+    Ok((start, end))
+}
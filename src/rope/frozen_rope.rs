@@ -0,0 +1,357 @@
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use super::metrics::{convert, count};
+use super::utils::panic_messages as panic;
+use super::{Rope, RopeSlice};
+
+/// The number of bytes covered by every entry of a [`FrozenRope`]'s line
+/// index.
+const BLOCK_BYTES: usize = 1024;
+
+/// A packed, read-only snapshot of a [`Rope`].
+///
+/// A `FrozenRope` stores its text in a single contiguous buffer with no slack,
+/// plus a sparse line index with one entry every KB of text, which makes it a
+/// lot smaller than a `Rope` with the same contents. It's meant for
+/// applications holding many documents open without modifying them.
+///
+/// Line lookups are O(log n) to find the right block of the index followed by
+/// a scan of at most a KB of text. Cloning a `FrozenRope` is O(1).
+///
+/// A `FrozenRope` can be turned back into an editable `Rope` at any time by
+/// calling [`thaw()`](Self::thaw()), which is O(n).
+#[derive(Clone)]
+pub struct FrozenRope {
+    text: Arc<str>,
+
+    /// The number of line breaks before the start of each block of
+    /// `BLOCK_BYTES` bytes.
+    line_breaks_before_block: Arc<[usize]>,
+
+    line_breaks: usize,
+}
+
+impl FrozenRope {
+    /// Returns the text of the `FrozenRope` as a string slice.
+    ///
+    /// Unlike with a `Rope`, this doesn't allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let frozen = Rope::from("Hello, world!").freeze();
+    ///
+    /// assert_eq!(frozen.as_str(), "Hello, world!");
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the byte length of the `FrozenRope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let frozen = Rope::from("è").freeze();
+    ///
+    /// assert_eq!(frozen.byte_len(), 2);
+    /// ```
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// Returns the byte offset of the start of the given line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let frozen = Rope::from("foo\nbar\r\nbaz").freeze();
+    ///
+    /// assert_eq!(frozen.byte_of_line(0), 0);
+    /// assert_eq!(frozen.byte_of_line(1), 4);
+    /// assert_eq!(frozen.byte_of_line(2), 9);
+    /// assert_eq!(frozen.byte_of_line(3), 12);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_line(&self, line_offset: usize) -> usize {
+        if line_offset > self.line_len() {
+            panic::line_offset_out_of_bounds(line_offset, self.line_len());
+        }
+
+        if line_offset == 0 {
+            return 0;
+        }
+
+        if line_offset > self.line_breaks {
+            return self.byte_len();
+        }
+
+        // The last block starting before the `line_offset`-th line break.
+        let block = self
+            .line_breaks_before_block
+            .partition_point(|&breaks| breaks < line_offset)
+            - 1;
+
+        let block_start = self.block_start(block);
+
+        block_start
+            + convert::byte_of_line(
+                &self.text[block_start..],
+                line_offset - self.line_breaks_before_block[block],
+            )
+    }
+
+    /// Returns the byte offset of the first char boundary at or after the
+    /// start of the given block.
+    #[inline]
+    fn block_start(&self, block: usize) -> usize {
+        let mut offset = block * BLOCK_BYTES;
+        while !self.text.is_char_boundary(offset) {
+            offset += 1;
+        }
+        offset
+    }
+
+    /// Returns `true` if the `FrozenRope`'s byte length is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// assert!(Rope::new().freeze().is_empty());
+    /// assert!(!Rope::from("foo").freeze().is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.byte_len() == 0
+    }
+
+    /// Returns the line at `line_index`, without its line terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line index is out of bounds (i.e. greater than or equal
+    /// to [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let frozen = Rope::from("foo\nbar\r\nbaz").freeze();
+    ///
+    /// assert_eq!(frozen.line(0), "foo");
+    /// assert_eq!(frozen.line(1), "bar");
+    /// assert_eq!(frozen.line(2), "baz");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line(&self, line_index: usize) -> &str {
+        if line_index >= self.line_len() {
+            panic::line_index_out_of_bounds(line_index, self.line_len());
+        }
+
+        let start = self.byte_of_line(line_index);
+        let end = self.byte_of_line(line_index + 1);
+
+        let line = &self.text[start..end];
+
+        match line.strip_suffix('\n') {
+            Some(line) => line.strip_suffix('\r').unwrap_or(line),
+            None => line,
+        }
+    }
+
+    /// Returns the number of lines in the `FrozenRope`.
+    ///
+    /// Like for [`Rope::line_len()`], the final line break is optional and
+    /// doesn't count as a separate empty line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// assert_eq!(Rope::from("foo\nbar").freeze().line_len(), 2);
+    /// assert_eq!(Rope::from("foo\nbar\n").freeze().line_len(), 2);
+    /// ```
+    #[inline]
+    pub fn line_len(&self) -> usize {
+        self.line_breaks + 1
+            - (self.text.ends_with('\n') as usize)
+            - (self.is_empty() as usize)
+    }
+
+    /// Returns the line offset of the given byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let frozen = Rope::from("foo\nbar\r\nbaz").freeze();
+    ///
+    /// assert_eq!(frozen.line_of_byte(0), 0);
+    /// assert_eq!(frozen.line_of_byte(4), 1);
+    /// assert_eq!(frozen.line_of_byte(frozen.byte_len()), 2);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line_of_byte(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let block = byte_offset / BLOCK_BYTES;
+
+        let Some(&line_breaks) = self.line_breaks_before_block.get(block)
+        else {
+            return self.line_breaks;
+        };
+
+        line_breaks
+            + line_breaks_in_bytes(
+                &self.text.as_bytes()[block * BLOCK_BYTES..byte_offset],
+            )
+    }
+
+    /// Returns a new [`Rope`] with the same contents as this `FrozenRope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let frozen = Rope::from("Hello").freeze();
+    ///
+    /// let mut r = frozen.thaw();
+    /// r.insert(5, ", world!");
+    ///
+    /// assert_eq!(r, "Hello, world!");
+    /// assert_eq!(frozen.as_str(), "Hello");
+    /// ```
+    #[inline]
+    pub fn thaw(&self) -> Rope {
+        Rope::from(self.as_str())
+    }
+}
+
+impl From<RopeSlice<'_>> for FrozenRope {
+    #[inline]
+    fn from(slice: RopeSlice<'_>) -> Self {
+        let mut text = String::with_capacity(slice.byte_len());
+
+        let mut line_breaks_before_block =
+            Vec::with_capacity(slice.byte_len().div_ceil(BLOCK_BYTES));
+
+        let mut line_breaks = 0;
+
+        for chunk in slice.chunks() {
+            text.push_str(chunk);
+
+            // Add an entry for every block starting within this chunk.
+            while line_breaks_before_block.len() * BLOCK_BYTES < text.len() {
+                let block_start = line_breaks_before_block.len() * BLOCK_BYTES;
+
+                let chunk_start = text.len() - chunk.len();
+
+                line_breaks_before_block.push(
+                    line_breaks
+                        + line_breaks_in_bytes(
+                            &chunk.as_bytes()[..block_start - chunk_start],
+                        ),
+                );
+            }
+
+            line_breaks += count::line_breaks(chunk);
+        }
+
+        Self {
+            text: text.into(),
+            line_breaks_before_block: line_breaks_before_block.into(),
+            line_breaks,
+        }
+    }
+}
+
+impl From<&Rope> for FrozenRope {
+    #[inline]
+    fn from(rope: &Rope) -> Self {
+        rope.byte_slice(..).into()
+    }
+}
+
+impl From<FrozenRope> for Rope {
+    #[inline]
+    fn from(frozen: FrozenRope) -> Self {
+        frozen.thaw()
+    }
+}
+
+impl core::fmt::Debug for FrozenRope {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("FrozenRope(")?;
+        core::fmt::Debug::fmt(self.as_str(), f)?;
+        f.write_str(")")
+    }
+}
+
+impl core::fmt::Display for FrozenRope {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl core::cmp::PartialEq for FrozenRope {
+    #[inline]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.as_str() == rhs.as_str()
+    }
+}
+
+impl core::cmp::Eq for FrozenRope {}
+
+impl core::cmp::PartialEq<str> for FrozenRope {
+    #[inline]
+    fn eq(&self, rhs: &str) -> bool {
+        self.as_str() == rhs
+    }
+}
+
+impl core::cmp::PartialEq<&str> for FrozenRope {
+    #[inline]
+    fn eq(&self, rhs: &&str) -> bool {
+        self.as_str() == *rhs
+    }
+}
+
+/// Counts the line breaks in a byte slice, which doesn't have to start or end
+/// on a char boundary.
+#[inline]
+fn line_breaks_in_bytes(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|&&byte| byte == b'\n').count()
+}
@@ -5,13 +5,17 @@
 
 use alloc::boxed::Box;
 use alloc::vec::Vec;
+use core::mem::ManuallyDrop;
 use core::ops::{Range, RangeBounds};
 
+use super::aggregate::ChunkAggregate;
 use super::gap_slice::GapSlice;
 use super::metrics::{ByteMetric, ChunkSummary};
 use super::utils::{panic_messages as panic, *};
 use crate::range_bounds_to_start_end;
 use crate::tree::{
+    Aggregate,
+    Aggregated,
     AsSlice,
     BalancedLeaf,
     BaseMeasured,
@@ -37,11 +41,73 @@ use crate::tree::{
 /// [gap buffer]: https://en.wikipedia.org/wiki/Gap_buffer
 #[derive(Clone)]
 pub struct GapBuffer<const MAX_BYTES: usize> {
-    pub(super) bytes: Box<[u8; MAX_BYTES]>,
+    pub(super) bytes: ChunkBytes<MAX_BYTES>,
     pub(super) left_summary: ChunkSummary,
     pub(super) len_right: u16,
 }
 
+/// The heap-allocated buffer backing a [`GapBuffer`].
+///
+/// This is just a `Box<[u8; MAX_BYTES]>`, except that when the `pool` feature
+/// is enabled the buffers are taken from and given back to the thread-local
+/// [`pool`](crate::pool) instead of going through the global allocator every
+/// time.
+pub(super) struct ChunkBytes<const MAX_BYTES: usize> {
+    bytes: ManuallyDrop<Box<[u8; MAX_BYTES]>>,
+}
+
+impl<const MAX_BYTES: usize> ChunkBytes<MAX_BYTES> {
+    /// Returns a new buffer. Its contents are unspecified.
+    #[inline]
+    pub(super) fn new() -> Self {
+        #[cfg(feature = "pool")]
+        if let Some(bytes) = super::pool::take::<MAX_BYTES>() {
+            return Self { bytes: ManuallyDrop::new(bytes) };
+        }
+
+        Self { bytes: ManuallyDrop::new(Box::new([0u8; MAX_BYTES])) }
+    }
+}
+
+impl<const MAX_BYTES: usize> Clone for ChunkBytes<MAX_BYTES> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let mut new = Self::new();
+        new.copy_from_slice(&**self);
+        new
+    }
+}
+
+impl<const MAX_BYTES: usize> core::ops::Deref for ChunkBytes<MAX_BYTES> {
+    type Target = [u8; MAX_BYTES];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+impl<const MAX_BYTES: usize> core::ops::DerefMut for ChunkBytes<MAX_BYTES> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.bytes
+    }
+}
+
+impl<const MAX_BYTES: usize> Drop for ChunkBytes<MAX_BYTES> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: the buffer is never accessed again after this.
+        let bytes = unsafe { ManuallyDrop::take(&mut self.bytes) };
+
+        #[cfg(feature = "pool")]
+        super::pool::give(bytes);
+
+        #[cfg(not(feature = "pool"))]
+        drop(bytes);
+    }
+}
+
 impl<const MAX_BYTES: usize> core::fmt::Debug for GapBuffer<MAX_BYTES> {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -57,7 +123,7 @@ impl<const MAX_BYTES: usize> Default for GapBuffer<MAX_BYTES> {
     #[inline]
     fn default() -> Self {
         Self {
-            bytes: Box::new([0u8; MAX_BYTES]),
+            bytes: ChunkBytes::new(),
             left_summary: ChunkSummary::default(),
             len_right: 0,
         }
@@ -336,7 +402,7 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
 
         let to_left = total_len / 2;
 
-        let mut bytes = Box::new([0u8; MAX_BYTES]);
+        let mut bytes = ChunkBytes::new();
 
         let mut summary_left = ChunkSummary::new();
 
@@ -404,6 +470,24 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
         unreachable!("This can only be reached if the total length is zero");
     }
 
+    /// Creates a new buffer containing the given string, using `summary` as
+    /// its summary instead of computing it.
+    ///
+    /// The whole string is placed before the gap, so `summary` must be the
+    /// summary of `s`.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(super) fn from_summarized(s: &str, summary: ChunkSummary) -> Self {
+        debug_assert!(s.len() <= MAX_BYTES);
+        debug_assert_eq!(s.len(), summary.bytes());
+
+        let mut bytes = ChunkBytes::new();
+
+        bytes[..s.len()].copy_from_slice(s.as_bytes());
+
+        Self { bytes, left_summary: summary, len_right: 0 }
+    }
+
     /// Returns `true` if the buffer ends with a newline ('\n') character.
     #[inline]
     pub(super) fn has_trailing_newline(&self) -> bool {
@@ -506,6 +590,11 @@ impl<const MAX_BYTES: usize> GapBuffer<MAX_BYTES> {
         self.len_right as _
     }
 
+    /// The maximum number of bytes this buffer can hold.
+    pub(super) const fn max_bytes() -> usize {
+        MAX_BYTES
+    }
+
     /// The minimum number of bytes this buffer should have to not be
     /// considered underfilled.
     pub(super) const fn min_bytes() -> usize {
@@ -1217,7 +1306,7 @@ impl<const MAX_BYTES: usize> BaseMeasured for GapBuffer<MAX_BYTES> {
 impl<const MAX_BYTES: usize> From<GapSlice<'_>> for GapBuffer<MAX_BYTES> {
     #[inline]
     fn from(slice: GapSlice<'_>) -> Self {
-        let mut bytes = Box::new([0u8; MAX_BYTES]);
+        let mut bytes = ChunkBytes::new();
 
         bytes[..slice.len_left()]
             .copy_from_slice(slice.left_chunk().as_bytes());
@@ -1253,6 +1342,17 @@ impl<const MAX_BYTES: usize> AsSlice for GapBuffer<MAX_BYTES> {
     }
 }
 
+impl<const MAX_BYTES: usize> Aggregated for GapBuffer<MAX_BYTES> {
+    type Aggregate = ChunkAggregate;
+
+    #[inline]
+    fn aggregate(slice: GapSlice<'_>) -> ChunkAggregate {
+        let mut aggregate = ChunkAggregate::from(slice.left_chunk());
+        aggregate.append(&ChunkAggregate::from(slice.right_chunk()));
+        aggregate
+    }
+}
+
 impl<const MAX_BYTES: usize> BalancedLeaf for GapBuffer<MAX_BYTES> {
     #[inline]
     fn is_underfilled(&self, summary: &ChunkSummary) -> bool {
@@ -0,0 +1,310 @@
+use alloc::vec::Vec;
+use core::ops::RangeBounds;
+
+use super::{Change, Rope};
+
+/// A [`Rope`] together with the history of the edits made to it, which can be
+/// undone and redone.
+///
+/// Since cloning a `Rope` is O(1) and an edit only copies the O(log n) nodes
+/// on the path to the edited leaves, the history is stored as a list of
+/// snapshots sharing most of their contents with each other. This makes
+/// [`undo()`](Self::undo()) and [`redo()`](Self::redo()) O(1), and the memory
+/// used by each entry in the history proportional to the size of the changes
+/// it reverts.
+///
+/// Every edit is recorded as its own entry unless it happens inside a
+/// transaction, in which case all the edits made between
+/// [`begin_transaction()`](Self::begin_transaction()) and
+/// [`commit_transaction()`](Self::commit_transaction()) are undone and redone
+/// together.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::{History, Rope};
+/// #
+/// let mut history = History::new(Rope::from("Hello"));
+///
+/// history.insert(5, " World");
+///
+/// history.begin_transaction();
+/// history.replace(6..11, "Earth");
+/// history.insert(11, " 🌎!");
+/// history.commit_transaction();
+///
+/// assert_eq!(history.rope(), "Hello Earth 🌎!");
+///
+/// history.undo();
+/// assert_eq!(history.rope(), "Hello World");
+///
+/// history.undo();
+/// assert_eq!(history.rope(), "Hello");
+///
+/// history.redo();
+/// assert_eq!(history.rope(), "Hello World");
+/// ```
+#[derive(Clone, Default)]
+pub struct History {
+    rope: Rope,
+
+    /// The states of the `Rope` before each of the recorded edits, from
+    /// oldest to newest.
+    undo_stack: Vec<Rope>,
+
+    /// The states of the `Rope` before each of the undone edits, from newest
+    /// to oldest.
+    redo_stack: Vec<Rope>,
+
+    /// The state of the `Rope` at the start of the current transaction, if
+    /// there's one in progress.
+    transaction: Option<Transaction>,
+}
+
+#[derive(Clone)]
+struct Transaction {
+    start: Rope,
+    has_edits: bool,
+}
+
+impl History {
+    /// Begins a new transaction. All the edits made until the next call to
+    /// [`commit_transaction()`](Self::commit_transaction()) will be recorded
+    /// as a single entry in the history.
+    ///
+    /// Calling this while a transaction is already in progress does nothing.
+    #[inline]
+    pub fn begin_transaction(&mut self) {
+        if self.transaction.is_none() {
+            self.transaction = Some(Transaction {
+                start: self.rope.clone(),
+                has_edits: false,
+            });
+        }
+    }
+
+    /// Returns whether there are any edits that can be redone.
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Returns whether there are any edits that can be undone, including the
+    /// ones made in the current transaction.
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+            || self.transaction.as_ref().is_some_and(|t| t.has_edits)
+    }
+
+    /// Clears the undo and redo history, keeping the current contents of the
+    /// `Rope`.
+    ///
+    /// If a transaction is in progress its edits can't be undone anymore, but
+    /// the transaction itself stays open.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+
+        if let Some(transaction) = &mut self.transaction {
+            transaction.start = self.rope.clone();
+            transaction.has_edits = false;
+        }
+    }
+
+    /// Ends the current transaction, recording all the edits made since the
+    /// last call to [`begin_transaction()`](Self::begin_transaction()) as a
+    /// single entry in the history.
+    ///
+    /// Calling this when there's no transaction in progress does nothing.
+    #[inline]
+    pub fn commit_transaction(&mut self) {
+        if let Some(Transaction { start, has_edits }) = self.transaction.take()
+        {
+            if has_edits {
+                self.undo_stack.push(start);
+            }
+        }
+    }
+
+    /// Deletes the text in the given byte range, recording the edit in the
+    /// history.
+    ///
+    /// See [`Rope::delete()`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than the byte length of the `Rope`).
+    #[track_caller]
+    #[inline]
+    pub fn delete<R>(&mut self, byte_range: R) -> Change
+    where
+        R: RangeBounds<usize>,
+    {
+        let before = self.rope.clone();
+        let change = self.rope.delete(byte_range);
+        self.record(before);
+        change
+    }
+
+    /// Applies an arbitrary edit to the `Rope`, recording it in the history.
+    ///
+    /// This is useful to record edits made through methods of [`Rope`] that
+    /// `History` doesn't expose directly, like
+    /// [`replace_all()`](Rope::replace_all()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{History, Rope};
+    /// #
+    /// let mut history = History::new(Rope::from("foo bar foo"));
+    ///
+    /// history.edit(|rope| rope.replace_all("foo", "baz"));
+    /// assert_eq!(history.rope(), "baz bar baz");
+    ///
+    /// history.undo();
+    /// assert_eq!(history.rope(), "foo bar foo");
+    /// ```
+    #[inline]
+    pub fn edit<F, T>(&mut self, edit: F) -> T
+    where
+        F: FnOnce(&mut Rope) -> T,
+    {
+        let before = self.rope.clone();
+        let ret = edit(&mut self.rope);
+        self.record(before);
+        ret
+    }
+
+    /// Inserts `text` at the given byte offset, recording the edit in the
+    /// history.
+    ///
+    /// See [`Rope::insert()`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is not a char boundary or if it's out of
+    /// bounds (i.e. greater than the byte length of the `Rope`).
+    #[track_caller]
+    #[inline]
+    pub fn insert<T>(&mut self, byte_offset: usize, text: T) -> Change
+    where
+        T: AsRef<str>,
+    {
+        let before = self.rope.clone();
+        let change = self.rope.insert(byte_offset, text);
+        self.record(before);
+        change
+    }
+
+    /// Returns the underlying `Rope`, dropping the history.
+    #[inline]
+    pub fn into_rope(self) -> Rope {
+        self.rope
+    }
+
+    /// Creates a new `History` with `rope` as its initial state.
+    #[inline]
+    pub fn new(rope: Rope) -> Self {
+        Self { rope, ..Default::default() }
+    }
+
+    /// Records an edit that turned `before` into the current state of the
+    /// `Rope`.
+    #[inline]
+    fn record(&mut self, before: Rope) {
+        match &mut self.transaction {
+            Some(transaction) => transaction.has_edits = true,
+            None => self.undo_stack.push(before),
+        }
+
+        self.redo_stack.clear();
+    }
+
+    /// Redoes the last undone entry in the history, returning whether there
+    /// was one.
+    ///
+    /// Redoing commits the current transaction, if there's one in progress.
+    #[inline]
+    pub fn redo(&mut self) -> bool {
+        self.commit_transaction();
+
+        match self.redo_stack.pop() {
+            Some(rope) => {
+                let current = core::mem::replace(&mut self.rope, rope);
+                self.undo_stack.push(current);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Replaces the text in the given byte range with `text`, recording the
+    /// edit in the history.
+    ///
+    /// See [`Rope::replace()`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end, if the end is out of
+    /// bounds (i.e. greater than the byte length of the `Rope`) or if either
+    /// of them is not a char boundary.
+    #[track_caller]
+    #[inline]
+    pub fn replace<R, T>(&mut self, byte_range: R, text: T) -> Change
+    where
+        R: RangeBounds<usize>,
+        T: AsRef<str>,
+    {
+        let before = self.rope.clone();
+        let change = self.rope.replace(byte_range, text);
+        self.record(before);
+        change
+    }
+
+    /// Returns a reference to the current state of the `Rope`.
+    #[inline]
+    pub fn rope(&self) -> &Rope {
+        &self.rope
+    }
+
+    /// Undoes the last entry in the history, returning whether there was
+    /// one.
+    ///
+    /// Undoing commits the current transaction, if there's one in progress,
+    /// so that all of its edits are undone.
+    #[inline]
+    pub fn undo(&mut self) -> bool {
+        self.commit_transaction();
+
+        match self.undo_stack.pop() {
+            Some(rope) => {
+                let current = core::mem::replace(&mut self.rope, rope);
+                self.redo_stack.push(current);
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+impl From<Rope> for History {
+    #[inline]
+    fn from(rope: Rope) -> Self {
+        Self::new(rope)
+    }
+}
+
+impl core::fmt::Debug for History {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("History")
+            .field("rope", &self.rope)
+            .field("undo_len", &self.undo_stack.len())
+            .field("redo_len", &self.redo_stack.len())
+            .finish()
+    }
+}
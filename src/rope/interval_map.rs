@@ -0,0 +1,444 @@
+use alloc::vec::Vec;
+use core::ops::{Range, RangeBounds};
+
+use super::utils::panic_messages as panic;
+use super::{Change, Delta, Gravity};
+use crate::iter::{Intervals, OverlappingIntervals};
+use crate::range_bounds_to_start_end;
+
+/// A map from byte ranges of a [`Rope`](crate::Rope) to values of type `T`,
+/// whose ranges are kept in sync with the edits made to the `Rope`.
+///
+/// This can be used to store syntax highlights, diagnostics, folds and any
+/// other data attached to a region of text. The ranges can overlap with each
+/// other, and the same range can be associated to multiple values.
+///
+/// Like with a [`MarkerSet`](crate::MarkerSet), every time the `Rope` is
+/// edited the same edit should be reported to the map with
+/// [`apply_edit()`](Self::apply_edit()) or
+/// [`apply_delta()`](Self::apply_delta()). Text inserted strictly inside an
+/// interval extends it, while text inserted at one of its ends doesn't.
+/// Intervals whose text is entirely deleted or replaced are removed from the
+/// map, except for empty intervals, which behave like a marker with
+/// [`Gravity::Left`].
+///
+/// # Examples
+///
+/// ```
+/// # use crop::{IntervalMap, Rope};
+/// #
+/// let mut r = Rope::from("let foo = 42;");
+///
+/// let mut highlights = IntervalMap::new();
+/// highlights.insert(0..3, "keyword");
+/// highlights.insert(10..12, "number");
+///
+/// r.replace(4..7, "answer");
+/// highlights.apply_edit(4..7, "answer".len());
+///
+/// let (range, &kind) = highlights.iter().nth(1).unwrap();
+/// assert_eq!(r.byte_slice(range), "42");
+/// assert_eq!(kind, "number");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalMap<T> {
+    /// The intervals in the map, sorted by the start of their byte ranges.
+    pub(super) intervals: Vec<(Range<usize>, T)>,
+
+    /// A segment tree over `intervals` holding the largest end of the
+    /// intervals under every node, which lets overlap queries skip the
+    /// intervals ending before the queried range. The root is at index 1 and
+    /// the leaves start at `max_ends.len() / 2`. Empty intervals are stored as
+    /// if they ended one byte after their start.
+    max_ends: Vec<usize>,
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for IntervalMap<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("IntervalMap")
+            .field("intervals", &self.intervals)
+            .finish()
+    }
+}
+
+impl<T> Default for IntervalMap<T> {
+    #[inline]
+    fn default() -> Self {
+        Self { intervals: Vec::new(), max_ends: Vec::new() }
+    }
+}
+
+impl<T> IntervalMap<T> {
+    /// Updates the intervals to reflect the [`Change`] returned by one of the
+    /// editing methods of [`Rope`](crate::Rope).
+    #[inline]
+    pub fn apply_change(&mut self, change: &Change) {
+        self.apply_edit(change.byte_range.clone(), change.inserted_len);
+    }
+
+    /// Updates the intervals to reflect all the edits in the [`Delta`], which
+    /// are assumed to have been applied to the `Rope` with
+    /// [`Rope::apply_delta()`](crate::Rope::apply_delta()).
+    #[inline]
+    pub fn apply_delta(&mut self, delta: &Delta) {
+        for edit in delta.edits().iter().rev() {
+            self.apply_edit(edit.byte_range.clone(), edit.text.len());
+        }
+    }
+
+    /// Updates the intervals to reflect the replacement of the given byte
+    /// range with some text of length `inserted_len`.
+    ///
+    /// This runs in O(n), where n is the number of intervals in the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the
+    /// range has an unbounded end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::IntervalMap;
+    /// #
+    /// let mut map = IntervalMap::new();
+    ///
+    /// map.insert(0..4, 'a');
+    /// map.insert(2..6, 'b');
+    /// map.insert(6..8, 'c');
+    ///
+    /// // Replace the bytes in 1..6 with 3 bytes of text.
+    /// map.apply_edit(1..6, 3);
+    ///
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), [(0..1, &'a'), (4..6, &'c')]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn apply_edit<R>(&mut self, byte_range: R, inserted_len: usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, usize::MAX);
+
+        assert!(end < usize::MAX, "the byte range must have a bounded end");
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        self.intervals.retain_mut(|(range, _)| {
+            if range.start == range.end {
+                let offset =
+                    Gravity::Left.shift(range.start, start..end, inserted_len);
+                *range = offset..offset;
+                return true;
+            }
+
+            let new_start =
+                Gravity::Right.shift(range.start, start..end, inserted_len);
+
+            let new_end =
+                Gravity::Left.shift(range.end, start..end, inserted_len);
+
+            *range = new_start..new_end;
+
+            new_start < new_end
+        });
+
+        // Empty intervals inside the edited range move to its start while the
+        // non-empty ones starting in it move to its end, which can leave the
+        // intervals out of order.
+        if !self.intervals.is_sorted_by_key(|(range, _)| range.start) {
+            self.intervals.sort_by_key(|(range, _)| range.start);
+        }
+
+        self.rebuild_max_ends();
+    }
+
+    /// Removes all the intervals from the map.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.intervals.clear();
+        self.max_ends.clear();
+    }
+
+    /// Inserts a new interval associating the given byte range to `value`.
+    ///
+    /// The intervals are kept sorted by their start in a `Vec`, so this runs
+    /// in O(n - i + log n), where n is the number of intervals in the map and
+    /// i is the position of the new interval. Inserting the intervals in
+    /// order of their start is O(log n) amortized, but inserting them in any
+    /// other order is quadratic: to build a map out of many intervals at
+    /// once, [`collect()`](Iterator::collect) them into it instead, which
+    /// sorts them once in O(n log n).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the
+    /// range has an unbounded end.
+    #[track_caller]
+    #[inline]
+    pub fn insert<R>(&mut self, byte_range: R, value: T)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, usize::MAX);
+
+        assert!(end < usize::MAX, "the byte range must have a bounded end");
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        let idx =
+            self.intervals.partition_point(|(range, _)| range.start <= start);
+
+        self.intervals.insert(idx, (start..end, value));
+
+        self.update_max_ends(idx);
+    }
+
+    /// Returns `true` if the map doesn't contain any intervals.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Returns an iterator over the byte ranges and values of all the
+    /// intervals in the map, sorted by the start of their ranges.
+    #[inline]
+    pub fn iter(&self) -> Intervals<'_, T> {
+        Intervals::from(self)
+    }
+
+    /// Returns the number of intervals in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Creates a new, empty `IntervalMap`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns an iterator over the byte ranges and values of the intervals
+    /// overlapping the given byte range, sorted by the start of their ranges.
+    ///
+    /// Two ranges overlap if they have at least one byte in common, with
+    /// empty ranges being treated as if they contained the byte right after
+    /// them.
+    ///
+    /// The intervals ending before the range are skipped without being
+    /// visited, so this runs in O((k + 1) log n), where k is the number of
+    /// overlapping intervals and n is the number of intervals in the map.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the
+    /// range has an unbounded end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::IntervalMap;
+    /// #
+    /// let mut map = IntervalMap::new();
+    ///
+    /// map.insert(0..4, 'a');
+    /// map.insert(2..6, 'b');
+    /// map.insert(6..6, 'c');
+    ///
+    /// let values = |range| map.overlapping(range).map(|(_, &v)| v);
+    ///
+    /// assert_eq!(values(3..5).collect::<String>(), "ab");
+    /// assert_eq!(values(4..4).collect::<String>(), "b");
+    /// assert_eq!(values(6..7).collect::<String>(), "c");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn overlapping<R>(&self, byte_range: R) -> OverlappingIntervals<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, usize::MAX);
+
+        assert!(end < usize::MAX, "the byte range must have a bounded end");
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        OverlappingIntervals::new(self, start..end.max(start + 1))
+    }
+
+    /// Returns the index of the first interval in `idx_range` that ends
+    /// after `byte_offset`, if there is one.
+    #[inline]
+    pub(super) fn next_ending_after(
+        &self,
+        idx_range: Range<usize>,
+        byte_offset: usize,
+    ) -> Option<usize> {
+        if idx_range.is_empty() {
+            return None;
+        }
+
+        let leaves = self.max_ends.len() / 2;
+
+        self.descend_max_ends(1, 0..leaves, idx_range, byte_offset)
+    }
+
+    /// The recursive step of [`next_ending_after()`](Self::next_ending_after)
+    /// for the node at `node_idx`, whose leaves span the intervals in
+    /// `node_range`.
+    #[inline]
+    fn descend_max_ends(
+        &self,
+        node_idx: usize,
+        node_range: Range<usize>,
+        idx_range: Range<usize>,
+        byte_offset: usize,
+    ) -> Option<usize> {
+        if node_range.end <= idx_range.start
+            || node_range.start >= idx_range.end
+            || self.max_ends[node_idx] <= byte_offset
+        {
+            return None;
+        }
+
+        if node_range.len() == 1 {
+            return Some(node_range.start);
+        }
+
+        let mid = node_range.start + node_range.len() / 2;
+
+        self.descend_max_ends(
+            2 * node_idx,
+            node_range.start..mid,
+            idx_range.clone(),
+            byte_offset,
+        )
+        .or_else(|| {
+            self.descend_max_ends(
+                2 * node_idx + 1,
+                mid..node_range.end,
+                idx_range,
+                byte_offset,
+            )
+        })
+    }
+
+    /// Updates `max_ends` after the intervals from `first` onwards have
+    /// changed, only visiting their leaves and the nodes above them, or
+    /// rebuilds it if it's too small for the intervals.
+    #[inline]
+    fn update_max_ends(&mut self, first: usize) {
+        let leaves = self.max_ends.len() / 2;
+
+        let len = self.intervals.len();
+
+        if len > leaves {
+            return self.rebuild_max_ends();
+        }
+
+        for (idx, (range, _)) in self.intervals.iter().enumerate().skip(first)
+        {
+            self.max_ends[leaves + idx] = range.end.max(range.start + 1);
+        }
+
+        // The nodes to update at every level are the ones between the
+        // parents of the first and last nodes updated at the level below.
+        let mut lo = leaves + first;
+        let mut hi = leaves + len - 1;
+
+        while lo > 1 {
+            lo /= 2;
+            hi /= 2;
+
+            for idx in lo..=hi {
+                self.max_ends[idx] =
+                    self.max_ends[2 * idx].max(self.max_ends[2 * idx + 1]);
+            }
+        }
+    }
+
+    /// Recomputes `max_ends` from scratch after the intervals have changed.
+    #[inline]
+    fn rebuild_max_ends(&mut self) {
+        self.max_ends.clear();
+
+        if self.intervals.is_empty() {
+            return;
+        }
+
+        let leaves = self.intervals.len().next_power_of_two();
+
+        self.max_ends.resize(2 * leaves, 0);
+
+        for (idx, (range, _)) in self.intervals.iter().enumerate() {
+            self.max_ends[leaves + idx] = range.end.max(range.start + 1);
+        }
+
+        for idx in (1..leaves).rev() {
+            self.max_ends[idx] =
+                self.max_ends[2 * idx].max(self.max_ends[2 * idx + 1]);
+        }
+    }
+
+    /// Retains only the intervals for which the predicate returns `true`.
+    #[inline]
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Range<usize>, &mut T) -> bool,
+    {
+        self.intervals.retain_mut(|(range, value)| f(range.clone(), value));
+        self.rebuild_max_ends();
+    }
+}
+
+/// Builds a map out of the intervals in the iterator, sorting them once in
+/// O(n log n). This is the fastest way to create a map from many intervals,
+/// since [`insert()`](IntervalMap::insert()) has to shift the intervals after
+/// the new one.
+impl<T, R> FromIterator<(R, T)> for IntervalMap<T>
+where
+    R: RangeBounds<usize>,
+{
+    #[track_caller]
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (R, T)>,
+    {
+        let mut intervals = iter
+            .into_iter()
+            .map(|(byte_range, value)| {
+                let (start, end) =
+                    range_bounds_to_start_end(byte_range, 0, usize::MAX);
+
+                assert!(
+                    end < usize::MAX,
+                    "the byte range must have a bounded end"
+                );
+
+                if start > end {
+                    panic::byte_start_after_end(start, end);
+                }
+
+                (start..end, value)
+            })
+            .collect::<Vec<_>>();
+
+        intervals.sort_by_key(|(range, _)| range.start);
+
+        let mut map = Self { intervals, max_ends: Vec::new() };
+        map.rebuild_max_ends();
+        map
+    }
+}
@@ -1,7 +1,14 @@
-use super::metrics::{ByteMetric, LineMetric, RawLineMetric};
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::byte_chunk::ByteChunk;
+use super::markers::Slot;
+use super::metrics::{ByteMetric, ChunkSummary, LineMetric, RawLineMetric};
+use super::pattern::{self, Pattern};
 use super::rope::RopeChunk;
-use super::{Rope, RopeSlice};
-use crate::tree::{Leaves, Units};
+use super::{IntervalMap, MarkerId, MarkerSet, Rope, RopeChain, RopeSlice};
+use crate::tree::{self, Leaves, Units};
 
 /// An iterator over the `&str` chunks of `Rope`s and `RopeSlice`s.
 ///
@@ -68,8 +75,12 @@ impl<'a> Iterator for Chunks<'a> {
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let exact = self.leaves.len();
-        (exact, Some(exact * 2))
+        // Each leaf yields one or two chunks, plus the ones we've already
+        // taken out of a leaf but haven't yielded yet.
+        let leaves = self.leaves.len();
+        let extras = self.forward_extra_right.is_some() as usize
+            + self.backward_extra_left.is_some() as usize;
+        (leaves + extras, Some(leaves * 2 + extras))
     }
 }
 
@@ -104,6 +115,133 @@ impl DoubleEndedIterator for Chunks<'_> {
 
 impl core::iter::FusedIterator for Chunks<'_> {}
 
+/// An iterator over the `&str` chunks of `Rope`s and `RopeSlice`s, together
+/// with the byte offset at which each chunk starts.
+///
+/// This struct is created by the `chunks_with_offsets` method on
+/// [`Rope`](Rope::chunks_with_offsets()) and
+/// [`RopeSlice`](RopeSlice::chunks_with_offsets()). See their documentation
+/// for more.
+#[derive(Clone)]
+pub struct ChunksWithOffsets<'a> {
+    chunks: Chunks<'a>,
+
+    /// The byte offset of the start of the next chunk yielded from the front.
+    forward_offset: usize,
+
+    /// The byte offset of the end of the next chunk yielded from the back.
+    backward_offset: usize,
+}
+
+impl<'a> From<&'a Rope> for ChunksWithOffsets<'a> {
+    #[inline]
+    fn from(rope: &'a Rope) -> Self {
+        Self {
+            chunks: rope.chunks(),
+            forward_offset: 0,
+            backward_offset: rope.byte_len(),
+        }
+    }
+}
+
+impl<'a> From<&RopeSlice<'a>> for ChunksWithOffsets<'a> {
+    #[inline]
+    fn from(slice: &RopeSlice<'a>) -> Self {
+        Self {
+            chunks: slice.chunks(),
+            forward_offset: 0,
+            backward_offset: slice.byte_len(),
+        }
+    }
+}
+
+impl<'a> Iterator for ChunksWithOffsets<'a> {
+    type Item = (usize, &'a str);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunks.next()?;
+        let offset = self.forward_offset;
+        self.forward_offset += chunk.len();
+        Some((offset, chunk))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ChunksWithOffsets<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunks.next_back()?;
+        self.backward_offset -= chunk.len();
+        Some((self.backward_offset, chunk))
+    }
+}
+
+impl core::iter::FusedIterator for ChunksWithOffsets<'_> {}
+
+/// An iterator over the summaries of the leaves of `Rope`s and `RopeSlice`s.
+///
+/// This struct is created by the `leaf_summaries` method on
+/// [`Rope`](Rope::leaf_summaries()) and
+/// [`RopeSlice`](RopeSlice::leaf_summaries()). See their documentation for
+/// more.
+#[derive(Clone)]
+pub struct LeafSummaries<'a> {
+    summaries: tree::LeafSummaries<'a, { Rope::arity() }, RopeChunk>,
+    byte_offset: usize,
+}
+
+impl<'a> From<&'a Rope> for LeafSummaries<'a> {
+    #[inline]
+    fn from(rope: &'a Rope) -> Self {
+        let mut summaries = rope.tree.leaf_summaries();
+        if rope.is_empty() {
+            let _ = summaries.next();
+        }
+        Self { summaries, byte_offset: 0 }
+    }
+}
+
+impl<'a> From<&RopeSlice<'a>> for LeafSummaries<'a> {
+    #[inline]
+    fn from(slice: &RopeSlice<'a>) -> Self {
+        let mut summaries = slice.tree_slice.leaf_summaries();
+        if slice.is_empty() {
+            let _ = summaries.next();
+        }
+        Self { summaries, byte_offset: 0 }
+    }
+}
+
+impl Iterator for LeafSummaries<'_> {
+    type Item = (usize, ChunkSummary);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let summary = self.summaries.next()?;
+
+        #[cfg(feature = "small_chunks")]
+        if summary.bytes() == 0 {
+            return self.next();
+        }
+
+        let byte_offset = self.byte_offset;
+        self.byte_offset += summary.bytes();
+        Some((byte_offset, summary))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.summaries.size_hint()
+    }
+}
+
+impl core::iter::FusedIterator for LeafSummaries<'_> {}
+
 /// An iterator over the bytes of `Rope`s and `RopeSlice`s.
 ///
 /// This struct is created by the `bytes` method on [`Rope`](Rope::bytes())
@@ -249,6 +387,12 @@ pub struct Chars<'a> {
 
     /// The number of bytes of `backward_chunk` which are yet to be yielded.
     backward_byte_idx: usize,
+
+    /// The number of bytes that have been yielded so far.
+    bytes_yielded: usize,
+
+    /// The total number of bytes this iterator will yield.
+    bytes_total: usize,
 }
 
 impl<'a> From<&'a Rope> for Chars<'a> {
@@ -260,6 +404,8 @@ impl<'a> From<&'a Rope> for Chars<'a> {
             forward_byte_idx: 0,
             backward_chunk: "",
             backward_byte_idx: 0,
+            bytes_yielded: 0,
+            bytes_total: rope.byte_len(),
         }
     }
 }
@@ -273,6 +419,8 @@ impl<'a> From<&RopeSlice<'a>> for Chars<'a> {
             forward_byte_idx: 0,
             backward_chunk: "",
             backward_byte_idx: 0,
+            bytes_yielded: 0,
+            bytes_total: slice.byte_len(),
         }
     }
 }
@@ -301,6 +449,7 @@ impl Iterator for Chars<'_> {
                 let len = ch.len_utf8();
                 self.backward_chunk = &self.backward_chunk[len..];
                 self.backward_byte_idx -= len;
+                self.bytes_yielded += len;
                 return Some(ch);
             }
         }
@@ -316,9 +465,17 @@ impl Iterator for Chars<'_> {
         };
 
         self.forward_byte_idx += ch.len_utf8();
+        self.bytes_yielded += ch.len_utf8();
 
         Some(ch)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every char is between 1 and 4 bytes long.
+        let bytes_left = self.bytes_total - self.bytes_yielded;
+        (bytes_left.div_ceil(4), Some(bytes_left))
+    }
 }
 
 impl DoubleEndedIterator for Chars<'_> {
@@ -343,6 +500,7 @@ impl DoubleEndedIterator for Chars<'_> {
 
                 self.forward_chunk = &self.forward_chunk
                     [..self.forward_chunk.len() - ch.len_utf8()];
+                self.bytes_yielded += ch.len_utf8();
 
                 return Some(ch);
             }
@@ -358,6 +516,7 @@ impl DoubleEndedIterator for Chars<'_> {
         };
 
         self.backward_byte_idx -= ch.len_utf8();
+        self.bytes_yielded += ch.len_utf8();
 
         Some(ch)
     }
@@ -365,6 +524,74 @@ impl DoubleEndedIterator for Chars<'_> {
 
 impl core::iter::FusedIterator for Chars<'_> {}
 
+/// An iterator over the [`char`]s of `Rope`s and `RopeSlice`s, together with
+/// their byte offsets.
+///
+/// This struct is created by the `char_indices` method on
+/// [`Rope`](Rope::char_indices()) and
+/// [`RopeSlice`](RopeSlice::char_indices()). See their documentation for
+/// more.
+#[derive(Clone)]
+pub struct CharIndices<'a> {
+    chars: Chars<'a>,
+
+    /// The byte offset of the next char yielded from the front.
+    forward_offset: usize,
+
+    /// The byte offset right after the next char yielded from the back.
+    backward_offset: usize,
+}
+
+impl<'a> From<&'a Rope> for CharIndices<'a> {
+    #[inline]
+    fn from(rope: &'a Rope) -> Self {
+        Self {
+            chars: rope.chars(),
+            forward_offset: 0,
+            backward_offset: rope.byte_len(),
+        }
+    }
+}
+
+impl<'a> From<&RopeSlice<'a>> for CharIndices<'a> {
+    #[inline]
+    fn from(slice: &RopeSlice<'a>) -> Self {
+        Self {
+            chars: slice.chars(),
+            forward_offset: 0,
+            backward_offset: slice.byte_len(),
+        }
+    }
+}
+
+impl Iterator for CharIndices<'_> {
+    type Item = (usize, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let ch = self.chars.next()?;
+        let offset = self.forward_offset;
+        self.forward_offset += ch.len_utf8();
+        Some((offset, ch))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chars.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for CharIndices<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let ch = self.chars.next_back()?;
+        self.backward_offset -= ch.len_utf8();
+        Some((self.backward_offset, ch))
+    }
+}
+
+impl core::iter::FusedIterator for CharIndices<'_> {}
+
 /// An iterator over the lines of `Rope`s and `RopeSlice`s, including the line
 /// terminators (`\n` or `\r\n`).
 ///
@@ -409,6 +636,12 @@ impl<'a> Iterator for RawLines<'a> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        // The units iterated from the front and from the back don't know
+        // about each other, so we stop once all the lines have been yielded.
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
+
         let (tree_slice, _) = self.units.next()?;
         self.lines_yielded += 1;
         Some(RopeSlice::from(tree_slice))
@@ -424,6 +657,10 @@ impl<'a> Iterator for RawLines<'a> {
 impl DoubleEndedIterator for RawLines<'_> {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
+
         let (tree_slice, _) = self.units.next_back()?;
         self.lines_yielded += 1;
         Some(RopeSlice::from(tree_slice))
@@ -477,11 +714,17 @@ impl<'a> From<&RopeSlice<'a>> for Lines<'a> {
     }
 }
 
-impl<'a> Iterator for Lines<'a> {
-    type Item = RopeSlice<'a>;
-
+impl<'a> Lines<'a> {
+    /// Returns the next line together with the number of bytes it spans
+    /// including its line terminator.
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
+    fn next_with_advance(&mut self) -> Option<(RopeSlice<'a>, usize)> {
+        // The units iterated from the front and from the back don't know
+        // about each other, so we stop once all the lines have been yielded.
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
+
         let (tree_slice, ByteMetric(advance)) = self.units.next()?;
         self.lines_yielded += 1;
 
@@ -497,19 +740,17 @@ impl<'a> Iterator for Lines<'a> {
             slice.truncate_last_char();
         }
 
-        Some(slice)
+        Some((slice, advance))
     }
 
+    /// Same as [`next_with_advance`](Self::next_with_advance()), but from
+    /// the back.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let exact = self.len();
-        (exact, Some(exact))
-    }
-}
+    fn next_back_with_advance(&mut self) -> Option<(RopeSlice<'a>, usize)> {
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
 
-impl DoubleEndedIterator for Lines<'_> {
-    #[inline]
-    fn next_back(&mut self) -> Option<Self::Item> {
         let (tree_slice, ByteMetric(advance)) = self.units.next_back()?;
         self.lines_yielded += 1;
 
@@ -522,7 +763,29 @@ impl DoubleEndedIterator for Lines<'_> {
             slice.truncate_last_char();
         }
 
-        Some(slice)
+        Some((slice, advance))
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = RopeSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_advance().map(|(line, _)| line)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let exact = self.len();
+        (exact, Some(exact))
+    }
+}
+
+impl DoubleEndedIterator for Lines<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.next_back_with_advance().map(|(line, _)| line)
     }
 }
 
@@ -535,82 +798,1244 @@ impl ExactSizeIterator for Lines<'_> {
 
 impl core::iter::FusedIterator for Lines<'_> {}
 
-#[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
-#[cfg(feature = "graphemes")]
-pub use graphemes::Graphemes;
+/// An iterator over the byte ranges of the lines of `Rope`s and
+/// `RopeSlice`s, not including the line terminators (`\n` or `\r\n`).
+///
+/// This struct is created by the `line_ranges` method on
+/// [`Rope`](Rope::line_ranges()) and [`RopeSlice`](RopeSlice::line_ranges()).
+/// See their documentation for more.
+#[derive(Clone)]
+pub struct LineRanges<'a> {
+    lines: Lines<'a>,
 
-#[cfg(feature = "graphemes")]
-mod graphemes {
-    use alloc::borrow::Cow;
+    /// The byte offset of the start of the next line yielded from the front.
+    forward_offset: usize,
 
-    use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete};
+    /// The byte offset right after the terminator of the next line yielded
+    /// from the back.
+    backward_offset: usize,
+}
 
-    use super::*;
+impl<'a> From<&'a Rope> for LineRanges<'a> {
+    #[inline]
+    fn from(rope: &'a Rope) -> Self {
+        Self {
+            lines: Lines::from(rope),
+            forward_offset: 0,
+            backward_offset: rope.byte_len(),
+        }
+    }
+}
 
-    /// An iterator over the extended grapheme clusters of `Rope`s and
-    /// `RopeSlice`s.
-    ///
-    /// This struct is created by the `graphemes` method on
-    /// [`Rope`](Rope::graphemes()) and [`RopeSlice`](RopeSlice::graphemes()).
-    /// See their documentation for more.
-    #[derive(Clone)]
-    pub struct Graphemes<'a> {
-        chunks: Chunks<'a>,
+impl<'a> From<&RopeSlice<'a>> for LineRanges<'a> {
+    #[inline]
+    fn from(slice: &RopeSlice<'a>) -> Self {
+        Self {
+            lines: Lines::from(slice),
+            forward_offset: 0,
+            backward_offset: slice.byte_len(),
+        }
+    }
+}
 
-        /// The slice we're iterating over, used to provide precontext to the
-        /// `GraphemeCursor`s.
-        slice: RopeSlice<'a>,
+impl Iterator for LineRanges<'_> {
+    type Item = Range<usize>;
 
-        /// The cursor used when calling [`Graphemes::next()`].
-        forward_cursor: GraphemeCursor,
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (line, advance) = self.lines.next_with_advance()?;
+        let start = self.forward_offset;
+        self.forward_offset += advance;
+        Some(start..start + line.byte_len())
+    }
 
-        /// The chunk used when calling [`Graphemes::next()`].
-        forward_chunk: &'a str,
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lines.size_hint()
+    }
+}
 
-        /// The byte offset of the start of
-        /// [`forward_chunk`](Self::forward_chunk) from the beginning of the
-        /// iterating range, which is also the sum of the bytes of all the
-        /// graphemes that have been yielded by [`Self::next()`].
-        forward_offset: usize,
+impl DoubleEndedIterator for LineRanges<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (line, advance) = self.lines.next_back_with_advance()?;
+        self.backward_offset -= advance;
+        let start = self.backward_offset;
+        Some(start..start + line.byte_len())
+    }
+}
 
-        /// The cursor used when calling [`Graphemes::next_back()`].
-        backward_cursor: GraphemeCursor,
+impl ExactSizeIterator for LineRanges<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.lines.len()
+    }
+}
 
-        /// The chunk used when calling [`Graphemes::next_back()`].
-        backward_chunk: &'a str,
+impl core::iter::FusedIterator for LineRanges<'_> {}
 
-        /// The byte offset of the end of
-        /// [`backward_chunk`](Self::backward_chunk) from the end of the
-        /// iterating range, which is also the sum of the bytes of all the
-        /// graphemes that have been yielded by [`Self::next_back()`].
-        backward_offset: usize,
-    }
+/// An iterator over the lines of `Rope`s and `RopeSlice`s, not including the
+/// line terminators, where a line can end with any of the mandatory line
+/// breaks of [UAX #14](https://www.unicode.org/reports/tr14/).
+///
+/// This struct is created by the `unicode_lines` method on
+/// [`Rope`](Rope::unicode_lines()) and
+/// [`RopeSlice`](RopeSlice::unicode_lines()). See their documentation for
+/// more.
+#[derive(Clone)]
+pub struct UnicodeLines<'a> {
+    slice: RopeSlice<'a>,
+    chunks: Chunks<'a>,
 
-    impl<'a> From<&'a Rope> for Graphemes<'a> {
-        #[inline]
-        fn from(rope: &'a Rope) -> Self {
-            let len = rope.byte_len();
+    /// The part of the current chunk that hasn't been scanned yet, and the
+    /// byte offset of its start.
+    chunk: &'a str,
+    chunk_offset: usize,
 
-            Self {
-                chunks: rope.chunks(),
-                slice: rope.byte_slice(..),
-                forward_cursor: GraphemeCursor::new(0, len, true),
-                forward_offset: 0,
-                forward_chunk: "",
-                backward_cursor: GraphemeCursor::new(len, len, true),
-                backward_chunk: "",
-                backward_offset: len,
-            }
-        }
-    }
+    /// The byte offset of the start of the next line.
+    line_start: usize,
 
-    impl<'a> From<&RopeSlice<'a>> for Graphemes<'a> {
-        #[inline]
-        fn from(slice: &RopeSlice<'a>) -> Self {
-            let len = slice.byte_len();
+    /// Whether the last line ended with a CR, in which case a LF right after
+    /// it is part of the same line terminator.
+    after_cr: bool,
+}
 
-            Self {
-                chunks: slice.chunks(),
+impl<'a> From<&'a Rope> for UnicodeLines<'a> {
+    #[inline]
+    fn from(rope: &'a Rope) -> Self {
+        Self::from(&rope.byte_slice(..))
+    }
+}
+
+impl<'a> From<&RopeSlice<'a>> for UnicodeLines<'a> {
+    #[inline]
+    fn from(slice: &RopeSlice<'a>) -> Self {
+        Self {
+            slice: *slice,
+            chunks: slice.chunks(),
+            chunk: "",
+            chunk_offset: 0,
+            line_start: 0,
+            after_cr: false,
+        }
+    }
+}
+
+impl<'a> Iterator for UnicodeLines<'a> {
+    type Item = RopeSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.chunk.is_empty() {
+                match self.chunks.next() {
+                    Some(chunk) => {
+                        self.chunk = chunk;
+                        continue;
+                    },
+
+                    // The final line break is optional, so we only yield
+                    // the last line if it's not empty.
+                    None => {
+                        let byte_len = self.slice.byte_len();
+
+                        if self.line_start == byte_len {
+                            return None;
+                        }
+
+                        let line = self.slice.byte_slice(self.line_start..);
+                        self.line_start = byte_len;
+                        return Some(line);
+                    },
+                }
+            }
+
+            if core::mem::take(&mut self.after_cr)
+                && self.chunk.starts_with('\n')
+            {
+                self.chunk = &self.chunk[1..];
+                self.chunk_offset += 1;
+                self.line_start += 1;
+                continue;
+            }
+
+            let Some((idx, ch)) =
+                self.chunk.char_indices().find(|&(_, ch)| {
+                    super::line_ending::is_unicode_line_break(ch)
+                })
+            else {
+                self.chunk_offset += self.chunk.len();
+                self.chunk = "";
+                continue;
+            };
+
+            let line_end = self.chunk_offset + idx;
+
+            let terminator_end = idx + ch.len_utf8();
+            self.chunk = &self.chunk[terminator_end..];
+            self.chunk_offset += terminator_end;
+
+            let line = self.slice.byte_slice(self.line_start..line_end);
+            self.line_start = self.chunk_offset;
+            self.after_cr = ch == '\r';
+
+            return Some(line);
+        }
+    }
+}
+
+impl core::iter::FusedIterator for UnicodeLines<'_> {}
+
+/// An iterator over the chunks of the members of a [`RopeChain`].
+///
+/// This struct is created by the [`chunks`](RopeChain::chunks()) method on
+/// [`RopeChain`]. See its documentation for more.
+#[derive(Clone)]
+pub struct ChainChunks<'c, 'a> {
+    members: core::slice::Iter<'c, RopeSlice<'a>>,
+    chunks: Option<Chunks<'a>>,
+}
+
+impl<'c, 'a> From<&'c RopeChain<'a>> for ChainChunks<'c, 'a> {
+    #[inline]
+    fn from(chain: &'c RopeChain<'a>) -> Self {
+        Self { members: chain.members().iter(), chunks: None }
+    }
+}
+
+impl<'a> Iterator for ChainChunks<'_, 'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(chunk) = self.chunks.as_mut().and_then(Iterator::next)
+            {
+                return Some(chunk);
+            }
+
+            self.chunks = Some(self.members.next()?.chunks());
+        }
+    }
+}
+
+impl core::iter::FusedIterator for ChainChunks<'_, '_> {}
+
+/// An iterator over the bytes of a [`RopeChain`].
+///
+/// This struct is created by the [`bytes`](RopeChain::bytes()) method on
+/// [`RopeChain`]. See its documentation for more.
+#[derive(Clone)]
+pub struct ChainBytes<'c, 'a> {
+    chunks: ChainChunks<'c, 'a>,
+    bytes: core::str::Bytes<'a>,
+}
+
+impl<'c, 'a> From<&'c RopeChain<'a>> for ChainBytes<'c, 'a> {
+    #[inline]
+    fn from(chain: &'c RopeChain<'a>) -> Self {
+        Self { chunks: chain.chunks(), bytes: "".bytes() }
+    }
+}
+
+impl Iterator for ChainBytes<'_, '_> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(byte) = self.bytes.next() {
+                return Some(byte);
+            }
+
+            self.bytes = self.chunks.next()?.bytes();
+        }
+    }
+}
+
+impl core::iter::FusedIterator for ChainBytes<'_, '_> {}
+
+/// An iterator over the `char`s of a [`RopeChain`].
+///
+/// This struct is created by the [`chars`](RopeChain::chars()) method on
+/// [`RopeChain`]. See its documentation for more.
+#[derive(Clone)]
+pub struct ChainChars<'c, 'a> {
+    chunks: ChainChunks<'c, 'a>,
+    chars: core::str::Chars<'a>,
+}
+
+impl<'c, 'a> From<&'c RopeChain<'a>> for ChainChars<'c, 'a> {
+    #[inline]
+    fn from(chain: &'c RopeChain<'a>) -> Self {
+        Self { chunks: chain.chunks(), chars: "".chars() }
+    }
+}
+
+impl Iterator for ChainChars<'_, '_> {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(ch) = self.chars.next() {
+                return Some(ch);
+            }
+
+            self.chars = self.chunks.next()?.chars();
+        }
+    }
+}
+
+impl core::iter::FusedIterator for ChainChars<'_, '_> {}
+
+/// An iterator over the markers of a [`MarkerSet`].
+///
+/// This struct is created by the [`iter`](MarkerSet::iter()) method on
+/// [`MarkerSet`]. See its documentation for more.
+#[derive(Clone)]
+pub struct Markers<'a> {
+    slots: core::iter::Enumerate<core::slice::Iter<'a, Slot>>,
+    len: usize,
+}
+
+impl<'a> From<&'a MarkerSet> for Markers<'a> {
+    #[inline]
+    fn from(markers: &'a MarkerSet) -> Self {
+        Self { slots: markers.slots.iter().enumerate(), len: markers.len() }
+    }
+}
+
+impl Iterator for Markers<'_> {
+    type Item = (MarkerId, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (idx, slot) in self.slots.by_ref() {
+            if let Some(marker) = slot.marker {
+                self.len -= 1;
+                let id = MarkerId { slot: idx, generation: slot.generation };
+                return Some((id, marker.byte_offset));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl ExactSizeIterator for Markers<'_> {}
+
+impl core::iter::FusedIterator for Markers<'_> {}
+
+/// An iterator over the byte ranges and values of the intervals in an
+/// [`IntervalMap`].
+///
+/// This struct is created by the [`iter`](IntervalMap::iter()) method on
+/// [`IntervalMap`]. See its documentation for more.
+pub struct Intervals<'a, T> {
+    intervals: core::slice::Iter<'a, (Range<usize>, T)>,
+}
+
+impl<T> Clone for Intervals<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { intervals: self.intervals.clone() }
+    }
+}
+
+impl<'a, T> From<&'a IntervalMap<T>> for Intervals<'a, T> {
+    #[inline]
+    fn from(map: &'a IntervalMap<T>) -> Self {
+        Self { intervals: map.intervals.iter() }
+    }
+}
+
+impl<'a, T> Iterator for Intervals<'a, T> {
+    type Item = (Range<usize>, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.intervals.next().map(|(range, value)| (range.clone(), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.intervals.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Intervals<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.intervals.next_back().map(|(range, value)| (range.clone(), value))
+    }
+}
+
+impl<T> ExactSizeIterator for Intervals<'_, T> {}
+
+impl<T> core::iter::FusedIterator for Intervals<'_, T> {}
+
+/// An iterator over the byte ranges and values of the intervals in an
+/// [`IntervalMap`] overlapping a given byte range.
+///
+/// This struct is created by the [`overlapping`](IntervalMap::overlapping())
+/// method on [`IntervalMap`]. See its documentation for more.
+pub struct OverlappingIntervals<'a, T> {
+    map: &'a IntervalMap<T>,
+
+    /// The index of the next interval that could overlap the byte range.
+    next_idx: usize,
+
+    /// The index of the first interval starting after the end of the byte
+    /// range, which can't overlap it and neither can the ones after it.
+    end_idx: usize,
+
+    /// The byte range being queried, which is never empty.
+    byte_range: Range<usize>,
+}
+
+impl<T> Clone for OverlappingIntervals<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map,
+            next_idx: self.next_idx,
+            end_idx: self.end_idx,
+            byte_range: self.byte_range.clone(),
+        }
+    }
+}
+
+impl<'a, T> OverlappingIntervals<'a, T> {
+    #[inline]
+    pub(super) fn new(
+        map: &'a IntervalMap<T>,
+        byte_range: Range<usize>,
+    ) -> Self {
+        debug_assert!(!byte_range.is_empty());
+
+        // The intervals are sorted by their start, so we can binary search
+        // the ones starting before the end of the byte range.
+        let end_idx = map
+            .intervals
+            .partition_point(|(range, _)| range.start < byte_range.end);
+
+        Self { map, next_idx: 0, end_idx, byte_range }
+    }
+}
+
+impl<'a, T> Iterator for OverlappingIntervals<'a, T> {
+    type Item = (Range<usize>, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(idx) = self.map.next_ending_after(
+            self.next_idx..self.end_idx,
+            self.byte_range.start,
+        ) else {
+            self.next_idx = self.end_idx;
+            return None;
+        };
+
+        self.next_idx = idx + 1;
+
+        let (range, value) = &self.map.intervals[idx];
+
+        Some((range.clone(), value))
+    }
+}
+
+impl<T> core::iter::FusedIterator for OverlappingIntervals<'_, T> {}
+
+/// An iterator over the byte ranges of the non-overlapping matches of a
+/// [`Pattern`] in a [`Rope`] or [`RopeSlice`].
+///
+/// This struct is created by the `find_iter` method on
+/// [`Rope`](Rope::find_iter()) and [`RopeSlice`](RopeSlice::find_iter()).
+/// See their documentation for more.
+#[derive(Clone)]
+pub struct FindIter<'a, P> {
+    slice: RopeSlice<'a>,
+
+    pattern: P,
+
+    /// The byte offset where the next search starts, or `None` if we're
+    /// done.
+    start: Option<usize>,
+
+    /// Whether `start` is the end of a previous match.
+    after_match: bool,
+}
+
+impl<'a, P: Pattern> FindIter<'a, P> {
+    #[inline]
+    pub(crate) fn new(slice: RopeSlice<'a>, pattern: P) -> Self {
+        Self { slice, pattern, start: Some(0), after_match: false }
+    }
+}
+
+impl<P: Pattern> Iterator for FindIter<'_, P> {
+    type Item = core::ops::Range<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.start?;
+
+        let Some(range) = pattern::next_match(
+            &mut self.pattern,
+            self.slice,
+            start,
+            self.after_match,
+        ) else {
+            self.start = None;
+            return None;
+        };
+
+        self.start = Some(range.end);
+        self.after_match = true;
+        Some(range)
+    }
+}
+
+impl<P: Pattern> core::iter::FusedIterator for FindIter<'_, P> {}
+
+/// Searches for the non-overlapping occurrences of a string in a
+/// [`RopeSlice`], including the ones spanning several chunks.
+///
+/// This is what the `&str` [`Pattern`] uses to find its matches.
+#[derive(Clone)]
+pub(crate) struct StrFindIter<'a, 'p> {
+    chunks: Chunks<'a>,
+
+    /// The chunk currently being searched.
+    chunk: &'a str,
+
+    /// The byte offset of the start of `chunk`.
+    chunk_start: usize,
+
+    /// The byte offset in `chunk` where the next search starts.
+    pos: usize,
+
+    pattern: &'p str,
+
+    /// The last `pattern.len() - 1` bytes before `chunk` that come after the
+    /// end of the last match, which is where an occurrence spanning multiple
+    /// chunks would start.
+    window: alloc::vec::Vec<u8>,
+
+    /// Whether we've already looked for an occurrence starting in `window`
+    /// and ending in `chunk`.
+    searched_window: bool,
+
+    /// Only used with an empty pattern, which matches at every char
+    /// boundary including the one at the very end.
+    yielded_end: bool,
+}
+
+impl<'a, 'p> StrFindIter<'a, 'p> {
+    #[inline]
+    pub(crate) fn new(slice: RopeSlice<'a>, pattern: &'p str) -> Self {
+        let overlap = pattern.len().saturating_sub(1);
+
+        Self {
+            chunks: slice.chunks(),
+            chunk: "",
+            chunk_start: 0,
+            pos: 0,
+            pattern,
+            window: alloc::vec::Vec::with_capacity(2 * overlap),
+            searched_window: true,
+            yielded_end: false,
+        }
+    }
+
+    /// Advances to the next chunk, returning `false` if there are none left.
+    #[inline]
+    fn next_chunk(&mut self) -> bool {
+        let Some(chunk) = self.chunks.next() else { return false };
+        self.chunk_start += self.chunk.len();
+        self.chunk = chunk;
+        self.pos = 0;
+        self.searched_window = false;
+        true
+    }
+
+    #[inline]
+    fn next_empty(&mut self) -> Option<core::ops::Range<usize>> {
+        while self.pos == self.chunk.len() {
+            if !self.next_chunk() {
+                if self.yielded_end {
+                    return None;
+                }
+                self.yielded_end = true;
+                let end = self.chunk_start + self.chunk.len();
+                return Some(end..end);
+            }
+        }
+
+        let offset = self.chunk_start + self.pos;
+        self.pos += self.chunk[self.pos..].chars().next().unwrap().len_utf8();
+        Some(offset..offset)
+    }
+}
+
+impl Iterator for StrFindIter<'_, '_> {
+    type Item = core::ops::Range<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pattern.is_empty() {
+            return self.next_empty();
+        }
+
+        let pattern = self.pattern.as_bytes();
+
+        let overlap = pattern.len() - 1;
+
+        loop {
+            if !self.searched_window && !self.window.is_empty() {
+                self.searched_window = true;
+
+                let window_len = self.window.len();
+
+                let head =
+                    &self.chunk.as_bytes()[..self.chunk.len().min(overlap)];
+
+                self.window.extend_from_slice(head);
+
+                let idx = self
+                    .window
+                    .windows(pattern.len())
+                    .position(|window| window == pattern);
+
+                self.window.truncate(window_len);
+
+                if let Some(idx) = idx.filter(|&idx| idx < window_len) {
+                    let start = self.chunk_start - window_len + idx;
+                    let end = start + pattern.len();
+                    self.window.clear();
+                    self.pos = end - self.chunk_start;
+                    return Some(start..end);
+                }
+            }
+
+            if let Some(idx) = self.chunk[self.pos..].find(self.pattern) {
+                let start = self.chunk_start + self.pos + idx;
+                self.window.clear();
+                self.pos += idx + pattern.len();
+                return Some(start..start + pattern.len());
+            }
+
+            // Keep the last `overlap` bytes after the last match around.
+            let rest = &self.chunk.as_bytes()[self.pos..];
+
+            if rest.len() >= overlap {
+                self.window.clear();
+                self.window.extend_from_slice(&rest[rest.len() - overlap..]);
+            } else {
+                self.window.extend_from_slice(rest);
+                let excess = self.window.len().saturating_sub(overlap);
+                self.window.drain(..excess);
+            }
+
+            self.pos = self.chunk.len();
+
+            if !self.next_chunk() {
+                return None;
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for StrFindIter<'_, '_> {}
+
+/// An iterator over the sub-slices of a [`Rope`] or [`RopeSlice`] matching a
+/// [`Pattern`].
+///
+/// This struct is created by the `matches` method on
+/// [`Rope`](Rope::matches()) and [`RopeSlice`](RopeSlice::matches()). See
+/// their documentation for more.
+#[derive(Clone)]
+pub struct Matches<'a, P> {
+    slice: RopeSlice<'a>,
+
+    pattern: P,
+
+    /// The byte offset where the next search starts, or `None` if we're
+    /// done.
+    start: Option<usize>,
+
+    /// Whether `start` is the end of a previous match.
+    after_match: bool,
+}
+
+impl<'a, P: Pattern> Matches<'a, P> {
+    #[inline]
+    pub(crate) fn new(slice: RopeSlice<'a>, pattern: P) -> Self {
+        Self { slice, pattern, start: Some(0), after_match: false }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for Matches<'a, P> {
+    type Item = RopeSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.start?;
+
+        let Some(range) = pattern::next_match(
+            &mut self.pattern,
+            self.slice,
+            start,
+            self.after_match,
+        ) else {
+            self.start = None;
+            return None;
+        };
+
+        self.start = Some(range.end);
+        self.after_match = true;
+        Some(self.slice.byte_slice(range))
+    }
+}
+
+impl<P: Pattern> core::iter::FusedIterator for Matches<'_, P> {}
+
+/// An iterator over the sub-slices of a [`Rope`] or [`RopeSlice`] separated
+/// by a [`Pattern`].
+///
+/// This struct is created by the `split` method on [`Rope`](Rope::split())
+/// and [`RopeSlice`](RopeSlice::split()). See their documentation for more.
+#[derive(Clone)]
+pub struct Split<'a, P> {
+    slice: RopeSlice<'a>,
+
+    pattern: P,
+
+    /// The byte offset of the start of the text that hasn't been yielded
+    /// yet, or `None` if we're done.
+    start: Option<usize>,
+
+    /// Whether `start` is the end of a previous match.
+    after_match: bool,
+}
+
+impl<'a, P: Pattern> Split<'a, P> {
+    #[inline]
+    pub(crate) fn new(slice: RopeSlice<'a>, pattern: P) -> Self {
+        Self { slice, pattern, start: Some(0), after_match: false }
+    }
+
+    /// Yields all the text that's left, ending the iteration.
+    #[inline]
+    fn remainder(&mut self) -> Option<RopeSlice<'a>> {
+        let start = self.start.take()?;
+        Some(self.slice.byte_slice(start..))
+    }
+}
+
+impl<'a, P: Pattern> Iterator for Split<'a, P> {
+    type Item = RopeSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.start?;
+
+        let Some(range) = pattern::next_match(
+            &mut self.pattern,
+            self.slice,
+            start,
+            self.after_match,
+        ) else {
+            return self.remainder();
+        };
+
+        self.start = Some(range.end);
+        self.after_match = true;
+        Some(self.slice.byte_slice(start..range.start))
+    }
+}
+
+impl<P: Pattern> core::iter::FusedIterator for Split<'_, P> {}
+
+/// An iterator over the sub-slices of a [`Rope`] or [`RopeSlice`] separated
+/// by a [`Pattern`], starting from the end.
+///
+/// This struct is created by the `rsplit` method on
+/// [`Rope`](Rope::rsplit()) and [`RopeSlice`](RopeSlice::rsplit()). See
+/// their documentation for more.
+#[derive(Clone)]
+pub struct RSplit<'a, P> {
+    slice: RopeSlice<'a>,
+
+    pattern: P,
+
+    /// The byte offset of the end of the text that hasn't been yielded yet,
+    /// or `None` if we're done.
+    end: Option<usize>,
+
+    /// Whether `end` is the start of a previous match.
+    before_match: bool,
+}
+
+impl<'a, P: Pattern> RSplit<'a, P> {
+    #[inline]
+    pub(crate) fn new(slice: RopeSlice<'a>, pattern: P) -> Self {
+        Self {
+            slice,
+            pattern,
+            end: Some(slice.byte_len()),
+            before_match: false,
+        }
+    }
+
+    /// Yields all the text that's left, ending the iteration.
+    #[inline]
+    fn remainder(&mut self) -> Option<RopeSlice<'a>> {
+        let end = self.end.take()?;
+        Some(self.slice.byte_slice(..end))
+    }
+}
+
+impl<'a, P: Pattern> Iterator for RSplit<'a, P> {
+    type Item = RopeSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.end?;
+
+        let Some(range) = pattern::prev_match(
+            &mut self.pattern,
+            self.slice,
+            end,
+            self.before_match,
+        ) else {
+            return self.remainder();
+        };
+
+        self.end = Some(range.start);
+        self.before_match = true;
+        Some(self.slice.byte_slice(range.end..end))
+    }
+}
+
+impl<P: Pattern> core::iter::FusedIterator for RSplit<'_, P> {}
+
+/// An iterator over at most `n` sub-slices of a [`Rope`] or [`RopeSlice`]
+/// separated by a [`Pattern`], the last of which contains the rest of the
+/// text.
+///
+/// This struct is created by the `splitn` method on
+/// [`Rope`](Rope::splitn()) and [`RopeSlice`](RopeSlice::splitn()). See
+/// their documentation for more.
+#[derive(Clone)]
+pub struct SplitN<'a, P> {
+    split: Split<'a, P>,
+
+    /// The number of sub-slices left to yield.
+    count: usize,
+}
+
+impl<'a, P: Pattern> SplitN<'a, P> {
+    #[inline]
+    pub(crate) fn new(slice: RopeSlice<'a>, count: usize, pattern: P) -> Self {
+        Self { split: Split::new(slice, pattern), count }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for SplitN<'a, P> {
+    type Item = RopeSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.split.remainder()
+            },
+            _ => {
+                self.count -= 1;
+                self.split.next()
+            },
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.count))
+    }
+}
+
+impl<P: Pattern> core::iter::FusedIterator for SplitN<'_, P> {}
+
+/// An iterator over at most `n` sub-slices of a [`Rope`] or [`RopeSlice`]
+/// separated by a [`Pattern`], starting from the end, the last of which
+/// contains the rest of the text.
+///
+/// This struct is created by the `rsplitn` method on
+/// [`Rope`](Rope::rsplitn()) and [`RopeSlice`](RopeSlice::rsplitn()). See
+/// their documentation for more.
+#[derive(Clone)]
+pub struct RSplitN<'a, P> {
+    split: RSplit<'a, P>,
+
+    /// The number of sub-slices left to yield.
+    count: usize,
+}
+
+impl<'a, P: Pattern> RSplitN<'a, P> {
+    #[inline]
+    pub(crate) fn new(slice: RopeSlice<'a>, count: usize, pattern: P) -> Self {
+        Self { split: RSplit::new(slice, pattern), count }
+    }
+}
+
+impl<'a, P: Pattern> Iterator for RSplitN<'a, P> {
+    type Item = RopeSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.split.remainder()
+            },
+            _ => {
+                self.count -= 1;
+                self.split.next()
+            },
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.count))
+    }
+}
+
+impl<P: Pattern> core::iter::FusedIterator for RSplitN<'_, P> {}
+
+/// An iterator over the byte ranges of the non-overlapping case-insensitive
+/// occurrences of a pattern in a [`Rope`] or [`RopeSlice`].
+///
+/// This struct is created by the `find_iter_ignore_case` method on
+/// [`Rope`](Rope::find_iter_ignore_case()) and
+/// [`RopeSlice`](RopeSlice::find_iter_ignore_case()). See their
+/// documentation for more.
+#[derive(Clone)]
+pub struct FindIterIgnoreCase<'a> {
+    char_indices: CharIndices<'a>,
+
+    /// The case-folded chars of the pattern.
+    pattern: Vec<char>,
+
+    /// The Knuth-Morris-Pratt failure function of `pattern`, i.e.
+    /// `failure[i]` is the length of the longest proper prefix of
+    /// `pattern[..=i]` that's also a suffix of it.
+    failure: Vec<usize>,
+
+    /// The byte offsets of the chars currently matching the start of the
+    /// pattern.
+    match_offsets: VecDeque<usize>,
+
+    /// The byte length of the text being searched, which is where an empty
+    /// pattern matches last.
+    byte_len: usize,
+
+    /// Only used with an empty pattern, which matches at every char
+    /// boundary including the one at the very end.
+    yielded_end: bool,
+}
+
+impl<'a> FindIterIgnoreCase<'a> {
+    #[inline]
+    pub(crate) fn new(slice: RopeSlice<'a>, pattern: &str) -> Self {
+        let pattern =
+            pattern.chars().map(super::case::fold_char).collect::<Vec<_>>();
+
+        let mut failure = alloc::vec![0; pattern.len()];
+
+        let mut len = 0;
+
+        for idx in 1..pattern.len() {
+            while len > 0 && pattern[idx] != pattern[len] {
+                len = failure[len - 1];
+            }
+            if pattern[idx] == pattern[len] {
+                len += 1;
+            }
+            failure[idx] = len;
+        }
+
+        Self {
+            char_indices: CharIndices::from(&slice),
+            match_offsets: VecDeque::with_capacity(pattern.len()),
+            pattern,
+            failure,
+            byte_len: slice.byte_len(),
+            yielded_end: false,
+        }
+    }
+}
+
+impl Iterator for FindIterIgnoreCase<'_> {
+    type Item = core::ops::Range<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pattern.is_empty() {
+            if let Some((offset, _)) = self.char_indices.next() {
+                return Some(offset..offset);
+            }
+            if self.yielded_end {
+                return None;
+            }
+            self.yielded_end = true;
+            return Some(self.byte_len..self.byte_len);
+        }
+
+        for (offset, ch) in self.char_indices.by_ref() {
+            let folded = super::case::fold_char(ch);
+
+            let mut matched = self.match_offsets.len();
+
+            while matched > 0 && self.pattern[matched] != folded {
+                let shorter = self.failure[matched - 1];
+                self.match_offsets.drain(..matched - shorter);
+                matched = shorter;
+            }
+
+            if self.pattern[matched] != folded {
+                continue;
+            }
+
+            self.match_offsets.push_back(offset);
+
+            if self.match_offsets.len() == self.pattern.len() {
+                let start = self.match_offsets[0];
+                self.match_offsets.clear();
+                return Some(start..offset + ch.len_utf8());
+            }
+        }
+
+        None
+    }
+}
+
+impl core::iter::FusedIterator for FindIterIgnoreCase<'_> {}
+
+/// An iterator over the byte chunks of `ByteRope`s and `ByteRopeSlice`s.
+///
+/// This struct is created by the `chunks` method on
+/// [`ByteRope`](super::ByteRope::chunks()) and
+/// [`ByteRopeSlice`](super::ByteRopeSlice::chunks()). See their documentation for
+/// more.
+#[derive(Clone)]
+pub struct ByteRopeChunks<'a> {
+    pub(super) leaves: Leaves<'a, { Rope::arity() }, ByteChunk>,
+}
+
+impl<'a> Iterator for ByteRopeChunks<'a> {
+    type Item = &'a [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let chunk = self.leaves.next()?;
+            if !chunk.is_empty() {
+                return Some(chunk);
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.leaves.len()))
+    }
+}
+
+impl DoubleEndedIterator for ByteRopeChunks<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let chunk = self.leaves.next_back()?;
+            if !chunk.is_empty() {
+                return Some(chunk);
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for ByteRopeChunks<'_> {}
+
+/// An iterator over the bytes of `ByteRope`s and `ByteRopeSlice`s.
+///
+/// This struct is created by the `bytes` method on
+/// [`ByteRope`](super::ByteRope::bytes()) and
+/// [`ByteRopeSlice`](super::ByteRopeSlice::bytes()). See their documentation for
+/// more.
+#[derive(Clone)]
+pub struct ByteRopeBytes<'a> {
+    chunks: ByteRopeChunks<'a>,
+
+    /// The bytes yet to be yielded of the chunk used when calling
+    /// [`ByteRopeBytes::next()`].
+    forward_chunk: &'a [u8],
+
+    /// The bytes yet to be yielded of the chunk used when calling
+    /// [`ByteRopeBytes::next_back()`].
+    backward_chunk: &'a [u8],
+
+    /// The number of bytes this iterator has yet to yield.
+    bytes_remaining: usize,
+}
+
+impl<'a> ByteRopeBytes<'a> {
+    #[inline]
+    pub(super) fn new(chunks: ByteRopeChunks<'a>, len: usize) -> Self {
+        Self {
+            chunks,
+            forward_chunk: &[],
+            backward_chunk: &[],
+            bytes_remaining: len,
+        }
+    }
+}
+
+impl Iterator for ByteRopeBytes<'_> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.forward_chunk.is_empty() {
+            self.forward_chunk = match self.chunks.next() {
+                Some(chunk) => chunk,
+                None => core::mem::take(&mut self.backward_chunk),
+            };
+        }
+
+        let (&byte, rest) = self.forward_chunk.split_first()?;
+        self.forward_chunk = rest;
+        self.bytes_remaining -= 1;
+        Some(byte)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let exact = self.len();
+        (exact, Some(exact))
+    }
+}
+
+impl DoubleEndedIterator for ByteRopeBytes<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.backward_chunk.is_empty() {
+            self.backward_chunk = match self.chunks.next_back() {
+                Some(chunk) => chunk,
+                None => core::mem::take(&mut self.forward_chunk),
+            };
+        }
+
+        let (&byte, rest) = self.backward_chunk.split_last()?;
+        self.backward_chunk = rest;
+        self.bytes_remaining -= 1;
+        Some(byte)
+    }
+}
+
+impl ExactSizeIterator for ByteRopeBytes<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.bytes_remaining
+    }
+}
+
+impl core::iter::FusedIterator for ByteRopeBytes<'_> {}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+#[cfg(feature = "graphemes")]
+pub use graphemes::Graphemes;
+
+#[cfg(feature = "graphemes")]
+mod graphemes {
+    use alloc::borrow::Cow;
+
+    use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete};
+
+    use super::*;
+
+    /// An iterator over the extended grapheme clusters of `Rope`s and
+    /// `RopeSlice`s.
+    ///
+    /// This struct is created by the `graphemes` method on
+    /// [`Rope`](Rope::graphemes()) and [`RopeSlice`](RopeSlice::graphemes()).
+    /// See their documentation for more.
+    #[derive(Clone)]
+    pub struct Graphemes<'a> {
+        chunks: Chunks<'a>,
+
+        /// The slice we're iterating over, used to provide precontext to the
+        /// `GraphemeCursor`s.
+        slice: RopeSlice<'a>,
+
+        /// The cursor used when calling [`Graphemes::next()`].
+        forward_cursor: GraphemeCursor,
+
+        /// The chunk used when calling [`Graphemes::next()`].
+        forward_chunk: &'a str,
+
+        /// The byte offset of the start of
+        /// [`forward_chunk`](Self::forward_chunk) from the beginning of the
+        /// iterating range, which is also the sum of the bytes of all the
+        /// graphemes that have been yielded by [`Self::next()`].
+        forward_offset: usize,
+
+        /// The cursor used when calling [`Graphemes::next_back()`].
+        backward_cursor: GraphemeCursor,
+
+        /// The chunk used when calling [`Graphemes::next_back()`].
+        backward_chunk: &'a str,
+
+        /// The byte offset of the end of
+        /// [`backward_chunk`](Self::backward_chunk) from the end of the
+        /// iterating range, which is also the sum of the bytes of all the
+        /// graphemes that have been yielded by [`Self::next_back()`].
+        backward_offset: usize,
+    }
+
+    impl<'a> From<&'a Rope> for Graphemes<'a> {
+        #[inline]
+        fn from(rope: &'a Rope) -> Self {
+            let len = rope.byte_len();
+
+            Self {
+                chunks: rope.chunks(),
+                slice: rope.byte_slice(..),
+                forward_cursor: GraphemeCursor::new(0, len, true),
+                forward_offset: 0,
+                forward_chunk: "",
+                backward_cursor: GraphemeCursor::new(len, len, true),
+                backward_chunk: "",
+                backward_offset: len,
+            }
+        }
+    }
+
+    impl<'a> From<&RopeSlice<'a>> for Graphemes<'a> {
+        #[inline]
+        fn from(slice: &RopeSlice<'a>) -> Self {
+            let len = slice.byte_len();
+
+            Self {
+                chunks: slice.chunks(),
                 slice: *slice,
                 forward_cursor: GraphemeCursor::new(0, len, true),
                 forward_offset: 0,
@@ -622,289 +2047,1275 @@ mod graphemes {
         }
     }
 
-    impl<'a> Iterator for Graphemes<'a> {
+    impl<'a> Iterator for Graphemes<'a> {
+        type Item = Cow<'a, str>;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            debug_assert_eq!(
+                self.forward_offset,
+                self.forward_cursor.cur_cursor()
+            );
+
+            let mut using_backward_chunk = false;
+
+            if self.forward_chunk.is_empty() {
+                self.forward_chunk = if let Some(next) = self.chunks.next() {
+                    next
+                } else if !self.backward_chunk.is_empty() {
+                    debug_assert!(
+                        self.backward_cursor.cur_cursor()
+                            > self.forward_cursor.cur_cursor()
+                    );
+                    using_backward_chunk = true;
+                    self.backward_chunk
+                } else {
+                    return None;
+                }
+            }
+
+            let mut grapheme = Cow::Borrowed("");
+
+            loop {
+                match self
+                    .forward_cursor
+                    // The chunk passed to `next_boundary` can't be empty or
+                    // it'll panic.
+                    .next_boundary(self.forward_chunk, self.forward_offset)
+                {
+                    Ok(Some(byte_end)) => {
+                        if byte_end == self.forward_offset {
+                            debug_assert!(!grapheme.is_empty());
+                            return Some(grapheme);
+                        }
+
+                        debug_assert!(byte_end > self.forward_offset);
+
+                        let grapheme_end = &self.forward_chunk
+                            [..byte_end - self.forward_offset];
+
+                        match &mut grapheme {
+                            Cow::Borrowed(gr) if gr.is_empty() => {
+                                *gr = grapheme_end;
+                            },
+
+                            Cow::Borrowed(gr) => {
+                                let mut gr = gr.to_owned();
+                                gr.push_str(grapheme_end);
+                                grapheme = Cow::Owned(gr);
+                            },
+
+                            Cow::Owned(gr) => {
+                                gr.push_str(grapheme_end);
+                            },
+                        }
+
+                        self.forward_offset += grapheme_end.len();
+
+                        self.forward_chunk =
+                            &self.forward_chunk[grapheme_end.len()..];
+
+                        if using_backward_chunk {
+                            self.backward_chunk = self.forward_chunk;
+                        }
+
+                        return Some(grapheme);
+                    },
+
+                    Ok(None) => return None,
+
+                    Err(GraphemeIncomplete::NextChunk) => {
+                        match &mut grapheme {
+                            Cow::Borrowed(gr) if gr.is_empty() => {
+                                *gr = self.forward_chunk;
+                            },
+
+                            Cow::Borrowed(gr) => {
+                                let mut gr = gr.to_owned();
+                                gr.push_str(self.forward_chunk);
+                                grapheme = Cow::Owned(gr);
+                            },
+
+                            Cow::Owned(gr) => gr.push_str(self.forward_chunk),
+                        }
+
+                        self.forward_offset += self.forward_chunk.len();
+
+                        self.forward_chunk =
+                            if let Some(next) = self.chunks.next() {
+                                next
+                            } else if !self.backward_chunk.is_empty() {
+                                debug_assert!(
+                                    self.backward_cursor.cur_cursor()
+                                        > self.forward_cursor.cur_cursor()
+                                );
+                                using_backward_chunk = true;
+                                self.backward_chunk
+                            } else {
+                                return None;
+                            }
+                    },
+
+                    // Why does it ask for precontext if we've been feeding it
+                    // stuff from the beginning of the range?
+                    Err(GraphemeIncomplete::PreContext(byte_idx)) => {
+                        let slice = self.slice.byte_slice(..byte_idx);
+                        let chunk = slice.chunks().next_back().unwrap();
+                        self.forward_cursor
+                            .provide_context(chunk, byte_idx - chunk.len());
+                    },
+
+                    Err(_) => {
+                        unreachable!();
+                    },
+                }
+            }
+        }
+
+        #[inline]
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let hi = self.backward_offset - self.forward_offset;
+            let lo = (hi != 0) as usize;
+            (lo, Some(hi))
+        }
+    }
+
+    impl DoubleEndedIterator for Graphemes<'_> {
+        #[inline]
+        fn next_back(&mut self) -> Option<Self::Item> {
+            debug_assert_eq!(
+                self.backward_offset,
+                self.backward_cursor.cur_cursor()
+            );
+
+            let mut using_forward_chunk = false;
+
+            if self.backward_chunk.is_empty() {
+                self.backward_chunk =
+                    if let Some(prev) = self.chunks.next_back() {
+                        prev
+                    } else if !self.forward_chunk.is_empty() {
+                        debug_assert!(
+                            self.backward_cursor.cur_cursor()
+                                > self.forward_cursor.cur_cursor()
+                        );
+                        using_forward_chunk = true;
+                        self.forward_chunk
+                    } else {
+                        return None;
+                    }
+            }
+
+            let mut grapheme = Cow::Borrowed("");
+
+            loop {
+                match self
+                    .backward_cursor
+                    // The chunk passed to `prev_boundary` can't be empty or
+                    // it'll panic.
+                    .prev_boundary(
+                        self.backward_chunk,
+                        self.backward_cursor.cur_cursor()
+                            - self.backward_chunk.len(),
+                    ) {
+                    Ok(Some(byte_start)) => {
+                        if byte_start == self.backward_offset {
+                            debug_assert!(!grapheme.is_empty());
+                            return Some(grapheme);
+                        }
+
+                        debug_assert!(byte_start < self.backward_offset);
+
+                        let grapheme_start = {
+                            let start_len = self.backward_offset - byte_start;
+                            let chunk_len = self.backward_chunk.len();
+                            &self.backward_chunk[(chunk_len - start_len)..]
+                        };
+
+                        match &mut grapheme {
+                            Cow::Borrowed(gr) if gr.is_empty() => {
+                                *gr = grapheme_start;
+                            },
+
+                            Cow::Borrowed(gr) => {
+                                let mut new = String::with_capacity(
+                                    grapheme_start.len() + gr.len(),
+                                );
+                                new.push_str(grapheme_start);
+                                new.push_str(gr);
+                                grapheme = Cow::Owned(new);
+                            },
+
+                            Cow::Owned(gr) => {
+                                let mut new = String::with_capacity(
+                                    grapheme_start.len() + gr.len(),
+                                );
+                                new.push_str(grapheme_start);
+                                new.push_str(&*gr);
+                                *gr = new;
+                            },
+                        }
+
+                        self.backward_offset -= grapheme_start.len();
+
+                        self.backward_chunk =
+                            &self.backward_chunk[..self.backward_chunk.len()
+                                - grapheme_start.len()];
+
+                        if using_forward_chunk {
+                            self.forward_chunk = self.backward_chunk;
+                        }
+
+                        return Some(grapheme);
+                    },
+
+                    Ok(None) => return None,
+
+                    Err(GraphemeIncomplete::PrevChunk) => {
+                        match &mut grapheme {
+                            Cow::Borrowed(gr) if gr.is_empty() => {
+                                *gr = self.backward_chunk;
+                            },
+
+                            Cow::Borrowed(gr) => {
+                                let mut new = String::with_capacity(
+                                    self.backward_chunk.len() + gr.len(),
+                                );
+                                new.push_str(self.backward_chunk);
+                                new.push_str(gr);
+                                grapheme = Cow::Owned(new);
+                            },
+
+                            Cow::Owned(gr) => {
+                                let mut new = String::with_capacity(
+                                    self.backward_chunk.len() + gr.len(),
+                                );
+                                new.push_str(self.backward_chunk);
+                                new.push_str(gr);
+                                *gr = new;
+                            },
+                        }
+
+                        self.backward_offset -= self.backward_chunk.len();
+
+                        self.backward_chunk =
+                            if let Some(prev) = self.chunks.next_back() {
+                                prev
+                            } else if !self.forward_chunk.is_empty() {
+                                debug_assert!(
+                                    self.backward_cursor.cur_cursor()
+                                        > self.forward_cursor.cur_cursor()
+                                );
+                                using_forward_chunk = true;
+                                self.forward_chunk
+                            } else {
+                                return None;
+                            }
+                    },
+
+                    // Why does it ask for precontext if we're iterating
+                    // backward? Shouldn't it always just ask for the previous
+                    // chunk?
+                    Err(GraphemeIncomplete::PreContext(byte_idx)) => {
+                        let slice = self.slice.byte_slice(..byte_idx);
+                        let chunk = slice.chunks().next_back().unwrap();
+                        self.backward_cursor
+                            .provide_context(chunk, byte_idx - chunk.len());
+                    },
+
+                    Err(_) => {
+                        unreachable!();
+                    },
+                }
+            }
+        }
+    }
+
+    impl core::iter::FusedIterator for Graphemes<'_> {}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+#[cfg(feature = "graphemes")]
+pub use segments::{Sentences, WordBounds, Words};
+
+#[cfg(feature = "graphemes")]
+mod segments {
+    use alloc::borrow::Cow;
+    use alloc::string::String;
+    use alloc::vec::{self, Vec};
+
+    use unicode_segmentation::{
+        USentenceBounds,
+        UWordBounds,
+        UnicodeSegmentation,
+    };
+
+    use super::*;
+
+    /// A kind of text segmentation defined by UAX #29.
+    trait Segmentation {
+        type Bounds<'a>: Iterator<Item = &'a str> + Clone;
+
+        /// Splits a `&str` into its segments.
+        fn bounds(text: &str) -> Self::Bounds<'_>;
+
+        /// Returns whether the text can be segmented independently on either
+        /// side of the boundary between `prev` and `next`, i.e. whether
+        /// splitting it there gives the same segments as if the two sides
+        /// were the end and the start of the text.
+        fn is_split_point(prev: char, next: char) -> bool;
+    }
+
+    #[derive(Clone)]
+    struct WordSegmentation;
+
+    impl Segmentation for WordSegmentation {
+        type Bounds<'a> = UWordBounds<'a>;
+
+        #[inline]
+        fn bounds(text: &str) -> Self::Bounds<'_> {
+            text.split_word_bounds()
+        }
+
+        /// A line feed is always followed by a word boundary, and a space
+        /// that isn't preceded by another space is always preceded by one.
+        /// None of the rules look past either of those.
+        #[inline]
+        fn is_split_point(prev: char, next: char) -> bool {
+            prev == '\n' || (is_space(next) && !is_space(prev))
+        }
+    }
+
+    #[derive(Clone)]
+    struct SentenceSegmentation;
+
+    impl Segmentation for SentenceSegmentation {
+        type Bounds<'a> = USentenceBounds<'a>;
+
+        #[inline]
+        fn bounds(text: &str) -> Self::Bounds<'_> {
+            text.split_sentence_bounds()
+        }
+
+        /// A line feed is always followed by a sentence boundary, and none of
+        /// the rules look past it.
+        #[inline]
+        fn is_split_point(prev: char, _next: char) -> bool {
+            prev == '\n'
+        }
+    }
+
+    /// Returns whether `ch` has the `WSegSpace` word break property.
+    #[inline]
+    fn is_space(ch: char) -> bool {
+        matches!(
+            ch,
+            ' ' | '\u{1680}'
+                | '\u{2000}'..='\u{2006}'
+                | '\u{2008}'..='\u{200A}'
+                | '\u{205F}'
+                | '\u{3000}'
+        )
+    }
+
+    /// The segments of the text yielded by a `Chunks` iterator.
+    ///
+    /// To avoid copying the text, the chunks are only segmented up to their
+    /// last split point, and what comes after it is buffered until the next
+    /// split point is found.
+    #[derive(Clone)]
+    struct Segments<'a, S: Segmentation> {
+        chunks: Chunks<'a>,
+
+        /// The segments of the part of the current chunk that's already been
+        /// segmented.
+        borrowed: S::Bounds<'a>,
+
+        /// The segments spanning more than one chunk that haven't been
+        /// yielded yet.
+        owned: vec::IntoIter<String>,
+
+        /// The text read from the chunks that hasn't been segmented yet
+        /// because it could still be part of the same segment as the start of
+        /// the next chunk.
+        pending: String,
+    }
+
+    impl<'a, S: Segmentation> Segments<'a, S> {
+        #[inline]
+        fn new(chunks: Chunks<'a>) -> Self {
+            Self {
+                chunks,
+                borrowed: S::bounds(""),
+                owned: Vec::new().into_iter(),
+                pending: String::new(),
+            }
+        }
+
+        /// Returns the byte offset of the first split point in `chunk`, where
+        /// `prev` is the char right before it.
+        #[inline]
+        fn first_split_point(mut prev: char, chunk: &str) -> Option<usize> {
+            for (offset, ch) in chunk.char_indices() {
+                if S::is_split_point(prev, ch) {
+                    return Some(offset);
+                }
+                prev = ch;
+            }
+            None
+        }
+
+        /// Segments the pending text, which will be yielded before anything
+        /// else.
+        #[inline]
+        fn flush_pending(&mut self) {
+            debug_assert!(self.owned.as_slice().is_empty());
+
+            self.owned = S::bounds(&self.pending)
+                .map(String::from)
+                .collect::<Vec<_>>()
+                .into_iter();
+
+            self.pending.clear();
+        }
+
+        /// Returns the byte offset of the last split point in `chunk`, not
+        /// counting its start.
+        #[inline]
+        fn last_split_point(chunk: &str) -> Option<usize> {
+            let mut chars = chunk.char_indices().rev();
+            let (mut offset, mut next) = chars.next()?;
+            for (prev_offset, prev) in chars {
+                if S::is_split_point(prev, next) {
+                    return Some(offset);
+                }
+                offset = prev_offset;
+                next = prev;
+            }
+            None
+        }
+
+        /// Reads the next chunk, segmenting as much of it as possible.
+        /// Returns `false` if there's nothing left to read.
+        #[inline]
+        fn read_chunk(&mut self) -> bool {
+            let Some(chunk) = self.chunks.next() else {
+                if self.pending.is_empty() {
+                    return false;
+                }
+                self.flush_pending();
+                return true;
+            };
+
+            let mut rest = chunk;
+
+            if let Some(last) = self.pending.chars().next_back() {
+                let Some(split) = Self::first_split_point(last, chunk) else {
+                    self.pending.push_str(chunk);
+                    return true;
+                };
+
+                self.pending.push_str(&chunk[..split]);
+                self.flush_pending();
+                rest = &chunk[split..];
+            }
+
+            match Self::last_split_point(rest) {
+                Some(split) => {
+                    self.borrowed = S::bounds(&rest[..split]);
+                    self.pending.push_str(&rest[split..]);
+                },
+                None => self.pending.push_str(rest),
+            }
+
+            true
+        }
+    }
+
+    impl<'a, S: Segmentation> Iterator for Segments<'a, S> {
         type Item = Cow<'a, str>;
 
         #[inline]
         fn next(&mut self) -> Option<Self::Item> {
-            debug_assert_eq!(
-                self.forward_offset,
-                self.forward_cursor.cur_cursor()
-            );
+            loop {
+                if let Some(segment) = self.owned.next() {
+                    return Some(Cow::Owned(segment));
+                }
 
-            let mut using_backward_chunk = false;
+                if let Some(segment) = self.borrowed.next() {
+                    return Some(Cow::Borrowed(segment));
+                }
 
-            if self.forward_chunk.is_empty() {
-                self.forward_chunk = if let Some(next) = self.chunks.next() {
-                    next
-                } else if !self.backward_chunk.is_empty() {
-                    debug_assert!(
-                        self.backward_cursor.cur_cursor()
-                            > self.forward_cursor.cur_cursor()
-                    );
-                    using_backward_chunk = true;
-                    self.backward_chunk
-                } else {
+                if !self.read_chunk() {
                     return None;
                 }
             }
+        }
+    }
 
-            let mut grapheme = Cow::Borrowed("");
+    /// An iterator over the word boundaries of `Rope`s and `RopeSlice`s, as
+    /// defined by [Unicode Standard Annex #29][uax29].
+    ///
+    /// Every word, run of whitespace and punctuation mark is yielded, so
+    /// concatenating all the items gives back the original text.
+    ///
+    /// This struct is created by the `word_bounds` method on
+    /// [`Rope`](Rope::word_bounds()) and
+    /// [`RopeSlice`](RopeSlice::word_bounds()). See their documentation for
+    /// more.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    #[derive(Clone)]
+    pub struct WordBounds<'a> {
+        segments: Segments<'a, WordSegmentation>,
+    }
 
-            loop {
-                match self
-                    .forward_cursor
-                    // The chunk passed to `next_boundary` can't be empty or
-                    // it'll panic.
-                    .next_boundary(self.forward_chunk, self.forward_offset)
-                {
-                    Ok(Some(byte_end)) => {
-                        if byte_end == self.forward_offset {
-                            debug_assert!(!grapheme.is_empty());
-                            return Some(grapheme);
-                        }
+    impl<'a> From<&'a Rope> for WordBounds<'a> {
+        #[inline]
+        fn from(rope: &'a Rope) -> Self {
+            Self { segments: Segments::new(rope.chunks()) }
+        }
+    }
 
-                        debug_assert!(byte_end > self.forward_offset);
+    impl<'a> From<&RopeSlice<'a>> for WordBounds<'a> {
+        #[inline]
+        fn from(slice: &RopeSlice<'a>) -> Self {
+            Self { segments: Segments::new(slice.chunks()) }
+        }
+    }
+
+    impl<'a> Iterator for WordBounds<'a> {
+        type Item = Cow<'a, str>;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            self.segments.next()
+        }
+    }
+
+    impl core::iter::FusedIterator for WordBounds<'_> {}
+
+    /// An iterator over the words of `Rope`s and `RopeSlice`s, as defined by
+    /// [Unicode Standard Annex #29][uax29].
+    ///
+    /// This is the same as [`WordBounds`] except that it skips the items
+    /// that don't contain any alphanumeric characters, like whitespace and
+    /// punctuation.
+    ///
+    /// This struct is created by the `words` method on
+    /// [`Rope`](Rope::words()) and [`RopeSlice`](RopeSlice::words()). See
+    /// their documentation for more.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    #[derive(Clone)]
+    pub struct Words<'a> {
+        bounds: WordBounds<'a>,
+    }
+
+    impl<'a> From<&'a Rope> for Words<'a> {
+        #[inline]
+        fn from(rope: &'a Rope) -> Self {
+            Self { bounds: WordBounds::from(rope) }
+        }
+    }
+
+    impl<'a> From<&RopeSlice<'a>> for Words<'a> {
+        #[inline]
+        fn from(slice: &RopeSlice<'a>) -> Self {
+            Self { bounds: WordBounds::from(slice) }
+        }
+    }
+
+    impl<'a> Iterator for Words<'a> {
+        type Item = Cow<'a, str>;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            self.bounds
+                .by_ref()
+                .find(|word| word.chars().any(char::is_alphanumeric))
+        }
+    }
+
+    impl core::iter::FusedIterator for Words<'_> {}
+
+    /// An iterator over the sentences of `Rope`s and `RopeSlice`s, as defined
+    /// by [Unicode Standard Annex #29][uax29].
+    ///
+    /// Each sentence includes the whitespace and line breaks following it, so
+    /// concatenating all the items gives back the original text.
+    ///
+    /// This struct is created by the `sentences` method on
+    /// [`Rope`](Rope::sentences()) and [`RopeSlice`](RopeSlice::sentences()).
+    /// See their documentation for more.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Sentence_Boundaries
+    #[derive(Clone)]
+    pub struct Sentences<'a> {
+        segments: Segments<'a, SentenceSegmentation>,
+    }
+
+    impl<'a> From<&'a Rope> for Sentences<'a> {
+        #[inline]
+        fn from(rope: &'a Rope) -> Self {
+            Self { segments: Segments::new(rope.chunks()) }
+        }
+    }
+
+    impl<'a> From<&RopeSlice<'a>> for Sentences<'a> {
+        #[inline]
+        fn from(slice: &RopeSlice<'a>) -> Self {
+            Self { segments: Segments::new(slice.chunks()) }
+        }
+    }
+
+    impl<'a> Iterator for Sentences<'a> {
+        type Item = Cow<'a, str>;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            self.segments.next()
+        }
+    }
+
+    impl core::iter::FusedIterator for Sentences<'_> {}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "display-width")))]
+#[cfg(feature = "display-width")]
+pub use wrap_points::WrapPoints;
+
+#[cfg(feature = "display-width")]
+mod wrap_points {
+    use super::*;
+    use crate::rope::position::visual_width;
+
+    /// An iterator over the byte offsets where the visual rows of soft-wrapped
+    /// lines start.
+    ///
+    /// This struct is created by the `wrap_points` method on
+    /// [`Rope`](Rope::wrap_points()) and
+    /// [`RopeSlice`](RopeSlice::wrap_points()). See their documentation for
+    /// more.
+    #[derive(Clone)]
+    pub struct WrapPoints<'a> {
+        /// The lines that haven't been wrapped yet.
+        lines: RawLines<'a>,
+
+        /// The chars of the line currently being wrapped, or `None` if we
+        /// need to move to the next line.
+        chars: Option<Chars<'a>>,
+
+        /// The byte offset of the next char yielded by `chars`.
+        byte_offset: usize,
+
+        /// The width (in columns) of the current row.
+        col: usize,
+
+        /// The byte offset right after the last whitespace in the current row
+        /// together with the width of the row up to that point, if there's
+        /// been any.
+        last_break: Option<(usize, usize)>,
+
+        /// The byte offset of a row start that's been found but not yet
+        /// yielded.
+        pending: Option<usize>,
+
+        /// The maximum width of a row.
+        max_width: usize,
+
+        /// The number of columns between two consecutive tab stops.
+        tab_width: usize,
+    }
+
+    impl<'a> WrapPoints<'a> {
+        #[track_caller]
+        #[inline]
+        pub(crate) fn new(
+            lines: RopeSlice<'a>,
+            byte_offset: usize,
+            max_width: usize,
+            tab_width: usize,
+        ) -> Self {
+            assert!(max_width > 0, "the wrap width must be positive");
+
+            Self {
+                lines: lines.raw_lines(),
+                chars: None,
+                byte_offset,
+                col: 0,
+                last_break: None,
+                pending: None,
+                max_width,
+                tab_width,
+            }
+        }
+
+        /// Returns the width of `ch` if it's placed `col` columns after the
+        /// start of a row.
+        #[inline]
+        fn char_width(&self, ch: char, col: usize) -> usize {
+            visual_width(ch, col, self.tab_width)
+        }
+
+        /// Starts a new row at `row_start`, whose width is `col`.
+        #[inline]
+        fn start_row(&mut self, row_start: usize, col: usize) -> usize {
+            self.col = col;
+            self.last_break = None;
+            row_start
+        }
+    }
+
+    impl Iterator for WrapPoints<'_> {
+        type Item = usize;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            if let Some(row_start) = self.pending.take() {
+                return Some(row_start);
+            }
+
+            let Some(mut chars) = self.chars.take() else {
+                let line = self.lines.next()?;
+                self.chars = Some(line.chars());
+                return Some(self.start_row(self.byte_offset, 0));
+            };
+
+            while let Some(ch) = chars.next() {
+                let offset = self.byte_offset;
+
+                self.byte_offset += ch.len_utf8();
+
+                if ch.is_whitespace() {
+                    // Whitespace never causes a row to wrap, it's allowed to
+                    // hang past the wrap width.
+                    self.col += self.char_width(ch, self.col);
+                    self.last_break = Some((self.byte_offset, self.col));
+                    continue;
+                }
+
+                let width = self.char_width(ch, self.col);
+
+                if self.col == 0 || self.col + width <= self.max_width {
+                    self.col += width;
+                    continue;
+                }
+
+                self.chars = Some(chars);
+
+                // The char doesn't fit in the current row. Wrap at the last
+                // whitespace if there was one, or right before the char if
+                // there wasn't.
+                let Some((row_start, col_at_break)) = self.last_break else {
+                    return Some(self.start_row(offset, width));
+                };
+
+                let col = self.col - col_at_break;
+
+                if col + width <= self.max_width {
+                    return Some(self.start_row(row_start, col + width));
+                }
+
+                // The word after the last whitespace doesn't fit in a row on
+                // its own, so we also have to break it right before the char.
+                self.pending = Some(offset);
+                return Some(self.start_row(row_start, width));
+            }
+
+            self.next()
+        }
+    }
+
+    impl core::iter::FusedIterator for WrapPoints<'_> {}
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+#[cfg(feature = "regex")]
+pub use regex_find_iter::RegexFindIter;
+
+#[cfg(feature = "regex")]
+mod regex_find_iter {
+    use regex_automata::Anchored;
+    use regex_automata::hybrid::dfa::DFA;
+    use regex_automata::hybrid::regex::{Cache, Regex};
+    use regex_automata::hybrid::{LazyStateID, dfa};
+    use regex_automata::util::start;
+
+    use super::*;
+
+    /// An iterator over the byte ranges of the non-overlapping matches of a
+    /// [`Regex`] in a [`Rope`] or [`RopeSlice`].
+    ///
+    /// The regex's lazy DFAs are run directly over the chunks, so the text
+    /// is never copied into a contiguous buffer.
+    ///
+    /// This struct is created by the `regex_find_iter` method on
+    /// [`Rope`](Rope::regex_find_iter()) and
+    /// [`RopeSlice`](RopeSlice::regex_find_iter()). See their documentation
+    /// for more.
+    #[derive(Clone)]
+    pub struct RegexFindIter<'a, 'r> {
+        slice: RopeSlice<'a>,
+
+        regex: &'r Regex,
+
+        cache: Cache,
+
+        /// The byte offset where the next search starts, or `None` if
+        /// we're done.
+        start: Option<usize>,
+
+        /// The end of the last match we've yielded.
+        last_end: Option<usize>,
+    }
+
+    impl<'a, 'r> RegexFindIter<'a, 'r> {
+        #[inline]
+        pub(crate) fn new(slice: RopeSlice<'a>, regex: &'r Regex) -> Self {
+            Self {
+                slice,
+                regex,
+                cache: regex.create_cache(),
+                start: Some(0),
+                last_end: None,
+            }
+        }
+
+        /// Makes the search start at the given byte offset instead of at the
+        /// start of the slice, which is still used as the look-behind
+        /// context.
+        #[inline]
+        pub(crate) fn starting_at(mut self, byte_offset: usize) -> Self {
+            self.start = Some(byte_offset);
+            self
+        }
+
+        /// Returns the first char boundary after `byte_offset`, or `None` if
+        /// it's at the end of the slice.
+        #[inline]
+        fn next_boundary(&self, mut byte_offset: usize) -> Option<usize> {
+            if byte_offset == self.slice.byte_len() {
+                return None;
+            }
+            byte_offset += 1;
+            while !self.slice.is_char_boundary(byte_offset) {
+                byte_offset += 1;
+            }
+            Some(byte_offset)
+        }
+
+        /// Returns the end of the leftmost-first match starting at or after
+        /// `start`, if there is one.
+        #[track_caller]
+        #[inline]
+        fn find_end(&mut self, start: usize) -> Option<usize> {
+            self.search_end(start, Anchored::No, self.slice.byte_len())
+        }
+
+        /// Returns the end of the leftmost-first match starting at or after
+        /// `start` (or exactly at `start` if `anchored` is [`Anchored::Yes`])
+        /// and ending at or before `limit`, if there is one.
+        ///
+        /// The text after `limit` is still used as look-ahead context.
+        #[track_caller]
+        #[inline]
+        fn search_end(
+            &mut self,
+            start: usize,
+            anchored: Anchored,
+            limit: usize,
+        ) -> Option<usize> {
+            let dfa = self.regex.forward();
+            let cache = self.cache.forward_mut();
+
+            let look_behind = (start > 0).then(|| self.slice.byte(start - 1));
+
+            let config = start::Config::new()
+                .anchored(anchored)
+                .look_behind(look_behind);
+
+            let mut sid = dfa
+                .start_state(cache, &config)
+                .unwrap_or_else(|_| panic_gave_up());
+
+            let mut end = None;
+
+            let mut byte_offset = start;
+
+            for chunk in self.slice.byte_slice(start..limit).chunks() {
+                for &byte in chunk.as_bytes() {
+                    sid = next_state(dfa, cache, sid, byte);
+
+                    // Matches are delayed by one byte, so entering a match
+                    // state means a match ended right before this byte.
+                    if sid.is_match() {
+                        end = Some(byte_offset);
+                    } else if sid.is_dead() {
+                        return end;
+                    } else if sid.is_quit() {
+                        panic_gave_up();
+                    }
+
+                    byte_offset += 1;
+                }
+            }
+
+            sid = if limit < self.slice.byte_len() {
+                next_state(dfa, cache, sid, self.slice.byte(limit))
+            } else {
+                dfa.next_eoi_state(cache, sid)
+                    .unwrap_or_else(|_| panic_gave_up())
+            };
+
+            if sid.is_match() {
+                end = Some(byte_offset);
+            }
+
+            end
+        }
+
+        /// Returns the start of the match ending at `end`, given that the
+        /// search started at `start`.
+        #[track_caller]
+        #[inline]
+        fn find_start(&mut self, start: usize, end: usize) -> usize {
+            self.longest_match_ending_at(start, end).unwrap()
+        }
+
+        /// Returns the smallest offset at or after `start` where a match
+        /// ending exactly at `end` starts, if there is one.
+        #[track_caller]
+        #[inline]
+        fn longest_match_ending_at(
+            &mut self,
+            start: usize,
+            end: usize,
+        ) -> Option<usize> {
+            let dfa = self.regex.reverse();
+            let cache = self.cache.reverse_mut();
+
+            let look_behind =
+                (end < self.slice.byte_len()).then(|| self.slice.byte(end));
+
+            let config = start::Config::new()
+                .anchored(Anchored::Yes)
+                .look_behind(look_behind);
+
+            let mut sid = dfa
+                .start_state(cache, &config)
+                .unwrap_or_else(|_| panic_gave_up());
+
+            let mut match_start = None;
+
+            let mut byte_offset = end;
+
+            for byte in self.slice.byte_slice(start..end).bytes().rev() {
+                byte_offset -= 1;
+
+                sid = next_state(dfa, cache, sid, byte);
+
+                if sid.is_match() {
+                    match_start = Some(byte_offset + 1);
+                } else if sid.is_dead() {
+                    return match_start;
+                } else if sid.is_quit() {
+                    panic_gave_up();
+                }
+            }
+
+            sid = if start > 0 {
+                next_state(dfa, cache, sid, self.slice.byte(start - 1))
+            } else {
+                dfa.next_eoi_state(cache, sid)
+                    .unwrap_or_else(|_| panic_gave_up())
+            };
+
+            if sid.is_match() {
+                match_start = Some(start);
+            }
+
+            match_start
+        }
+
+        /// Returns the byte range of the last match ending at or before
+        /// `byte_offset`, i.e. the longest of the matches ending the furthest
+        /// back, if there is one.
+        ///
+        /// That match can overlap the ones found by iterating forward (e.g.
+        /// the last match of `aa` in `aaa` is at `1..3`), so after finding
+        /// the last of the forward matches we still run the regex backwards
+        /// from every char boundary after its end, from back to front.
+        #[track_caller]
+        #[inline]
+        pub(crate) fn rfind(
+            mut self,
+            byte_offset: usize,
+        ) -> Option<Range<usize>> {
+            let last = self
+                .by_ref()
+                .take_while(|range| range.end <= byte_offset)
+                .last()?;
+
+            let mut end = byte_offset;
+
+            while end > last.end {
+                if self.slice.is_char_boundary(end) {
+                    if let Some(start) = self
+                        .longest_match_ending_at(0, end)
+                        .filter(|&start| self.slice.is_char_boundary(start))
+                    {
+                        return Some(start..end);
+                    }
+                }
+                end -= 1;
+            }
+
+            Some(self.find_start(0, last.end)..last.end)
+        }
+
+        /// Returns the start of the longest match ending at the end of the
+        /// slice, if there is one.
+        #[track_caller]
+        #[inline]
+        pub(crate) fn suffix_start(mut self) -> Option<usize> {
+            let end = self.slice.byte_len();
+            self.longest_match_ending_at(0, end)
+                .filter(|&start| self.slice.is_char_boundary(start))
+        }
+    }
 
-                        let grapheme_end = &self.forward_chunk
-                            [..byte_end - self.forward_offset];
+    impl Iterator for RegexFindIter<'_, '_> {
+        type Item = core::ops::Range<usize>;
 
-                        match &mut grapheme {
-                            Cow::Borrowed(gr) if gr.is_empty() => {
-                                *gr = grapheme_end;
-                            },
+        #[track_caller]
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                let start = self.start?;
 
-                            Cow::Borrowed(gr) => {
-                                let mut gr = gr.to_owned();
-                                gr.push_str(grapheme_end);
-                                grapheme = Cow::Owned(gr);
-                            },
+                let Some(end) = self.find_end(start) else {
+                    self.start = None;
+                    return None;
+                };
 
-                            Cow::Owned(gr) => {
-                                gr.push_str(grapheme_end);
-                            },
-                        }
+                // Only empty matches can end inside a char, and we don't
+                // want to yield those.
+                if !self.slice.is_char_boundary(end) {
+                    self.start = self.next_boundary(end);
+                    continue;
+                }
 
-                        self.forward_offset += grapheme_end.len();
+                let match_start = self.find_start(start, end);
 
-                        self.forward_chunk =
-                            &self.forward_chunk[grapheme_end.len()..];
+                // Like the `regex` crate, we don't yield an empty match
+                // right after the end of the previous match.
+                if match_start == end && self.last_end == Some(end) {
+                    self.start = self.next_boundary(end);
+                    continue;
+                }
 
-                        if using_backward_chunk {
-                            self.backward_chunk = self.forward_chunk;
-                        }
+                self.start = Some(end);
+                self.last_end = Some(end);
 
-                        return Some(grapheme);
-                    },
+                return Some(match_start..end);
+            }
+        }
+    }
 
-                    Ok(None) => return None,
+    impl core::iter::FusedIterator for RegexFindIter<'_, '_> {}
 
-                    Err(GraphemeIncomplete::NextChunk) => {
-                        match &mut grapheme {
-                            Cow::Borrowed(gr) if gr.is_empty() => {
-                                *gr = self.forward_chunk;
-                            },
+    #[track_caller]
+    #[inline]
+    fn next_state(
+        dfa: &DFA,
+        cache: &mut dfa::Cache,
+        sid: LazyStateID,
+        byte: u8,
+    ) -> LazyStateID {
+        dfa.next_state(cache, sid, byte).unwrap_or_else(|_| panic_gave_up())
+    }
 
-                            Cow::Borrowed(gr) => {
-                                let mut gr = gr.to_owned();
-                                gr.push_str(self.forward_chunk);
-                                grapheme = Cow::Owned(gr);
-                            },
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    fn panic_gave_up() -> ! {
+        panic!(
+            "the regex's lazy DFA gave up on the search, which can only \
+             happen if it was configured with quit bytes or with a minimum \
+             cache clear count"
+        );
+    }
+}
 
-                            Cow::Owned(gr) => gr.push_str(self.forward_chunk),
-                        }
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+#[cfg(feature = "rayon")]
+pub use par_iter::{ParChunks, ParLines};
 
-                        self.forward_offset += self.forward_chunk.len();
+#[cfg(feature = "rayon")]
+mod par_iter {
+    use rayon::iter::ParallelIterator;
+    use rayon::iter::plumbing::{
+        Folder,
+        UnindexedConsumer,
+        UnindexedProducer,
+        bridge_unindexed,
+    };
 
-                        self.forward_chunk =
-                            if let Some(next) = self.chunks.next() {
-                                next
-                            } else if !self.backward_chunk.is_empty() {
-                                debug_assert!(
-                                    self.backward_cursor.cur_cursor()
-                                        > self.forward_cursor.cur_cursor()
-                                );
-                                using_backward_chunk = true;
-                                self.backward_chunk
-                            } else {
-                                return None;
-                            }
-                    },
+    use super::*;
 
-                    // Why does it ask for precontext if we've been feeding it
-                    // stuff from the beginning of the range?
-                    Err(GraphemeIncomplete::PreContext(byte_idx)) => {
-                        let slice = self.slice.byte_slice(..byte_idx);
-                        let chunk = slice.chunks().next_back().unwrap();
-                        self.forward_cursor
-                            .provide_context(chunk, byte_idx - chunk.len());
-                    },
+    /// A parallel iterator over the `&str` chunks of `Rope`s and
+    /// `RopeSlice`s.
+    ///
+    /// The text is split between threads at leaf boundaries, so the chunks
+    /// are the same ones yielded by [`Chunks`].
+    ///
+    /// This struct is created by the `par_chunks` method on
+    /// [`Rope`](Rope::par_chunks()) and
+    /// [`RopeSlice`](RopeSlice::par_chunks()). See their documentation for
+    /// more.
+    #[derive(Copy, Clone)]
+    pub struct ParChunks<'a> {
+        slice: RopeSlice<'a>,
+    }
 
-                    Err(_) => {
-                        unreachable!();
-                    },
-                }
-            }
+    impl<'a> From<&'a Rope> for ParChunks<'a> {
+        #[inline]
+        fn from(rope: &'a Rope) -> Self {
+            Self { slice: rope.byte_slice(..) }
         }
+    }
 
+    impl<'a> From<&RopeSlice<'a>> for ParChunks<'a> {
         #[inline]
-        fn size_hint(&self) -> (usize, Option<usize>) {
-            let hi = self.backward_offset - self.forward_offset;
-            let lo = (hi != 0) as usize;
-            (lo, Some(hi))
+        fn from(slice: &RopeSlice<'a>) -> Self {
+            Self { slice: *slice }
         }
     }
 
-    impl DoubleEndedIterator for Graphemes<'_> {
-        #[inline]
-        fn next_back(&mut self) -> Option<Self::Item> {
-            debug_assert_eq!(
-                self.backward_offset,
-                self.backward_cursor.cur_cursor()
-            );
+    impl<'a> ParallelIterator for ParChunks<'a> {
+        type Item = &'a str;
 
-            let mut using_forward_chunk = false;
+        #[inline]
+        fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where
+            C: UnindexedConsumer<Self::Item>,
+        {
+            bridge_unindexed(self, consumer)
+        }
+    }
 
-            if self.backward_chunk.is_empty() {
-                self.backward_chunk =
-                    if let Some(prev) = self.chunks.next_back() {
-                        prev
-                    } else if !self.forward_chunk.is_empty() {
-                        debug_assert!(
-                            self.backward_cursor.cur_cursor()
-                                > self.forward_cursor.cur_cursor()
-                        );
-                        using_forward_chunk = true;
-                        self.forward_chunk
-                    } else {
-                        return None;
-                    }
-            }
+    impl<'a> UnindexedProducer for ParChunks<'a> {
+        type Item = &'a str;
 
-            let mut grapheme = Cow::Borrowed("");
+        #[inline]
+        fn split(self) -> (Self, Option<Self>) {
+            let byte_len = self.slice.byte_len();
 
-            loop {
-                match self
-                    .backward_cursor
-                    // The chunk passed to `prev_boundary` can't be empty or
-                    // it'll panic.
-                    .prev_boundary(
-                        self.backward_chunk,
-                        self.backward_cursor.cur_cursor()
-                            - self.backward_chunk.len(),
-                    ) {
-                    Ok(Some(byte_start)) => {
-                        if byte_start == self.backward_offset {
-                            debug_assert!(!grapheme.is_empty());
-                            return Some(grapheme);
-                        }
+            if self.slice.tree_slice.leaf_count() < 2 {
+                return (self, None);
+            }
 
-                        debug_assert!(byte_start < self.backward_offset);
+            let (leaf, ByteMetric(leaf_start)) = self
+                .slice
+                .tree_slice
+                .leaf_at_measure(ByteMetric(byte_len / 2));
 
-                        let grapheme_start = {
-                            let start_len = self.backward_offset - byte_start;
-                            let chunk_len = self.backward_chunk.len();
-                            &self.backward_chunk[(chunk_len - start_len)..]
-                        };
+            let split_at =
+                if leaf_start > 0 { leaf_start } else { leaf.len() };
 
-                        match &mut grapheme {
-                            Cow::Borrowed(gr) if gr.is_empty() => {
-                                *gr = grapheme_start;
-                            },
+            if split_at == 0 || split_at >= byte_len {
+                return (self, None);
+            }
 
-                            Cow::Borrowed(gr) => {
-                                let mut new = String::with_capacity(
-                                    grapheme_start.len() + gr.len(),
-                                );
-                                new.push_str(grapheme_start);
-                                new.push_str(gr);
-                                grapheme = Cow::Owned(new);
-                            },
+            (
+                Self { slice: self.slice.byte_slice(..split_at) },
+                Some(Self { slice: self.slice.byte_slice(split_at..) }),
+            )
+        }
 
-                            Cow::Owned(gr) => {
-                                let mut new = String::with_capacity(
-                                    grapheme_start.len() + gr.len(),
-                                );
-                                new.push_str(grapheme_start);
-                                new.push_str(&*gr);
-                                *gr = new;
-                            },
-                        }
+        #[inline]
+        fn fold_with<F>(self, folder: F) -> F
+        where
+            F: Folder<Self::Item>,
+        {
+            folder.consume_iter(self.slice.chunks())
+        }
+    }
 
-                        self.backward_offset -= grapheme_start.len();
+    /// A parallel iterator over the lines of `Rope`s and `RopeSlice`s.
+    ///
+    /// The text is split between threads at line boundaries, so the lines
+    /// are the same ones yielded by [`Lines`].
+    ///
+    /// This struct is created by the `par_lines` method on
+    /// [`Rope`](Rope::par_lines()) and
+    /// [`RopeSlice`](RopeSlice::par_lines()). See their documentation for
+    /// more.
+    #[derive(Copy, Clone)]
+    pub struct ParLines<'a> {
+        slice: RopeSlice<'a>,
+    }
 
-                        self.backward_chunk =
-                            &self.backward_chunk[..self.backward_chunk.len()
-                                - grapheme_start.len()];
+    impl<'a> From<&'a Rope> for ParLines<'a> {
+        #[inline]
+        fn from(rope: &'a Rope) -> Self {
+            Self { slice: rope.byte_slice(..) }
+        }
+    }
 
-                        if using_forward_chunk {
-                            self.forward_chunk = self.backward_chunk;
-                        }
+    impl<'a> From<&RopeSlice<'a>> for ParLines<'a> {
+        #[inline]
+        fn from(slice: &RopeSlice<'a>) -> Self {
+            Self { slice: *slice }
+        }
+    }
 
-                        return Some(grapheme);
-                    },
+    impl<'a> ParallelIterator for ParLines<'a> {
+        type Item = RopeSlice<'a>;
 
-                    Ok(None) => return None,
+        #[inline]
+        fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where
+            C: UnindexedConsumer<Self::Item>,
+        {
+            bridge_unindexed(self, consumer)
+        }
+    }
 
-                    Err(GraphemeIncomplete::PrevChunk) => {
-                        match &mut grapheme {
-                            Cow::Borrowed(gr) if gr.is_empty() => {
-                                *gr = self.backward_chunk;
-                            },
+    impl<'a> UnindexedProducer for ParLines<'a> {
+        type Item = RopeSlice<'a>;
 
-                            Cow::Borrowed(gr) => {
-                                let mut new = String::with_capacity(
-                                    self.backward_chunk.len() + gr.len(),
-                                );
-                                new.push_str(self.backward_chunk);
-                                new.push_str(gr);
-                                grapheme = Cow::Owned(new);
-                            },
+        #[inline]
+        fn split(self) -> (Self, Option<Self>) {
+            // There's no point in splitting slices contained in a single
+            // leaf, they're faster to go through on a single thread.
+            if self.slice.tree_slice.leaf_count() < 2 {
+                return (self, None);
+            }
 
-                            Cow::Owned(gr) => {
-                                let mut new = String::with_capacity(
-                                    self.backward_chunk.len() + gr.len(),
-                                );
-                                new.push_str(self.backward_chunk);
-                                new.push_str(gr);
-                                *gr = new;
-                            },
-                        }
+            let line_len = self.slice.line_len();
 
-                        self.backward_offset -= self.backward_chunk.len();
+            if line_len < 2 {
+                return (self, None);
+            }
 
-                        self.backward_chunk =
-                            if let Some(prev) = self.chunks.next_back() {
-                                prev
-                            } else if !self.forward_chunk.is_empty() {
-                                debug_assert!(
-                                    self.backward_cursor.cur_cursor()
-                                        > self.forward_cursor.cur_cursor()
-                                );
-                                using_forward_chunk = true;
-                                self.forward_chunk
-                            } else {
-                                return None;
-                            }
-                    },
+            // The first half ends with the line break of its last line, so
+            // it yields exactly the lines before `line_len / 2`.
+            let split_at = self.slice.byte_of_line(line_len / 2);
 
-                    // Why does it ask for precontext if we're iterating
-                    // backward? Shouldn't it always just ask for the previous
-                    // chunk?
-                    Err(GraphemeIncomplete::PreContext(byte_idx)) => {
-                        let slice = self.slice.byte_slice(..byte_idx);
-                        let chunk = slice.chunks().next_back().unwrap();
-                        self.backward_cursor
-                            .provide_context(chunk, byte_idx - chunk.len());
-                    },
+            (
+                Self { slice: self.slice.byte_slice(..split_at) },
+                Some(Self { slice: self.slice.byte_slice(split_at..) }),
+            )
+        }
 
-                    Err(_) => {
-                        unreachable!();
-                    },
-                }
-            }
+        #[inline]
+        fn fold_with<F>(self, folder: F) -> F
+        where
+            F: Folder<Self::Item>,
+        {
+            folder.consume_iter(self.slice.lines())
         }
     }
-
-    impl core::iter::FusedIterator for Graphemes<'_> {}
 }
@@ -0,0 +1,234 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::{Rope, RopeSlice};
+
+/// A line ending style, as returned by [`LineEndings::style()`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// Every line ends with a line feed (`"\n"`).
+    Lf,
+
+    /// Every line ends with a carriage return followed by a line feed
+    /// (`"\r\n"`).
+    Crlf,
+
+    /// Some lines end with `"\n"` and others with `"\r\n"`.
+    Mixed,
+}
+
+/// The number of line endings of each style in a piece of text.
+///
+/// This is returned by
+/// [`Rope::detect_line_ending()`](crate::Rope::detect_line_ending()). See
+/// its documentation for more.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LineEndings {
+    /// The number of line feeds not preceded by a carriage return.
+    pub lf: usize,
+
+    /// The number of carriage returns followed by a line feed.
+    pub crlf: usize,
+}
+
+impl LineEndings {
+    /// Returns the line ending style of the text, or `None` if it doesn't
+    /// contain any line breaks.
+    #[inline]
+    pub fn style(&self) -> Option<LineEnding> {
+        match (self.lf, self.crlf) {
+            (0, 0) => None,
+            (_, 0) => Some(LineEnding::Lf),
+            (0, _) => Some(LineEnding::Crlf),
+            _ => Some(LineEnding::Mixed),
+        }
+    }
+}
+
+/// Counts the line endings of each style in the slice.
+///
+/// The total number of line feeds is stored in the slice's summary, so only
+/// the slices containing at least one of them have to be scanned to tell the
+/// CRLFs apart.
+#[inline]
+pub(super) fn detect_line_ending(slice: RopeSlice<'_>) -> LineEndings {
+    let line_breaks = slice.tree_slice.summary().line_breaks();
+
+    if line_breaks == 0 {
+        return LineEndings::default();
+    }
+
+    let mut crlf = 0;
+
+    // Whether the last non-empty chunk ended with a CR, in which case a LF
+    // at the start of the next chunk is part of a CRLF.
+    let mut after_cr = false;
+
+    for chunk in slice.chunks() {
+        if chunk.is_empty() {
+            continue;
+        }
+
+        if after_cr && chunk.starts_with('\n') {
+            crlf += 1;
+        }
+
+        crlf += chunk.matches("\r\n").count();
+
+        after_cr = chunk.ends_with('\r');
+    }
+
+    LineEndings { lf: line_breaks - crlf, crlf }
+}
+
+/// Rewrites the line endings of the `Rope` to the given style.
+///
+/// Only the leaves containing line endings of the other style are rewritten,
+/// and the leaves without any line feeds are skipped without reading their
+/// text.
+#[track_caller]
+#[inline]
+pub(super) fn normalize_line_endings(
+    rope: &mut Rope,
+    line_ending: LineEnding,
+) {
+    let to_crlf = match line_ending {
+        LineEnding::Lf => false,
+        LineEnding::Crlf => true,
+        LineEnding::Mixed => {
+            panic!("can't normalize line endings to LineEnding::Mixed")
+        },
+    };
+
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+
+    for (leaf_start, summary) in rope.leaf_summaries() {
+        if summary.line_breaks() == 0 {
+            continue;
+        }
+
+        let mut leaf_end = leaf_start + summary.bytes();
+
+        let leaf = rope.byte_slice(leaf_start..leaf_end);
+
+        let mut start = leaf_start;
+
+        let after_cr = leaf_start > 0 && rope.byte(leaf_start - 1) == b'\r';
+
+        let mut text = String::with_capacity(
+            summary.bytes() + if to_crlf { summary.line_breaks() } else { 0 },
+        );
+
+        let mut is_changed = false;
+
+        if to_crlf {
+            let mut after_cr = after_cr;
+
+            for ch in leaf.chars() {
+                if ch == '\n' && !after_cr {
+                    text.push('\r');
+                    is_changed = true;
+                }
+                text.push(ch);
+                after_cr = ch == '\r';
+            }
+        } else {
+            // All the CRs before a LF are removed, not just the last one,
+            // or "\r\r\n" would become another CRLF. If they're in the
+            // previous leaves they're removed together with this one.
+            let leading_crs = leaf.bytes().take_while(|&b| b == b'\r').count();
+
+            if after_cr && leaf.bytes().nth(leading_crs) == Some(b'\n') {
+                while start > 0 && rope.byte(start - 1) == b'\r' {
+                    start -= 1;
+                }
+                is_changed = true;
+            }
+
+            for ch in leaf.chars() {
+                if ch == '\n' {
+                    while text.ends_with('\r') {
+                        text.pop();
+                        is_changed = true;
+                    }
+                }
+                text.push(ch);
+            }
+
+            // For the same reason the CRs at the end of the leaf are left
+            // out of the rewritten range, since they're handled with the
+            // next leaf if it starts with a LF.
+            while text.ends_with('\r') {
+                text.pop();
+                leaf_end -= 1;
+            }
+        }
+
+        if !is_changed {
+            continue;
+        }
+
+        // Merge the rewrites of adjacent leaves into a single edit.
+        match edits.last_mut() {
+            Some((range, last_text)) if range.end == start => {
+                range.end = leaf_end;
+                last_text.push_str(&text);
+            },
+            _ => edits.push((start..leaf_end, text)),
+        }
+    }
+
+    let new_len = edits.iter().fold(rope.byte_len(), |len, (range, text)| {
+        len - range.len() + text.len()
+    });
+
+    rope.assert_within_depth_limit(new_len);
+
+    for (byte_range, text) in edits.into_iter().rev() {
+        rope.replace(byte_range, text);
+    }
+}
+
+/// Returns whether the char is one of the mandatory line breaks of
+/// [UAX #14](https://www.unicode.org/reports/tr14/), i.e. LF, VT, FF, CR,
+/// NEL, LS or PS.
+#[inline]
+pub(super) fn is_unicode_line_break(ch: char) -> bool {
+    matches!(
+        ch,
+        '\n' | '\u{000B}'
+            | '\u{000C}'
+            | '\r'
+            | '\u{0085}'
+            | '\u{2028}'
+            | '\u{2029}'
+    )
+}
+
+/// Returns the number of lines in the slice when they can be terminated by
+/// any of the [Unicode line breaks](is_unicode_line_break), with CRLF
+/// counting as a single one.
+#[inline]
+pub(super) fn unicode_line_len(slice: RopeSlice<'_>) -> usize {
+    let mut line_breaks = 0;
+
+    let mut after_cr = false;
+
+    let mut ends_with_break = false;
+
+    for chunk in slice.chunks() {
+        for ch in chunk.chars() {
+            if ch == '\n' && after_cr {
+                after_cr = false;
+                continue;
+            }
+
+            after_cr = ch == '\r';
+            ends_with_break = is_unicode_line_break(ch);
+            line_breaks += ends_with_break as usize;
+        }
+    }
+
+    line_breaks + 1 - (ends_with_break as usize) - (slice.is_empty() as usize)
+}
@@ -0,0 +1,344 @@
+use alloc::vec::Vec;
+use core::ops::{Range, RangeBounds};
+
+use super::utils::panic_messages as panic;
+use super::{Change, Delta};
+use crate::iter::Markers;
+use crate::range_bounds_to_start_end;
+
+/// A set of byte offsets into a [`Rope`](crate::Rope) which are kept in sync
+/// with the edits made to it.
+///
+/// Markers can be used to track the position of cursors, selections,
+/// breakpoints and the like. Every time the `Rope` is edited, the same edit
+/// should be reported to the `MarkerSet` with
+/// [`apply_edit()`](Self::apply_edit()) or
+/// [`apply_delta()`](Self::apply_delta()), which shifts the markers after
+/// the edit and collapses the ones inside the replaced text to one of its
+/// ends depending on their [`Gravity`].
+///
+/// # Examples
+///
+/// ```
+/// # use crop::{Gravity, MarkerSet, Rope};
+/// #
+/// let mut r = Rope::from("Hello World!");
+///
+/// let mut markers = MarkerSet::new();
+/// let world = markers.add(6, Gravity::Left);
+/// let bang = markers.add(11, Gravity::Left);
+///
+/// r.insert(0, "👋 ");
+/// markers.apply_edit(0..0, "👋 ".len());
+///
+/// assert_eq!(markers.get(world), Some(11));
+/// assert_eq!(r.byte_slice(markers.get(bang).unwrap()..), "!");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MarkerSet {
+    pub(super) slots: Vec<Slot>,
+
+    /// The indexes of the empty slots in `slots`, which are reused when new
+    /// markers are added.
+    free: Vec<usize>,
+
+    /// The indexes of the non-empty slots in `slots`, sorted by the byte
+    /// offsets of their markers, so that edits only have to visit the
+    /// markers after their start.
+    order: Vec<usize>,
+}
+
+/// A handle to a marker in a [`MarkerSet`].
+///
+/// Handles are never reused: once a marker has been removed its handle is
+/// stale, and every method of the set returns `None` for it, even after the
+/// slot it used to occupy has been given to a new marker.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MarkerId {
+    pub(super) slot: usize,
+    pub(super) generation: u32,
+}
+
+/// Which way a marker moves when text is inserted at its position, or when
+/// the text around it is replaced.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Gravity {
+    /// The marker sticks to the text before it, staying at the start of any
+    /// text inserted at its position.
+    #[default]
+    Left,
+
+    /// The marker sticks to the text after it, moving to the end of any text
+    /// inserted at its position.
+    Right,
+}
+
+impl Gravity {
+    /// Returns where a byte offset with this gravity ends up after the given
+    /// byte range is replaced with `inserted_len` bytes of text.
+    #[inline]
+    pub(super) fn shift(
+        self,
+        byte_offset: usize,
+        byte_range: Range<usize>,
+        inserted_len: usize,
+    ) -> usize {
+        let Range { start, end } = byte_range;
+
+        if byte_offset < start {
+            byte_offset
+        } else if byte_offset > end {
+            byte_offset - (end - start) + inserted_len
+        } else {
+            match self {
+                Gravity::Left => start,
+                Gravity::Right => start + inserted_len,
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(super) struct Marker {
+    pub(super) byte_offset: usize,
+    gravity: Gravity,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(super) struct Slot {
+    pub(super) marker: Option<Marker>,
+
+    /// Bumped every time the marker in this slot is removed, so that the
+    /// handles of the removed markers don't match the ones added after them.
+    pub(super) generation: u32,
+}
+
+impl MarkerSet {
+    /// Adds a new marker at the given byte offset, returning its handle.
+    ///
+    /// The markers are kept sorted by their byte offset in a `Vec`, so this
+    /// runs in O(m), where m is the number of markers in the set, although
+    /// shifting the markers after the new one is a single `memmove`.
+    #[inline]
+    pub fn add(&mut self, byte_offset: usize, gravity: Gravity) -> MarkerId {
+        let marker = Some(Marker { byte_offset, gravity });
+
+        let slot = match self.free.pop() {
+            Some(idx) => {
+                self.slots[idx].marker = marker;
+                idx
+            },
+
+            None => {
+                self.slots.push(Slot { marker, generation: 0 });
+                self.slots.len() - 1
+            },
+        };
+
+        let order_idx = self.first_at_or_after(byte_offset);
+        self.order.insert(order_idx, slot);
+
+        MarkerId { slot, generation: self.slots[slot].generation }
+    }
+
+    /// Updates the markers to reflect the [`Change`] returned by one of the
+    /// editing methods of [`Rope`](crate::Rope).
+    #[inline]
+    pub fn apply_change(&mut self, change: &Change) {
+        self.apply_edit(change.byte_range.clone(), change.inserted_len);
+    }
+
+    /// Updates the markers to reflect all the edits in the [`Delta`], which
+    /// are assumed to have been applied to the `Rope` with
+    /// [`Rope::apply_delta()`](crate::Rope::apply_delta()).
+    #[inline]
+    pub fn apply_delta(&mut self, delta: &Delta) {
+        for edit in delta.edits().iter().rev() {
+            self.apply_edit(edit.byte_range.clone(), edit.text.len());
+        }
+    }
+
+    /// Updates the markers to reflect the replacement of the given byte range
+    /// with some text of length `inserted_len`.
+    ///
+    /// Markers before the start of the range are left untouched, markers
+    /// after its end are shifted by the difference between `inserted_len` and
+    /// the length of the range, and markers within the range are moved to the
+    /// start or the end of the inserted text, depending on their gravity.
+    ///
+    /// The markers are kept sorted by their byte offset, so this runs in
+    /// O(log m + k), where m is the number of markers in the set and k is the
+    /// number of markers at or after the start of the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end, or if the
+    /// range has an unbounded end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Gravity, MarkerSet};
+    /// #
+    /// let mut markers = MarkerSet::new();
+    ///
+    /// let left = markers.add(3, Gravity::Left);
+    /// let right = markers.add(3, Gravity::Right);
+    /// let after = markers.add(5, Gravity::Left);
+    ///
+    /// // Replace the bytes in 2..4 with 10 bytes of text.
+    /// markers.apply_edit(2..4, 10);
+    ///
+    /// assert_eq!(markers.get(left), Some(2));
+    /// assert_eq!(markers.get(right), Some(12));
+    /// assert_eq!(markers.get(after), Some(13));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn apply_edit<R>(&mut self, byte_range: R, inserted_len: usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, usize::MAX);
+
+        assert!(end < usize::MAX, "the byte range must have a bounded end");
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        let first = self.first_at_or_after(start);
+
+        let collapsed_end = first
+            + self.order[first..]
+                .partition_point(|&slot| self.marker(slot).byte_offset <= end);
+
+        let Self { slots, order, .. } = self;
+
+        for &slot in &order[first..] {
+            let marker = slots[slot].marker.as_mut().unwrap();
+            marker.byte_offset = marker.gravity.shift(
+                marker.byte_offset,
+                start..end,
+                inserted_len,
+            );
+        }
+
+        // The markers inside the range are moved to either end of the
+        // inserted text depending on their gravity, which can change their
+        // order. The ones after it are all shifted by the same amount.
+        order[first..collapsed_end]
+            .sort_by_key(|&slot| slots[slot].marker.unwrap().byte_offset);
+    }
+
+    /// Returns the index in `order` of the first marker whose byte offset is
+    /// greater than or equal to `byte_offset`.
+    #[inline]
+    fn first_at_or_after(&self, byte_offset: usize) -> usize {
+        self.order.partition_point(|&slot| {
+            self.marker(slot).byte_offset < byte_offset
+        })
+    }
+
+    /// Returns the byte offset of the marker, or `None` if it has been
+    /// removed.
+    #[inline]
+    pub fn get(&self, id: MarkerId) -> Option<usize> {
+        self.is_live(id).then(|| self.marker(id.slot).byte_offset)
+    }
+
+    /// Returns whether the handle refers to a marker that's still in the
+    /// set.
+    #[inline]
+    fn is_live(&self, id: MarkerId) -> bool {
+        self.slots.get(id.slot).is_some_and(|slot| {
+            slot.generation == id.generation && slot.marker.is_some()
+        })
+    }
+
+    /// Returns `true` if the set doesn't contain any markers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the handles and byte offsets of all the
+    /// markers in the set, in no particular order.
+    #[inline]
+    pub fn iter(&self) -> Markers<'_> {
+        Markers::from(self)
+    }
+
+    /// Returns the marker in the given slot, which must not be empty.
+    #[inline]
+    fn marker(&self, slot: usize) -> &Marker {
+        self.slots[slot].marker.as_ref().unwrap()
+    }
+
+    /// Returns the index in `order` of the marker in the given slot.
+    #[inline]
+    fn order_idx(&self, slot: usize) -> usize {
+        let byte_offset = self.marker(slot).byte_offset;
+
+        let first = self.first_at_or_after(byte_offset);
+
+        // Several markers can be at the same offset.
+        first
+            + self.order[first..]
+                .iter()
+                .position(|&other| other == slot)
+                .unwrap()
+    }
+
+    /// Returns the number of markers in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Creates a new, empty `MarkerSet`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes the marker from the set, returning its byte offset if it
+    /// hadn't already been removed.
+    ///
+    /// Like [`add()`](Self::add()), this runs in O(m), where m is the number
+    /// of markers in the set.
+    #[inline]
+    pub fn remove(&mut self, id: MarkerId) -> Option<usize> {
+        if !self.is_live(id) {
+            return None;
+        }
+        let order_idx = self.order_idx(id.slot);
+        self.order.remove(order_idx);
+        let slot = &mut self.slots[id.slot];
+        let marker = slot.marker.take().unwrap();
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.slot);
+        Some(marker.byte_offset)
+    }
+
+    /// Moves the marker to the given byte offset, returning its previous
+    /// offset, or `None` if it has been removed.
+    ///
+    /// Like [`add()`](Self::add()), this runs in O(m), where m is the number
+    /// of markers in the set.
+    #[inline]
+    pub fn set(&mut self, id: MarkerId, byte_offset: usize) -> Option<usize> {
+        if !self.is_live(id) {
+            return None;
+        }
+        let order_idx = self.order_idx(id.slot);
+        self.order.remove(order_idx);
+        let marker = self.slots[id.slot].marker.as_mut().unwrap();
+        let previous =
+            core::mem::replace(&mut marker.byte_offset, byte_offset);
+        let order_idx = self.first_at_or_after(byte_offset);
+        self.order.insert(order_idx, id.slot);
+        Some(previous)
+    }
+}
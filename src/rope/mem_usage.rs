@@ -0,0 +1,43 @@
+use super::Rope;
+use super::rope::CHUNK_MAX_BYTES;
+
+/// A breakdown of the heap memory used by a [`Rope`].
+///
+/// This is returned by [`Rope::mem_usage()`](crate::Rope::mem_usage()). See
+/// its documentation for more.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MemUsage {
+    /// The number of bytes of text stored in the `Rope`, i.e. its
+    /// [`byte_len()`](crate::Rope::byte_len()).
+    pub text_bytes: usize,
+
+    /// The number of bytes allocated for the chunks of the `Rope` that don't
+    /// currently hold any text, i.e. the gaps of its gap buffers.
+    pub gap_bytes: usize,
+
+    /// The number of bytes allocated for the nodes of the B-tree, excluding
+    /// the chunks they point to.
+    pub node_bytes: usize,
+}
+
+impl MemUsage {
+    /// Returns the total number of bytes allocated on the heap, i.e. the sum
+    /// of the text, gap and node bytes.
+    #[inline]
+    pub fn total_bytes(&self) -> usize {
+        self.text_bytes + self.gap_bytes + self.node_bytes
+    }
+}
+
+#[inline]
+pub(super) fn mem_usage(rope: &Rope) -> MemUsage {
+    let text_bytes = rope.byte_len();
+
+    let chunk_bytes = rope.tree.leaf_count() * CHUNK_MAX_BYTES;
+
+    MemUsage {
+        text_bytes,
+        gap_bytes: chunk_bytes - text_bytes,
+        node_bytes: rope.tree.node_bytes(),
+    }
+}
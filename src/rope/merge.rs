@@ -0,0 +1,162 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::{Delta, Edit, Rope};
+
+/// A region of a three-way merge that was changed in different ways by the
+/// two sides being merged.
+///
+/// This is returned by [`Rope::merge()`]. See its documentation for more.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    /// The byte range of the merged `Rope` containing our version of the
+    /// region.
+    pub byte_range: Range<usize>,
+
+    /// The byte range of the base `Rope` the region corresponds to.
+    pub base_byte_range: Range<usize>,
+
+    /// The byte range of their `Rope` containing their version of the
+    /// region.
+    pub their_byte_range: Range<usize>,
+}
+
+#[inline]
+pub(super) fn merge(
+    base: &Rope,
+    ours: &Rope,
+    theirs: &Rope,
+) -> (Rope, Vec<Conflict>) {
+    let our_delta = base.diff(ours);
+    let their_delta = base.diff(theirs);
+
+    let mut our_edits = our_delta.edits();
+    let mut their_edits = their_delta.edits();
+
+    let mut merged_edits = Vec::new();
+
+    let mut conflicts = Vec::new();
+
+    // The number of bytes the text before the current region grew by in the
+    // merged `Rope` and in theirs, which can be negative.
+    let mut merged_shift = 0isize;
+    let mut their_shift = 0isize;
+
+    while !our_edits.is_empty() || !their_edits.is_empty() {
+        let ours_first = match (our_edits.first(), their_edits.first()) {
+            (Some(ours), Some(theirs)) => {
+                ours.byte_range.start <= theirs.byte_range.start
+            },
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+
+        let first = if ours_first { &our_edits[0] } else { &their_edits[0] };
+
+        let start = first.byte_range.start;
+        let mut end = first.byte_range.end;
+
+        let mut our_len = usize::from(ours_first);
+        let mut their_len = usize::from(!ours_first);
+
+        // Extend the region with all the edits from either side that touch
+        // or overlap it.
+        loop {
+            if let Some(edit) = our_edits.get(our_len) {
+                if edit.byte_range.start <= end {
+                    end = end.max(edit.byte_range.end);
+                    our_len += 1;
+                    continue;
+                }
+            }
+
+            if let Some(edit) = their_edits.get(their_len) {
+                if edit.byte_range.start <= end {
+                    end = end.max(edit.byte_range.end);
+                    their_len += 1;
+                    continue;
+                }
+            }
+
+            break;
+        }
+
+        let (ours_in_region, rest) = our_edits.split_at(our_len);
+        our_edits = rest;
+
+        let (theirs_in_region, rest) = their_edits.split_at(their_len);
+        their_edits = rest;
+
+        let region = start..end;
+
+        let (text, their_text_len) = match (ours_in_region, theirs_in_region) {
+            ([], [edit]) | ([edit], []) => {
+                let their_text_len = if ours_in_region.is_empty() {
+                    edit.text.len()
+                } else {
+                    region.len()
+                };
+                (edit.text.clone(), their_text_len)
+            },
+
+            _ => {
+                let our_text =
+                    apply_edits(base, region.clone(), ours_in_region);
+
+                let their_text =
+                    apply_edits(base, region.clone(), theirs_in_region);
+
+                let their_start = (start as isize + their_shift) as usize;
+
+                if our_text != their_text {
+                    let merged_start =
+                        (start as isize + merged_shift) as usize;
+
+                    conflicts.push(Conflict {
+                        byte_range: merged_start
+                            ..merged_start + our_text.len(),
+                        base_byte_range: region.clone(),
+                        their_byte_range: their_start
+                            ..their_start + their_text.len(),
+                    });
+                }
+
+                (our_text, their_text.len())
+            },
+        };
+
+        merged_shift += text.len() as isize - region.len() as isize;
+        their_shift += their_text_len as isize - region.len() as isize;
+
+        merged_edits.push(Edit { byte_range: region, text });
+    }
+
+    let mut merged = base.clone();
+    merged.apply_delta(&Delta::from_edits(merged_edits));
+
+    (merged, conflicts)
+}
+
+/// Returns the text in the given byte range of `base` after applying `edits`
+/// to it, which must all be contained in the range.
+#[inline]
+fn apply_edits(
+    base: &Rope,
+    byte_range: Range<usize>,
+    edits: &[Edit],
+) -> String {
+    let mut text = String::new();
+
+    let mut offset = byte_range.start;
+
+    for edit in edits {
+        text.extend(base.byte_slice(offset..edit.byte_range.start).chunks());
+        text.push_str(&edit.text);
+        offset = edit.byte_range.end;
+    }
+
+    text.extend(base.byte_slice(offset..byte_range.end).chunks());
+
+    text
+}
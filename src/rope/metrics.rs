@@ -2,15 +2,27 @@ use core::ops::{Add, AddAssign, Sub, SubAssign};
 
 use super::gap_buffer::GapBuffer;
 use super::gap_slice::GapSlice;
+#[cfg(feature = "utf16-metric")]
+use super::utils::panic_messages as panic;
 use crate::tree::{DoubleEndedUnitMetric, Metric, SlicingMetric, UnitMetric};
 
-#[derive(Copy, Clone, Default, Debug, PartialEq)]
-#[doc(hidden)]
+/// A summary of a piece of text, such as one of the leaves of a
+/// [`Rope`](crate::Rope).
+///
+/// Summaries are computed once when a leaf is created or edited and are
+/// stored together with it, so reading them never requires scanning the
+/// text. See [`Rope::leaf_summaries()`](crate::Rope::leaf_summaries()) for
+/// how to obtain them.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 pub struct ChunkSummary {
-    bytes: usize,
-    line_breaks: usize,
+    pub(super) bytes: usize,
+    pub(super) line_breaks: usize,
+    #[cfg(feature = "char-metric")]
+    pub(super) chars: usize,
     #[cfg(feature = "utf16-metric")]
-    utf16_code_units: usize,
+    pub(super) utf16_code_units: usize,
+    #[cfg(feature = "width-metric")]
+    pub(super) width: usize,
 }
 
 impl From<&str> for ChunkSummary {
@@ -19,8 +31,12 @@ impl From<&str> for ChunkSummary {
         Self {
             bytes: s.len(),
             line_breaks: count::line_breaks(s),
+            #[cfg(feature = "char-metric")]
+            chars: count::chars(s),
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: count::utf16_code_units(s),
+            #[cfg(feature = "width-metric")]
+            width: count::width(s),
         }
     }
 }
@@ -31,18 +47,32 @@ impl From<char> for ChunkSummary {
         Self {
             bytes: ch.len_utf8(),
             line_breaks: (ch == '\n') as usize,
+            #[cfg(feature = "char-metric")]
+            chars: 1,
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: ch.len_utf16(),
+            #[cfg(feature = "width-metric")]
+            width: count::char_width(ch),
         }
     }
 }
 
 impl ChunkSummary {
+    /// Returns the number of bytes in the text.
     #[inline]
     pub fn bytes(&self) -> usize {
         self.bytes
     }
 
+    /// Returns the number of Unicode code points in the text.
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[inline]
+    pub fn chars(&self) -> usize {
+        self.chars
+    }
+
+    /// Returns the number of line feeds (`'\n'`) in the text.
     #[inline]
     pub fn line_breaks(&self) -> usize {
         self.line_breaks
@@ -54,11 +84,27 @@ impl ChunkSummary {
         Self::default()
     }
 
+    /// Returns the number of UTF-16 code units the text would have if it was
+    /// encoded in UTF-16.
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
     #[cfg(feature = "utf16-metric")]
     #[inline]
     pub fn utf16_code_units(&self) -> usize {
         self.utf16_code_units
     }
+
+    /// Returns the number of terminal columns the text would take up if it
+    /// was displayed on a single row.
+    ///
+    /// This is the sum of the widths of every char as given by the
+    /// [`unicode-width`](https://docs.rs/unicode-width) crate, with control
+    /// characters like tabs and line breaks always having a width of zero.
+    #[cfg_attr(docsrs, doc(cfg(feature = "width-metric")))]
+    #[cfg(feature = "width-metric")]
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
 }
 
 impl Add<Self> for ChunkSummary {
@@ -106,10 +152,18 @@ impl AddAssign<Self> for ChunkSummary {
     fn add_assign(&mut self, rhs: Self) {
         self.bytes += rhs.bytes;
         self.line_breaks += rhs.line_breaks;
+        #[cfg(feature = "char-metric")]
+        {
+            self.chars += rhs.chars;
+        }
         #[cfg(feature = "utf16-metric")]
         {
             self.utf16_code_units += rhs.utf16_code_units;
         }
+        #[cfg(feature = "width-metric")]
+        {
+            self.width += rhs.width;
+        }
     }
 }
 
@@ -118,10 +172,18 @@ impl SubAssign<Self> for ChunkSummary {
     fn sub_assign(&mut self, rhs: Self) {
         self.bytes -= rhs.bytes;
         self.line_breaks -= rhs.line_breaks;
+        #[cfg(feature = "char-metric")]
+        {
+            self.chars -= rhs.chars;
+        }
         #[cfg(feature = "utf16-metric")]
         {
             self.utf16_code_units -= rhs.utf16_code_units;
         }
+        #[cfg(feature = "width-metric")]
+        {
+            self.width -= rhs.width;
+        }
     }
 }
 
@@ -237,12 +299,18 @@ impl SummaryUpTo for ByteMetric {
                 str_summary.line_breaks,
             ),
 
+            #[cfg(feature = "char-metric")]
+            chars: count::chars_up_to(in_str, byte_offset, str_summary.chars),
+
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: count::utf16_code_units_up_to(
                 in_str,
                 byte_offset,
                 str_summary.utf16_code_units,
             ),
+
+            #[cfg(feature = "width-metric")]
+            width: count::width_up_to(in_str, byte_offset, str_summary.width),
         }
     }
 }
@@ -343,7 +411,14 @@ impl ToByteOffset for RawLineMetric {
 }
 
 impl SummaryUpTo for RawLineMetric {
-    #[cfg_attr(not(feature = "utf16-metric"), allow(unused_variables))]
+    #[cfg_attr(
+        not(any(
+            feature = "char-metric",
+            feature = "utf16-metric",
+            feature = "width-metric"
+        )),
+        allow(unused_variables)
+    )]
     #[inline]
     fn up_to(
         in_str: &str,
@@ -356,12 +431,18 @@ impl SummaryUpTo for RawLineMetric {
 
             line_breaks: line_offset,
 
+            #[cfg(feature = "char-metric")]
+            chars: count::chars_up_to(in_str, byte_offset, str_summary.chars),
+
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: count::utf16_code_units_up_to(
                 in_str,
                 byte_offset,
                 str_summary.utf16_code_units,
             ),
+
+            #[cfg(feature = "width-metric")]
+            width: count::width_up_to(in_str, byte_offset, str_summary.width),
         }
     }
 }
@@ -573,6 +654,141 @@ impl<const MAX_BYTES: usize> DoubleEndedUnitMetric<GapBuffer<MAX_BYTES>>
     }
 }
 
+#[cfg(feature = "char-metric")]
+pub use char_metric::CharMetric;
+
+#[cfg(feature = "char-metric")]
+mod char_metric {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct CharMetric(pub usize);
+
+    impl Add<Self> for CharMetric {
+        type Output = Self;
+
+        #[inline]
+        fn add(self, other: Self) -> Self {
+            Self(self.0 + other.0)
+        }
+    }
+
+    impl Sub for CharMetric {
+        type Output = Self;
+
+        #[inline]
+        fn sub(self, other: Self) -> Self {
+            Self(self.0 - other.0)
+        }
+    }
+
+    impl AddAssign for CharMetric {
+        #[inline]
+        fn add_assign(&mut self, other: Self) {
+            self.0 += other.0
+        }
+    }
+
+    impl SubAssign for CharMetric {
+        #[inline]
+        fn sub_assign(&mut self, other: Self) {
+            self.0 -= other.0
+        }
+    }
+
+    impl ToByteOffset for CharMetric {
+        #[inline]
+        fn to_byte_offset(&self, in_str: &str) -> usize {
+            convert::byte_of_char(in_str, self.0)
+        }
+    }
+
+    impl SummaryUpTo for CharMetric {
+        #[inline]
+        fn up_to(
+            in_str: &str,
+            str_summary: ChunkSummary,
+            Self(char_offset): Self,
+            byte_offset: usize,
+        ) -> ChunkSummary {
+            ChunkSummary {
+                bytes: byte_offset,
+
+                line_breaks: count::line_breaks_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.line_breaks,
+                ),
+
+                chars: char_offset,
+
+                #[cfg(feature = "utf16-metric")]
+                utf16_code_units: count::utf16_code_units_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.utf16_code_units,
+                ),
+
+                #[cfg(feature = "width-metric")]
+                width: count::width_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.width,
+                ),
+            }
+        }
+    }
+
+    impl Metric<ChunkSummary> for CharMetric {
+        #[inline]
+        fn zero() -> Self {
+            Self(0)
+        }
+
+        #[inline]
+        fn one() -> Self {
+            Self(1)
+        }
+
+        #[inline]
+        fn measure(summary: &ChunkSummary) -> Self {
+            Self(summary.chars)
+        }
+    }
+
+    impl<const MAX_BYTES: usize> SlicingMetric<GapBuffer<MAX_BYTES>>
+        for CharMetric
+    {
+        #[track_caller]
+        #[inline]
+        fn slice_up_to<'a>(
+            chunk: GapSlice<'a>,
+            char_offset: Self,
+            &summary: &ChunkSummary,
+        ) -> (GapSlice<'a>, ChunkSummary)
+        where
+            'a: 'a,
+        {
+            let (left, _) = chunk.split_at_offset(char_offset, summary);
+            left
+        }
+
+        #[track_caller]
+        #[inline]
+        fn slice_from<'a>(
+            chunk: GapSlice<'a>,
+            char_offset: Self,
+            &summary: &ChunkSummary,
+        ) -> (GapSlice<'a>, ChunkSummary)
+        where
+            'a: 'a,
+        {
+            let (_, right) = chunk.split_at_offset(char_offset, summary);
+            right
+        }
+    }
+}
+
 #[cfg(feature = "utf16-metric")]
 pub use utf16_metric::Utf16Metric;
 
@@ -619,10 +835,20 @@ mod utf16_metric {
         #[track_caller]
         #[inline]
         fn to_byte_offset(&self, in_str: &str) -> usize {
-            // TODO: we should panic the given UTF-16 offset doesn't lie on a
-            // char boundary. Right now we just return the byte offset up to
-            // the previous char boundary.
-            convert::byte_of_utf16_code_unit(in_str, self.0)
+            let byte_offset = convert::byte_of_utf16_code_unit(in_str, self.0);
+
+            // If the offset is in the middle of a surrogate pair the
+            // conversion returns the start of the char it's inside of, so the
+            // code units before it are one less than the given offset.
+            if count::utf16_code_units(&in_str[..byte_offset]) != self.0 {
+                panic::utf16_offset_not_char_boundary(
+                    in_str,
+                    self.0,
+                    byte_offset,
+                );
+            }
+
+            byte_offset
         }
     }
 
@@ -643,7 +869,21 @@ mod utf16_metric {
                     str_summary.line_breaks,
                 ),
 
+                #[cfg(feature = "char-metric")]
+                chars: count::chars_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.chars,
+                ),
+
                 utf16_code_units: utf16_code_unit_offset,
+
+                #[cfg(feature = "width-metric")]
+                width: count::width_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.width,
+                ),
             }
         }
     }
@@ -700,9 +940,150 @@ mod utf16_metric {
     }
 }
 
-use str_utils::*;
+#[cfg(feature = "width-metric")]
+pub use width_metric::WidthMetric;
+
+#[cfg(feature = "width-metric")]
+mod width_metric {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct WidthMetric(pub usize);
+
+    impl Add<Self> for WidthMetric {
+        type Output = Self;
+
+        #[inline]
+        fn add(self, other: Self) -> Self {
+            Self(self.0 + other.0)
+        }
+    }
+
+    impl Sub for WidthMetric {
+        type Output = Self;
+
+        #[inline]
+        fn sub(self, other: Self) -> Self {
+            Self(self.0 - other.0)
+        }
+    }
+
+    impl AddAssign for WidthMetric {
+        #[inline]
+        fn add_assign(&mut self, other: Self) {
+            self.0 += other.0
+        }
+    }
+
+    impl SubAssign for WidthMetric {
+        #[inline]
+        fn sub_assign(&mut self, other: Self) {
+            self.0 -= other.0
+        }
+    }
+
+    impl ToByteOffset for WidthMetric {
+        #[inline]
+        fn to_byte_offset(&self, in_str: &str) -> usize {
+            convert::byte_of_width(in_str, self.0)
+        }
+    }
+
+    impl SummaryUpTo for WidthMetric {
+        #[inline]
+        fn up_to(
+            in_str: &str,
+            str_summary: ChunkSummary,
+            _: Self,
+            byte_offset: usize,
+        ) -> ChunkSummary {
+            ChunkSummary {
+                bytes: byte_offset,
+
+                line_breaks: count::line_breaks_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.line_breaks,
+                ),
+
+                #[cfg(feature = "char-metric")]
+                chars: count::chars_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.chars,
+                ),
+
+                #[cfg(feature = "utf16-metric")]
+                utf16_code_units: count::utf16_code_units_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.utf16_code_units,
+                ),
+
+                // The byte offset can be past the given width if it fell in
+                // the middle of a wide char, so we have to measure it.
+                width: count::width_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.width,
+                ),
+            }
+        }
+    }
+
+    impl Metric<ChunkSummary> for WidthMetric {
+        #[inline]
+        fn zero() -> Self {
+            Self(0)
+        }
+
+        #[inline]
+        fn one() -> Self {
+            Self(1)
+        }
+
+        #[inline]
+        fn measure(summary: &ChunkSummary) -> Self {
+            Self(summary.width)
+        }
+    }
+
+    impl<const MAX_BYTES: usize> SlicingMetric<GapBuffer<MAX_BYTES>>
+        for WidthMetric
+    {
+        #[inline]
+        fn slice_up_to<'a>(
+            chunk: GapSlice<'a>,
+            width_offset: Self,
+            &summary: &ChunkSummary,
+        ) -> (GapSlice<'a>, ChunkSummary)
+        where
+            'a: 'a,
+        {
+            let (left, _) = chunk.split_at_offset(width_offset, summary);
+            left
+        }
+
+        #[inline]
+        fn slice_from<'a>(
+            chunk: GapSlice<'a>,
+            width_offset: Self,
+            &summary: &ChunkSummary,
+        ) -> (GapSlice<'a>, ChunkSummary)
+        where
+            'a: 'a,
+        {
+            let (_, right) = chunk.split_at_offset(width_offset, summary);
+            right
+        }
+    }
+}
+
+pub(super) use str_utils::*;
 
 mod str_utils {
+    #[cfg(all(not(miri), feature = "char-metric"))]
+    use str_indices::chars;
     #[cfg(not(miri))]
     use str_indices::lines_lf as lines;
     #[cfg(all(not(miri), feature = "utf16-metric"))]
@@ -724,6 +1105,19 @@ mod str_utils {
             }
         }
 
+        #[cfg(feature = "char-metric")]
+        #[inline]
+        pub fn chars(s: &str) -> usize {
+            #[cfg(not(miri))]
+            {
+                chars::count(s)
+            }
+            #[cfg(miri)]
+            {
+                s.chars().count()
+            }
+        }
+
         #[cfg(feature = "utf16-metric")]
         #[inline]
         pub fn utf16_code_units(s: &str) -> usize {
@@ -737,6 +1131,19 @@ mod str_utils {
             }
         }
 
+        #[cfg(feature = "width-metric")]
+        #[inline]
+        pub fn char_width(ch: char) -> usize {
+            use unicode_width::UnicodeWidthChar;
+            ch.width().unwrap_or(0)
+        }
+
+        #[cfg(feature = "width-metric")]
+        #[inline]
+        pub fn width(s: &str) -> usize {
+            s.chars().map(char_width).sum()
+        }
+
         #[inline(always)]
         pub fn line_breaks_up_to(
             s: &str,
@@ -746,6 +1153,16 @@ mod str_utils {
             metric_up_to(s, byte_offset, tot_line_breaks, line_breaks)
         }
 
+        #[cfg(feature = "char-metric")]
+        #[inline(always)]
+        pub fn chars_up_to(
+            s: &str,
+            byte_offset: usize,
+            tot_chars: usize,
+        ) -> usize {
+            metric_up_to(s, byte_offset, tot_chars, chars)
+        }
+
         #[cfg(feature = "utf16-metric")]
         #[inline(always)]
         pub fn utf16_code_units_up_to(
@@ -761,6 +1178,16 @@ mod str_utils {
             )
         }
 
+        #[cfg(feature = "width-metric")]
+        #[inline(always)]
+        pub fn width_up_to(
+            s: &str,
+            byte_offset: usize,
+            tot_width: usize,
+        ) -> usize {
+            metric_up_to(s, byte_offset, tot_width, width)
+        }
+
         #[inline(always)]
         fn metric_up_to(
             s: &str,
@@ -818,6 +1245,38 @@ mod str_utils {
             }
         }
 
+        #[cfg(feature = "char-metric")]
+        #[inline]
+        pub fn byte_of_char(s: &str, char_offset: usize) -> usize {
+            #[cfg(not(miri))]
+            {
+                chars::to_byte_idx(s, char_offset)
+            }
+
+            #[cfg(miri)]
+            {
+                s.char_indices().nth(char_offset).map_or(s.len(), |(i, _)| i)
+            }
+        }
+
+        /// Returns the byte offset of the end of the shortest prefix of `s`
+        /// whose width is at least `width_offset`, or the length of `s` if
+        /// there isn't one.
+        #[cfg(feature = "width-metric")]
+        #[inline]
+        pub fn byte_of_width(s: &str, width_offset: usize) -> usize {
+            let mut width = 0;
+
+            for (byte_offset, ch) in s.char_indices() {
+                if width >= width_offset {
+                    return byte_offset;
+                }
+                width += super::count::char_width(ch);
+            }
+
+            s.len()
+        }
+
         #[cfg(feature = "utf16-metric")]
         #[inline]
         pub fn byte_of_utf16_code_unit(
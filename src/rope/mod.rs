@@ -1,12 +1,75 @@
+pub(crate) mod aggregate;
+#[cfg(feature = "arbitrary")]
+pub(crate) mod arbitrary;
+mod byte_chunk;
+mod byte_rope;
+mod byte_rope_slice;
+mod case;
+mod cursor;
+mod delta;
+mod diff;
+#[cfg(feature = "encoding")]
+pub(crate) mod encoding;
+mod error;
+mod frozen_rope;
 pub(crate) mod gap_buffer;
 pub(crate) mod gap_slice;
+mod history;
+mod interval_map;
 pub(crate) mod iterators;
+mod line_ending;
+mod markers;
+mod mem_usage;
+mod merge;
 pub mod metrics;
+#[cfg(feature = "unicode-normalization")]
+mod normalization;
+pub(crate) mod pattern;
+#[cfg(feature = "pool")]
+pub(crate) mod pool;
+mod position;
+#[cfg(feature = "proptest")]
+pub(crate) mod proptest;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+#[cfg(feature = "rkyv")]
+mod rkyv;
 mod rope;
 mod rope_builder;
+mod rope_chain;
+#[cfg(feature = "std")]
+mod rope_reader;
 mod rope_slice;
+mod rope_snapshot;
+#[cfg(feature = "ropey-compat")]
+pub(crate) mod ropey_compat;
+#[cfg(feature = "std")]
+mod tree_dump;
+mod tree_stats;
 mod utils;
+mod weak_rope;
 
+pub use byte_rope::ByteRope;
+pub use byte_rope_slice::ByteRopeSlice;
+pub use cursor::Cursor;
+pub use delta::{Change, Delta, Edit};
+pub use error::{Error, Utf8Error};
+pub use frozen_rope::FrozenRope;
+pub use history::History;
+pub use interval_map::IntervalMap;
+pub use line_ending::{LineEnding, LineEndings};
+pub use markers::{Gravity, MarkerId, MarkerSet};
+pub use mem_usage::MemUsage;
+pub use merge::Conflict;
+pub use position::{Position, PositionEncoding};
+#[cfg(feature = "rkyv")]
+pub use rkyv::ArchivedRope;
 pub use rope::Rope;
 pub use rope_builder::RopeBuilder;
+pub use rope_chain::RopeChain;
+#[cfg(feature = "std")]
+pub use rope_reader::RopeReader;
 pub use rope_slice::RopeSlice;
+pub use rope_snapshot::RopeSnapshot;
+pub use tree_stats::TreeStats;
+pub use weak_rope::WeakRope;
@@ -0,0 +1,29 @@
+use unicode_normalization::{IsNormalized, UnicodeNormalization};
+
+use super::{Rope, RopeSlice};
+
+/// Returns whether the text of the slice is in Normalization Form C.
+///
+/// Most text can be classified by the quick check alone, which only looks at
+/// every char once. The text is only normalized and compared against itself
+/// when the quick check can't tell.
+#[inline]
+pub(super) fn is_nfc(slice: RopeSlice<'_>) -> bool {
+    match unicode_normalization::is_nfc_quick(slice.chars()) {
+        IsNormalized::Yes => true,
+        IsNormalized::No => false,
+        IsNormalized::Maybe => slice.chars().eq(slice.chars().nfc()),
+    }
+}
+
+/// Returns a new `Rope` with the text of the slice in Normalization Form C.
+#[inline]
+pub(super) fn to_nfc(slice: RopeSlice<'_>) -> Rope {
+    slice.chars().nfc().collect()
+}
+
+/// Returns a new `Rope` with the text of the slice in Normalization Form D.
+#[inline]
+pub(super) fn to_nfd(slice: RopeSlice<'_>) -> Rope {
+    slice.chars().nfd().collect()
+}
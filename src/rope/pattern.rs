@@ -0,0 +1,617 @@
+use alloc::string::String;
+use core::ops::Range;
+
+use super::{Rope, RopeSlice};
+
+/// A pattern that can be searched for in a [`Rope`](crate::Rope) or
+/// [`RopeSlice`](crate::RopeSlice).
+///
+/// This is crop's counterpart of the `Pattern` trait used by the search
+/// methods of `str`, and it's accepted by `find`, `rfind`, `find_iter`,
+/// `matches`, `replace_all`, the `split` family, `starts_with`, `ends_with`
+/// and the `trim` family. It's implemented for:
+///
+/// - `char`s;
+/// - `&str`s and `&String`s;
+/// - [`RopeSlice`](crate::RopeSlice)s and `&`[`Rope`](crate::Rope)s;
+/// - `[char; N]`s, `&[char; N]`s and `&[char]`s, which match any of the
+///   chars they contain;
+/// - `FnMut(char) -> bool` closures, which match any char they return
+///   `true` for;
+/// - `&`[`Regex`](crate::Regex)es, if the `regex` feature is enabled.
+///
+/// String patterns spanning several chunks are compared chunk by chunk,
+/// without allocating.
+///
+/// Searching backwards for a regex (e.g. with `rfind` or `rsplit`) returns
+/// the longest of the matches ending the furthest back, even if it overlaps
+/// one found by searching forward (e.g. the last match of `aa` in `aaa` is at
+/// `1..3`). This scans the text from the start to find the last forward
+/// match, and then runs the regex backwards from every char boundary after
+/// it. Suffixes (e.g. with `ends_with` or `trim_end_matches`) are instead
+/// matched by running the regex backwards from the end only, so they don't
+/// scan the rest of the text.
+///
+/// This trait is sealed and can't be implemented outside of crop.
+pub trait Pattern: private::Sealed {
+    /// Returns the byte length of the prefix of `slice` matched by the
+    /// pattern, or `None` if it doesn't start with it.
+    #[doc(hidden)]
+    fn prefix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize>;
+
+    /// Returns the byte length of the suffix of `slice` matched by the
+    /// pattern, or `None` if it doesn't end with it.
+    #[doc(hidden)]
+    fn suffix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize>;
+
+    /// Returns the byte range of the first match of the pattern in `slice`
+    /// that starts at or after `byte_offset`, if there is one.
+    #[doc(hidden)]
+    fn find_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>>;
+
+    /// Returns the byte range of the last match of the pattern in `slice`
+    /// that ends at or before `byte_offset`, if there is one.
+    #[doc(hidden)]
+    fn rfind_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>>;
+
+    /// Returns the byte length of the longest prefix of `slice` made of
+    /// repeated matches of the pattern.
+    #[doc(hidden)]
+    #[inline]
+    fn repeated_prefix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        let mut len = 0;
+
+        while let Some(match_len) = self.prefix_len(slice.byte_slice(len..)) {
+            // An empty pattern would match forever.
+            if match_len == 0 {
+                break;
+            }
+            len += match_len;
+        }
+
+        len
+    }
+
+    /// Returns the byte length of the longest suffix of `slice` made of
+    /// repeated matches of the pattern.
+    #[doc(hidden)]
+    #[inline]
+    fn repeated_suffix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        let mut len = 0;
+
+        while let Some(match_len) =
+            self.suffix_len(slice.byte_slice(..slice.byte_len() - len))
+        {
+            if match_len == 0 {
+                break;
+            }
+            len += match_len;
+        }
+
+        len
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for char {}
+    impl Sealed for &str {}
+    impl Sealed for &alloc::string::String {}
+    impl Sealed for super::RopeSlice<'_> {}
+    impl Sealed for &super::Rope {}
+    impl Sealed for &[char] {}
+    impl<const N: usize> Sealed for [char; N] {}
+    impl<const N: usize> Sealed for &[char; N] {}
+    impl<F: FnMut(char) -> bool> Sealed for F {}
+    #[cfg(feature = "regex")]
+    impl Sealed for &crate::Regex {}
+}
+
+impl Pattern for char {
+    #[inline]
+    fn prefix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        (&*self.encode_utf8(&mut [0; 4])).prefix_len(slice)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        (&*self.encode_utf8(&mut [0; 4])).suffix_len(slice)
+    }
+
+    #[inline]
+    fn find_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        (&*self.encode_utf8(&mut [0; 4])).find_in(slice, byte_offset)
+    }
+
+    #[inline]
+    fn rfind_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        (&*self.encode_utf8(&mut [0; 4])).rfind_in(slice, byte_offset)
+    }
+
+    #[inline]
+    fn repeated_prefix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        (|ch| ch == *self).repeated_prefix_len(slice)
+    }
+
+    #[inline]
+    fn repeated_suffix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        (|ch| ch == *self).repeated_suffix_len(slice)
+    }
+}
+
+impl Pattern for &str {
+    #[inline]
+    fn prefix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        affix_len::<false>(slice, core::iter::once(*self), self.len())
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        affix_len::<true>(slice, core::iter::once(*self), self.len())
+    }
+
+    #[inline]
+    fn find_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        super::iterators::StrFindIter::new(
+            slice.byte_slice(byte_offset..),
+            self,
+        )
+        .next()
+        .map(|range| byte_offset + range.start..byte_offset + range.end)
+    }
+
+    #[inline]
+    fn rfind_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        super::utils::rfind(slice.byte_slice(..byte_offset), self)
+            .map(|start| start..start + self.len())
+    }
+}
+
+impl Pattern for &String {
+    #[inline]
+    fn prefix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        self.as_str().prefix_len(slice)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        self.as_str().suffix_len(slice)
+    }
+
+    #[inline]
+    fn find_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        self.as_str().find_in(slice, byte_offset)
+    }
+
+    #[inline]
+    fn rfind_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        self.as_str().rfind_in(slice, byte_offset)
+    }
+}
+
+impl Pattern for RopeSlice<'_> {
+    #[inline]
+    fn prefix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        affix_len::<false>(slice, self.chunks(), self.byte_len())
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        affix_len::<true>(slice, self.chunks().rev(), self.byte_len())
+    }
+
+    // Searching for a pattern spanning several chunks needs it to be
+    // contiguous, so we only allocate in that case.
+
+    #[inline]
+    fn find_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        (&*self.to_cow()).find_in(slice, byte_offset)
+    }
+
+    #[inline]
+    fn rfind_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        (&*self.to_cow()).rfind_in(slice, byte_offset)
+    }
+}
+
+impl Pattern for &Rope {
+    #[inline]
+    fn prefix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        self.byte_slice(..).prefix_len(slice)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        self.byte_slice(..).suffix_len(slice)
+    }
+
+    #[inline]
+    fn find_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        self.byte_slice(..).find_in(slice, byte_offset)
+    }
+
+    #[inline]
+    fn rfind_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        self.byte_slice(..).rfind_in(slice, byte_offset)
+    }
+}
+
+impl<F: FnMut(char) -> bool> Pattern for F {
+    #[inline]
+    fn prefix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        slice.chars().next().filter(|&ch| self(ch)).map(char::len_utf8)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        slice.chars().next_back().filter(|&ch| self(ch)).map(char::len_utf8)
+    }
+
+    #[inline]
+    fn find_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        slice
+            .byte_slice(byte_offset..)
+            .char_indices()
+            .find(|&(_, ch)| self(ch))
+            .map(|(idx, ch)| {
+                let start = byte_offset + idx;
+                start..start + ch.len_utf8()
+            })
+    }
+
+    #[inline]
+    fn rfind_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        slice
+            .byte_slice(..byte_offset)
+            .char_indices()
+            .rev()
+            .find(|&(_, ch)| self(ch))
+            .map(|(start, ch)| start..start + ch.len_utf8())
+    }
+
+    // Unlike the default implementations, these scan the chars of the slice
+    // once instead of re-slicing it after every match.
+
+    #[inline]
+    fn repeated_prefix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        slice.chars().take_while(|&ch| self(ch)).map(char::len_utf8).sum()
+    }
+
+    #[inline]
+    fn repeated_suffix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        slice
+            .chars()
+            .rev()
+            .take_while(|&ch| self(ch))
+            .map(char::len_utf8)
+            .sum()
+    }
+}
+
+impl Pattern for &[char] {
+    #[inline]
+    fn prefix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        (|ch| self.contains(&ch)).prefix_len(slice)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        (|ch| self.contains(&ch)).suffix_len(slice)
+    }
+
+    #[inline]
+    fn find_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        (|ch| self.contains(&ch)).find_in(slice, byte_offset)
+    }
+
+    #[inline]
+    fn rfind_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        (|ch| self.contains(&ch)).rfind_in(slice, byte_offset)
+    }
+
+    #[inline]
+    fn repeated_prefix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        (|ch| self.contains(&ch)).repeated_prefix_len(slice)
+    }
+
+    #[inline]
+    fn repeated_suffix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        (|ch| self.contains(&ch)).repeated_suffix_len(slice)
+    }
+}
+
+impl<const N: usize> Pattern for [char; N] {
+    #[inline]
+    fn prefix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        (&self[..]).prefix_len(slice)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        (&self[..]).suffix_len(slice)
+    }
+
+    #[inline]
+    fn find_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        (&self[..]).find_in(slice, byte_offset)
+    }
+
+    #[inline]
+    fn rfind_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        (&self[..]).rfind_in(slice, byte_offset)
+    }
+
+    #[inline]
+    fn repeated_prefix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        (&self[..]).repeated_prefix_len(slice)
+    }
+
+    #[inline]
+    fn repeated_suffix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        (&self[..]).repeated_suffix_len(slice)
+    }
+}
+
+impl<const N: usize> Pattern for &[char; N] {
+    #[inline]
+    fn prefix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        (&self[..]).prefix_len(slice)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        (&self[..]).suffix_len(slice)
+    }
+
+    #[inline]
+    fn find_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        (&self[..]).find_in(slice, byte_offset)
+    }
+
+    #[inline]
+    fn rfind_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        (&self[..]).rfind_in(slice, byte_offset)
+    }
+
+    #[inline]
+    fn repeated_prefix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        (&self[..]).repeated_prefix_len(slice)
+    }
+
+    #[inline]
+    fn repeated_suffix_len(&mut self, slice: RopeSlice<'_>) -> usize {
+        (&self[..]).repeated_suffix_len(slice)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Pattern for &crate::Regex {
+    #[inline]
+    fn prefix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        // The leftmost-first match starts at 0 if any match does.
+        self.find_in(slice, 0)
+            .filter(|range| range.start == 0)
+            .map(|range| range.end)
+    }
+
+    #[inline]
+    fn suffix_len(&mut self, slice: RopeSlice<'_>) -> Option<usize> {
+        // Searching backwards from the end finds the longest match ending
+        // there, which can overlap the last match found searching forward.
+        crate::iter::RegexFindIter::new(slice, *self)
+            .suffix_start()
+            .map(|start| slice.byte_len() - start)
+    }
+
+    #[inline]
+    fn find_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        crate::iter::RegexFindIter::new(slice, *self)
+            .starting_at(byte_offset)
+            .next()
+    }
+
+    #[inline]
+    fn rfind_in(
+        &mut self,
+        slice: RopeSlice<'_>,
+        byte_offset: usize,
+    ) -> Option<Range<usize>> {
+        crate::iter::RegexFindIter::new(slice, *self).rfind(byte_offset)
+    }
+}
+
+/// Returns the byte range of the next match of the pattern in `slice`
+/// starting at or after `byte_offset`.
+///
+/// If `skip_empty` is `true`, an empty match at `byte_offset` is skipped.
+/// This is used when `byte_offset` is the end of the previous match, which
+/// an empty match must not immediately follow (otherwise an empty pattern
+/// would keep matching at the same offset).
+#[inline]
+pub(crate) fn next_match<P: Pattern>(
+    pattern: &mut P,
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+    skip_empty: bool,
+) -> Option<Range<usize>> {
+    let range = pattern.find_in(slice, byte_offset)?;
+
+    if !(skip_empty && range.is_empty() && range.start == byte_offset) {
+        return Some(range);
+    }
+
+    let next_char = slice.byte_slice(byte_offset..).chars().next()?;
+
+    pattern.find_in(slice, byte_offset + next_char.len_utf8())
+}
+
+/// Returns the byte range of the previous match of the pattern in `slice`
+/// ending at or before `byte_offset`.
+///
+/// This is the backward counterpart of [`next_match()`], where
+/// `skip_empty` skips an empty match at `byte_offset`.
+#[inline]
+pub(crate) fn prev_match<P: Pattern>(
+    pattern: &mut P,
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+    skip_empty: bool,
+) -> Option<Range<usize>> {
+    let range = pattern.rfind_in(slice, byte_offset)?;
+
+    if !(skip_empty && range.is_empty() && range.end == byte_offset) {
+        return Some(range);
+    }
+
+    let prev_char = slice.byte_slice(..byte_offset).chars().next_back()?;
+
+    pattern.rfind_in(slice, byte_offset - prev_char.len_utf8())
+}
+
+/// Returns `pattern_len` if the text of `slice` starts with (or ends with,
+/// if `FROM_END` is `true`) the concatenation of the pattern's chunks.
+///
+/// The pattern's chunks have to be given in the order they're compared in,
+/// i.e. from back to front if `FROM_END` is `true`.
+#[inline]
+fn affix_len<'p, const FROM_END: bool>(
+    slice: RopeSlice<'_>,
+    mut pattern: impl Iterator<Item = &'p str>,
+    pattern_len: usize,
+) -> Option<usize> {
+    if pattern_len > slice.byte_len() {
+        return None;
+    }
+
+    let mut chunks = slice.chunks();
+
+    let mut next_chunk = || {
+        if FROM_END { chunks.next_back() } else { chunks.next() }
+    };
+
+    let mut text: &[u8] = &[];
+
+    let mut pat: &[u8] = &[];
+
+    loop {
+        if pat.is_empty() {
+            match pattern.next() {
+                Some(next) => pat = next.as_bytes(),
+                None => return Some(pattern_len),
+            }
+            continue;
+        }
+
+        if text.is_empty() {
+            // The pattern isn't longer than the slice, so we can't run out of
+            // text before running out of pattern.
+            text = next_chunk()?.as_bytes();
+            continue;
+        }
+
+        let len = text.len().min(pat.len());
+
+        if FROM_END {
+            let (text_rest, text_tail) = text.split_at(text.len() - len);
+            let (pat_rest, pat_tail) = pat.split_at(pat.len() - len);
+            if text_tail != pat_tail {
+                return None;
+            }
+            text = text_rest;
+            pat = pat_rest;
+        } else {
+            let (text_head, text_rest) = text.split_at(len);
+            let (pat_head, pat_rest) = pat.split_at(len);
+            if text_head != pat_head {
+                return None;
+            }
+            text = text_rest;
+            pat = pat_rest;
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+/// The default number of buffers each thread's pool can hold.
+const DEFAULT_CAPACITY: usize = 64;
+
+std::thread_local! {
+    static POOL: RefCell<Pool> = const { RefCell::new(Pool::new()) };
+}
+
+struct Pool {
+    /// One free list for every buffer length that's been given back to the
+    /// pool, so that buffers of one length are never thrown away to make
+    /// room for another.
+    free_lists: Vec<FreeList>,
+    capacity: usize,
+    stats: PoolStats,
+}
+
+struct FreeList {
+    buffer_len: usize,
+    buffers: Vec<Box<[u8]>>,
+}
+
+impl Pool {
+    #[inline]
+    const fn new() -> Self {
+        Self {
+            free_lists: Vec::new(),
+            capacity: DEFAULT_CAPACITY,
+            stats: PoolStats {
+                hits: 0,
+                misses: 0,
+                recycled: 0,
+                discarded: 0,
+                pooled: 0,
+            },
+        }
+    }
+
+    #[inline]
+    fn free_list(&mut self, buffer_len: usize) -> Option<&mut FreeList> {
+        self.free_lists.iter_mut().find(|list| list.buffer_len == buffer_len)
+    }
+
+    #[inline]
+    fn update_pooled(&mut self) {
+        self.stats.pooled =
+            self.free_lists.iter().map(|list| list.buffers.len()).sum();
+    }
+}
+
+/// Statistics about the leaf buffer pool of the current thread, returned by
+/// [`stats()`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// The number of buffers that were taken from the pool instead of being
+    /// allocated.
+    pub hits: usize,
+
+    /// The number of buffers that had to be allocated because the pool had
+    /// no buffer of the right size.
+    pub misses: usize,
+
+    /// The number of freed buffers that were given back to the pool.
+    pub recycled: usize,
+
+    /// The number of freed buffers that were deallocated because the pool
+    /// was already holding as many buffers of their size as it can.
+    pub discarded: usize,
+
+    /// The number of buffers currently held by the pool.
+    pub pooled: usize,
+}
+
+/// Returns the maximum number of buffers of each size the pool of the current
+/// thread can hold.
+///
+/// Every buffer size gets its own free list, so buffers of one size never
+/// take up the slots of another.
+///
+/// # Examples
+///
+/// ```
+/// crop::pool::set_capacity(128);
+/// assert_eq!(crop::pool::capacity(), 128);
+/// ```
+#[inline]
+pub fn capacity() -> usize {
+    POOL.with_borrow(|pool| pool.capacity)
+}
+
+/// Deallocates all the buffers held by the pool of the current thread.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::Rope;
+/// #
+/// drop(Rope::from("Hello Earth 🌎!"));
+/// assert!(crop::pool::stats().pooled > 0);
+///
+/// crop::pool::clear();
+/// assert_eq!(crop::pool::stats().pooled, 0);
+/// ```
+#[inline]
+pub fn clear() {
+    POOL.with_borrow_mut(|pool| {
+        pool.free_lists = Vec::new();
+        pool.stats.pooled = 0;
+    })
+}
+
+/// Sets the maximum number of buffers of each size the pool of the current
+/// thread can hold, deallocating the excess buffers if it's already holding
+/// more than that.
+///
+/// Setting the capacity to zero effectively disables the pool.
+#[inline]
+pub fn set_capacity(capacity: usize) {
+    POOL.with_borrow_mut(|pool| {
+        pool.capacity = capacity;
+        for list in &mut pool.free_lists {
+            list.buffers.truncate(capacity);
+            list.buffers.shrink_to(capacity);
+        }
+        pool.update_pooled();
+    })
+}
+
+/// Returns the statistics of the pool of the current thread.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::Rope;
+/// #
+/// let before = crop::pool::stats();
+///
+/// // Dropping a rope gives its buffers back to the pool..
+/// drop(Rope::from("Hello Earth 🌎!"));
+///
+/// let after_drop = crop::pool::stats();
+/// assert!(after_drop.recycled > before.recycled);
+///
+/// // ..and creating a new one reuses them.
+/// let _ = Rope::from("Hello Earth 🌎!");
+///
+/// let after_new = crop::pool::stats();
+/// assert!(after_new.hits > after_drop.hits);
+/// ```
+#[inline]
+pub fn stats() -> PoolStats {
+    POOL.with_borrow(|pool| pool.stats)
+}
+
+/// Takes a buffer from the pool of the current thread, if it has one of the
+/// right size.
+#[inline]
+pub(super) fn take<const MAX_BYTES: usize>() -> Option<Box<[u8; MAX_BYTES]>> {
+    POOL.try_with(|pool| {
+        let mut pool = pool.borrow_mut();
+
+        let Some(buffer) =
+            pool.free_list(MAX_BYTES).and_then(|list| list.buffers.pop())
+        else {
+            pool.stats.misses += 1;
+            return None;
+        };
+
+        pool.stats.hits += 1;
+        pool.stats.pooled -= 1;
+
+        buffer.try_into().ok()
+    })
+    .ok()
+    .flatten()
+}
+
+/// Gives a freed buffer back to the pool of the current thread, or
+/// deallocates it if the pool is already holding as many buffers of its
+/// size as it can.
+#[inline]
+pub(super) fn give<const MAX_BYTES: usize>(buffer: Box<[u8; MAX_BYTES]>) {
+    // If the thread-local has already been destroyed (e.g. because the
+    // buffer is being dropped by another thread-local's destructor) the
+    // buffer is simply deallocated.
+    let _ = POOL.try_with(|pool| {
+        let mut pool = pool.borrow_mut();
+
+        let capacity = pool.capacity;
+
+        if pool.free_list(MAX_BYTES).is_none() {
+            pool.free_lists
+                .push(FreeList { buffer_len: MAX_BYTES, buffers: Vec::new() });
+        }
+
+        let list = pool.free_list(MAX_BYTES).unwrap();
+
+        if list.buffers.len() < capacity {
+            list.buffers.push(buffer);
+            pool.stats.recycled += 1;
+            pool.stats.pooled += 1;
+        } else {
+            pool.stats.discarded += 1;
+        }
+    });
+}
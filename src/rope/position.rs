@@ -0,0 +1,229 @@
+use super::RopeSlice;
+use super::utils::panic_messages as panic;
+
+/// A position in a piece of text expressed as a line offset and a column
+/// offset within that line, like the ones used by the Language Server
+/// Protocol.
+///
+/// How the column is measured depends on the [`PositionEncoding`] passed
+/// together with the position.
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct Position {
+    /// The line offset of the position.
+    pub line: usize,
+
+    /// The column offset of the position, measured in the units of the
+    /// [`PositionEncoding`] used to interpret it.
+    pub column: usize,
+}
+
+impl Position {
+    /// Creates a new `Position` from a line and a column offset.
+    #[inline]
+    pub const fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+/// The units in which the column of a [`Position`] is measured.
+///
+/// These map one to one to the position encodings defined by the Language
+/// Server Protocol. The default is [`Utf16`](Self::Utf16), which is the only
+/// encoding every LSP client is required to support.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PositionEncoding {
+    /// Columns are measured in bytes.
+    Utf8,
+
+    /// Columns are measured in UTF-16 code units.
+    #[default]
+    Utf16,
+
+    /// Columns are measured in Unicode code points, i.e. `char`s.
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// Returns the number of units the given `char` takes up in this
+    /// encoding.
+    #[inline]
+    fn char_len(self, ch: char) -> usize {
+        match self {
+            Self::Utf8 => ch.len_utf8(),
+            Self::Utf16 => ch.len_utf16(),
+            Self::Utf32 => 1,
+        }
+    }
+}
+
+/// Returns the byte offset of the given position in the slice.
+///
+/// The column is located by scanning the line the position is on, so this
+/// runs in O(log n + line length).
+#[track_caller]
+#[inline]
+pub(super) fn byte_of_position(
+    slice: RopeSlice<'_>,
+    Position { line, column }: Position,
+    encoding: PositionEncoding,
+) -> usize {
+    if line > slice.line_len() {
+        panic::line_offset_out_of_bounds(line, slice.line_len());
+    }
+
+    let line_start = slice.byte_of_line(line);
+
+    // After the last line there's nothing left to scan, and only column 0 is
+    // valid.
+    if line == slice.line_len() {
+        if column > 0 {
+            panic::column_offset_out_of_bounds(column, 0);
+        }
+        return line_start;
+    }
+
+    let line = slice.line(line);
+
+    let mut byte_offset = 0;
+
+    let mut units = 0;
+
+    for chunk in line.chunks() {
+        for ch in chunk.chars() {
+            if units == column {
+                return line_start + byte_offset;
+            }
+
+            units += encoding.char_len(ch);
+
+            if units > column {
+                panic::column_offset_not_char_boundary(column, ch);
+            }
+
+            byte_offset += ch.len_utf8();
+        }
+    }
+
+    if units < column {
+        panic::column_offset_out_of_bounds(column, units);
+    }
+
+    line_start + byte_offset
+}
+
+/// Returns the position of the given byte offset in the slice.
+///
+/// The column is measured by scanning the line the byte offset is on, so
+/// this runs in O(log n + line length).
+#[track_caller]
+#[inline]
+pub(super) fn position_of_byte(
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+    encoding: PositionEncoding,
+) -> Position {
+    if byte_offset > slice.byte_len() {
+        panic::byte_offset_out_of_bounds(byte_offset, slice.byte_len());
+    }
+
+    let line = slice.line_of_byte(byte_offset);
+
+    let line_start = slice.byte_of_line(line);
+
+    let column = match encoding {
+        PositionEncoding::Utf8 => byte_offset - line_start,
+
+        _ => slice
+            .byte_slice(line_start..byte_offset)
+            .chars()
+            .map(|ch| encoding.char_len(ch))
+            .sum(),
+    };
+
+    Position { line, column }
+}
+
+/// Returns the byte offset of the given visual column in the given line of
+/// the slice, with tabs expanding to the next multiple of `tab_width`
+/// columns.
+///
+/// If the column falls in the middle of a char, like a tab or a wide char,
+/// the offset of the start of that char is returned. If it's past the end of
+/// the line, the offset of the end of the line is returned.
+#[cfg(feature = "display-width")]
+#[track_caller]
+#[inline]
+pub(super) fn byte_of_visual_col(
+    slice: RopeSlice<'_>,
+    line: usize,
+    visual_col: usize,
+    tab_width: usize,
+) -> usize {
+    if line > slice.line_len() {
+        panic::line_offset_out_of_bounds(line, slice.line_len());
+    }
+
+    let mut byte_offset = slice.byte_of_line(line);
+
+    if line == slice.line_len() {
+        return byte_offset;
+    }
+
+    let mut col = 0;
+
+    for ch in slice.line(line).chars() {
+        let width = visual_width(ch, col, tab_width);
+
+        if col + width > visual_col {
+            break;
+        }
+
+        col += width;
+
+        byte_offset += ch.len_utf8();
+    }
+
+    byte_offset
+}
+
+/// Returns the visual column of the given byte offset in the slice, with
+/// tabs expanding to the next multiple of `tab_width` columns.
+#[cfg(feature = "display-width")]
+#[track_caller]
+#[inline]
+pub(super) fn visual_col_of_byte(
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+    tab_width: usize,
+) -> usize {
+    if byte_offset > slice.byte_len() {
+        panic::byte_offset_out_of_bounds(byte_offset, slice.byte_len());
+    }
+
+    let line_start = slice.byte_of_line(slice.line_of_byte(byte_offset));
+
+    slice
+        .byte_slice(line_start..byte_offset)
+        .chars()
+        .fold(0, |col, ch| col + visual_width(ch, col, tab_width))
+}
+
+/// Returns the number of columns `ch` takes up when it's displayed `col`
+/// columns after the start of a line.
+///
+/// The width of every char is measured with the `unicode-width` crate,
+/// except for tabs, which expand to the next multiple of `tab_width` columns,
+/// and line breaks, which have no width.
+#[cfg(feature = "display-width")]
+#[inline]
+pub(super) fn visual_width(ch: char, col: usize, tab_width: usize) -> usize {
+    use unicode_width::UnicodeWidthChar;
+
+    match ch {
+        '\t' if tab_width > 0 => tab_width - col % tab_width,
+        '\t' | '\n' | '\r' => 0,
+        _ => ch.width().unwrap_or(0),
+    }
+}
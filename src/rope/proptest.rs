@@ -0,0 +1,188 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use ::proptest::collection::vec;
+use ::proptest::prelude::*;
+use ::proptest::sample::Index;
+
+use super::Rope;
+
+/// Where to leave the gap of one of the chunks of a generated `Rope`.
+#[derive(Clone, Debug)]
+enum Gap {
+    /// At the given byte offset of the `Rope`.
+    At(Index),
+
+    /// At the start of one of its chunks.
+    ChunkStart(Index),
+
+    /// At the end of one of its chunks.
+    ChunkEnd(Index),
+}
+
+/// Returns a strategy generating `Rope`s with arbitrary text and varied chunk
+/// layouts.
+///
+/// The text is biased towards line breaks, and especially towards `"\r"`s
+/// and `"\n"`s close to each other, so that some CRLF pairs end up being
+/// split between two chunks. See [`rope_from()`] for how the chunk layouts
+/// are generated.
+///
+/// # Examples
+///
+/// ```
+/// # use proptest::test_runner::TestRunner;
+/// #
+/// let mut runner = TestRunner::default();
+///
+/// runner
+///     .run(&crop::proptest::rope(), |r| {
+///         assert_eq!(r.lines().count(), r.line_len());
+///         Ok(())
+///     })
+///     .unwrap();
+/// ```
+#[inline]
+pub fn rope() -> impl Strategy<Value = Rope> {
+    rope_from(text())
+}
+
+/// Returns a strategy generating `Rope`s with the text generated by the
+/// given strategy and varied chunk layouts.
+///
+/// Instead of building the `Rope` from a single string, which always packs
+/// its chunks as tightly as possible, the text is split into pieces which
+/// are either appended or prepended to it one at a time. Then the gaps of
+/// some of its chunks are moved to arbitrary offsets, including the very
+/// start and end of a chunk, by inserting and immediately deleting some text
+/// at those offsets.
+///
+/// The contents of the generated `Rope`s are always equal to the generated
+/// text, no matter how their chunks are laid out.
+///
+/// # Examples
+///
+/// ```
+/// # use proptest::prelude::*;
+/// # use proptest::test_runner::TestRunner;
+/// #
+/// let text = "foo\r\nbar\r\nbaz".repeat(100);
+///
+/// let mut runner = TestRunner::default();
+///
+/// runner
+///     .run(&crop::proptest::rope_from(Just(text.clone())), |r| {
+///         assert_eq!(r, text);
+///         Ok(())
+///     })
+///     .unwrap();
+/// ```
+#[inline]
+pub fn rope_from<S>(text: S) -> impl Strategy<Value = Rope>
+where
+    S: Strategy<Value = String>,
+{
+    (text, vec(any::<Index>(), 0..16), any::<bool>(), vec(gap(), 0..16))
+        .prop_map(|(text, cuts, prepend, gaps)| {
+            build(&text, &cuts, prepend, &gaps)
+        })
+}
+
+#[inline]
+fn build(text: &str, cuts: &[Index], prepend: bool, gaps: &[Gap]) -> Rope {
+    let mut cuts = cuts
+        .iter()
+        .map(|cut| next_char_boundary(text, cut.index(text.len() + 1)))
+        .collect::<Vec<_>>();
+
+    cuts.push(0);
+    cuts.push(text.len());
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    let pieces = cuts.windows(2).map(|w| &text[w[0]..w[1]]);
+
+    let mut rope = Rope::new();
+
+    if prepend {
+        for piece in pieces.rev() {
+            rope.insert(0, piece);
+        }
+    } else {
+        for piece in pieces {
+            rope.insert(rope.byte_len(), piece);
+        }
+    }
+
+    // An empty rope doesn't yield any leaf summaries, and there's nowhere to
+    // move its gap to anyway.
+    if rope.is_empty() {
+        return rope;
+    }
+
+    for gap in gaps {
+        let byte_offset = match gap {
+            Gap::At(idx) => idx.index(rope.byte_len() + 1),
+
+            Gap::ChunkStart(idx) | Gap::ChunkEnd(idx) => {
+                let (start, summary) = rope
+                    .leaf_summaries()
+                    .nth(idx.index(rope.tree.leaf_count()))
+                    .unwrap();
+
+                if matches!(gap, Gap::ChunkStart(_)) {
+                    start
+                } else {
+                    start + summary.bytes()
+                }
+            },
+        };
+
+        let byte_offset = next_char_boundary_rope(&rope, byte_offset);
+
+        rope.insert(byte_offset, "~");
+        rope.delete(byte_offset..byte_offset + 1);
+    }
+
+    rope
+}
+
+#[inline]
+fn gap() -> impl Strategy<Value = Gap> {
+    prop_oneof![
+        any::<Index>().prop_map(Gap::At),
+        any::<Index>().prop_map(Gap::ChunkStart),
+        any::<Index>().prop_map(Gap::ChunkEnd),
+    ]
+}
+
+#[inline]
+fn next_char_boundary(s: &str, mut byte_offset: usize) -> usize {
+    while !s.is_char_boundary(byte_offset) {
+        byte_offset += 1;
+    }
+    byte_offset
+}
+
+#[inline]
+fn next_char_boundary_rope(rope: &Rope, mut byte_offset: usize) -> usize {
+    while !rope.is_char_boundary(byte_offset) {
+        byte_offset += 1;
+    }
+    byte_offset
+}
+
+/// Returns a strategy generating text made of short runs of ASCII letters,
+/// arbitrary chars and line breaks.
+#[inline]
+fn text() -> impl Strategy<Value = String> {
+    let piece = prop_oneof![
+        4 => "[a-zA-Z ]{1,32}",
+        2 => Just(String::from("\r\n")),
+        1 => Just(String::from("\r")),
+        1 => Just(String::from("\n")),
+        1 => any::<char>().prop_map(String::from),
+    ];
+
+    vec(piece, 0..256).prop_map(|pieces| pieces.concat())
+}
@@ -0,0 +1,51 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+
+use ::quickcheck::{Arbitrary, Gen};
+
+use super::Rope;
+
+/// An arbitrary `Rope` is built by inserting a sequence of arbitrary strings,
+/// with a bias towards line breaks, at arbitrary offsets, so that its chunks
+/// aren't all packed to the brim and their gaps are scattered around instead
+/// of being all at the end.
+///
+/// Shrinking first tries to repack the same text into as few chunks as
+/// possible, and then shrinks the text itself.
+impl Arbitrary for Rope {
+    #[inline]
+    fn arbitrary(g: &mut Gen) -> Self {
+        let mut rope = Rope::new();
+
+        for _ in 0..usize::arbitrary(g) % (g.size() + 1) {
+            let text = if bool::arbitrary(g) {
+                String::arbitrary(g)
+            } else {
+                String::from(*g.choose(&["\r\n", "\r", "\n"]).unwrap())
+            };
+
+            let mut byte_offset = usize::arbitrary(g) % (rope.byte_len() + 1);
+
+            while !rope.is_char_boundary(byte_offset) {
+                byte_offset += 1;
+            }
+
+            rope.insert(byte_offset, text);
+        }
+
+        rope
+    }
+
+    #[inline]
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let text = self.to_string();
+
+        let packed = Rope::from(text.as_str());
+
+        let repacked = (packed.tree_stats().leaf_count
+            < self.tree_stats().leaf_count)
+            .then_some(packed);
+
+        Box::new(repacked.into_iter().chain(text.shrink().map(Rope::from)))
+    }
+}
@@ -0,0 +1,154 @@
+use alloc::vec::Vec;
+
+use ::rkyv::bytecheck::CheckBytes;
+use ::rkyv::rancor::{Fallible, Source};
+use ::rkyv::ser::{Allocator, Writer};
+use ::rkyv::string::{ArchivedString, StringResolver};
+use ::rkyv::vec::{ArchivedVec, VecResolver};
+use ::rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+
+use super::Rope;
+use crate::RopeBuilder;
+
+/// The archived version of a [`Rope`], as produced by
+/// [`rkyv`](https://docs.rs/rkyv).
+///
+/// The text is archived as a sequence of chunks, so it can be read directly
+/// from the archive with [`chunks()`](Self::chunks()) without having to
+/// rebuild a `Rope` first. Deserializing it appends all the chunks to a
+/// [`RopeBuilder`](crate::RopeBuilder), without re-validating or re-parsing
+/// the text.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::{ArchivedRope, Rope};
+/// # use rkyv::rancor::Error;
+/// #
+/// let r = Rope::from("Hello World!");
+///
+/// let bytes = rkyv::to_bytes::<Error>(&r).unwrap();
+///
+/// let archived = rkyv::access::<ArchivedRope, Error>(&bytes).unwrap();
+/// assert_eq!(archived.chunks().collect::<String>(), "Hello World!");
+///
+/// let deserialized = rkyv::deserialize::<Rope, Error>(archived).unwrap();
+/// assert_eq!(deserialized, r);
+/// ```
+#[repr(transparent)]
+pub struct ArchivedRope {
+    chunks: ArchivedVec<ArchivedString>,
+}
+
+impl ArchivedRope {
+    /// Returns the byte length of the archived text.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.chunks().map(str::len).sum()
+    }
+
+    /// Returns an iterator over the chunks of the archived text.
+    #[inline]
+    pub fn chunks(&self) -> impl DoubleEndedIterator<Item = &str> + '_ {
+        self.chunks.iter().map(ArchivedString::as_str)
+    }
+
+    /// Returns `true` if the archived text is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+}
+
+impl core::fmt::Debug for ArchivedRope {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_list().entries(self.chunks()).finish()
+    }
+}
+
+// SAFETY: `ArchivedRope` is a transparent wrapper around an `ArchivedVec`,
+// which is `Portable`.
+unsafe impl Portable for ArchivedRope {}
+
+// SAFETY: `ArchivedRope` is a transparent wrapper around an `ArchivedVec`, so
+// it's valid exactly when the latter is.
+unsafe impl<C> CheckBytes<C> for ArchivedRope
+where
+    C: Fallible + ?Sized,
+    ArchivedVec<ArchivedString>: CheckBytes<C>,
+{
+    #[inline]
+    unsafe fn check_bytes(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<(), C::Error> {
+        unsafe {
+            ArchivedVec::<ArchivedString>::check_bytes(value.cast(), context)
+        }
+    }
+}
+
+impl Archive for Rope {
+    type Archived = ArchivedRope;
+    type Resolver = VecResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        // SAFETY: `ArchivedRope` is a transparent wrapper around an
+        // `ArchivedVec`.
+        let out =
+            unsafe { out.cast_unchecked::<ArchivedVec<ArchivedString>>() };
+        ArchivedVec::resolve_from_len(self.chunks().count(), resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for Rope
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<VecResolver, S::Error> {
+        let chunks = self.chunks().map(ChunkRef).collect::<Vec<_>>();
+        ArchivedVec::serialize_from_slice(&chunks, serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Rope, D> for ArchivedRope {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<Rope, D::Error> {
+        let mut builder = RopeBuilder::new();
+        for chunk in self.chunks() {
+            builder.append(chunk);
+        }
+        Ok(builder.build())
+    }
+}
+
+/// A chunk of a `Rope`, archived as an `ArchivedString`.
+struct ChunkRef<'a>(&'a str);
+
+impl Archive for ChunkRef<'_> {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedString::resolve_from_str(self.0, resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for ChunkRef<'_>
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    #[inline]
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<StringResolver, S::Error> {
+        ArchivedString::serialize_from_str(self.0, serializer)
+    }
+}
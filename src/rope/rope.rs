@@ -1,11 +1,32 @@
 use alloc::string::String;
-use core::ops::RangeBounds;
+use core::ops::{ControlFlow, Range, RangeBounds};
 
-use super::RopeSlice;
+use super::error::{
+    Error,
+    Utf8Error,
+    check_byte_range,
+    check_line_index,
+    check_line_range,
+};
 use super::gap_buffer::GapBuffer;
-use super::iterators::{Bytes, Chars, Chunks, Lines, RawLines};
+use super::iterators::{
+    Bytes,
+    Chars,
+    Chunks,
+    FindIter,
+    LeafSummaries,
+    Lines,
+    RawLines,
+};
 use super::metrics::{ByteMetric, RawLineMetric};
+use super::position::{
+    Position,
+    PositionEncoding,
+    byte_of_position,
+    position_of_byte,
+};
 use super::utils::{panic_messages as panic, *};
+use super::{Change, Cursor, FrozenRope, RopeSlice, WeakRope};
 use crate::range_bounds_to_start_end;
 use crate::tree::Tree;
 
@@ -16,14 +37,14 @@ const ARITY: usize = 4;
 const ARITY: usize = 16;
 
 #[cfg(any(test, feature = "small_chunks"))]
-const CHUNK_MAX_BYTES: usize = 4;
+pub(super) const CHUNK_MAX_BYTES: usize = 4;
 
 // With 4-byte chunks, fuzzing is unbearably slow.
 #[cfg(fuzzing)]
-const CHUNK_MAX_BYTES: usize = 16;
+pub(super) const CHUNK_MAX_BYTES: usize = 16;
 
 #[cfg(not(any(test, fuzzing, feature = "small_chunks")))]
-const CHUNK_MAX_BYTES: usize = 2048;
+pub(super) const CHUNK_MAX_BYTES: usize = 2048;
 
 pub(super) type RopeChunk = GapBuffer<CHUNK_MAX_BYTES>;
 
@@ -32,9 +53,145 @@ pub(super) type RopeChunk = GapBuffer<CHUNK_MAX_BYTES>;
 pub struct Rope {
     pub(super) tree: Tree<{ Self::arity() }, RopeChunk>,
     pub(super) has_trailing_newline: bool,
+    pub(super) has_bom: bool,
+    pub(super) depth_limit: Option<usize>,
 }
 
 impl Rope {
+    /// Applies all the edits in the [`Delta`](crate::Delta) to the `Rope`.
+    ///
+    /// All the edits are validated before the `Rope` is modified, so if this
+    /// panics the `Rope` is left untouched. The edits are then applied from
+    /// back to front, so this runs in O(e log n), where e is the number of
+    /// edits in the `Delta`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the edits' byte ranges is out of bounds, if either of
+    /// its ends is not a char boundary, or if the edits would make the `Rope`
+    /// exceed its [depth limit](Self::set_depth_limit()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Delta, Rope};
+    /// #
+    /// let mut delta = Delta::new();
+    /// delta.replace(6..11, "Earth").insert(12, " 🌎");
+    ///
+    /// let mut r = Rope::from("Hello World!");
+    /// r.apply_delta(&delta);
+    ///
+    /// assert_eq!(r, "Hello Earth! 🌎");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn apply_delta(&mut self, delta: &crate::Delta) {
+        let mut new_len = self.byte_len();
+
+        for edit in delta.edits() {
+            // Slicing panics on ranges that are out of bounds or not on char
+            // boundaries, without us having to repeat the checks.
+            let _ = self.byte_slice(edit.byte_range.clone());
+
+            new_len = new_len - edit.byte_range.len() + edit.text.len();
+        }
+
+        self.assert_within_depth_limit(new_len);
+
+        for edit in delta.edits().iter().rev() {
+            self.replace(edit.byte_range.clone(), &edit.text);
+        }
+    }
+
+    /// Applies a batch of edits to the `Rope`, each one replacing a byte range
+    /// with some text.
+    ///
+    /// Unlike with [`apply_delta()`](Self::apply_delta()), the byte ranges
+    /// all refer to the text as it is before any of the edits are applied,
+    /// and they can be given in any order. Insertions at the same byte offset
+    /// are applied in the order they're given.
+    ///
+    /// All the edits are validated before the `Rope` is modified, so if this
+    /// panics the `Rope` is left untouched. The edits are then applied from
+    /// back to front, so this runs in O(e log e + e log n), where e is the
+    /// number of edits, and the subtrees they don't touch stay shared with
+    /// any clones of the `Rope`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the byte ranges is out of bounds, if its start is
+    /// greater than its end, if either of its ends is not a char boundary, if
+    /// two byte ranges overlap, or if the edits would make the `Rope` exceed
+    /// its [depth limit](Self::set_depth_limit()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello World!");
+    ///
+    /// r.apply_edits(&[(11..12, " 🌎"), (0..5, "Hi"), (6..11, "Earth")]);
+    ///
+    /// assert_eq!(r, "Hi Earth 🌎");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn apply_edits<T>(&mut self, edits: &[(Range<usize>, T)])
+    where
+        T: AsRef<str>,
+    {
+        let mut order = (0..edits.len()).collect::<alloc::vec::Vec<_>>();
+
+        order.sort_by_key(|&idx| {
+            let (byte_range, _) = &edits[idx];
+            (byte_range.start, byte_range.end)
+        });
+
+        let mut prev_range: Option<&Range<usize>> = None;
+
+        let mut new_len = self.byte_len();
+
+        for &idx in &order {
+            let (byte_range, text) = &edits[idx];
+
+            // Slicing panics on ranges that are out of bounds, reversed or
+            // not on char boundaries, without us having to repeat the
+            // checks.
+            let _ = self.byte_slice(byte_range.clone());
+
+            if let Some(prev) = prev_range {
+                if prev.end > byte_range.start {
+                    panic::byte_ranges_overlap(
+                        prev.clone(),
+                        byte_range.clone(),
+                    );
+                }
+            }
+
+            prev_range = Some(byte_range);
+
+            new_len = new_len - byte_range.len() + text.as_ref().len();
+        }
+
+        self.assert_within_depth_limit(new_len);
+
+        for &idx in order.iter().rev() {
+            let (byte_range, text) = &edits[idx];
+            self.replace(byte_range.clone(), text);
+        }
+    }
+
+    /// Returns the number of bytes in the longest prefix and suffix of the
+    /// `Rope` stored in nodes it shares with `other`.
+    #[doc(hidden)]
+    pub fn shared_byte_len(&self, other: &Self) -> usize {
+        let prefix = usize::from(self.tree.shared_prefix(&other.tree));
+        let suffix = usize::from(self.tree.shared_suffix(&other.tree));
+        (prefix + suffix).min(self.byte_len()).min(other.byte_len())
+    }
+
     #[doc(hidden)]
     pub fn assert_invariants(&self) {
         self.tree.assert_invariants();
@@ -112,6 +269,37 @@ impl Rope {
         self.tree.summary().bytes()
     }
 
+    /// Returns the byte offset corresponding to the given char offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the char offset is out of bounds (i.e. greater than
+    /// [`char_len()`](Self::char_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // The "è" character is encoded using two bytes in UTF-8.
+    /// let r = Rope::from("aèb");
+    /// assert_eq!(r.byte_of_char(2), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_char(&self, char_offset: usize) -> usize {
+        if char_offset > self.char_len() {
+            panic::char_offset_out_of_bounds(char_offset, self.char_len())
+        }
+
+        let ByteMetric(byte_offset) =
+            self.tree.convert_measure(super::metrics::CharMetric(char_offset));
+
+        byte_offset
+    }
+
     /// Returns the byte offset of the start of the given line.
     ///
     /// # Panics
@@ -148,6 +336,46 @@ impl Rope {
         byte_offset
     }
 
+    /// Returns the byte offset of the given [`Position`], where the column is
+    /// measured in the units of the given [`PositionEncoding`].
+    ///
+    /// This is meant to convert positions received from a language server
+    /// client. The column is located by scanning its line, so this runs in
+    /// O(log n + line length).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())), if the column is past the end of
+    /// the line (not counting its line terminator) or if it doesn't lie on a
+    /// code point boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Position, PositionEncoding, Rope};
+    /// #
+    /// let r = Rope::from("foo\na𐐀bc");
+    ///
+    /// // "𐐀" is 4 bytes in UTF-8 and 2 code units in UTF-16.
+    /// let utf8 = Position::new(1, 5);
+    /// let utf16 = Position::new(1, 3);
+    /// let utf32 = Position::new(1, 2);
+    ///
+    /// assert_eq!(r.byte_of_position(utf8, PositionEncoding::Utf8), 9);
+    /// assert_eq!(r.byte_of_position(utf16, PositionEncoding::Utf16), 9);
+    /// assert_eq!(r.byte_of_position(utf32, PositionEncoding::Utf32), 9);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_position(
+        &self,
+        position: Position,
+        encoding: PositionEncoding,
+    ) -> usize {
+        byte_of_position(self.byte_slice(..), position, encoding)
+    }
+
     /// Returns the byte offset corresponding to the given UTF-16 code unit
     /// offset.
     ///
@@ -183,6 +411,94 @@ impl Rope {
         byte_offset
     }
 
+    /// Returns the byte offset of the given visual column in the given line,
+    /// with tabs expanding to the next multiple of `tab_width` columns.
+    ///
+    /// The width of every other char is measured with the
+    /// [`unicode-width`](https://docs.rs/unicode-width) crate. If the column
+    /// falls in the middle of a char, like a tab or a wide char, the byte
+    /// offset of the start of that char is returned, and if it's past the end
+    /// of the line the byte offset of the end of the line (not counting its
+    /// line terminator) is returned.
+    ///
+    /// The column is located by scanning its line, so this runs in O(log n +
+    /// line length).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\na\t日b\n");
+    ///
+    /// assert_eq!(r.byte_of_visual_col(1, 4, 4), 6);
+    /// assert_eq!(r.byte_of_visual_col(1, 2, 4), 5);
+    /// assert_eq!(r.byte_of_visual_col(1, 6, 4), 9);
+    /// assert_eq!(r.byte_of_visual_col(1, 42, 4), 10);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "display-width")))]
+    #[cfg(feature = "display-width")]
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_visual_col(
+        &self,
+        line_offset: usize,
+        visual_col: usize,
+        tab_width: usize,
+    ) -> usize {
+        super::position::byte_of_visual_col(
+            self.byte_slice(..),
+            line_offset,
+            visual_col,
+            tab_width,
+        )
+    }
+
+    /// Returns the byte offset corresponding to the given width offset, i.e.
+    /// the shortest prefix of the `Rope` that takes up at least
+    /// `width_offset` terminal columns.
+    ///
+    /// If the width offset falls in the middle of a wide char, the byte
+    /// offset after that char is returned. See
+    /// [`width()`](Self::width()) for how the width is computed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width offset is out of bounds (i.e. greater than
+    /// [`width()`](Self::width())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // The "日" character is two columns wide and encoded using three bytes
+    /// // in UTF-8.
+    /// let r = Rope::from("a日b");
+    /// assert_eq!(r.byte_of_width(3), 4);
+    /// assert_eq!(r.byte_of_width(2), 4);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "width-metric")))]
+    #[cfg(feature = "width-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_width(&self, width_offset: usize) -> usize {
+        if width_offset > self.width() {
+            panic::width_offset_out_of_bounds(width_offset, self.width())
+        }
+
+        let ByteMetric(byte_offset) = self
+            .tree
+            .convert_measure(super::metrics::WidthMetric(width_offset));
+
+        byte_offset
+    }
+
     /// Returns an immutable slice of the `Rope` in the specified byte range,
     /// where the start and end of the range are interpreted as offsets.
     ///
@@ -244,121 +560,118 @@ impl Rope {
         Bytes::from(self)
     }
 
-    /// Returns an iterator over the [`char`]s of this `Rope`.
+    /// Returns a pair of iterators over the bytes before and after the
+    /// given byte offset.
+    ///
+    /// The first iterator yields the bytes before the offset in reverse
+    /// order, i.e. moving backward from the offset, while the second one
+    /// yields the bytes after it. Both are positioned in O(log n).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("🐻‍❄️");
+    /// let r = Rope::from("foo bar");
     ///
-    /// let mut chars = r.chars();
+    /// let (mut before, mut after) = r.bytes_at(3);
     ///
-    /// assert_eq!(Some('🐻'), chars.next());
-    /// assert_eq!(Some('\u{200d}'), chars.next());
-    /// assert_eq!(Some('❄'), chars.next());
-    /// assert_eq!(Some('\u{fe0f}'), chars.next());
-    /// assert_eq!(None, chars.next());
+    /// assert_eq!(Some(b'o'), before.next());
+    /// assert_eq!(Some(b' '), after.next());
+    /// assert_eq!(Some(b'b'), after.next());
     /// ```
+    #[track_caller]
     #[inline]
-    pub fn chars(&self) -> Chars<'_> {
-        Chars::from(self)
-    }
-
-    /// Returns an iterator over the chunks of this [`Rope`].
-    #[inline]
-    pub fn chunks(&self) -> Chunks<'_> {
-        Chunks::from(self)
+    pub fn bytes_at(
+        &self,
+        byte_offset: usize,
+    ) -> (core::iter::Rev<Bytes<'_>>, Bytes<'_>) {
+        self.byte_slice(..).bytes_at(byte_offset)
     }
 
-    /// Deletes the contents of the `Rope` within the specified byte range,
-    /// where the start and end of the range are interpreted as offsets.
+    /// Returns the byte offset of the first grapheme cluster boundary at or
+    /// after the given byte offset.
+    ///
+    /// Unlike [`next_grapheme_boundary()`](Self::next_grapheme_boundary()),
+    /// this returns the byte offset itself if it's already a boundary, and
+    /// the offset doesn't have to lie on a code point boundary.
     ///
     /// # Panics
     ///
-    /// Panics if the start or the end of the byte range don't lie on a code
-    /// point boundary, if the start is greater than the end or if the end is
-    /// out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let mut r = Rope::from("Hello Earth 🌎!");
+    /// let r = Rope::from("a🐻‍❄️\r\nb");
     ///
-    /// r.delete(5..16);
-    /// assert_eq!(r, "Hello!");
+    /// assert_eq!(r.ceil_grapheme_boundary(1), 1);
+    /// assert_eq!(r.ceil_grapheme_boundary(3), 14); // inside '🐻‍❄️'
+    /// assert_eq!(r.ceil_grapheme_boundary(15), 16); // between '\r' and '\n'
     /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
     #[track_caller]
     #[inline]
-    pub fn delete<R>(&mut self, byte_range: R)
-    where
-        R: RangeBounds<usize>,
-    {
-        self.replace(byte_range, "");
-    }
+    pub fn ceil_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
 
-    pub(super) const fn arity() -> usize {
-        ARITY
+        ceil_grapheme_boundary(self.byte_slice(..), byte_offset)
     }
 
-    /// Returns an iterator over the extended grapheme clusters of this
-    /// `Rope`.
+    /// Returns an iterator over the [`char`]s of this `Rope` and their byte
+    /// offsets, like [`str::char_indices()`].
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("arg!\r\n🐻‍❄️");
+    /// let r = Rope::from("aè🐸");
     ///
-    /// let mut graphemes = r.graphemes();
+    /// let mut char_indices = r.char_indices();
     ///
-    /// assert_eq!(Some("a"), graphemes.next().as_deref());
-    /// assert_eq!(Some("r"), graphemes.next().as_deref());
-    /// assert_eq!(Some("g"), graphemes.next().as_deref());
-    /// assert_eq!(Some("!"), graphemes.next().as_deref());
-    /// assert_eq!(Some("\r\n"), graphemes.next().as_deref());
-    /// assert_eq!(Some("🐻‍❄️"), graphemes.next().as_deref());
-    /// assert_eq!(None, graphemes.next());
+    /// assert_eq!(Some((0, 'a')), char_indices.next());
+    /// assert_eq!(Some((1, 'è')), char_indices.next());
+    /// assert_eq!(Some((3, '🐸')), char_indices.next());
+    /// assert_eq!(None, char_indices.next());
     /// ```
-    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
-    #[cfg(feature = "graphemes")]
     #[inline]
-    pub fn graphemes(&self) -> crate::iter::Graphemes<'_> {
-        crate::iter::Graphemes::from(self)
+    pub fn char_indices(&self) -> crate::iter::CharIndices<'_> {
+        crate::iter::CharIndices::from(self)
     }
 
-    /// Inserts `text` in the `Rope` at the given byte offset.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the byte offset doesn't lie on a code point boundary or if
-    /// it's out of bounds (i.e. greater than
-    /// [`byte_len()`](Self::byte_len())).
+    /// Returns the number of `char`s (i.e. Unicode code points) in the
+    /// `Rope`.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let mut r = Rope::from("Hello Earth!");
-    ///
-    /// r.insert(11, " 🌎");
-    /// assert_eq!(r, "Hello Earth 🌎!");
+    /// // The "🐸" emoji is encoded using four bytes in UTF-8.
+    /// let r = Rope::from("abc🐸");
+    /// assert_eq!(r.char_len(), 4);
     /// ```
-    #[track_caller]
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
     #[inline]
-    pub fn insert<T>(&mut self, byte_offset: usize, text: T)
-    where
-        T: AsRef<str>,
-    {
-        self.replace(byte_offset..byte_offset, text)
+    pub fn char_len(&self) -> usize {
+        self.tree.summary().chars()
     }
 
-    /// Returns `true` if the given byte offset lies on a [`char`] boundary.
+    /// Returns the char offset corresponding to the given byte offset.
     ///
     /// # Panics
     ///
@@ -370,451 +683,3448 @@ impl Rope {
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("Löwe 老虎 Léopard");
-    ///
-    /// assert!(r.is_char_boundary(0));
-    /// assert!(r.is_char_boundary(r.byte_len()));
-    /// assert!(r.is_char_boundary(6)); // between ' ' and '老'
-    /// assert!(!r.is_char_boundary(2)); // between the 1st and 2nd byte of 'ö'
+    /// // The "è" character is encoded using two bytes in UTF-8.
+    /// let r = Rope::from("aèb");
+    /// assert_eq!(r.char_of_byte(3), 2);
     /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
     #[track_caller]
     #[inline]
-    pub fn is_char_boundary(&self, byte_offset: usize) -> bool {
+    pub fn char_of_byte(&self, byte_offset: usize) -> usize {
         if byte_offset > self.byte_len() {
             panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
         }
 
-        let (chunk, ByteMetric(chunk_byte_offset)) =
-            self.tree.leaf_at_measure(ByteMetric(byte_offset));
+        let super::metrics::CharMetric(char_offset) =
+            self.tree.convert_measure(ByteMetric(byte_offset));
 
-        chunk.is_char_boundary(byte_offset - chunk_byte_offset)
+        char_offset
     }
 
-    /// Returns `true` if the `Rope`'s byte length is zero.
+    /// Returns an immutable slice of the `Rope` in the specified char range,
+    /// where the start and end of the range are interpreted as offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`char_len()`](Self::char_len())).
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let mut r = Rope::new();
-    /// assert!(r.is_empty());
+    /// let r = Rope::from("aè🐸d");
     ///
-    /// r.insert(0, "hey");
-    /// assert!(!r.is_empty());
+    /// assert_eq!(r.char_slice(..2), "aè");
+    /// assert_eq!(r.char_slice(2..), "🐸d");
+    /// assert_eq!(r.char_slice(1..3), "è🐸");
     /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.byte_len() == 0
+    pub fn char_slice<R>(&self, char_range: R) -> RopeSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        use super::metrics::CharMetric;
+
+        let (start, end) =
+            range_bounds_to_start_end(char_range, 0, self.char_len());
+
+        if start > end {
+            panic::char_start_after_end(start, end);
+        }
+
+        if end > self.char_len() {
+            panic::char_offset_out_of_bounds(end, self.char_len());
+        }
+
+        self.tree.slice(CharMetric(start)..CharMetric(end)).into()
     }
 
-    /// Returns `true` if the given byte offset lies on a grapheme cluster
-    /// boundary.
+    /// Returns an iterator over the [`char`]s of this `Rope`.
     ///
-    /// # Panics
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🐻‍❄️");
+    ///
+    /// let mut chars = r.chars();
+    ///
+    /// assert_eq!(Some('🐻'), chars.next());
+    /// assert_eq!(Some('\u{200d}'), chars.next());
+    /// assert_eq!(Some('❄'), chars.next());
+    /// assert_eq!(Some('\u{fe0f}'), chars.next());
+    /// assert_eq!(None, chars.next());
+    /// ```
+    #[inline]
+    pub fn chars(&self) -> Chars<'_> {
+        Chars::from(self)
+    }
+
+    /// Returns a pair of iterators over the [`char`]s before and after the
+    /// given byte offset.
+    ///
+    /// The first iterator yields the chars before the offset in reverse
+    /// order, i.e. moving backward from the offset, while the second one
+    /// yields the chars after it. Both are positioned in O(log n).
+    ///
+    /// # Panics
     ///
     /// Panics if the byte offset is out of bounds (i.e. greater than
-    /// [`byte_len()`](Self::byte_len())).
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
+    /// let r = Rope::from("foo  \tbar");
     ///
-    /// assert!(r.is_grapheme_boundary(0));
-    /// assert!(r.is_grapheme_boundary(r.byte_len()));
-    /// assert!(!r.is_grapheme_boundary(7)); // between '\r' and '\n'
-    /// assert!(r.is_grapheme_boundary(8)); // between '\n' and '🐻‍❄️'
-    /// assert!(!r.is_grapheme_boundary(12)); // between the 1st and 2nd code point of '🐻‍❄️'
+    /// // Find the last non-whitespace char before "bar".
+    /// let (before, _) = r.chars_at(6);
+    /// assert_eq!(before.skip_while(|ch| ch.is_whitespace()).next(), Some('o'));
+    ///
+    /// let (_, mut after) = r.chars_at(6);
+    /// assert_eq!(after.next(), Some('b'));
     /// ```
-    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
-    #[cfg(feature = "graphemes")]
     #[track_caller]
     #[inline]
-    pub fn is_grapheme_boundary(&self, byte_offset: usize) -> bool {
-        if byte_offset > self.byte_len() {
-            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
-        }
-
-        is_grapheme_boundary(self.chunks(), self.byte_len(), byte_offset)
+    pub fn chars_at(
+        &self,
+        byte_offset: usize,
+    ) -> (core::iter::Rev<Chars<'_>>, Chars<'_>) {
+        self.byte_slice(..).chars_at(byte_offset)
     }
 
-    /// Returns the line at `line_index`, without its line terminator.
+    /// Returns the chunk containing the start of the given line, together
+    /// with the byte offset and the line offset of the start of the chunk.
     ///
-    /// If you want to include the line break consider taking a
-    /// [`line_slice()`](Self::line_slice()) in the
-    /// `line_index..line_index + 1` range.
+    /// This can be used as the entry point of incremental parsers that need
+    /// to resume scanning from a line boundary. Like with
+    /// [`Cursor::chunk()`](crate::Cursor::chunk()), if the line starts at
+    /// the boundary between two chunks this can return either of them.
     ///
     /// # Panics
     ///
-    /// Panics if the line index is out of bounds (i.e. greater than or equal
-    /// to [`line_len()`](Self::byte_len())).
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("foo\nbar\r\nbaz");
+    /// let r = Rope::from("foo\nbar\nbaz\n");
     ///
-    /// assert_eq!(r.line(0), "foo");
-    /// assert_eq!(r.line(1), "bar");
-    /// assert_eq!(r.line(2), "baz");
+    /// let (chunk, byte_offset, line_offset) = r.chunk_at_line(1);
+    ///
+    /// let line_start = r.byte_of_line(1);
+    /// assert!(byte_offset <= line_start);
+    /// assert!(line_start <= byte_offset + chunk.len());
+    /// assert_eq!(line_offset, r.line_of_byte(byte_offset));
     /// ```
     #[track_caller]
     #[inline]
-    pub fn line(&self, line_index: usize) -> RopeSlice<'_> {
-        if line_index >= self.line_len() {
-            panic::line_index_out_of_bounds(line_index, self.line_len());
-        }
-
-        let tree_slice = self
-            .tree
-            .slice(RawLineMetric(line_index)..RawLineMetric(line_index + 1));
-
-        let mut line = RopeSlice { tree_slice, has_trailing_newline: false };
-
-        if line.tree_slice.summary().line_breaks() == 1 {
-            line.truncate_trailing_line_break();
-        }
+    pub fn chunk_at_line(&self, line_offset: usize) -> (&str, usize, usize) {
+        self.byte_slice(..).chunk_at_line(line_offset)
+    }
 
-        line
+    /// Returns an iterator over the chunks of this [`Rope`].
+    #[inline]
+    pub fn chunks(&self) -> Chunks<'_> {
+        Chunks::from(self)
     }
 
-    /// Returns the number of lines in the `Rope`.
-    ///
-    /// The final line break is optional and doesn't count as a separate empty
-    /// line.
+    /// Returns an iterator over the chunks of this [`Rope`], together with
+    /// the byte offset at which each chunk starts.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let mut r = Rope::new();
+    /// let r = Rope::from("Hello World!");
     ///
-    /// assert_eq!(r.line_len(), 0);
+    /// for (offset, chunk) in r.chunks_with_offsets() {
+    ///     assert_eq!(r.byte_slice(offset..offset + chunk.len()), chunk);
+    /// }
+    /// ```
+    #[inline]
+    pub fn chunks_with_offsets(&self) -> crate::iter::ChunksWithOffsets<'_> {
+        crate::iter::ChunksWithOffsets::from(self)
+    }
+
+    /// Returns a [`Cursor`](crate::Cursor) positioned at the given byte
+    /// offset of the `Rope`.
     ///
-    /// r.insert(0, "a");
-    /// assert_eq!(r.line_len(), 1);
+    /// # Panics
     ///
-    /// r.insert(1, "\n");
-    /// assert_eq!(r.line_len(), 1);
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
     ///
-    /// r.insert(2, "b");
-    /// assert_eq!(r.line_len(), 2);
+    /// # Examples
     ///
-    /// r.insert(3, "\r\n");
-    /// assert_eq!(r.line_len(), 2);
     /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("ƒoo");
+    ///
+    /// let mut cursor = r.cursor(2);
+    ///
+    /// assert_eq!(cursor.next_char(), Some('o'));
+    /// assert_eq!(cursor.prev_char(), Some('o'));
+    /// assert_eq!(cursor.prev_char(), Some('ƒ'));
+    /// assert_eq!(cursor.prev_char(), None);
+    /// ```
+    #[track_caller]
     #[inline]
-    pub fn line_len(&self) -> usize {
-        self.tree.summary().line_breaks() + 1
-            - (self.has_trailing_newline as usize)
-            - (self.is_empty() as usize)
+    pub fn cursor(&self, byte_offset: usize) -> Cursor<'_> {
+        Cursor::new(self.byte_slice(..), byte_offset)
     }
 
-    /// Returns the line offset of the given byte.
+    /// Deletes the contents of the `Rope` within the specified byte range,
+    /// where the start and end of the range are interpreted as offsets.
+    ///
+    /// Returns a [`Change`] describing the edit.
     ///
     /// # Panics
     ///
-    /// Panics if the byte offset is out of bounds (i.e. greater than
-    /// [`byte_len()`](Self::byte_len())).
+    /// Panics if the start or the end of the byte range don't lie on a code
+    /// point boundary, if the start is greater than the end or if the end is
+    /// out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("foo\nbar\r\nbaz");
+    /// let mut r = Rope::from("Hello Earth 🌎!");
     ///
-    /// assert_eq!(r.line_of_byte(0), 0);
-    /// assert_eq!(r.line_of_byte(3), 0);
-    /// assert_eq!(r.line_of_byte(4), 1);
-    /// assert_eq!(r.line_of_byte(8), 1); // between the '\r' and the '\n'
-    /// assert_eq!(r.line_of_byte(r.byte_len()), 2);
+    /// r.delete(5..16);
+    /// assert_eq!(r, "Hello!");
     /// ```
     #[track_caller]
     #[inline]
-    pub fn line_of_byte(&self, byte_offset: usize) -> usize {
-        if byte_offset > self.byte_len() {
-            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
-        }
-
-        let RawLineMetric(line_offset) =
-            self.tree.convert_measure(ByteMetric(byte_offset));
-
-        line_offset
+    pub fn delete<R>(&mut self, byte_range: R) -> Change
+    where
+        R: RangeBounds<usize>,
+    {
+        self.replace(byte_range, "")
     }
 
-    /// Returns an immutable slice of the `Rope` in the specified line range,
-    /// where the start and end of the range are interpreted as offsets.
+    /// Deletes the text in the specified char range.
+    ///
+    /// This is equivalent to calling [`delete()`](Self::delete()) with the
+    /// corresponding byte range.
     ///
     /// # Panics
     ///
     /// Panics if the start is greater than the end or if the end is out of
-    /// bounds (i.e. greater than [`line_len()`](Self::line_len())).
+    /// bounds (i.e. greater than [`char_len()`](Self::char_len())).
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("foo\nbar\r\nbaz\nfoobar\n");
+    /// let mut r = Rope::from("ƒoo bär");
     ///
-    /// assert_eq!(r.line_slice(..1), "foo\n");
-    /// assert_eq!(r.line_slice(1..3), "bar\r\nbaz\n");
-    /// assert_eq!(r.line_slice(3..4), "foobar\n");
-    /// assert_eq!(r.line_slice(4..), "");
+    /// r.delete_chars(3..);
+    /// assert_eq!(r, "ƒoo");
     /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
     #[track_caller]
     #[inline]
-    pub fn line_slice<R>(&self, line_range: R) -> RopeSlice<'_>
+    pub fn delete_chars<R>(&mut self, char_range: R) -> Change
     where
         R: RangeBounds<usize>,
     {
         let (start, end) =
-            range_bounds_to_start_end(line_range, 0, self.line_len());
+            range_bounds_to_start_end(char_range, 0, self.char_len());
 
         if start > end {
-            panic::line_start_after_end(start, end);
-        }
-
-        if end > self.line_len() {
-            panic::line_offset_out_of_bounds(end, self.line_len());
+            panic::char_start_after_end(start, end);
         }
 
-        self.tree.slice(RawLineMetric(start)..RawLineMetric(end)).into()
+        let byte_start = self.byte_of_char(start);
+        let byte_end = self.byte_of_char(end);
+        self.delete(byte_start..byte_end)
     }
 
-    /// Returns an iterator over the lines of this `Rope`, not including the
-    /// line terminators.
+    /// Returns the depth of the B-tree backing the `Rope`, i.e. the number of
+    /// internal nodes that have to be traversed to go from its root to any of
+    /// its leaves.
     ///
-    /// The final line break is optional and doesn't cause the iterator to
-    /// return a final empty line.
+    /// The depth is always less than or equal to
+    /// [`max_depth(self.byte_len())`](Self::max_depth()).
     ///
-    /// If you want to include the line breaks consider using the
-    /// [`raw_lines()`](Self::raw_lines()) method instead.
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!");
+    /// assert!(r.depth() <= Rope::max_depth(r.byte_len()));
+    ///
+    /// let r = Rope::from("🌎".repeat(1 << 16));
+    /// assert!(r.depth() > 0);
+    /// assert!(r.depth() <= Rope::max_depth(r.byte_len()));
+    /// ```
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.tree.depth()
+    }
+
+    /// Returns the depth limit set by
+    /// [`set_depth_limit()`](Self::set_depth_limit()), if any.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    /// let mut r = Rope::new();
+    /// assert_eq!(r.depth_limit(), None);
     ///
-    /// let mut lines = r.lines();
+    /// r.set_depth_limit(Some(3));
+    /// assert_eq!(r.depth_limit(), Some(3));
+    /// ```
+    #[inline]
+    pub fn depth_limit(&self) -> Option<usize> {
+        self.depth_limit
+    }
+
+    /// Counts the line endings of each style in this `Rope`, which can be
+    /// used to decide which line ending to insert when the user presses
+    /// Enter.
     ///
-    /// assert_eq!("foo", lines.next().unwrap());
-    /// assert_eq!("bar", lines.next().unwrap());
-    /// assert_eq!("baz", lines.next().unwrap());
-    /// assert_eq!(None, lines.next());
+    /// The total number of line feeds is read from the summary of the
+    /// B-tree, so this returns immediately if there aren't any, but telling
+    /// `"\n"`s and `"\r\n"`s apart requires scanning the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{LineEnding, LineEndings, Rope};
+    /// #
+    /// let r = Rope::from("foo\r\nbar\nbaz\r\n");
+    ///
+    /// let endings = r.detect_line_ending();
+    ///
+    /// assert_eq!(endings, LineEndings { lf: 1, crlf: 2 });
+    /// assert_eq!(endings.style(), Some(LineEnding::Mixed));
     /// ```
     #[inline]
-    pub fn lines(&self) -> Lines<'_> {
-        Lines::from(self)
+    pub fn detect_line_ending(&self) -> crate::LineEndings {
+        super::line_ending::detect_line_ending(self.byte_slice(..))
     }
 
-    /// Returns an iterator over the lines of this `Rope`, including the
-    /// line terminators.
+    /// Returns a [`Delta`](crate::Delta) which turns this `Rope` into `other`
+    /// when applied to it with [`apply_delta()`](Self::apply_delta()).
     ///
-    /// The final line break is optional and doesn't cause the iterator to
-    /// return a final empty line.
+    /// The common prefix and suffix of the two `Rope`s are skipped first,
+    /// which is O(log n) for the parts made up of nodes the two `Rope`s share,
+    /// e.g. if one was obtained by cloning and editing the other. The rest is
+    /// diffed char by char using Myers' algorithm, which runs in O((N + M) D)
+    /// time and O(N + M) space, where N and M are the lengths of the two
+    /// middle sections and D is the number of chars inserted or deleted.
     ///
-    /// If you don't want to include the line breaks consider using the
-    /// [`lines()`](Self::lines()) method instead.
+    /// The resulting `Delta` is minimal unless the two `Rope`s are very
+    /// different from each other, in which case the search gives up early
+    /// and replaces larger ranges than strictly necessary.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let mut r = Rope::from("foo\nbar\r\nbaz");
+    /// let old = Rope::from("Hello World!");
     ///
-    /// let mut raw_lines = r.raw_lines();
+    /// let mut new = old.clone();
+    /// new.replace(6..11, "🌎");
     ///
-    /// assert_eq!("foo\n", raw_lines.next().unwrap());
-    /// assert_eq!("bar\r\n", raw_lines.next().unwrap());
-    /// assert_eq!("baz", raw_lines.next().unwrap());
-    /// assert_eq!(None, raw_lines.next());
+    /// let delta = old.diff(&new);
+    /// assert_eq!(delta.edits().len(), 1);
     ///
-    /// r.insert(r.byte_len(), "\n");
+    /// let mut r = old.clone();
+    /// r.apply_delta(&delta);
+    /// assert_eq!(r, new);
+    /// ```
+    #[inline]
+    pub fn diff(&self, other: &Self) -> crate::Delta {
+        super::diff::diff(self, other)
+    }
+
+    /// Returns a [`WeakRope`] pointing to the current contents of the `Rope`.
     ///
-    /// let mut raw_lines = r.raw_lines();
+    /// The returned handle doesn't keep the text alive: it can be upgraded
+    /// back into a `Rope` only as long as this `Rope`, or one of its clones,
+    /// hasn't been dropped or edited.
     ///
-    /// assert_eq!("foo\n", raw_lines.next().unwrap());
-    /// assert_eq!("bar\r\n", raw_lines.next().unwrap());
-    /// assert_eq!("baz\n", raw_lines.next().unwrap());
-    /// assert_eq!(None, raw_lines.next());
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// let weak = r.downgrade();
+    /// assert_eq!(weak.upgrade().unwrap(), "Hello Earth 🌎!");
+    ///
+    /// r.delete(5..16);
+    /// assert!(weak.upgrade().is_none());
     /// ```
     #[inline]
-    pub fn raw_lines(&self) -> RawLines<'_> {
-        RawLines::from(self)
+    pub fn downgrade(&self) -> WeakRope {
+        WeakRope {
+            tree: self.tree.downgrade(),
+            has_trailing_newline: self.has_trailing_newline,
+            has_bom: self.has_bom,
+            depth_limit: self.depth_limit,
+        }
     }
 
-    /// Returns a new empty [`Rope`].
+    pub(super) const fn arity() -> usize {
+        ARITY
+    }
+
+    /// Returns whether the `Rope` ends with the given
+    /// [`Pattern`](crate::pattern::Pattern).
+    ///
+    /// The pattern is compared against the chunks at the end of the `Rope`
+    /// from back to front, so this only reads as many bytes as the pattern
+    /// is long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎");
+    ///
+    /// assert!(r.ends_with('🌎'));
+    /// assert!(r.ends_with("Earth 🌎"));
+    /// assert!(r.ends_with(r.byte_slice(6..)));
+    /// assert!(!r.ends_with("Earth"));
+    /// ```
     #[inline]
-    pub fn new() -> Self {
-        Self::default()
+    pub fn ends_with<P: crate::pattern::Pattern>(
+        &self,
+        mut pattern: P,
+    ) -> bool {
+        pattern.suffix_len(self.byte_slice(..)).is_some()
     }
 
-    /// Replaces the contents of the `Rope` within the specified byte range
-    /// with the given string, where the start and end of the range are
-    /// interpreted as byte offsets.
+    /// Returns whether the text of the `Rope` is equal to `other`, ignoring
+    /// differences in case.
+    ///
+    /// The two texts are compared char by char after mapping every char to
+    /// its simple case folding, which works across scripts and doesn't
+    /// allocate. Chars whose case mapping expands to several chars (e.g.
+    /// `'ß'` and `"SS"`) aren't considered equal, and the texts aren't
+    /// normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Wörld!");
+    ///
+    /// assert!(r.eq_ignore_case("hELLO WÖRLD!"));
+    /// assert!(!r.eq_ignore_case("Hello World!"));
+    /// ```
+    #[inline]
+    pub fn eq_ignore_case(&self, other: &str) -> bool {
+        super::case::eq_ignore_case(self.byte_slice(..), other)
+    }
+
+    /// Returns the byte offset of the first match of `pattern` in the `Rope`,
+    /// or `None` if it doesn't occur.
+    ///
+    /// The pattern can be any [`Pattern`](crate::pattern::Pattern), e.g. a
+    /// `&str`, a `char` or a closure. Occurrences spanning multiple chunks
+    /// are found without allocating a `String` for the whole text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar foo");
+    ///
+    /// assert_eq!(r.find("foo"), Some(0));
+    /// assert_eq!(r.find("bar"), Some(4));
+    /// assert_eq!(r.find("baz"), None);
+    /// assert_eq!(r.find('o'), Some(1));
+    /// assert_eq!(r.find(char::is_whitespace), Some(3));
+    /// ```
+    #[inline]
+    pub fn find<P: crate::pattern::Pattern>(
+        &self,
+        pattern: P,
+    ) -> Option<usize> {
+        self.byte_slice(..).find(pattern)
+    }
+
+    /// Returns the byte range of the first case-insensitive occurrence of
+    /// `pattern` in the `Rope`, or `None` if it doesn't occur.
+    ///
+    /// Unlike [`find()`](Self::find()) this returns the whole range of the
+    /// occurrence, since its length can differ from the length of `pattern`
+    /// (e.g. the 3-byte Kelvin sign `'\u{212A}'` matches `'k'`). See
+    /// [`eq_ignore_case()`](Self::eq_ignore_case()) for how case is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Ὀδυσσεύς, ΟΔΥΣΣΕΎΣ");
+    ///
+    /// assert_eq!(r.find_ignore_case("ΟΔΥΣ"), Some(19..27));
+    /// assert_eq!(r.find_ignore_case("ὀδυς"), Some(0..9));
+    /// ```
+    #[inline]
+    pub fn find_ignore_case(
+        &self,
+        pattern: &str,
+    ) -> Option<core::ops::Range<usize>> {
+        self.find_iter_ignore_case(pattern).next()
+    }
+
+    /// Returns an iterator over the byte ranges of the non-overlapping
+    /// matches of `pattern` in the `Rope`, from front to back. The pattern
+    /// can be any [`Pattern`](crate::pattern::Pattern).
+    ///
+    /// Like with [`str::match_indices()`], an empty string matches at every
+    /// char boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar foo");
+    ///
+    /// let mut matches = r.find_iter("foo");
+    ///
+    /// assert_eq!(matches.next(), Some(0..3));
+    /// assert_eq!(matches.next(), Some(8..11));
+    /// assert_eq!(matches.next(), None);
+    ///
+    /// let mut matches = r.find_iter(char::is_whitespace);
+    ///
+    /// assert_eq!(matches.next(), Some(3..4));
+    /// assert_eq!(matches.next(), Some(7..8));
+    /// assert_eq!(matches.next(), None);
+    /// ```
+    #[inline]
+    pub fn find_iter<P: crate::pattern::Pattern>(
+        &self,
+        pattern: P,
+    ) -> FindIter<'_, P> {
+        FindIter::new(self.byte_slice(..), pattern)
+    }
+
+    /// Returns an iterator over the byte ranges of the non-overlapping
+    /// case-insensitive occurrences of `pattern` in the `Rope`, from front to
+    /// back.
+    ///
+    /// See [`find_ignore_case()`](Self::find_ignore_case()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Foo bar FOO");
+    ///
+    /// let mut matches = r.find_iter_ignore_case("foo");
+    ///
+    /// assert_eq!(matches.next(), Some(0..3));
+    /// assert_eq!(matches.next(), Some(8..11));
+    /// assert_eq!(matches.next(), None);
+    /// ```
+    #[inline]
+    pub fn find_iter_ignore_case(
+        &self,
+        pattern: &str,
+    ) -> crate::iter::FindIterIgnoreCase<'_> {
+        crate::iter::FindIterIgnoreCase::new(self.byte_slice(..), pattern)
+    }
+
+    /// Returns the byte offset of the last grapheme cluster boundary at or
+    /// before the given byte offset.
+    ///
+    /// Unlike [`prev_grapheme_boundary()`](Self::prev_grapheme_boundary()),
+    /// this returns the byte offset itself if it's already a boundary, and
+    /// the offset doesn't have to lie on a code point boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a🐻‍❄️\r\nb");
+    ///
+    /// assert_eq!(r.floor_grapheme_boundary(1), 1);
+    /// assert_eq!(r.floor_grapheme_boundary(3), 1); // inside '🐻‍❄️'
+    /// assert_eq!(r.floor_grapheme_boundary(15), 14); // between '\r' and '\n'
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn floor_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        floor_grapheme_boundary(self.byte_slice(..), byte_offset)
+    }
+
+    /// Calls `fun` on every chunk of the `Rope` within the specified byte
+    /// range, from front to back, stopping as soon as it returns
+    /// [`ControlFlow::Break`].
+    ///
+    /// This yields the same chunks as calling [`chunks()`](Self::chunks()) on
+    /// the corresponding [`byte_slice()`](Self::byte_slice()), but it doesn't
+    /// allocate and it tends to optimize better than the iterator in tight
+    /// loops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or the end of the byte range don't lie on a code
+    /// point boundary, if the start is greater than the end or if the end is
+    /// out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use core::ops::ControlFlow;
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// let find_earth = |chunk: &str| {
+    ///     if chunk.contains('🌎') {
+    ///         ControlFlow::Break(())
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// };
+    ///
+    /// assert!(r.for_each_chunk(6.., find_earth).is_break());
+    /// assert!(r.for_each_chunk(..6, find_earth).is_continue());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn for_each_chunk<'a, R, B, F>(
+        &'a self,
+        byte_range: R,
+        fun: F,
+    ) -> ControlFlow<B>
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&'a str) -> ControlFlow<B>,
+    {
+        for_each_chunk(self.byte_slice(byte_range), fun)
+    }
+
+    /// Returns a [`FrozenRope`] with the same contents as this `Rope`.
+    ///
+    /// A `FrozenRope` is a packed, read-only representation that uses a lot
+    /// less memory than a `Rope`, and which can be turned back into a `Rope`
+    /// by calling [`FrozenRope::thaw()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\n");
+    ///
+    /// let frozen = r.freeze();
+    ///
+    /// assert_eq!(frozen.as_str(), "foo\nbar\n");
+    /// assert_eq!(frozen.line(1), "bar");
+    /// assert_eq!(frozen.thaw(), r);
+    /// ```
+    #[inline]
+    pub fn freeze(&self) -> FrozenRope {
+        FrozenRope::from(self)
+    }
+
+    /// Creates a new `Rope` by decoding `bytes` from the given encoding.
+    ///
+    /// A byte order mark matching the encoding at the start of `bytes` is
+    /// skipped, and a UTF-8 one is remembered in
+    /// [`has_bom()`](Self::has_bom()). Use [`Encoding::detect()`](crate::encoding::Encoding::detect())
+    /// to pick the encoding from the byte order mark itself.
+    ///
+    /// The text is transcoded a kilobyte at a time directly into the chunks
+    /// of the `Rope`, without first collecting it into a `String`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DecodeError`](crate::encoding::DecodeError) with the byte
+    /// offset of the first invalid sequence if `bytes` aren't valid in the
+    /// encoding. Decoding from a single-byte encoding like
+    /// [`Latin1`](crate::encoding::Encoding::Latin1) never fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// # use crop::encoding::Encoding;
+    /// #
+    /// let bytes = b"\xFF\xFEH\x00i\x00!\x00";
+    ///
+    /// let r = Rope::from_bytes_with_encoding(bytes, Encoding::detect(bytes))?;
+    /// assert_eq!(r, "Hi!");
+    /// # Ok::<(), crop::encoding::DecodeError>(())
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "encoding")))]
+    #[cfg(feature = "encoding")]
+    #[inline]
+    pub fn from_bytes_with_encoding(
+        bytes: &[u8],
+        encoding: crate::encoding::Encoding,
+    ) -> Result<Self, crate::encoding::DecodeError> {
+        super::encoding::decode(bytes, encoding)
+    }
+
+    /// Creates a new `Rope` from the text read from `reader`.
+    ///
+    /// This is a shorthand for
+    /// [`RopeBuilder::append_reader()`](crate::RopeBuilder::append_reader())
+    /// followed by [`RopeBuilder::build()`](crate::RopeBuilder::build()), and
+    /// it's meant for loading large files without first reading them into a
+    /// `String`. A leading byte order mark is kept as text, use a
+    /// `RopeBuilder` with [`strip_bom()`](crate::RopeBuilder::strip_bom())
+    /// to strip it instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails or if the text isn't valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from_reader(File::open("my_little_poem.txt")?)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_reader<R>(reader: R) -> std::io::Result<Self>
+    where
+        R: std::io::Read,
+    {
+        let mut builder = super::RopeBuilder::new();
+        builder.append_reader(reader)?;
+        Ok(builder.build())
+    }
+
+    /// Returns the byte at `byte_index`, or `None` if the index is out of
+    /// bounds.
+    ///
+    /// This is the non-panicking version of [`byte()`](Self::byte()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("bar");
+    ///
+    /// assert_eq!(r.get_byte(2), Some(b'r'));
+    /// assert_eq!(r.get_byte(3), None);
+    /// ```
+    #[inline]
+    pub fn get_byte(&self, byte_index: usize) -> Option<u8> {
+        (byte_index < self.byte_len()).then(|| self.byte(byte_index))
+    }
+
+    /// Returns the slice in the specified byte range, or `None` if the range
+    /// is invalid.
+    ///
+    /// This is the non-panicking version of
+    /// [`byte_slice()`](Self::byte_slice()). Use
+    /// [`try_byte_slice()`](Self::try_byte_slice()) to know why the range
+    /// was rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🗻∈🌏");
+    ///
+    /// assert_eq!(r.get_byte_slice(4..7).unwrap(), "∈");
+    /// assert!(r.get_byte_slice(..5).is_none());
+    /// assert!(r.get_byte_slice(..12).is_none());
+    /// ```
+    #[inline]
+    pub fn get_byte_slice<R>(&self, byte_range: R) -> Option<RopeSlice<'_>>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_byte_slice(byte_range).ok()
+    }
+
+    /// Returns the line at `line_index`, or `None` if the index is out of
+    /// bounds.
+    ///
+    /// This is the non-panicking version of [`line()`](Self::line()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// assert_eq!(r.get_line(1).unwrap(), "bar");
+    /// assert!(r.get_line(2).is_none());
+    /// ```
+    #[inline]
+    pub fn get_line(&self, line_index: usize) -> Option<RopeSlice<'_>> {
+        self.try_line(line_index).ok()
+    }
+
+    /// Returns the slice in the specified line range, or `None` if the range
+    /// is invalid.
+    ///
+    /// This is the non-panicking version of
+    /// [`line_slice()`](Self::line_slice()). Use
+    /// [`try_line_slice()`](Self::try_line_slice()) to know why the range
+    /// was rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    ///
+    /// assert_eq!(r.get_line_slice(1..).unwrap(), "bar\r\nbaz\n");
+    /// assert!(r.get_line_slice(..4).is_none());
+    /// ```
+    #[inline]
+    pub fn get_line_slice<R>(&self, line_range: R) -> Option<RopeSlice<'_>>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_line_slice(line_range).ok()
+    }
+
+    /// Returns an immutable slice of the `Rope` in the specified byte range,
+    /// widened so that it doesn't split any grapheme cluster.
+    ///
+    /// The start of the range is moved back to the
+    /// [previous](Self::floor_grapheme_boundary()) grapheme boundary and its
+    /// end forward to the [next](Self::ceil_grapheme_boundary()) one, so
+    /// neither of them has to lie on a code point boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a🐻‍❄️\r\nb");
+    ///
+    /// assert_eq!(r.grapheme_slice(..1), "a");
+    /// assert_eq!(r.grapheme_slice(..3), "a🐻‍❄️");
+    /// assert_eq!(r.grapheme_slice(15..), "\r\nb");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn grapheme_slice<R>(&self, byte_range: R) -> RopeSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.byte_len() {
+            panic::byte_offset_out_of_bounds(end, self.byte_len());
+        }
+
+        let start = floor_grapheme_boundary(self.byte_slice(..), start);
+        let end = ceil_grapheme_boundary(self.byte_slice(..), end);
+
+        self.byte_slice(start..end)
+    }
+
+    /// Returns an iterator over the extended grapheme clusters of this
+    /// `Rope`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("arg!\r\n🐻‍❄️");
+    ///
+    /// let mut graphemes = r.graphemes();
+    ///
+    /// assert_eq!(Some("a"), graphemes.next().as_deref());
+    /// assert_eq!(Some("r"), graphemes.next().as_deref());
+    /// assert_eq!(Some("g"), graphemes.next().as_deref());
+    /// assert_eq!(Some("!"), graphemes.next().as_deref());
+    /// assert_eq!(Some("\r\n"), graphemes.next().as_deref());
+    /// assert_eq!(Some("🐻‍❄️"), graphemes.next().as_deref());
+    /// assert_eq!(None, graphemes.next());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn graphemes(&self) -> crate::iter::Graphemes<'_> {
+        crate::iter::Graphemes::from(self)
+    }
+
+    /// Returns whether the `Rope` has a UTF-8 byte order mark.
+    ///
+    /// The byte order mark isn't part of the text of the `Rope`: it's only
+    /// remembered so that [`write_to()`](Self::write_to()) can write it back
+    /// before the text. It's set when a leading BOM is stripped by a
+    /// [`RopeBuilder`](crate::RopeBuilder) configured with
+    /// [`strip_bom()`](crate::RopeBuilder::strip_bom()), and it can be
+    /// changed with [`set_bom()`](Self::set_bom()). Editing the `Rope`
+    /// doesn't affect it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeBuilder};
+    /// #
+    /// let mut builder = RopeBuilder::new();
+    /// builder.strip_bom().append("\u{FEFF}Hello World!");
+    ///
+    /// let r = builder.build();
+    ///
+    /// assert_eq!(r, "Hello World!");
+    /// assert!(r.has_bom());
+    ///
+    /// assert!(!Rope::from("Hello World!").has_bom());
+    /// ```
+    #[inline]
+    pub fn has_bom(&self) -> bool {
+        self.has_bom
+    }
+
+    /// Inserts `text` in the `Rope` at the given byte offset, returning a
+    /// [`Change`] describing the edit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset doesn't lie on a code point boundary or if
+    /// it's out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello Earth!");
+    ///
+    /// r.insert(11, " 🌎");
+    /// assert_eq!(r, "Hello Earth 🌎!");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn insert<T>(&mut self, byte_offset: usize, text: T) -> Change
+    where
+        T: AsRef<str>,
+    {
+        self.replace(byte_offset..byte_offset, text)
+    }
+
+    /// Inserts `text` in the `Rope` at the given char offset.
+    ///
+    /// This is equivalent to calling [`insert()`](Self::insert()) with the
+    /// corresponding byte offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the char offset is out of bounds (i.e. greater than
+    /// [`char_len()`](Self::char_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("ƒar");
+    ///
+    /// r.insert_at_char(1, "ö");
+    /// assert_eq!(r, "ƒöar");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn insert_at_char<T>(&mut self, char_offset: usize, text: T) -> Change
+    where
+        T: AsRef<str>,
+    {
+        let byte_offset = self.byte_of_char(char_offset);
+        self.insert(byte_offset, text)
+    }
+
+    /// Returns `true` if the given byte offset lies on a [`char`] boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Löwe 老虎 Léopard");
+    ///
+    /// assert!(r.is_char_boundary(0));
+    /// assert!(r.is_char_boundary(r.byte_len()));
+    /// assert!(r.is_char_boundary(6)); // between ' ' and '老'
+    /// assert!(!r.is_char_boundary(2)); // between the 1st and 2nd byte of 'ö'
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn is_char_boundary(&self, byte_offset: usize) -> bool {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let (chunk, ByteMetric(chunk_byte_offset)) =
+            self.tree.leaf_at_measure(ByteMetric(byte_offset));
+
+        chunk.is_char_boundary(byte_offset - chunk_byte_offset)
+    }
+
+    /// Returns `true` if the `Rope`'s byte length is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::new();
+    /// assert!(r.is_empty());
+    ///
+    /// r.insert(0, "hey");
+    /// assert!(!r.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.byte_len() == 0
+    }
+
+    /// Returns `true` if the given byte offset lies on a grapheme cluster
+    /// boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
+    ///
+    /// assert!(r.is_grapheme_boundary(0));
+    /// assert!(r.is_grapheme_boundary(r.byte_len()));
+    /// assert!(!r.is_grapheme_boundary(7)); // between '\r' and '\n'
+    /// assert!(r.is_grapheme_boundary(8)); // between '\n' and '🐻‍❄️'
+    /// assert!(!r.is_grapheme_boundary(12)); // between the 1st and 2nd code point of '🐻‍❄️'
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn is_grapheme_boundary(&self, byte_offset: usize) -> bool {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        is_grapheme_boundary(self.byte_slice(..), byte_offset)
+    }
+
+    /// Returns whether the text of the `Rope` is in Unicode Normalization
+    /// Form C, i.e. whether [`to_nfc()`](Self::to_nfc()) would leave it
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// assert!(Rope::from("caf\u{e9}").is_nfc());
+    /// assert!(!Rope::from("cafe\u{301}").is_nfc());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-normalization")))]
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    pub fn is_nfc(&self) -> bool {
+        super::normalization::is_nfc(self.byte_slice(..))
+    }
+
+    /// Returns an iterator over the summaries of the leaves of this
+    /// `Rope`, together with the byte offset at which each leaf starts.
+    ///
+    /// The summaries are stored in the leaves themselves, so this doesn't
+    /// touch the text at all. It can be used to build external indexes (e.g.
+    /// of line starts) from metadata alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!\n");
+    ///
+    /// let mut bytes = 0;
+    /// let mut line_breaks = 0;
+    ///
+    /// for (offset, summary) in r.leaf_summaries() {
+    ///     assert_eq!(offset, bytes);
+    ///     bytes += summary.bytes();
+    ///     line_breaks += summary.line_breaks();
+    /// }
+    ///
+    /// assert_eq!(bytes, r.byte_len());
+    /// assert_eq!(line_breaks, 1);
+    /// ```
+    #[inline]
+    pub fn leaf_summaries(&self) -> LeafSummaries<'_> {
+        LeafSummaries::from(self)
+    }
+
+    /// Returns the line at `line_index`, without its line terminator.
+    ///
+    /// If you want to include the line break consider taking a
+    /// [`line_slice()`](Self::line_slice()) in the
+    /// `line_index..line_index + 1` range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line index is out of bounds (i.e. greater than or equal
+    /// to [`line_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz");
+    ///
+    /// assert_eq!(r.line(0), "foo");
+    /// assert_eq!(r.line(1), "bar");
+    /// assert_eq!(r.line(2), "baz");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line(&self, line_index: usize) -> RopeSlice<'_> {
+        if line_index >= self.line_len() {
+            panic::line_index_out_of_bounds(line_index, self.line_len());
+        }
+
+        let tree_slice = self
+            .tree
+            .slice(RawLineMetric(line_index)..RawLineMetric(line_index + 1));
+
+        let mut line = RopeSlice { tree_slice, has_trailing_newline: false };
+
+        if line.tree_slice.summary().line_breaks() == 1 {
+            line.truncate_trailing_line_break();
+        }
+
+        line
+    }
+
+    /// Returns the number of lines in the `Rope`.
+    ///
+    /// The final line break is optional and doesn't count as a separate empty
+    /// line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::new();
+    ///
+    /// assert_eq!(r.line_len(), 0);
+    ///
+    /// r.insert(0, "a");
+    /// assert_eq!(r.line_len(), 1);
+    ///
+    /// r.insert(1, "\n");
+    /// assert_eq!(r.line_len(), 1);
+    ///
+    /// r.insert(2, "b");
+    /// assert_eq!(r.line_len(), 2);
+    ///
+    /// r.insert(3, "\r\n");
+    /// assert_eq!(r.line_len(), 2);
+    /// ```
+    #[inline]
+    pub fn line_len(&self) -> usize {
+        self.tree.summary().line_breaks() + 1
+            - (self.has_trailing_newline as usize)
+            - (self.is_empty() as usize)
+    }
+
+    /// Returns the line offset of the given byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz");
+    ///
+    /// assert_eq!(r.line_of_byte(0), 0);
+    /// assert_eq!(r.line_of_byte(3), 0);
+    /// assert_eq!(r.line_of_byte(4), 1);
+    /// assert_eq!(r.line_of_byte(8), 1); // between the '\r' and the '\n'
+    /// assert_eq!(r.line_of_byte(r.byte_len()), 2);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line_of_byte(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let RawLineMetric(line_offset) =
+            self.tree.convert_measure(ByteMetric(byte_offset));
+
+        line_offset
+    }
+
+    /// Returns an iterator over the byte ranges of the lines of this `Rope`,
+    /// not including the line terminators.
+    ///
+    /// The ranges are the ones of the lines yielded by
+    /// [`lines()`](Self::lines()), so this is the same as calling
+    /// [`byte_of_line()`](Self::byte_of_line()) for every line, but without
+    /// descending the B-tree from its root every time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    ///
+    /// let mut ranges = r.line_ranges();
+    ///
+    /// assert_eq!(ranges.next(), Some(0..3));
+    /// assert_eq!(ranges.next(), Some(4..7));
+    /// assert_eq!(ranges.next(), Some(9..12));
+    /// assert_eq!(ranges.next(), None);
+    /// ```
+    #[inline]
+    pub fn line_ranges(&self) -> crate::iter::LineRanges<'_> {
+        crate::iter::LineRanges::from(self)
+    }
+
+    /// Returns an immutable slice of the `Rope` in the specified line range,
+    /// where the start and end of the range are interpreted as offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\nfoobar\n");
+    ///
+    /// assert_eq!(r.line_slice(..1), "foo\n");
+    /// assert_eq!(r.line_slice(1..3), "bar\r\nbaz\n");
+    /// assert_eq!(r.line_slice(3..4), "foobar\n");
+    /// assert_eq!(r.line_slice(4..), "");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line_slice<R>(&self, line_range: R) -> RopeSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(line_range, 0, self.line_len());
+
+        if start > end {
+            panic::line_start_after_end(start, end);
+        }
+
+        if end > self.line_len() {
+            panic::line_offset_out_of_bounds(end, self.line_len());
+        }
+
+        self.tree.slice(RawLineMetric(start)..RawLineMetric(end)).into()
+    }
+
+    /// Returns an iterator over the lines of this `Rope`, not including the
+    /// line terminators.
+    ///
+    /// The final line break is optional and doesn't cause the iterator to
+    /// return a final empty line.
+    ///
+    /// If you want to include the line breaks consider using the
+    /// [`raw_lines()`](Self::raw_lines()) method instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    ///
+    /// let mut lines = r.lines();
+    ///
+    /// assert_eq!("foo", lines.next().unwrap());
+    /// assert_eq!("bar", lines.next().unwrap());
+    /// assert_eq!("baz", lines.next().unwrap());
+    /// assert_eq!(None, lines.next());
+    /// ```
+    #[inline]
+    pub fn lines(&self) -> Lines<'_> {
+        Lines::from(self)
+    }
+
+    /// Returns a pair of iterators over the lines before and after the
+    /// given line offset, not including the line terminators.
+    ///
+    /// The first iterator yields the lines before `line_offset` in reverse
+    /// order, while the second one yields the lines starting from it. Both
+    /// are positioned in O(log n).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    ///
+    /// let (mut before, mut after) = r.lines_at(1);
+    ///
+    /// assert_eq!("foo", before.next().unwrap());
+    /// assert_eq!(None, before.next());
+    ///
+    /// assert_eq!("bar", after.next().unwrap());
+    /// assert_eq!("baz", after.next().unwrap());
+    /// assert_eq!(None, after.next());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn lines_at(
+        &self,
+        line_offset: usize,
+    ) -> (core::iter::Rev<Lines<'_>>, Lines<'_>) {
+        self.byte_slice(..).lines_at(line_offset)
+    }
+
+    /// Returns an iterator over the lines of this `Rope`, including the
+    /// line terminators.
+    ///
+    /// The final line break is optional and doesn't cause the iterator to
+    /// return a final empty line.
+    ///
+    /// If you don't want to include the line breaks consider using the
+    /// [`lines()`](Self::lines()) method instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("foo\nbar\r\nbaz");
+    ///
+    /// let mut raw_lines = r.raw_lines();
+    ///
+    /// assert_eq!("foo\n", raw_lines.next().unwrap());
+    /// assert_eq!("bar\r\n", raw_lines.next().unwrap());
+    /// assert_eq!("baz", raw_lines.next().unwrap());
+    /// assert_eq!(None, raw_lines.next());
+    ///
+    /// r.insert(r.byte_len(), "\n");
+    ///
+    /// let mut raw_lines = r.raw_lines();
+    ///
+    /// assert_eq!("foo\n", raw_lines.next().unwrap());
+    /// assert_eq!("bar\r\n", raw_lines.next().unwrap());
+    /// assert_eq!("baz\n", raw_lines.next().unwrap());
+    /// assert_eq!(None, raw_lines.next());
+    /// ```
+    #[inline]
+    pub fn raw_lines(&self) -> RawLines<'_> {
+        RawLines::from(self)
+    }
+
+    /// Returns an iterator over the non-overlapping matches of `pattern` in
+    /// the `Rope`, which can be any [`Pattern`](crate::pattern::Pattern).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a1, b22, c333");
+    ///
+    /// let digits = r.matches(char::is_numeric).count();
+    /// assert_eq!(digits, 6);
+    ///
+    /// let matches = r.matches(", ").collect::<Vec<_>>();
+    /// assert_eq!(matches, [", ", ", "]);
+    /// ```
+    #[inline]
+    pub fn matches<P: crate::pattern::Pattern>(
+        &self,
+        pattern: P,
+    ) -> crate::iter::Matches<'_, P> {
+        crate::iter::Matches::new(self.byte_slice(..), pattern)
+    }
+
+    /// Returns the maximum byte length a `Rope` can have while its B-tree is
+    /// still guaranteed to be at most `depth` levels deep, i.e. the largest
+    /// `byte_len` for which [`max_depth(byte_len)`](Self::max_depth()) is
+    /// less than or equal to `depth`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// for depth in 0..4 {
+    ///     let max_byte_len = Rope::max_byte_len(depth);
+    ///     assert!(Rope::max_depth(max_byte_len) <= depth);
+    ///     assert!(Rope::max_depth(max_byte_len + 1) > depth);
+    /// }
+    /// ```
+    #[inline]
+    pub fn max_byte_len(depth: usize) -> usize {
+        // This is the inverse of `max_depth()`: a tree of depth `depth + 1`
+        // needs at least `2 * min_children()^depth` leaves of at least
+        // `chunk_min()` bytes each.
+        let min_children =
+            Tree::<{ Self::arity() }, RopeChunk>::min_children();
+
+        let mut min_leaves = 2usize;
+
+        for _ in 0..depth {
+            min_leaves = min_leaves.saturating_mul(min_children);
+        }
+
+        RopeChunk::chunk_min()
+            .max(1)
+            .saturating_mul(min_leaves)
+            .saturating_sub(1)
+    }
+
+    /// Returns the maximum byte length allowed by the depth limit, if there
+    /// is one.
+    #[inline]
+    fn max_byte_len_for_limit(&self) -> Option<usize> {
+        self.depth_limit.map(Self::max_byte_len)
+    }
+
+    /// Panics if a `Rope` with the given byte length would exceed the depth
+    /// limit.
+    ///
+    /// The edits made of several replacements call this with their final
+    /// length before the first one, so that they can't panic halfway through
+    /// and leave the `Rope` partially edited.
+    #[track_caller]
+    #[inline]
+    pub(super) fn assert_within_depth_limit(&self, new_len: usize) {
+        if let Some(max_byte_len) = self.max_byte_len_for_limit() {
+            if new_len > max_byte_len {
+                panic::depth_limit_exceeded(new_len, max_byte_len);
+            }
+        }
+    }
+
+    /// Returns the maximum depth that the B-tree backing a `Rope` with the
+    /// given byte length can have, no matter how it's been created or edited.
+    ///
+    /// Every point edit (i.e. an insertion, deletion or replacement) has to
+    /// traverse at most this many internal nodes, which makes its worst-case
+    /// latency predictable.
+    ///
+    /// Note that the bound is determined by the maximum size of a leaf and the
+    /// maximum number of children of an internal node, both of which are
+    /// fixed at compile time. To keep a `Rope` below a given depth, use
+    /// [`set_depth_limit()`](Self::set_depth_limit()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// assert_eq!(Rope::max_depth(0), 0);
+    ///
+    /// // A rope can be shallower than the bound but never deeper.
+    /// let r = Rope::from("Hello Earth 🌎!\n".repeat(1 << 16));
+    /// assert!(r.depth() <= Rope::max_depth(r.byte_len()));
+    /// ```
+    #[inline]
+    pub fn max_depth(byte_len: usize) -> usize {
+        // Every leaf except the only leaf of a single-leaf tree contains at
+        // least `chunk_min()` bytes.
+        let max_leaves = byte_len / RopeChunk::chunk_min().max(1);
+
+        // The root has at least 2 children and every other internal node has
+        // at least `min_children()`, so a tree of depth `d` has at least
+        // `2 * min_children()^(d - 1)` leaves.
+        let min_children =
+            Tree::<{ Self::arity() }, RopeChunk>::min_children();
+
+        let mut depth = 0;
+        let mut min_leaves = 2usize;
+
+        while min_leaves <= max_leaves {
+            depth += 1;
+            min_leaves = min_leaves.saturating_mul(min_children);
+            if min_leaves == usize::MAX {
+                break;
+            }
+        }
+
+        depth
+    }
+
+    /// Returns the length in bytes of the longest line in the `Rope`, not
+    /// counting the `"\n"` or `"\r\n"` terminating it.
+    ///
+    /// This is the same as the maximum of the
+    /// [`byte_len()`](RopeSlice::byte_len())s of the lines yielded by
+    /// [`lines()`](Self::lines()), but it runs in O(1) since every node of the
+    /// B-tree keeps track of the longest line it contains. Keeping that up to
+    /// date is paid for by the edits instead: each one rescans the leaves it
+    /// modifies and updates the O(log n) internal nodes above them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("foo\nbarbaz\r\nqux");
+    /// assert_eq!(r.max_line_len(), 6);
+    ///
+    /// r.insert(2, "oooooo");
+    /// assert_eq!(r.max_line_len(), 9);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "max-line-len")))]
+    #[cfg(feature = "max-line-len")]
+    #[inline]
+    pub fn max_line_len(&self) -> usize {
+        self.tree.aggregate().line_lengths.max_line_len()
+    }
+
+    /// Returns a breakdown of the heap memory used by the `Rope`.
+    ///
+    /// The text of a `Rope` is stored in fixed-size chunks which are rarely
+    /// full, so the memory it uses is always greater than its
+    /// [`byte_len()`](Self::byte_len()). The [`MemUsage`](crate::MemUsage)
+    /// returned by this method tells how much of it is taken up by the text
+    /// itself, by the unused space in the chunks and by the nodes of the
+    /// B-tree.
+    ///
+    /// Nodes shared with clones of the `Rope` are counted in full, so the
+    /// total of two clones can be greater than their actual combined
+    /// footprint.
+    ///
+    /// This runs in O(n) in the number of chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello World!");
+    ///
+    /// let mem_usage = r.mem_usage();
+    ///
+    /// assert_eq!(mem_usage.text_bytes, r.byte_len());
+    /// assert!(mem_usage.total_bytes() > r.byte_len());
+    /// ```
+    #[inline]
+    pub fn mem_usage(&self) -> crate::MemUsage {
+        super::mem_usage::mem_usage(self)
+    }
+
+    /// Merges the changes made to `base` by `ours` and `theirs`, returning the
+    /// merged `Rope` together with the regions where they conflict.
+    ///
+    /// Both sides are diffed against `base` with [`diff()`](Self::diff()),
+    /// and the changes that touch or overlap each other are grouped into
+    /// regions. A region changed by only one of the two sides, or changed in
+    /// the exact same way by both, is merged cleanly. Otherwise the region
+    /// is a [`Conflict`](crate::Conflict), and the merged `Rope` contains our
+    /// version of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let base = Rope::from("foo\nbar\nbaz\n");
+    /// let ours = Rope::from("Foo\nbar\nbaz\n");
+    /// let theirs = Rope::from("foo\nbar\nBaz\n");
+    ///
+    /// let (merged, conflicts) = Rope::merge(&base, &ours, &theirs);
+    ///
+    /// assert_eq!(merged, "Foo\nbar\nBaz\n");
+    /// assert!(conflicts.is_empty());
+    ///
+    /// let theirs = Rope::from("FOO\nbar\nbaz\n");
+    ///
+    /// let (merged, conflicts) = Rope::merge(&base, &ours, &theirs);
+    ///
+    /// assert_eq!(merged, "Foo\nbar\nbaz\n");
+    /// assert_eq!(conflicts.len(), 1);
+    ///
+    /// let conflict = &conflicts[0];
+    /// assert_eq!(merged.byte_slice(conflict.byte_range.clone()), "Foo");
+    /// assert_eq!(theirs.byte_slice(conflict.their_byte_range.clone()), "FOO");
+    /// ```
+    #[inline]
+    pub fn merge(
+        base: &Self,
+        ours: &Self,
+        theirs: &Self,
+    ) -> (Self, alloc::vec::Vec<crate::Conflict>) {
+        super::merge::merge(base, ours, theirs)
+    }
+
+    /// Returns a new empty [`Rope`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the byte offset of the first grapheme cluster boundary after
+    /// the given byte offset, or `None` if the offset is at the end of the
+    /// `Rope`.
+    ///
+    /// This can be used to move a cursor one grapheme to the right, and it
+    /// only looks at the chunks around the byte offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
+    ///
+    /// assert_eq!(r.next_grapheme_boundary(5), Some(6));
+    /// assert_eq!(r.next_grapheme_boundary(6), Some(8)); // skips "\r\n"
+    /// assert_eq!(r.next_grapheme_boundary(8), Some(r.byte_len()));
+    /// assert_eq!(r.next_grapheme_boundary(r.byte_len()), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn next_grapheme_boundary(&self, byte_offset: usize) -> Option<usize> {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        next_grapheme_boundary(self.byte_slice(..), byte_offset)
+    }
+
+    /// Rewrites all the line endings of the `Rope` to the given style.
+    ///
+    /// Only the leaves containing line endings of the other style are
+    /// rewritten, with the consecutive ones being replaced in a single edit.
+    /// The rest of the tree is untouched, so it stays shared with any clones
+    /// of the `Rope`.
+    ///
+    /// When normalizing to LF all the carriage returns right before a line
+    /// feed are removed, so that `"\r\r\n"` becomes `"\n"`. Any other
+    /// carriage return is left as is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line ending is [`LineEnding::Mixed`](crate::LineEnding),
+    /// or if the new line endings would make the `Rope` exceed its
+    /// [depth limit](Self::set_depth_limit()), in which case the `Rope` is
+    /// left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{LineEnding, Rope};
+    /// #
+    /// let mut r = Rope::from("foo\r\nbar\nbaz\r\n");
+    ///
+    /// r.normalize_line_endings(LineEnding::Lf);
+    /// assert_eq!(r, "foo\nbar\nbaz\n");
+    ///
+    /// r.normalize_line_endings(LineEnding::Crlf);
+    /// assert_eq!(r, "foo\r\nbar\r\nbaz\r\n");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn normalize_line_endings(&mut self, line_ending: crate::LineEnding) {
+        super::line_ending::normalize_line_endings(self, line_ending);
+    }
+
+    /// Returns a parallel iterator over the chunks of the `Rope`.
+    ///
+    /// The `Rope` is recursively split in half at leaf boundaries, so the
+    /// chunks are the same ones yielded by [`chunks()`](Self::chunks()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// use rayon::prelude::*;
+    ///
+    /// let r = Rope::from("Hello\nWorld\n".repeat(1000));
+    ///
+    /// let line_breaks =
+    ///     r.par_chunks().map(|chunk| chunk.matches('\n').count()).sum::<usize>();
+    ///
+    /// assert_eq!(line_breaks, 2000);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_chunks(&self) -> crate::iter::ParChunks<'_> {
+        crate::iter::ParChunks::from(self)
+    }
+
+    /// Returns a parallel iterator over the lines of the `Rope`.
+    ///
+    /// The `Rope` is recursively split in half at line boundaries, so the
+    /// lines are the same ones yielded by [`lines()`](Self::lines()), and
+    /// collecting them preserves their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// use rayon::prelude::*;
+    ///
+    /// let r = Rope::from("foo\nbar baz\n".repeat(1000));
+    ///
+    /// let words = r
+    ///     .par_lines()
+    ///     .map(|line| line.chars().filter(|&ch| ch == ' ').count() + 1)
+    ///     .sum::<usize>();
+    ///
+    /// assert_eq!(words, 3000);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_lines(&self) -> crate::iter::ParLines<'_> {
+        crate::iter::ParLines::from(self)
+    }
+
+    /// Returns the [`Position`] of the given byte offset, where the column is
+    /// measured in the units of the given [`PositionEncoding`].
+    ///
+    /// This is meant to convert byte offsets into positions to be sent to a
+    /// language server client. The column is measured by scanning its line,
+    /// so this runs in O(log n + line length).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Position, PositionEncoding, Rope};
+    /// #
+    /// let r = Rope::from("foo\na𐐀bc");
+    ///
+    /// // "𐐀" is 4 bytes in UTF-8 and 2 code units in UTF-16.
+    /// assert_eq!(
+    ///     r.position_of_byte(10, PositionEncoding::Utf8),
+    ///     Position::new(1, 6)
+    /// );
+    /// assert_eq!(
+    ///     r.position_of_byte(10, PositionEncoding::Utf16),
+    ///     Position::new(1, 4)
+    /// );
+    /// assert_eq!(
+    ///     r.position_of_byte(10, PositionEncoding::Utf32),
+    ///     Position::new(1, 3)
+    /// );
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn position_of_byte(
+        &self,
+        byte_offset: usize,
+        encoding: PositionEncoding,
+    ) -> Position {
+        position_of_byte(self.byte_slice(..), byte_offset, encoding)
+    }
+
+    /// Returns the byte offset of the last grapheme cluster boundary before
+    /// the given byte offset, or `None` if the offset is at the start of the
+    /// `Rope`.
+    ///
+    /// This can be used to move a cursor one grapheme to the left or to
+    /// implement backspace, and it only looks at the chunks around the byte
+    /// offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
+    ///
+    /// assert_eq!(r.prev_grapheme_boundary(r.byte_len()), Some(8));
+    /// assert_eq!(r.prev_grapheme_boundary(8), Some(6)); // skips "\r\n"
+    /// assert_eq!(r.prev_grapheme_boundary(1), Some(0));
+    /// assert_eq!(r.prev_grapheme_boundary(0), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn prev_grapheme_boundary(&self, byte_offset: usize) -> Option<usize> {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        prev_grapheme_boundary(self.byte_slice(..), byte_offset)
+    }
+
+    /// Creates a new `Rope` from a dump of its B-tree written by
+    /// [`write_tree()`](Self::write_tree()).
+    ///
+    /// If the dump was written by a build of crop with the same chunk size
+    /// and metric features as this one, its leaves are used as they are
+    /// instead of re-segmenting the text and rebuilding the tree. The stored
+    /// summaries are still checked against the text of their leaves, so this
+    /// is a single linear pass over the input. Otherwise the summaries are
+    /// discarded and the `Rope` is rebuilt from the text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails, or an error of kind
+    /// [`InvalidData`](std::io::ErrorKind::InvalidData) if the data isn't a
+    /// valid dump, including if the summary stored for a leaf doesn't
+    /// match its text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello\nWorld!");
+    ///
+    /// let mut dump = Vec::new();
+    /// r.write_tree(&mut dump)?;
+    ///
+    /// assert_eq!(Rope::read_tree(dump.as_slice())?, r);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn read_tree<R>(reader: R) -> std::io::Result<Self>
+    where
+        R: std::io::Read,
+    {
+        super::tree_dump::read_tree(reader)
+    }
+
+    /// Returns a [`RopeReader`](crate::RopeReader) over the contents of the
+    /// `Rope`, which implements [`Read`](std::io::Read) and
+    /// [`BufRead`](std::io::BufRead).
+    ///
+    /// This lets a `Rope` be passed to any API taking a reader without first
+    /// copying its text into a contiguous buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// use std::io::Read;
+    ///
+    /// let r = Rope::from("ƒoo\nbär\r\nbaz");
+    ///
+    /// let mut buf = String::new();
+    /// r.reader().read_to_string(&mut buf)?;
+    ///
+    /// assert_eq!(buf, "ƒoo\nbär\r\nbaz");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn reader(&self) -> crate::RopeReader<'_> {
+        crate::RopeReader::new(self.chunks())
+    }
+
+    /// Returns an iterator over the byte ranges of the non-overlapping
+    /// matches of `regex` in the `Rope`, from front to back.
+    ///
+    /// The search runs directly over the chunks of the `Rope` without
+    /// copying its text, and it has the same semantics as
+    /// [`Regex::find_iter()`](crate::Regex::find_iter()). Within a
+    /// `Rope`, `^` and `$` match at its start and end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the regex's lazy DFA gives up on the search, which can only
+    /// happen if it was configured with quit bytes or with a minimum cache
+    /// clear count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Regex, Rope};
+    /// #
+    /// let r = Rope::from("foo 42, bar 1337");
+    /// let regex = Regex::new(r"[0-9]+").unwrap();
+    ///
+    /// let mut matches = r.regex_find_iter(&regex);
+    ///
+    /// assert_eq!(matches.next(), Some(4..6));
+    /// assert_eq!(matches.next(), Some(12..16));
+    /// assert_eq!(matches.next(), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    #[cfg(feature = "regex")]
+    #[inline]
+    pub fn regex_find_iter<'r>(
+        &self,
+        regex: &'r crate::Regex,
+    ) -> crate::iter::RegexFindIter<'_, 'r> {
+        crate::iter::RegexFindIter::new(self.byte_slice(..), regex)
+    }
+
+    /// Replaces the contents of the `Rope` within the specified byte range
+    /// with the given string, where the start and end of the range are
+    /// interpreted as byte offsets.
+    ///
+    /// Returns a [`Change`] describing the edit, which can be
+    /// used to keep other data structures in sync with the `Rope`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or the end of the byte range don't lie on a code
+    /// point boundary, if the start is greater than the end or if the end is
+    /// out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// r.replace(6..16, "Saturn 🪐");
+    /// assert_eq!(r, "Hello Saturn 🪐!");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn replace<R, T>(&mut self, byte_range: R, text: T) -> Change
+    where
+        R: RangeBounds<usize>,
+        T: AsRef<str>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.byte_len() {
+            panic::byte_offset_out_of_bounds(end, self.line_len());
+        }
+
+        let text = text.as_ref();
+
+        let new_len = self.byte_len() - (end - start) + text.len();
+
+        self.assert_within_depth_limit(new_len);
+
+        let mut update_trailing = false;
+
+        if end == self.byte_len() {
+            if !text.is_empty() {
+                self.has_trailing_newline = text.ends_with('\n');
+            } else if start == 0 {
+                self.has_trailing_newline = false;
+            } else {
+                update_trailing = true;
+            }
+        }
+
+        self.tree.replace(ByteMetric(start)..ByteMetric(end), text);
+
+        if update_trailing {
+            self.has_trailing_newline =
+                self.chunks().next_back().unwrap().ends_with('\n');
+        }
+
+        Change { byte_range: start..end, inserted_len: text.len() }
+    }
+
+    /// Replaces all the non-overlapping matches of `pattern` in the `Rope`
+    /// with `replacement`. The pattern can be any
+    /// [`Pattern`](crate::pattern::Pattern).
+    ///
+    /// The occurrences are replaced from back to front, so every edit only
+    /// touches the leaves containing it. Like with every other edit, the
+    /// subtrees shared with clones of this `Rope` are copied on write, so
+    /// cloning the `Rope` before calling this is a cheap way to get a new
+    /// `Rope` that shares all the untouched text with the original.
+    ///
+    /// # Panics
+    ///
+    /// Panics if replacing the matches would make the `Rope` exceed its
+    /// [depth limit](Self::set_depth_limit()), in which case the `Rope` is
+    /// left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar foo");
+    ///
+    /// let mut replaced = r.clone();
+    /// replaced.replace_all("foo", "baz");
+    ///
+    /// assert_eq!(replaced, "baz bar baz");
+    /// assert_eq!(r, "foo bar foo");
+    ///
+    /// replaced.replace_all(char::is_whitespace, "_");
+    /// assert_eq!(replaced, "baz_bar_baz");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn replace_all<P: crate::pattern::Pattern>(
+        &mut self,
+        pattern: P,
+        replacement: &str,
+    ) {
+        let matches = self.find_iter(pattern).collect::<alloc::vec::Vec<_>>();
+
+        let matched_len =
+            matches.iter().map(|range| range.len()).sum::<usize>();
+
+        let new_len =
+            self.byte_len() - matched_len + matches.len() * replacement.len();
+
+        self.assert_within_depth_limit(new_len);
+
+        for range in matches.into_iter().rev() {
+            self.replace(range, replacement);
+        }
+    }
+
+    /// Returns the byte offset of the last occurrence of `pattern` in the
+    /// `Rope`, or `None` if it doesn't occur.
+    ///
+    /// See [`find()`](Self::find()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar foo");
+    ///
+    /// assert_eq!(r.rfind("foo"), Some(8));
+    /// assert_eq!(r.rfind("bar"), Some(4));
+    /// assert_eq!(r.rfind("baz"), None);
+    /// ```
+    #[inline]
+    pub fn rfind<P: crate::pattern::Pattern>(
+        &self,
+        pattern: P,
+    ) -> Option<usize> {
+        self.byte_slice(..).rfind(pattern)
+    }
+
+    /// Returns an iterator over the sub-slices of the `Rope` separated by
+    /// `pattern`, starting from the end.
+    ///
+    /// This yields the same sub-slices as [`split()`](Self::split()), but in
+    /// reverse order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo, bar, baz");
+    ///
+    /// let fields = r.rsplit(", ").collect::<Vec<_>>();
+    ///
+    /// assert_eq!(fields, ["baz", "bar", "foo"]);
+    /// ```
+    #[inline]
+    pub fn rsplit<P: crate::pattern::Pattern>(
+        &self,
+        pattern: P,
+    ) -> crate::iter::RSplit<'_, P> {
+        crate::iter::RSplit::new(self.byte_slice(..), pattern)
+    }
+
+    /// Returns an iterator over at most `n` sub-slices of the `Rope`
+    /// separated by `pattern`, starting from the end.
+    ///
+    /// The last sub-slice yielded contains all the text before it, including
+    /// any further occurrences of `pattern`. This is handy to get the last
+    /// field of a line without splitting all the ones before it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("path/to/some/file.rs");
+    ///
+    /// let mut fields = r.rsplitn(2, "/");
+    ///
+    /// assert_eq!(fields.next().unwrap(), "file.rs");
+    /// assert_eq!(fields.next().unwrap(), "path/to/some");
+    /// assert_eq!(fields.next(), None);
+    /// ```
+    #[inline]
+    pub fn rsplitn<P: crate::pattern::Pattern>(
+        &self,
+        n: usize,
+        pattern: P,
+    ) -> crate::iter::RSplitN<'_, P> {
+        crate::iter::RSplitN::new(self.byte_slice(..), n, pattern)
+    }
+
+    /// Returns an iterator over the sentences of this `Rope`, as defined by
+    /// [Unicode Standard Annex #29][uax29].
+    ///
+    /// Each sentence includes the whitespace and line breaks following it, so
+    /// concatenating all the items gives back the original text.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Sentence_Boundaries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Pi is about 3.14. Is it?\nYes.");
+    ///
+    /// let sentences = r.sentences().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(sentences, ["Pi is about 3.14. ", "Is it?\n", "Yes."]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn sentences(&self) -> crate::iter::Sentences<'_> {
+        crate::iter::Sentences::from(self)
+    }
+
+    /// Sets whether the `Rope` has a UTF-8 byte order mark, which
+    /// [`write_to()`](Self::write_to()) writes before the text. See
+    /// [`has_bom()`](Self::has_bom()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("foo");
+    /// r.set_bom(true);
+    ///
+    /// let mut buf = Vec::new();
+    /// r.write_to(&mut buf)?;
+    ///
+    /// assert_eq!(buf, b"\xEF\xBB\xBFfoo");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[inline]
+    pub fn set_bom(&mut self, has_bom: bool) {
+        self.has_bom = has_bom;
+    }
+
+    /// Limits the depth of the B-tree backing the `Rope`, or removes the
+    /// limit if `depth_limit` is `None`.
+    ///
+    /// Every point edit has to traverse the whole height of the tree, so a
+    /// depth limit puts a hard bound on its worst-case latency. Since the
+    /// size of the leaves and the number of children of the internal nodes
+    /// are fixed at compile time, the limit is enforced by capping the byte
+    /// length of the `Rope` at [`max_byte_len(depth)`](Self::max_byte_len()):
+    /// any edit that would make it longer panics, and the `try_*` methods
+    /// return [`Error::DepthLimitExceeded`](crate::Error::DepthLimitExceeded)
+    /// instead. Edits made of several replacements, like
+    /// [`apply_edits()`](Self::apply_edits()) or
+    /// [`replace_all()`](Self::replace_all()), check their final length
+    /// before modifying anything.
+    ///
+    /// The limit doesn't make the leaves bigger to keep the tree shallower.
+    /// A leaf is an inline buffer whose capacity is the same for every
+    /// `Rope`, so it can't grow at runtime, and bigger leaves would only move
+    /// the latency elsewhere since every edit shifts the bytes of the leaf
+    /// it lands in. Instead, pick the limit from the longest text the `Rope`
+    /// has to hold: every extra level multiplies
+    /// [`max_byte_len()`](Self::max_byte_len()) by the minimum number of
+    /// children of an internal node.
+    ///
+    /// The limit is kept by clones of the `Rope` and by the `Rope`s returned
+    /// by [`split_off()`](Self::split_off()).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `Rope` is already longer than the limit allows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Error, Rope};
+    /// #
+    /// let mut r = Rope::new();
+    /// r.set_depth_limit(Some(0));
+    ///
+    /// let max_byte_len = Rope::max_byte_len(0);
+    /// let text = "a".repeat(max_byte_len + 1);
+    ///
+    /// assert_eq!(
+    ///     r.try_insert(0, &text),
+    ///     Err(Error::DepthLimitExceeded {
+    ///         byte_len: max_byte_len + 1,
+    ///         max_byte_len,
+    ///     })
+    /// );
+    ///
+    /// r.insert(0, &text[1..]);
+    /// assert_eq!(r.byte_len(), max_byte_len);
+    /// assert_eq!(r.depth(), 0);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn set_depth_limit(&mut self, depth_limit: Option<usize>) {
+        if let Some(depth) = depth_limit {
+            let max_byte_len = Self::max_byte_len(depth);
+
+            if self.byte_len() > max_byte_len {
+                panic::depth_limit_exceeded(self.byte_len(), max_byte_len);
+            }
+        }
+
+        self.depth_limit = depth_limit;
+    }
+
+    /// Repacks the text of the `Rope` into as few chunks as possible.
+    ///
+    /// After many deletions most of the chunks of a `Rope` can end up being
+    /// only partially filled, and the memory they take up is never given back
+    /// while the `Rope` is being edited. This method rebuilds the B-tree from
+    /// scratch by filling up each chunk before moving on to the next one,
+    /// which frees the space wasted by the gaps of the old chunks and reduces
+    /// the number of nodes needed to hold them.
+    ///
+    /// The chunks themselves have a fixed capacity, so the space left in the
+    /// last one can't be reclaimed. Also note that the new `Rope` doesn't
+    /// share any of its nodes with its clones, and that inserting text into a
+    /// full chunk has to split it in two.
+    ///
+    /// If the text is already stored in the fewest possible chunks this
+    /// doesn't do anything, otherwise it runs in O(n) in the length of the
+    /// `Rope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello World!\n".repeat(1000));
+    ///
+    /// for line_index in (0..r.line_len()).rev().step_by(2) {
+    ///     let start = r.byte_of_line(line_index);
+    ///     r.delete(start..start + "Hello ".len());
+    /// }
+    ///
+    /// let before = r.mem_usage();
+    ///
+    /// r.shrink_to_fit();
+    ///
+    /// let after = r.mem_usage();
+    ///
+    /// assert!(after.total_bytes() < before.total_bytes());
+    /// assert_eq!(after.text_bytes, before.text_bytes);
+    /// ```
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        let min_leaf_count = self.byte_len().div_ceil(CHUNK_MAX_BYTES).max(1);
+
+        if self.tree.leaf_count() <= min_leaf_count {
+            return;
+        }
+
+        let mut builder = crate::RopeBuilder::new();
+
+        for chunk in self.chunks() {
+            builder.append(chunk);
+        }
+
+        let has_bom = self.has_bom;
+        let depth_limit = self.depth_limit;
+        *self = builder.build();
+        self.has_bom = has_bom;
+        self.depth_limit = depth_limit;
+    }
+
+    /// Returns an immutable [`RopeSnapshot`](crate::RopeSnapshot) of the
+    /// current contents of the `Rope`, which can be sent to other threads.
+    ///
+    /// This is just a cheap clone of the `Rope`, so it's O(1), and later
+    /// edits to the `Rope` don't affect the snapshot. See the
+    /// [`RopeSnapshot`](crate::RopeSnapshot) docs for more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello World!");
+    ///
+    /// let snapshot = r.snapshot();
+    /// r.replace(6..11, "Earth");
+    ///
+    /// assert_eq!(r, "Hello Earth!");
+    /// assert_eq!(snapshot.as_slice(), "Hello World!");
+    /// ```
+    #[inline]
+    pub fn snapshot(&self) -> super::RopeSnapshot {
+        super::RopeSnapshot::new(self)
+    }
+
+    /// Returns an iterator over the sub-slices of the `Rope` separated by
+    /// `pattern`, which can be any [`Pattern`](crate::pattern::Pattern).
+    ///
+    /// Like [`str::split()`], this yields an empty sub-slice if the `Rope`
+    /// starts or ends with `pattern` or if two matches of it are adjacent.
+    /// A pattern that matches the empty string matches at every char
+    /// boundary, except right after the end of another match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo, bar, , baz");
+    ///
+    /// let fields = r.split(", ").collect::<Vec<_>>();
+    ///
+    /// assert_eq!(fields, ["foo", "bar", "", "baz"]);
+    /// ```
+    #[inline]
+    pub fn split<P: crate::pattern::Pattern>(
+        &self,
+        pattern: P,
+    ) -> crate::iter::Split<'_, P> {
+        crate::iter::Split::new(self.byte_slice(..), pattern)
+    }
+
+    /// Splits the `Rope` in two at the given byte offset, returning the text
+    /// after it and leaving the text before it in `self`.
+    ///
+    /// The returned `Rope` shares all the nodes of the B-tree lying entirely
+    /// after the byte offset, and the ones lying entirely before it are kept
+    /// in `self`, so only the O(log n) nodes on the path to the split point
+    /// have to be rebuilt.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello World!");
+    ///
+    /// let world = r.split_off(6);
+    ///
+    /// assert_eq!(r, "Hello ");
+    /// assert_eq!(world, "World!");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn split_off(&mut self, byte_offset: usize) -> Self {
+        let mut split = Self::from(self.byte_slice(byte_offset..));
+        split.depth_limit = self.depth_limit;
+        self.truncate(byte_offset);
+        split
+    }
+
+    /// Returns an iterator over at most `n` sub-slices of the `Rope`
+    /// separated by `pattern`.
+    ///
+    /// The last sub-slice yielded contains all the text after it, including
+    /// any further occurrences of `pattern`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("key=value=with=equals");
+    ///
+    /// let fields = r.splitn(2, "=").collect::<Vec<_>>();
+    ///
+    /// assert_eq!(fields, ["key", "value=with=equals"]);
+    ///
+    /// assert_eq!(r.splitn(0, "=").count(), 0);
+    /// ```
+    #[inline]
+    pub fn splitn<P: crate::pattern::Pattern>(
+        &self,
+        n: usize,
+        pattern: P,
+    ) -> crate::iter::SplitN<'_, P> {
+        crate::iter::SplitN::new(self.byte_slice(..), n, pattern)
+    }
+
+    /// Returns whether the `Rope` starts with the given
+    /// [`Pattern`](crate::pattern::Pattern).
+    ///
+    /// The pattern is compared against the chunks at the start of the `Rope`
+    /// from front to back, so this only reads as many bytes as the pattern
+    /// is long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎");
+    ///
+    /// assert!(r.starts_with('H'));
+    /// assert!(r.starts_with("Hello "));
+    /// assert!(r.starts_with(r.byte_slice(..5)));
+    /// assert!(!r.starts_with("Earth"));
+    /// ```
+    #[inline]
+    pub fn starts_with<P: crate::pattern::Pattern>(
+        &self,
+        mut pattern: P,
+    ) -> bool {
+        pattern.prefix_len(self.byte_slice(..)).is_some()
+    }
+
+    /// Returns a description of the B-tree backing the `Rope` in the
+    /// [DOT](https://graphviz.org/doc/info/lang.html) language, which can be
+    /// rendered with [Graphviz](https://graphviz.org) to see its shape.
+    ///
+    /// Every node is labelled with its summary, and every leaf also shows a
+    /// preview of the text before and after its gap together with the size
+    /// of the gap. This is only meant to help debug the shape of the tree
+    /// after a sequence of edits, and the exact format of the output may
+    /// change between releases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello World!\n".repeat(1000));
+    ///
+    /// let dot = r.to_dot();
+    ///
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert!(dot.ends_with("}\n"));
+    ///
+    /// // Every node except the root has an edge coming from its parent.
+    /// let edges = dot.lines().filter(|line| line.contains("->")).count();
+    /// assert_eq!(edges, r.tree_stats().node_count - 1);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "debug-viz")))]
+    #[cfg(feature = "debug-viz")]
+    #[inline]
+    pub fn to_dot(&self) -> String {
+        /// Returns the first few chars of the chunk, so that the labels don't
+        /// get too big.
+        fn preview(chunk: &str) -> String {
+            match chunk.char_indices().nth(16) {
+                Some((idx, _)) => alloc::format!("{}…", &chunk[..idx]),
+                None => String::from(chunk),
+            }
+        }
+
+        self.tree.to_dot(|leaf| {
+            alloc::format!(
+                "{:?} [gap: {}] {:?}",
+                preview(leaf.left_chunk()),
+                leaf.len_gap(),
+                preview(leaf.right_chunk()),
+            )
+        })
+    }
+
+    /// Returns a new `Rope` with the lowercase equivalent of the text of this
+    /// `Rope`, as defined by the Unicode `Lowercase` derived property.
+    ///
+    /// This gives the same result as [`str::to_lowercase()`], including the
+    /// context-dependent mapping of `'Σ'` at the end of a word, but the
+    /// converted text is appended to the new `Rope` a chunk at a time instead
+    /// of being collected into a `String` first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("HELLO ὈΔΥΣΣΕΎΣ!");
+    ///
+    /// assert_eq!(r.to_lowercase(), "hello ὀδυσσεύς!");
+    /// ```
+    #[inline]
+    pub fn to_lowercase(&self) -> Rope {
+        super::case::to_lowercase(self.byte_slice(..))
+    }
+
+    /// Returns a new `Rope` with the text of this `Rope` in Unicode
+    /// Normalization Form C, where characters are canonically decomposed and
+    /// then recomposed (e.g. `"e\u{301}"` becomes `"\u{e9}"`).
+    ///
+    /// Two texts that only differ in how their characters are composed are
+    /// only equal once they're normalized to the same form, so this should be
+    /// used before comparing or searching text coming from different
+    /// sources. The normalized text is appended to the new `Rope` as it's
+    /// produced, without being collected into a `String` first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("cafe\u{301}");
+    ///
+    /// assert_ne!(r, "caf\u{e9}");
+    /// assert_eq!(r.to_nfc(), "caf\u{e9}");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-normalization")))]
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    pub fn to_nfc(&self) -> Rope {
+        super::normalization::to_nfc(self.byte_slice(..))
+    }
+
+    /// Returns a new `Rope` with the text of this `Rope` in Unicode
+    /// Normalization Form D, where characters are canonically decomposed
+    /// (e.g. `"\u{e9}"` becomes `"e\u{301}"`).
+    ///
+    /// See [`to_nfc()`](Self::to_nfc()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("caf\u{e9}");
+    ///
+    /// assert_eq!(r.to_nfd(), "cafe\u{301}");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-normalization")))]
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    pub fn to_nfd(&self) -> Rope {
+        super::normalization::to_nfd(self.byte_slice(..))
+    }
+
+    /// Returns a new `Rope` with the uppercase equivalent of the text of this
+    /// `Rope`, as defined by the Unicode `Uppercase` derived property.
+    ///
+    /// Like [`str::to_uppercase()`], this handles chars whose uppercase
+    /// equivalent is made up of more than one char, so the new `Rope` can be
+    /// longer than this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Straße");
+    ///
+    /// assert_eq!(r.to_uppercase(), "STRASSE");
+    /// ```
+    #[inline]
+    pub fn to_uppercase(&self) -> Rope {
+        super::case::to_uppercase(self.byte_slice(..))
+    }
+
+    /// Returns statistics about the shape of the B-tree backing the `Rope`,
+    /// like its depth, its number of nodes and how full its leaves are.
+    ///
+    /// This is meant for diagnostics, e.g. to check how fragmented a `Rope`
+    /// has become after a long sequence of edits and whether calling
+    /// [`shrink_to_fit()`](Self::shrink_to_fit()) would help. See
+    /// [`TreeStats`](crate::TreeStats) for more.
+    ///
+    /// This runs in O(n) in the number of internal nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello World!");
+    ///
+    /// let stats = r.tree_stats();
+    ///
+    /// assert!(stats.depth <= Rope::max_depth(r.byte_len()));
+    /// assert!(stats.leaf_count >= 1);
+    /// assert!(stats.node_count >= stats.leaf_count);
+    /// assert!(stats.leaf_count * stats.leaf_capacity >= r.byte_len());
+    /// assert!(stats.avg_leaf_fill > 0.0 && stats.avg_leaf_fill <= 1.0);
+    /// ```
+    #[inline]
+    pub fn tree_stats(&self) -> crate::TreeStats {
+        super::tree_stats::tree_stats(self)
+    }
+
+    /// Shortens the `Rope`, keeping the text before the given byte offset and
+    /// dropping the rest.
+    ///
+    /// This is equivalent to calling [`delete()`](Self::delete()) with
+    /// `byte_offset..`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello World!");
+    ///
+    /// r.truncate(5);
+    /// assert_eq!(r, "Hello");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn truncate(&mut self, byte_offset: usize) {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        self.delete(byte_offset..);
+    }
+
+    /// Non-panicking version of [`byte_slice()`](Self::byte_slice()).
+    ///
+    /// Returns an [`Error`](crate::Error) if the start or the end of the byte
+    /// range don't lie on a code point boundary, if the start is greater than
+    /// the end or if the end is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Error, Rope};
+    /// #
+    /// let r = Rope::from("🗻∈🌏");
+    ///
+    /// assert_eq!(r.try_byte_slice(4..7).unwrap(), "∈");
+    ///
+    /// assert_eq!(
+    ///     r.try_byte_slice(..5),
+    ///     Err(Error::ByteOffsetNotCharBoundary { byte_offset: 5 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_byte_slice<R>(
+        &self,
+        byte_range: R,
+    ) -> Result<RopeSlice<'_>, Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = check_byte_range(self.byte_slice(..), byte_range)?;
+        Ok(self.byte_slice(start..end))
+    }
+
+    /// Non-panicking version of [`delete()`](Self::delete()).
+    ///
+    /// Returns an [`Error`](crate::Error) if the start or the end of the byte
+    /// range don't lie on a code point boundary, if the start is greater than
+    /// the end or if the end is out of bounds. The `Rope` is left unchanged
+    /// in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Error, Rope};
+    /// #
+    /// let mut r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// assert!(r.try_delete(11..16).is_ok());
+    /// assert_eq!(r, "Hello Earth!");
+    ///
+    /// assert_eq!(
+    ///     r.try_delete(6..20),
+    ///     Err(Error::ByteOffsetOutOfBounds { byte_offset: 20, byte_len: 12 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_delete<R>(&mut self, byte_range: R) -> Result<Change, Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_replace(byte_range, "")
+    }
+
+    /// Creates a new `Rope` from a vector of bytes, checking that they're
+    /// valid UTF-8.
+    ///
+    /// This is the owned counterpart of `Rope::try_from(&[u8])`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`Utf8Error`](crate::Utf8Error) carrying the byte offset
+    /// and the length of the first invalid sequence if the bytes aren't
+    /// valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::try_from_utf8(b"Hello World!".to_vec()).unwrap();
+    /// assert_eq!(r, "Hello World!");
+    ///
+    /// let err =
+    ///     Rope::try_from_utf8(b"Hello \xF0\x9F World!".to_vec()).unwrap_err();
+    ///
+    /// assert_eq!(err.byte_offset(), 6);
+    /// assert_eq!(err.invalid_len(), Some(2));
+    /// ```
+    #[inline]
+    pub fn try_from_utf8(
+        bytes: alloc::vec::Vec<u8>,
+    ) -> Result<Self, Utf8Error> {
+        String::from_utf8(bytes)
+            .map(Self::from)
+            .map_err(|err| Utf8Error::new(err.utf8_error()))
+    }
+
+    /// Non-panicking version of [`insert()`](Self::insert()).
+    ///
+    /// Returns an [`Error`](crate::Error) if the byte offset doesn't lie on a
+    /// code point boundary or if it's out of bounds. The `Rope` is left
+    /// unchanged in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Error, Rope};
+    /// #
+    /// let mut r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// assert!(r.try_insert(5, ",").is_ok());
+    /// assert_eq!(r, "Hello, Earth 🌎!");
+    ///
+    /// assert_eq!(
+    ///     r.try_insert(14, "!"),
+    ///     Err(Error::ByteOffsetNotCharBoundary { byte_offset: 14 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_insert<T>(
+        &mut self,
+        byte_offset: usize,
+        text: T,
+    ) -> Result<Change, Error>
+    where
+        T: AsRef<str>,
+    {
+        self.try_replace(byte_offset..byte_offset, text)
+    }
+
+    /// Non-panicking version of [`line()`](Self::line()).
+    ///
+    /// Returns an [`Error`](crate::Error) if the line index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Error, Rope};
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// assert_eq!(r.try_line(1).unwrap(), "bar");
+    ///
+    /// assert_eq!(
+    ///     r.try_line(2),
+    ///     Err(Error::LineIndexOutOfBounds { line_index: 2, line_len: 2 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_line(&self, line_index: usize) -> Result<RopeSlice<'_>, Error> {
+        check_line_index(self.byte_slice(..), line_index)?;
+        Ok(self.line(line_index))
+    }
+
+    /// Non-panicking version of [`line_slice()`](Self::line_slice()).
+    ///
+    /// Returns an [`Error`](crate::Error) if the start is greater than the
+    /// end or if the end is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Error, Rope};
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    ///
+    /// assert_eq!(r.try_line_slice(1..).unwrap(), "bar\r\nbaz\n");
+    ///
+    /// assert_eq!(
+    ///     r.try_line_slice(2..1),
+    ///     Err(Error::LineStartAfterEnd { line_start: 2, line_end: 1 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_line_slice<R>(
+        &self,
+        line_range: R,
+    ) -> Result<RopeSlice<'_>, Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = check_line_range(self.byte_slice(..), line_range)?;
+        Ok(self.line_slice(start..end))
+    }
+
+    /// Non-panicking version of [`replace()`](Self::replace()).
+    ///
+    /// Returns an [`Error`](crate::Error) if the start or the end of the byte
+    /// range don't lie on a code point boundary, if the start is greater than
+    /// the end or if the end is out of bounds. The `Rope` is left unchanged
+    /// in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Error, Rope};
+    /// #
+    /// let mut r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// assert!(r.try_replace(6..16, "Saturn 🪐").is_ok());
+    /// assert_eq!(r, "Hello Saturn 🪐!");
+    ///
+    /// assert_eq!(
+    ///     r.try_replace(7..6, ""),
+    ///     Err(Error::ByteStartAfterEnd { byte_start: 7, byte_end: 6 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_replace<R, T>(
+        &mut self,
+        byte_range: R,
+        text: T,
+    ) -> Result<Change, Error>
+    where
+        R: RangeBounds<usize>,
+        T: AsRef<str>,
+    {
+        let (start, end) = check_byte_range(self.byte_slice(..), byte_range)?;
+
+        let text = text.as_ref();
+
+        let new_len = self.byte_len() - (end - start) + text.len();
+
+        if let Some(max_byte_len) = self.max_byte_len_for_limit() {
+            if new_len > max_byte_len {
+                return Err(Error::DepthLimitExceeded {
+                    byte_len: new_len,
+                    max_byte_len,
+                });
+            }
+        }
+
+        Ok(self.replace(start..end, text))
+    }
+
+    /// Returns the number of lines in the `Rope` when any of the mandatory
+    /// line breaks of [UAX #14](https://www.unicode.org/reports/tr14/) (LF,
+    /// VT, FF, CR, CRLF, NEL, LS and PS) is treated as a line terminator.
+    ///
+    /// Unlike [`line_len()`](Self::line_len()), which only considers `\n`
+    /// and reads the line count from the B-tree, this has to scan the whole
+    /// text, so it runs in O(n).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\rbar\r\nbaz\u{2028}");
+    ///
+    /// assert_eq!(r.line_len(), 2);
+    /// assert_eq!(r.unicode_line_len(), 3);
+    /// ```
+    #[inline]
+    pub fn unicode_line_len(&self) -> usize {
+        super::line_ending::unicode_line_len(self.byte_slice(..))
+    }
+
+    /// Returns an iterator over the lines of this `Rope`, not including the
+    /// line terminators, when any of the mandatory line breaks of
+    /// [UAX #14](https://www.unicode.org/reports/tr14/) (LF, VT, FF, CR,
+    /// CRLF, NEL, LS and PS) is treated as a line terminator.
+    ///
+    /// Like with [`lines()`](Self::lines()), the final line break is
+    /// optional and doesn't cause the iterator to return a final empty line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\rbar\r\nbaz\u{000C}\u{2029}");
+    ///
+    /// let mut lines = r.unicode_lines();
+    ///
+    /// assert_eq!("foo", lines.next().unwrap());
+    /// assert_eq!("bar", lines.next().unwrap());
+    /// assert_eq!("baz", lines.next().unwrap());
+    /// assert_eq!("", lines.next().unwrap());
+    /// assert_eq!(None, lines.next());
+    /// ```
+    #[inline]
+    pub fn unicode_lines(&self) -> crate::iter::UnicodeLines<'_> {
+        crate::iter::UnicodeLines::from(self)
+    }
+
+    /// Returns the number of UTF-16 code units the `Rope` would have if it
+    /// stored its text as UTF-16 instead of UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // The "🐸" emoji is encoded using two UTF-16 code units.
+    /// let r = Rope::from("abc🐸");
+    /// assert_eq!(r.utf16_len(), 5);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn utf16_len(&self) -> usize {
+        self.tree.summary().utf16_code_units()
+    }
+
+    /// Returns the UTF-16 code unit offset corresponding to the given byte
+    /// offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // The "𐐀" character is encoded using two code units in UTF-16 and
+    /// // four bytes in UTF-8.
+    /// let r = Rope::from("a𐐀b");
+    /// assert_eq!(r.utf16_code_unit_of_byte(5), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn utf16_code_unit_of_byte(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let super::metrics::Utf16Metric(utf16_offset) =
+            self.tree.convert_measure(ByteMetric(byte_offset));
+
+        utf16_offset
+    }
+
+    /// Returns an immutable slice of the `Rope` in the specified UTF-16 code
+    /// unit range, where the start and end of the range are interpreted as
+    /// offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end, if the end is out of
+    /// bounds (i.e. greater than [`utf16_len()`](Self::utf16_len())) or if
+    /// either of them doesn't lie on a code point boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // Both "𐐀" and "🐸" are encoded using two code units in UTF-16.
+    /// let r = Rope::from("ab𐐀de🐸");
+    ///
+    /// assert_eq!(r.utf16_slice(..4), "ab𐐀");
+    /// assert_eq!(r.utf16_slice(5..), "e🐸");
+    /// assert_eq!(r.utf16_slice(2..4), "𐐀");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn utf16_slice<R>(&self, utf16_range: R) -> RopeSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        use super::metrics::Utf16Metric;
+
+        let (start, end) =
+            range_bounds_to_start_end(utf16_range, 0, self.utf16_len());
+
+        if start > end {
+            panic::utf16_start_after_end(start, end);
+        }
+
+        if end > self.utf16_len() {
+            panic::utf16_offset_out_of_bounds(end, self.utf16_len());
+        }
+
+        self.tree.slice(Utf16Metric(start)..Utf16Metric(end)).into()
+    }
+
+    /// Checks that all the internal invariants of the `Rope` hold, panicking
+    /// if they don't.
+    ///
+    /// This walks the whole B-tree, checking that its nodes are balanced,
+    /// that the summaries stored in them match their contents, that every
+    /// chunk is filled to at least its minimum size and that every gap
+    /// buffer is well formed. It's meant to be called by fuzzers and
+    /// property tests after every operation to catch any corruption as soon
+    /// as it happens.
+    ///
+    /// This runs in O(n) in the length of the `Rope`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the invariants doesn't hold, which would always be a
+    /// bug in crop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello World!");
+    ///
+    /// r.insert(5, ",");
+    /// r.validate();
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "validate")))]
+    #[cfg(feature = "validate")]
+    #[track_caller]
+    #[inline]
+    pub fn validate(&self) {
+        self.assert_invariants();
+    }
+
+    /// Returns the visual column of the given byte offset in its line, with
+    /// tabs expanding to the next multiple of `tab_width` columns.
+    ///
+    /// The width of every other char is measured with the
+    /// [`unicode-width`](https://docs.rs/unicode-width) crate. The column is
+    /// measured by scanning the line the byte offset is on, so this runs in
+    /// O(log n + line length).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\na\t日b\n");
+    ///
+    /// // The tab expands to the next tab stop and "日" is two columns wide.
+    /// assert_eq!(r.visual_col_of_byte(6, 4), 4);
+    /// assert_eq!(r.visual_col_of_byte(10, 4), 7);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "display-width")))]
+    #[cfg(feature = "display-width")]
+    #[track_caller]
+    #[inline]
+    pub fn visual_col_of_byte(
+        &self,
+        byte_offset: usize,
+        tab_width: usize,
+    ) -> usize {
+        super::position::visual_col_of_byte(
+            self.byte_slice(..),
+            byte_offset,
+            tab_width,
+        )
+    }
+
+    /// Returns the number of terminal columns this `Rope` would take up if
+    /// it was displayed on a single row.
+    ///
+    /// This is the sum of the widths of all its chars as given by the
+    /// [`unicode-width`](https://docs.rs/unicode-width) crate, with control
+    /// characters like tabs and line breaks always having a width of zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a日b");
+    /// assert_eq!(r.width(), 4);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "width-metric")))]
+    #[cfg(feature = "width-metric")]
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.tree.summary().width()
+    }
+
+    /// Returns the width offset corresponding to the given byte offset, i.e.
+    /// the number of terminal columns taken up by the text before it. See
+    /// [`width()`](Self::width()) for how the width is computed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a日b");
+    /// assert_eq!(r.width_of_byte(4), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "width-metric")))]
+    #[cfg(feature = "width-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn width_of_byte(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let super::metrics::WidthMetric(width_offset) =
+            self.tree.convert_measure(ByteMetric(byte_offset));
+
+        width_offset
+    }
+
+    /// Returns an iterator over the word boundaries of this `Rope`, as
+    /// defined by [Unicode Standard Annex #29][uax29].
+    ///
+    /// Every word, run of whitespace and punctuation mark is yielded, so
+    /// concatenating all the items gives back the original text. Use
+    /// [`words()`](Self::words()) to only get the words.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Can't stop, won't stop!");
+    ///
+    /// let bounds = r.word_bounds().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     bounds,
+    ///     ["Can't", " ", "stop", ",", " ", "won't", " ", "stop", "!"]
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn word_bounds(&self) -> crate::iter::WordBounds<'_> {
+        crate::iter::WordBounds::from(self)
+    }
+
+    /// Returns the number of whitespace-delimited words in the `Rope`, i.e.
+    /// the number of items [`str::split_whitespace()`] would yield on its
+    /// text.
+    ///
+    /// This runs in O(1) since every node of the B-tree keeps track of the
+    /// words it contains, including the ones spanning the boundaries between
+    /// its chunks.
+    ///
+    /// Note that unlike [`words()`](Self::words()) this doesn't follow the
+    /// Unicode word boundaries, so punctuation attached to a word is part of
+    /// it and punctuation surrounded by whitespace is counted as a word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Can't stop, won't stop!");
+    /// assert_eq!(r.word_len(), 4);
+    ///
+    /// r.insert(5, " -");
+    /// assert_eq!(r.word_len(), 5);
+    ///
+    /// r.delete(4..r.byte_len() - 5);
+    /// assert_eq!(r.word_len(), 1);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "word-count")))]
+    #[cfg(feature = "word-count")]
+    #[inline]
+    pub fn word_len(&self) -> usize {
+        self.tree.aggregate().word_count.words()
+    }
+
+    /// Returns an iterator over the words of this `Rope`, as defined by
+    /// [Unicode Standard Annex #29][uax29].
+    ///
+    /// This is the same as [`word_bounds()`](Self::word_bounds()) except
+    /// that the items not containing any alphanumeric characters, like
+    /// whitespace and punctuation, are skipped.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Can't stop, won't stop!");
+    ///
+    /// let words = r.words().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(words, ["Can't", "stop", "won't", "stop"]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn words(&self) -> crate::iter::Words<'_> {
+        crate::iter::Words::from(self)
+    }
+
+    /// Returns an iterator over the byte offsets where the visual rows of the
+    /// lines in the specified line range start when they're soft-wrapped to
+    /// fit in `width` columns.
+    ///
+    /// The first row of every line starts at the beginning of the line, so
+    /// the iterator yields at least one offset per line. Lines are broken
+    /// after the last whitespace that fits in a row, or in the middle of a
+    /// word if that word doesn't fit in a row on its own. Whitespace is
+    /// allowed to hang past the end of a row.
+    ///
+    /// The width of every char is measured with the
+    /// [`unicode-width`](https://docs.rs/unicode-width) crate, while tabs
+    /// expand to the next multiple of `tab_width` columns from the start of
+    /// the row they're in.
     ///
     /// # Panics
     ///
-    /// Panics if the start or the end of the byte range don't lie on a code
-    /// point boundary, if the start is greater than the end or if the end is
-    /// out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    /// Panics if `width` is zero, if the start is greater than the end or if
+    /// the end is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let mut r = Rope::from("Hello Earth 🌎!");
+    /// let r = Rope::from("Hello world, it's a long line\nshort\n");
     ///
-    /// r.replace(6..16, "Saturn 🪐");
-    /// assert_eq!(r, "Hello Saturn 🪐!");
+    /// let rows = r
+    ///     .wrap_points(.., 12, 4)
+    ///     .map(|offset| r.byte(offset) as char)
+    ///     .collect::<String>();
+    ///
+    /// assert_eq!(rows, "Hils");
     /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "display-width")))]
+    #[cfg(feature = "display-width")]
     #[track_caller]
     #[inline]
-    pub fn replace<R, T>(&mut self, byte_range: R, text: T)
+    pub fn wrap_points<R>(
+        &self,
+        line_range: R,
+        width: usize,
+        tab_width: usize,
+    ) -> crate::iter::WrapPoints<'_>
     where
         R: RangeBounds<usize>,
-        T: AsRef<str>,
     {
         let (start, end) =
-            range_bounds_to_start_end(byte_range, 0, self.byte_len());
-
-        if start > end {
-            panic::byte_start_after_end(start, end);
-        }
-
-        if end > self.byte_len() {
-            panic::byte_offset_out_of_bounds(end, self.line_len());
-        }
-
-        let text = text.as_ref();
-
-        let mut update_trailing = false;
+            range_bounds_to_start_end(line_range, 0, self.line_len());
 
-        if end == self.byte_len() {
-            if !text.is_empty() {
-                self.has_trailing_newline = text.ends_with('\n');
-            } else if start == 0 {
-                self.has_trailing_newline = false;
-            } else {
-                update_trailing = true;
-            }
-        }
+        let lines = self.line_slice(start..end);
 
-        self.tree.replace(ByteMetric(start)..ByteMetric(end), text);
+        let byte_offset = self.byte_of_line(start);
 
-        if update_trailing {
-            self.has_trailing_newline =
-                self.chunks().next_back().unwrap().ends_with('\n');
-        }
+        crate::iter::WrapPoints::new(lines, byte_offset, width, tab_width)
     }
 
-    /// Returns the number of UTF-16 code units the `Rope` would have if it
-    /// stored its text as UTF-16 instead of UTF-8.
-    ///
-    /// # Examples
+    /// Writes the contents of the `Rope` to `writer`, one chunk at a time.
     ///
-    /// ```
-    /// # use crop::Rope;
-    /// #
-    /// // The "🐸" emoji is encoded using two UTF-16 code units.
-    /// let r = Rope::from("abc🐸");
-    /// assert_eq!(r.utf16_len(), 5);
-    /// ```
-    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
-    #[cfg(feature = "utf16-metric")]
-    #[inline]
-    pub fn utf16_len(&self) -> usize {
-        self.tree.summary().utf16_code_units()
-    }
-
-    /// Returns the UTF-16 code unit offset corresponding to the given byte
-    /// offset.
+    /// Unlike going through [`to_string()`](ToString::to_string()), this
+    /// doesn't allocate. If the `Rope` [has a byte order
+    /// mark](Self::has_bom()) it's written before the text.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if the byte offset is out of bounds (i.e. greater than
-    /// [`byte_len()`](Self::byte_len())).
+    /// Returns the first error returned by `writer`, if any.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// // The "𐐀" character is encoded using two code units in UTF-16 and
-    /// // four bytes in UTF-8.
-    /// let r = Rope::from("a𐐀b");
-    /// assert_eq!(r.utf16_code_unit_of_byte(5), 3);
+    /// let r = Rope::from("ƒoo\nbär\r\nbaz");
+    ///
+    /// let mut buf = Vec::new();
+    /// r.write_to(&mut buf)?;
+    ///
+    /// assert_eq!(buf, "ƒoo\nbär\r\nbaz".as_bytes());
+    /// # Ok::<(), std::io::Error>(())
     /// ```
-    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
-    #[cfg(feature = "utf16-metric")]
-    #[track_caller]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
     #[inline]
-    pub fn utf16_code_unit_of_byte(&self, byte_offset: usize) -> usize {
-        if byte_offset > self.byte_len() {
-            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+    pub fn write_to<W>(&self, mut writer: W) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        if self.has_bom {
+            writer.write_all("\u{FEFF}".as_bytes())?;
         }
-
-        let super::metrics::Utf16Metric(utf16_offset) =
-            self.tree.convert_measure(ByteMetric(byte_offset));
-
-        utf16_offset
+        for chunk in self.chunks() {
+            writer.write_all(chunk.as_bytes())?;
+        }
+        Ok(())
     }
 
-    /// Returns an immutable slice of the `Rope` in the specified UTF-16 code
-    /// unit range, where the start and end of the range are interpreted as
-    /// offsets.
+    /// Writes the leaves of the `Rope` to `writer` together with their
+    /// summaries, so that it can be loaded back with
+    /// [`read_tree()`](Self::read_tree()) without having to re-segment its
+    /// text and rebuild the tree.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if the start is greater than the end or if the end is out of
-    /// bounds (i.e. greater than [`utf16_len()`](Self::utf16_len())).
+    /// Returns the first error returned by `writer`, if any.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// // Both "𐐀" and "🐸" are encoded using two code units in UTF-16.
-    /// let r = Rope::from("ab𐐀de🐸");
+    /// let r = Rope::from("ƒoo\nbär\r\nbaz");
     ///
-    /// assert_eq!(r.utf16_slice(..4), "ab𐐀");
-    /// assert_eq!(r.utf16_slice(5..), "e🐸");
-    /// assert_eq!(r.utf16_slice(2..4), "𐐀");
+    /// let mut dump = Vec::new();
+    /// r.write_tree(&mut dump)?;
+    ///
+    /// let loaded = Rope::read_tree(dump.as_slice())?;
+    /// assert_eq!(loaded, r);
+    /// assert_eq!(loaded.line_len(), 3);
+    /// # Ok::<(), std::io::Error>(())
     /// ```
-    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
-    #[cfg(feature = "utf16-metric")]
-    #[track_caller]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
     #[inline]
-    pub fn utf16_slice<R>(&self, utf16_range: R) -> RopeSlice<'_>
+    pub fn write_tree<W>(&self, writer: W) -> std::io::Result<()>
     where
-        R: RangeBounds<usize>,
+        W: std::io::Write,
     {
-        use super::metrics::Utf16Metric;
-
-        let (start, end) =
-            range_bounds_to_start_end(utf16_range, 0, self.utf16_len());
-
-        if start > end {
-            panic::utf16_start_after_end(start, end);
-        }
-
-        if end > self.utf16_len() {
-            panic::utf16_offset_out_of_bounds(end, self.utf16_len());
-        }
-
-        self.tree.slice(Utf16Metric(start)..Utf16Metric(end)).into()
+        super::tree_dump::write_tree(self, writer)
     }
 }
 
@@ -823,6 +4133,8 @@ impl From<RopeSlice<'_>> for Rope {
     fn from(rope_slice: RopeSlice<'_>) -> Rope {
         Self {
             has_trailing_newline: rope_slice.has_trailing_newline,
+            has_bom: false,
+            depth_limit: None,
             tree: Tree::from(rope_slice.tree_slice),
         }
     }
@@ -847,11 +4159,176 @@ impl core::fmt::Display for Rope {
     }
 }
 
+/// Appends the formatted text at the end of the `Rope`, so that
+/// `write!(rope, ..)` can be used without going through a temporary `String`.
+impl core::fmt::Write for Rope {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.insert(self.byte_len(), s);
+        Ok(())
+    }
+}
+
+impl core::ops::AddAssign<&str> for Rope {
+    #[inline]
+    fn add_assign(&mut self, rhs: &str) {
+        self.insert(self.byte_len(), rhs);
+    }
+}
+
+/// Appends a clone of the other `Rope`, which shares all of its nodes. See
+/// the `AddAssign<Rope>` implementation for more.
+impl core::ops::AddAssign<&Rope> for Rope {
+    #[track_caller]
+    #[inline]
+    fn add_assign(&mut self, rhs: &Rope) {
+        *self += rhs.clone();
+    }
+}
+
+/// Concatenates two `Rope`s by joining their B-trees, which runs in
+/// O(log n) and reuses all the nodes of both trees except the ones along the
+/// seam.
+///
+/// The concatenated `Rope` keeps the BOM flag and the depth limit of the
+/// left-hand side, and panics if it would exceed the limit.
+impl core::ops::AddAssign<Rope> for Rope {
+    #[track_caller]
+    #[inline]
+    fn add_assign(&mut self, rhs: Rope) {
+        self.assert_within_depth_limit(self.byte_len() + rhs.byte_len());
+
+        if rhs.byte_len() > 0 {
+            self.has_trailing_newline = rhs.has_trailing_newline;
+        }
+
+        self.tree.append(rhs.tree);
+    }
+}
+
+impl core::ops::Add<&str> for Rope {
+    type Output = Rope;
+
+    #[inline]
+    fn add(mut self, rhs: &str) -> Rope {
+        self += rhs;
+        self
+    }
+}
+
+impl core::ops::Add<&Rope> for Rope {
+    type Output = Rope;
+
+    #[track_caller]
+    #[inline]
+    fn add(mut self, rhs: &Rope) -> Rope {
+        self += rhs;
+        self
+    }
+}
+
+impl core::ops::Add<Rope> for Rope {
+    type Output = Rope;
+
+    #[track_caller]
+    #[inline]
+    fn add(mut self, rhs: Rope) -> Rope {
+        self += rhs;
+        self
+    }
+}
+
+/// Appends the strings at the end of the `Rope`, buffering small ones so
+/// that they're inserted a chunk at a time.
+impl<'a> Extend<&'a str> for Rope {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut buffer = String::new();
+
+        for s in iter {
+            if buffer.len() + s.len() > CHUNK_MAX_BYTES {
+                self.insert(self.byte_len(), &buffer);
+                buffer.clear();
+            }
+
+            if s.len() > CHUNK_MAX_BYTES {
+                self.insert(self.byte_len(), s);
+            } else {
+                buffer.push_str(s);
+            }
+        }
+
+        if !buffer.is_empty() {
+            self.insert(self.byte_len(), &buffer);
+        }
+    }
+}
+
+impl Extend<char> for Rope {
+    #[inline]
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut buffer = String::new();
+
+        for ch in iter {
+            if buffer.len() + ch.len_utf8() > CHUNK_MAX_BYTES {
+                self.insert(self.byte_len(), &buffer);
+                buffer.clear();
+            }
+
+            buffer.push(ch);
+        }
+
+        if !buffer.is_empty() {
+            self.insert(self.byte_len(), &buffer);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Rope {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut builder = crate::RopeBuilder::new();
+
+        for s in iter {
+            builder.append(s);
+        }
+
+        builder.build()
+    }
+}
+
+impl FromIterator<char> for Rope {
+    #[inline]
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = char>,
+    {
+        let mut builder = crate::RopeBuilder::new();
+
+        for ch in iter {
+            builder.append(ch.encode_utf8(&mut [0; 4]));
+        }
+
+        builder.build()
+    }
+}
+
 impl From<&str> for Rope {
     #[inline]
     fn from(s: &str) -> Self {
         Rope {
             has_trailing_newline: s.ends_with('\n'),
+            has_bom: false,
+            depth_limit: None,
             tree: Tree::from_leaves(
                 RopeChunk::segmenter(s).map(RopeChunk::from),
             ),
@@ -885,12 +4362,42 @@ impl core::str::FromStr for Rope {
     }
 }
 
+impl TryFrom<&[u8]> for Rope {
+    type Error = Utf8Error;
+
+    #[inline]
+    fn try_from(bytes: &[u8]) -> Result<Self, Utf8Error> {
+        core::str::from_utf8(bytes).map(Self::from).map_err(Utf8Error::new)
+    }
+}
+
+/// Only the bytes between the longest prefix and suffix made of nodes shared
+/// by the two `Rope`s are compared, so comparing a `Rope` with a lightly
+/// edited clone of itself doesn't have to go through all of its text.
 impl core::cmp::PartialEq<Rope> for Rope {
     #[inline]
     fn eq(&self, rhs: &Rope) -> bool {
-        (self.byte_len() == rhs.byte_len())
-            && (self.line_len() == rhs.line_len())
-            && chunks_eq_chunks(self.chunks(), rhs.chunks())
+        if (self.byte_len() != rhs.byte_len())
+            || (self.line_len() != rhs.line_len())
+        {
+            return false;
+        }
+
+        let prefix = usize::from(self.tree.shared_prefix(&rhs.tree));
+
+        if prefix == self.byte_len() {
+            return true;
+        }
+
+        let suffix = usize::from(self.tree.shared_suffix(&rhs.tree))
+            .min(self.byte_len() - prefix);
+
+        let range = prefix..self.byte_len() - suffix;
+
+        chunks_eq_chunks(
+            self.byte_slice(range.clone()).chunks(),
+            rhs.byte_slice(range).chunks(),
+        )
     }
 }
 
@@ -899,7 +4406,8 @@ impl core::cmp::PartialEq<RopeSlice<'_>> for Rope {
     fn eq(&self, rhs: &RopeSlice<'_>) -> bool {
         (self.byte_len() == rhs.byte_len())
             && (self.line_len() == rhs.line_len())
-            && chunks_eq_chunks(self.chunks(), rhs.chunks())
+            && (self.byte_slice(..).tree_slice.ptr_eq(&rhs.tree_slice)
+                || chunks_eq_chunks(self.chunks(), rhs.chunks()))
     }
 }
 
@@ -961,12 +4469,66 @@ impl core::cmp::PartialEq<Rope> for alloc::borrow::Cow<'_, str> {
 
 impl core::cmp::Eq for Rope {}
 
-#[cfg(feature = "serde")]
-mod serde_impls {
-    use alloc::borrow::Cow;
+/// Hashes the text of the `Rope`, so two `Rope`s that are equal have the
+/// same hash no matter how their text is split into chunks.
+impl core::hash::Hash for Rope {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        hash_chunks(self.chunks(), state);
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl core::cmp::Ord for Rope {
+    #[inline]
+    fn cmp(&self, rhs: &Rope) -> core::cmp::Ordering {
+        chunks_cmp_chunks(self.chunks(), rhs.chunks())
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_chunks(self.chunks(), rhs.chunks()))
+    }
+}
+
+impl core::cmp::PartialOrd<str> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &str) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_chunks(self.chunks(), core::iter::once(rhs)))
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for str {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+impl core::cmp::PartialOrd<&str> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &&str) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(*rhs)
+    }
+}
 
-    use serde::ser::SerializeSeq;
+impl core::cmp::PartialOrd<Rope> for &str {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
 
+#[cfg(feature = "serde")]
+mod serde_impls {
     use super::*;
     use crate::RopeBuilder;
 
@@ -976,11 +4538,7 @@ mod serde_impls {
         where
             S: serde::Serializer,
         {
-            let mut seq = serializer.serialize_seq(None)?;
-            for chunk in self.chunks() {
-                seq.serialize_element(chunk)?;
-            }
-            seq.end()
+            serialize_chunks(self.chunks(), serializer)
         }
     }
 
@@ -1000,7 +4558,15 @@ mod serde_impls {
                     &self,
                     formatter: &mut core::fmt::Formatter,
                 ) -> core::fmt::Result {
-                    formatter.write_str("a sequence of chunks")
+                    formatter.write_str("a string or a sequence of chunks")
+                }
+
+                #[inline]
+                fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+                where
+                    E: serde::de::Error,
+                {
+                    Ok(Rope::from(s))
                 }
 
                 #[inline]
@@ -1012,14 +4578,21 @@ mod serde_impls {
                     A: serde::de::SeqAccess<'de>,
                 {
                     let mut builder = RopeBuilder::new();
-                    while let Some(chunk) = seq.next_element::<Cow<str>>()? {
+                    while let Some(DeChunk(chunk)) = seq.next_element()? {
                         builder.append(chunk);
                     }
                     Ok(builder.build())
                 }
             }
 
-            deserializer.deserialize_seq(Visitor)
+            // Self-describing formats can also give us the whole text as a
+            // single string, which is then split into chunks directly
+            // without being collected into a `String` first.
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(Visitor)
+            } else {
+                deserializer.deserialize_seq(Visitor)
+            }
         }
     }
 }
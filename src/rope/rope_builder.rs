@@ -12,6 +12,22 @@ pub struct RopeBuilder {
     buffer: RopeChunk,
     buffer_len_left: usize,
     rope_has_trailing_newline: bool,
+    leading_bom: LeadingBom,
+}
+
+/// What a [`RopeBuilder`] does with a byte order mark at the start of the
+/// text.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+enum LeadingBom {
+    /// No text has been appended yet, and a BOM would be kept as text.
+    #[default]
+    Keep,
+
+    /// No text has been appended yet, and a BOM would be stripped.
+    Strip,
+
+    /// Some text has already been appended, so a BOM can't appear anymore.
+    Done { stripped: bool },
 }
 
 /// Pushes as mush of the slice as possible onto the left chunk of the gap
@@ -53,6 +69,21 @@ impl RopeBuilder {
     {
         let mut text = text.as_ref();
 
+        if !text.is_empty() {
+            match self.leading_bom {
+                LeadingBom::Keep => {
+                    self.leading_bom = LeadingBom::Done { stripped: false };
+                },
+                LeadingBom::Strip => {
+                    let rest = text.strip_prefix('\u{FEFF}');
+                    self.leading_bom =
+                        LeadingBom::Done { stripped: rest.is_some() };
+                    text = rest.unwrap_or(text);
+                },
+                LeadingBom::Done { .. } => {},
+            }
+        }
+
         while let Some(rest) = gap_buffer_push_with_remainder(
             &mut self.buffer,
             &mut self.buffer_len_left,
@@ -73,6 +104,93 @@ impl RopeBuilder {
         self
     }
 
+    /// Appends all the text read from `reader` to the end of the `Rope` being
+    /// built.
+    ///
+    /// The reader is consumed in fixed-size blocks which are validated and
+    /// copied into the leaves one at a time, so the whole text is never
+    /// buffered in memory. Chars split across two reads are handled
+    /// correctly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails (other than with
+    /// [`ErrorKind::Interrupted`](std::io::ErrorKind::Interrupted), which is
+    /// retried) or if the text isn't valid UTF-8, in which case the error has
+    /// kind [`ErrorKind::InvalidData`](std::io::ErrorKind::InvalidData). All
+    /// the valid text read before the error is appended to the builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::RopeBuilder;
+    /// #
+    /// let mut builder = RopeBuilder::new();
+    ///
+    /// builder.append("ƒoo\n").append_reader("bär\r\nbaz".as_bytes())?;
+    ///
+    /// assert_eq!(builder.build(), "ƒoo\nbär\r\nbaz");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn append_reader<R>(
+        &mut self,
+        mut reader: R,
+    ) -> std::io::Result<&mut Self>
+    where
+        R: std::io::Read,
+    {
+        use std::io::{Error, ErrorKind};
+
+        let mut buf = [0u8; 8192];
+
+        // The number of bytes at the start of `buf` making up the start of a
+        // char whose remaining bytes haven't been read yet.
+        let mut incomplete = 0;
+
+        loop {
+            let read = match reader.read(&mut buf[incomplete..]) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+
+            let filled = incomplete + read;
+
+            let (valid_up_to, invalid) =
+                match core::str::from_utf8(&buf[..filled]) {
+                    Ok(s) => (s.len(), None),
+                    Err(err) if err.error_len().is_none() => {
+                        (err.valid_up_to(), None)
+                    },
+                    Err(err) => (err.valid_up_to(), Some(err)),
+                };
+
+            // SAFETY: we've just checked that the bytes up to `valid_up_to`
+            // are valid UTF-8.
+            self.append(unsafe {
+                core::str::from_utf8_unchecked(&buf[..valid_up_to])
+            });
+
+            if let Some(err) = invalid {
+                return Err(Error::new(ErrorKind::InvalidData, err));
+            }
+
+            buf.copy_within(valid_up_to..filled, 0);
+
+            incomplete = filled - valid_up_to;
+        }
+
+        if let Err(err) = core::str::from_utf8(&buf[..incomplete]) {
+            return Err(Error::new(ErrorKind::InvalidData, err));
+        }
+
+        Ok(self)
+    }
+
     #[inline]
     fn buffer_left_chunk(&self) -> &str {
         // SAFETY: we only append string slices to the left chunk of the gap
@@ -115,6 +233,8 @@ impl RopeBuilder {
         Rope {
             tree: self.tree_builder.build(),
             has_trailing_newline: self.rope_has_trailing_newline,
+            has_bom: self.leading_bom == LeadingBom::Done { stripped: true },
+            depth_limit: None,
         }
     }
 
@@ -123,4 +243,68 @@ impl RopeBuilder {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Makes the `RopeBuilder` strip a UTF-8 byte order mark from the start
+    /// of the text, remembering it in the built `Rope` so that
+    /// [`Rope::write_to()`](crate::Rope::write_to()) can write it back.
+    ///
+    /// This only has an effect if called before any text is appended, since
+    /// only a BOM at the very start of the text is stripped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::RopeBuilder;
+    /// #
+    /// let mut builder = RopeBuilder::new();
+    ///
+    /// builder.strip_bom().append_reader(&b"\xEF\xBB\xBFfoo\n"[..])?;
+    ///
+    /// let r = builder.build();
+    ///
+    /// assert_eq!(r, "foo\n");
+    /// assert!(r.has_bom());
+    ///
+    /// let mut buf = Vec::new();
+    /// r.write_to(&mut buf)?;
+    /// assert_eq!(buf, b"\xEF\xBB\xBFfoo\n");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[inline]
+    pub fn strip_bom(&mut self) -> &mut Self {
+        if self.leading_bom == LeadingBom::Keep {
+            self.leading_bom = LeadingBom::Strip;
+        }
+        self
+    }
+
+    /// Creates a new `RopeBuilder` that's expected to be fed roughly
+    /// `byte_len` bytes of text.
+    ///
+    /// This only pre-sizes the builder's internal bookkeeping: the leaves of a
+    /// [`Rope`](crate::Rope) are always allocated with their maximum size, so
+    /// there's no text buffer to reserve up front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::RopeBuilder;
+    /// #
+    /// let mut builder = RopeBuilder::with_capacity(1024);
+    ///
+    /// for _ in 0..64 {
+    ///     builder.append("Hello, world!\n");
+    /// }
+    ///
+    /// assert_eq!(builder.build().line_len(), 64);
+    /// ```
+    #[inline]
+    pub fn with_capacity(byte_len: usize) -> Self {
+        let leaf_count = byte_len.div_ceil(RopeChunk::max_bytes());
+
+        Self {
+            tree_builder: TreeBuilder::with_capacity(leaf_count),
+            ..Self::default()
+        }
+    }
 }
@@ -0,0 +1,550 @@
+use alloc::vec::Vec;
+use core::ops::RangeBounds;
+
+use super::iterators::{ChainBytes, ChainChars, ChainChunks};
+use super::utils::panic_messages as panic;
+use super::{Rope, RopeSlice};
+use crate::range_bounds_to_start_end;
+
+/// A read-only view over the concatenation of several [`Rope`]s and
+/// [`RopeSlice`]s.
+///
+/// A `RopeChain` doesn't copy any text: it just stores the slices it's made
+/// of, together with their byte and line offsets within the chain. This makes
+/// it cheap to build a document out of multiple pieces (e.g. a header, a body
+/// and some injected sections) and to treat it as a single piece of text.
+///
+/// Most methods are O(log m + log n), where m is the number of members in the
+/// chain and n is the length of the member being queried.
+#[derive(Clone, Default)]
+pub struct RopeChain<'a> {
+    members: Vec<RopeSlice<'a>>,
+
+    /// The byte offset at which each member starts in the chain.
+    byte_offsets: Vec<usize>,
+
+    /// The number of line breaks before the start of each member.
+    line_break_offsets: Vec<usize>,
+
+    byte_len: usize,
+
+    line_breaks: usize,
+}
+
+impl<'a> RopeChain<'a> {
+    /// Returns the byte at `byte_index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte index is out of bounds (i.e. greater than or equal
+    /// to [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let (foo, bar) = (Rope::from("foo"), Rope::from("bar"));
+    ///
+    /// let chain = RopeChain::from_iter([&foo, &bar]);
+    ///
+    /// assert_eq!(chain.byte(2), b'o');
+    /// assert_eq!(chain.byte(3), b'b');
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte(&self, byte_index: usize) -> u8 {
+        if byte_index >= self.byte_len() {
+            panic::byte_index_out_of_bounds(byte_index, self.byte_len());
+        }
+
+        let (member, byte_offset) = self.member_of_byte(byte_index);
+
+        member.byte(byte_index - byte_offset)
+    }
+
+    /// Returns the combined byte length of all the members of the
+    /// `RopeChain`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let r = Rope::from("Hello\nworld");
+    ///
+    /// let chain = RopeChain::from_iter([r.line(0), r.line(1)]);
+    ///
+    /// assert_eq!(chain.byte_len(), 10);
+    /// ```
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.byte_len
+    }
+
+    /// Returns the byte offset of the start of the given line in the
+    /// `RopeChain`.
+    ///
+    /// Lines can span multiple members if a member doesn't end with a line
+    /// break.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let (foo, bar) = (Rope::from("foo"), Rope::from("\nbar\n"));
+    ///
+    /// let chain = RopeChain::from_iter([&foo, &bar]);
+    ///
+    /// assert_eq!(chain.byte_of_line(0), 0);
+    /// assert_eq!(chain.byte_of_line(1), 4);
+    /// assert_eq!(chain.byte_of_line(2), 8);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_line(&self, line_offset: usize) -> usize {
+        if line_offset > self.line_len() {
+            panic::line_offset_out_of_bounds(line_offset, self.line_len());
+        }
+
+        if line_offset == 0 {
+            return 0;
+        }
+
+        if line_offset > self.line_breaks {
+            return self.byte_len();
+        }
+
+        // Find the member containing the `line_offset`-th line break.
+        let idx = self
+            .line_break_offsets
+            .partition_point(|&breaks| breaks < line_offset)
+            - 1;
+
+        self.byte_offsets[idx]
+            + self.members[idx]
+                .byte_of_line(line_offset - self.line_break_offsets[idx])
+    }
+
+    /// Returns a new `RopeChain` containing the text within the specified
+    /// byte range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end, if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())) or if
+    /// either of them doesn't lie on a code point boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let (foo, bar) = (Rope::from("foo"), Rope::from("bar"));
+    ///
+    /// let chain = RopeChain::from_iter([&foo, &bar]);
+    ///
+    /// assert_eq!(chain.byte_slice(2..5).to_string(), "oba");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte_slice<R>(&self, byte_range: R) -> Self
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.byte_len() {
+            panic::byte_offset_out_of_bounds(end, self.byte_len());
+        }
+
+        let mut chain = Self::new();
+
+        if start == end {
+            return chain;
+        }
+
+        for (member, &byte_offset) in
+            self.members.iter().zip(&self.byte_offsets)
+        {
+            let member_end = byte_offset + member.byte_len();
+
+            if member_end <= start || member.is_empty() {
+                continue;
+            }
+
+            if byte_offset >= end {
+                break;
+            }
+
+            let slice_start = start.max(byte_offset) - byte_offset;
+            let slice_end = end.min(member_end) - byte_offset;
+            chain.push(member.byte_slice(slice_start..slice_end));
+        }
+
+        chain
+    }
+
+    /// Returns an iterator over the bytes of this `RopeChain`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let (foo, bar) = (Rope::from("fo"), Rope::from("o"));
+    ///
+    /// let chain = RopeChain::from_iter([&foo, &bar]);
+    ///
+    /// assert_eq!(chain.bytes().collect::<Vec<_>>(), b"foo");
+    /// ```
+    #[inline]
+    pub fn bytes(&self) -> ChainBytes<'_, 'a> {
+        ChainBytes::from(self)
+    }
+
+    /// Returns an iterator over the `char`s of this `RopeChain`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let (crab, shell) = (Rope::from("🦀"), Rope::from("🐚"));
+    ///
+    /// let chain = RopeChain::from_iter([&crab, &shell]);
+    ///
+    /// assert_eq!(chain.chars().collect::<String>(), "🦀🐚");
+    /// ```
+    #[inline]
+    pub fn chars(&self) -> ChainChars<'_, 'a> {
+        ChainChars::from(self)
+    }
+
+    /// Returns an iterator over the chunks of all the members of this
+    /// `RopeChain`, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let (foo, bar) = (Rope::from("foo"), Rope::from("bar"));
+    ///
+    /// let chain = RopeChain::from_iter([&foo, &bar]);
+    ///
+    /// assert_eq!(chain.chunks().collect::<String>(), "foobar");
+    /// ```
+    #[inline]
+    pub fn chunks(&self) -> ChainChunks<'_, 'a> {
+        ChainChunks::from(self)
+    }
+
+    /// Returns `true` if the `RopeChain`'s byte length is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let empty = Rope::new();
+    ///
+    /// let mut chain = RopeChain::new();
+    /// assert!(chain.is_empty());
+    ///
+    /// chain.push(&empty);
+    /// assert!(chain.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.byte_len() == 0
+    }
+
+    /// Returns the line at `line_index`, without its line terminator.
+    ///
+    /// The returned `RopeChain` can span multiple members if a member doesn't
+    /// end with a line break.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line index is out of bounds (i.e. greater than or equal
+    /// to [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let (foo, bar) = (Rope::from("foo\nb"), Rope::from("ar\r\nbaz"));
+    ///
+    /// let chain = RopeChain::from_iter([&foo, &bar]);
+    ///
+    /// assert_eq!(chain.line(0).to_string(), "foo");
+    /// assert_eq!(chain.line(1).to_string(), "bar");
+    /// assert_eq!(chain.line(2).to_string(), "baz");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line(&self, line_index: usize) -> Self {
+        if line_index >= self.line_len() {
+            panic::line_index_out_of_bounds(line_index, self.line_len());
+        }
+
+        let start = self.byte_of_line(line_index);
+
+        let mut end = self.byte_of_line(line_index + 1);
+
+        if end > start && self.byte(end - 1) == b'\n' {
+            end -= 1;
+
+            if end > start && self.byte(end - 1) == b'\r' {
+                end -= 1;
+            }
+        }
+
+        self.byte_slice(start..end)
+    }
+
+    /// Returns the number of lines in the `RopeChain`.
+    ///
+    /// Like for [`Rope::line_len()`], the final line break is optional and
+    /// doesn't count as a separate empty line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let (foo, bar) = (Rope::from("foo"), Rope::from("bar\n"));
+    ///
+    /// let chain = RopeChain::from_iter([&foo, &bar]);
+    ///
+    /// assert_eq!(chain.line_len(), 1);
+    /// ```
+    #[inline]
+    pub fn line_len(&self) -> usize {
+        let has_trailing_newline = self
+            .members
+            .iter()
+            .rev()
+            .find(|member| !member.is_empty())
+            .is_some_and(|member| member.has_trailing_newline);
+
+        self.line_breaks + 1
+            - (has_trailing_newline as usize)
+            - (self.is_empty() as usize)
+    }
+
+    /// Returns the line offset of the given byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let (foo, bar) = (Rope::from("foo\n"), Rope::from("bar\nbaz"));
+    ///
+    /// let chain = RopeChain::from_iter([&foo, &bar]);
+    ///
+    /// assert_eq!(chain.line_of_byte(3), 0);
+    /// assert_eq!(chain.line_of_byte(4), 1);
+    /// assert_eq!(chain.line_of_byte(chain.byte_len()), 2);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line_of_byte(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        if byte_offset == self.byte_len() {
+            return self.line_breaks;
+        }
+
+        let idx = self.member_idx_of_byte(byte_offset);
+
+        self.line_break_offsets[idx]
+            + self.members[idx]
+                .line_of_byte(byte_offset - self.byte_offsets[idx])
+    }
+
+    /// Returns the index of the member containing the given byte index.
+    #[inline]
+    fn member_idx_of_byte(&self, byte_index: usize) -> usize {
+        debug_assert!(byte_index < self.byte_len());
+
+        self.byte_offsets.partition_point(|&offset| offset <= byte_index) - 1
+    }
+
+    /// Returns the member containing the given byte index together with the
+    /// byte offset at which it starts.
+    #[inline]
+    fn member_of_byte(&self, byte_index: usize) -> (RopeSlice<'a>, usize) {
+        let idx = self.member_idx_of_byte(byte_index);
+        (self.members[idx], self.byte_offsets[idx])
+    }
+
+    /// Returns the members of the `RopeChain`, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let (foo, bar) = (Rope::from("foo"), Rope::from("bar"));
+    ///
+    /// let chain = RopeChain::from_iter([&foo, &bar]);
+    ///
+    /// assert_eq!(chain.members().len(), 2);
+    /// assert_eq!(chain.members()[1], "bar");
+    /// ```
+    #[inline]
+    pub fn members(&self) -> &[RopeSlice<'a>] {
+        &self.members
+    }
+
+    /// Creates a new, empty `RopeChain`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::RopeChain;
+    /// #
+    /// let chain = RopeChain::new();
+    ///
+    /// assert!(chain.is_empty());
+    /// assert!(chain.members().is_empty());
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a new member at the end of the `RopeChain`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Rope, RopeChain};
+    /// #
+    /// let (header, body) = (Rope::from("# Title\n"), Rope::from("Hi!\n"));
+    ///
+    /// let mut chain = RopeChain::new();
+    ///
+    /// chain.push(&header).push(body.byte_slice(..3));
+    ///
+    /// assert_eq!(chain.to_string(), "# Title\nHi!");
+    /// ```
+    #[inline]
+    pub fn push<S>(&mut self, member: S) -> &mut Self
+    where
+        S: Into<RopeSlice<'a>>,
+    {
+        let member = member.into();
+        self.byte_offsets.push(self.byte_len);
+        self.line_break_offsets.push(self.line_breaks);
+        self.byte_len += member.byte_len();
+        self.line_breaks += member.tree_slice.summary().line_breaks();
+        self.members.push(member);
+        self
+    }
+}
+
+impl<'a, S> FromIterator<S> for RopeChain<'a>
+where
+    S: Into<RopeSlice<'a>>,
+{
+    #[inline]
+    fn from_iter<I>(members: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+    {
+        let mut chain = Self::new();
+        for member in members {
+            chain.push(member);
+        }
+        chain
+    }
+}
+
+impl<'a, S> Extend<S> for RopeChain<'a>
+where
+    S: Into<RopeSlice<'a>>,
+{
+    #[inline]
+    fn extend<I>(&mut self, members: I)
+    where
+        I: IntoIterator<Item = S>,
+    {
+        for member in members {
+            self.push(member);
+        }
+    }
+}
+
+impl From<&RopeChain<'_>> for Rope {
+    #[inline]
+    fn from(chain: &RopeChain<'_>) -> Self {
+        let mut builder = crate::RopeBuilder::new();
+        for chunk in chain.chunks() {
+            builder.append(chunk);
+        }
+        builder.build()
+    }
+}
+
+impl core::fmt::Debug for RopeChain<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("RopeChain(")?;
+        f.debug_list().entries(&self.members).finish()?;
+        f.write_str(")")
+    }
+}
+
+impl core::fmt::Display for RopeChain<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        for chunk in self.chunks() {
+            f.write_str(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::cmp::PartialEq<str> for RopeChain<'_> {
+    #[inline]
+    fn eq(&self, rhs: &str) -> bool {
+        self.byte_len() == rhs.len() && {
+            let mut rhs = rhs.as_bytes();
+            self.chunks().all(|chunk| {
+                let (lhs, rest) = rhs.split_at(chunk.len());
+                rhs = rest;
+                lhs == chunk.as_bytes()
+            })
+        }
+    }
+}
+
+impl core::cmp::PartialEq<&str> for RopeChain<'_> {
+    #[inline]
+    fn eq(&self, rhs: &&str) -> bool {
+        self == *rhs
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::{BufRead, Read};
+
+use super::iterators::Chunks;
+
+/// An adapter implementing [`Read`] and [`BufRead`] over the contents of a
+/// [`Rope`](crate::Rope) or [`RopeSlice`](crate::RopeSlice).
+///
+/// This struct is created by the `reader()` method on
+/// [`Rope`](crate::Rope::reader()) and
+/// [`RopeSlice`](crate::RopeSlice::reader()). See their documentation for more.
+///
+/// The bytes are pulled out of the chunks one chunk at a time, so creating a
+/// `RopeReader` is O(log n) and it never copies the text into an intermediate
+/// buffer. Reading from it never fails.
+#[derive(Clone)]
+pub struct RopeReader<'a> {
+    chunks: Chunks<'a>,
+
+    /// The bytes of the current chunk that haven't been read yet.
+    current: &'a [u8],
+}
+
+impl<'a> RopeReader<'a> {
+    #[inline]
+    pub(super) fn new(chunks: Chunks<'a>) -> Self {
+        Self { chunks, current: &[] }
+    }
+}
+
+impl core::fmt::Debug for RopeReader<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RopeReader")
+            .field("current", &self.current)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Read for RopeReader<'_> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut written = 0;
+
+        while written < buf.len() {
+            let available = self.fill_buf()?;
+
+            if available.is_empty() {
+                break;
+            }
+
+            let len = available.len().min(buf.len() - written);
+
+            buf[written..written + len].copy_from_slice(&available[..len]);
+
+            self.consume(len);
+
+            written += len;
+        }
+
+        Ok(written)
+    }
+}
+
+impl BufRead for RopeReader<'_> {
+    #[inline]
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        while self.current.is_empty() {
+            match self.chunks.next() {
+                Some(chunk) => self.current = chunk.as_bytes(),
+                None => break,
+            }
+        }
+
+        Ok(self.current)
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.current = &self.current[amt..];
+    }
+}
@@ -1,11 +1,32 @@
-use alloc::string::String;
-use core::ops::RangeBounds;
-
-use super::Rope;
-use super::iterators::{Bytes, Chars, Chunks, Lines, RawLines};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use core::ops::{ControlFlow, RangeBounds};
+
+use super::error::{
+    Error,
+    check_byte_range,
+    check_line_index,
+    check_line_range,
+};
+use super::iterators::{
+    Bytes,
+    Chars,
+    Chunks,
+    FindIter,
+    LeafSummaries,
+    Lines,
+    RawLines,
+};
 use super::metrics::{ByteMetric, RawLineMetric};
+use super::position::{
+    Position,
+    PositionEncoding,
+    byte_of_position,
+    position_of_byte,
+};
 use super::rope::RopeChunk;
 use super::utils::{panic_messages as panic, *};
+use super::{Cursor, Rope};
 use crate::range_bounds_to_start_end;
 use crate::tree::TreeSlice;
 
@@ -17,6 +38,37 @@ pub struct RopeSlice<'a> {
 }
 
 impl<'a> RopeSlice<'a> {
+    /// Returns the contents of the `RopeSlice` as a string slice if they're
+    /// stored contiguously, i.e. if the slice doesn't span more than one
+    /// chunk, or `None` otherwise.
+    ///
+    /// Short slices like a single word or line are usually contained in a
+    /// single chunk, so this lets them be passed to APIs taking a `&str`
+    /// without allocating. See [`to_cow()`](Self::to_cow()) for a version
+    /// that falls back to allocating a `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello World!");
+    ///
+    /// let (chunk, _) = r.cursor(0).chunk();
+    /// let s = r.byte_slice(1..chunk.len());
+    ///
+    /// assert_eq!(s.as_str(), Some(&chunk[1..]));
+    /// assert_eq!(r.byte_slice(..0).as_str(), Some(""));
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> Option<&'a str> {
+        let mut chunks = self.chunks().filter(|chunk| !chunk.is_empty());
+
+        let Some(first) = chunks.next() else { return Some("") };
+
+        chunks.next().is_none().then_some(first)
+    }
+
     #[doc(hidden)]
     pub fn assert_invariants(&self) {
         self.tree_slice.assert_invariants();
@@ -82,6 +134,39 @@ impl<'a> RopeSlice<'a> {
         self.tree_slice.summary().bytes()
     }
 
+    /// Returns the byte offset corresponding to the given char offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the char offset is out of bounds (i.e. greater than
+    /// [`char_len()`](Self::char_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // The "è" character is encoded using two bytes in UTF-8.
+    /// let r = Rope::from("aèb");
+    /// let s = r.byte_slice(..);
+    /// assert_eq!(s.byte_of_char(2), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_char(&self, char_offset: usize) -> usize {
+        if char_offset > self.char_len() {
+            panic::char_offset_out_of_bounds(char_offset, self.char_len())
+        }
+
+        let ByteMetric(byte_offset) = self
+            .tree_slice
+            .convert_measure(super::metrics::CharMetric(char_offset));
+
+        byte_offset
+    }
+
     /// Returns the byte offset of the start of the given line.
     ///
     /// # Panics
@@ -121,6 +206,47 @@ impl<'a> RopeSlice<'a> {
         byte_offset
     }
 
+    /// Returns the byte offset of the given [`Position`], where the column is
+    /// measured in the units of the given [`PositionEncoding`].
+    ///
+    /// This is meant to convert positions received from a language server
+    /// client. The column is located by scanning its line, so this runs in
+    /// O(log n + line length).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())), if the column is past the end of
+    /// the line (not counting its line terminator) or if it doesn't lie on a
+    /// code point boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Position, PositionEncoding, Rope};
+    /// #
+    /// let r = Rope::from("foo\na𐐀bc");
+    /// let s = r.byte_slice(..);
+    ///
+    /// // "𐐀" is 4 bytes in UTF-8 and 2 code units in UTF-16.
+    /// let utf8 = Position::new(1, 5);
+    /// let utf16 = Position::new(1, 3);
+    /// let utf32 = Position::new(1, 2);
+    ///
+    /// assert_eq!(s.byte_of_position(utf8, PositionEncoding::Utf8), 9);
+    /// assert_eq!(s.byte_of_position(utf16, PositionEncoding::Utf16), 9);
+    /// assert_eq!(s.byte_of_position(utf32, PositionEncoding::Utf32), 9);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_position(
+        &self,
+        position: Position,
+        encoding: PositionEncoding,
+    ) -> usize {
+        byte_of_position(*self, position, encoding)
+    }
+
     /// Returns the byte offset corresponding to the given UTF-16 code unit
     /// offset.
     ///
@@ -157,6 +283,96 @@ impl<'a> RopeSlice<'a> {
         byte_offset
     }
 
+    /// Returns the byte offset of the given visual column in the given line,
+    /// with tabs expanding to the next multiple of `tab_width` columns.
+    ///
+    /// The width of every other char is measured with the
+    /// [`unicode-width`](https://docs.rs/unicode-width) crate. If the column
+    /// falls in the middle of a char, like a tab or a wide char, the byte
+    /// offset of the start of that char is returned, and if it's past the end
+    /// of the line the byte offset of the end of the line (not counting its
+    /// line terminator) is returned.
+    ///
+    /// The column is located by scanning its line, so this runs in O(log n +
+    /// line length).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\na\t日b\n");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.byte_of_visual_col(1, 4, 4), 6);
+    /// assert_eq!(s.byte_of_visual_col(1, 2, 4), 5);
+    /// assert_eq!(s.byte_of_visual_col(1, 6, 4), 9);
+    /// assert_eq!(s.byte_of_visual_col(1, 42, 4), 10);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "display-width")))]
+    #[cfg(feature = "display-width")]
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_visual_col(
+        &self,
+        line_offset: usize,
+        visual_col: usize,
+        tab_width: usize,
+    ) -> usize {
+        super::position::byte_of_visual_col(
+            *self,
+            line_offset,
+            visual_col,
+            tab_width,
+        )
+    }
+
+    /// Returns the byte offset corresponding to the given width offset, i.e.
+    /// the shortest prefix of the `RopeSlice` that takes up at least
+    /// `width_offset` terminal columns.
+    ///
+    /// If the width offset falls in the middle of a wide char, the byte
+    /// offset after that char is returned. See
+    /// [`width()`](Self::width()) for how the width is computed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width offset is out of bounds (i.e. greater than
+    /// [`width()`](Self::width())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // The "日" character is two columns wide and encoded using three bytes
+    /// // in UTF-8.
+    /// let r = Rope::from("a日b");
+    /// let s = r.byte_slice(..);
+    /// assert_eq!(s.byte_of_width(3), 4);
+    /// assert_eq!(s.byte_of_width(2), 4);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "width-metric")))]
+    #[cfg(feature = "width-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_width(&self, width_offset: usize) -> usize {
+        if width_offset > self.width() {
+            panic::width_offset_out_of_bounds(width_offset, self.width())
+        }
+
+        let ByteMetric(byte_offset) = self
+            .tree_slice
+            .convert_measure(super::metrics::WidthMetric(width_offset));
+
+        byte_offset
+    }
+
     /// Returns a sub-slice of this `RopeSlice` in the specified byte range,
     /// where the start and end of the range are interpreted as offsets.
     ///
@@ -220,112 +436,128 @@ impl<'a> RopeSlice<'a> {
         Bytes::from(self)
     }
 
-    /// Returns an iterator over the [`char`]s of this `RopeSlice`.
+    /// Returns a pair of iterators over the bytes before and after the
+    /// given byte offset.
+    ///
+    /// See [`Rope::bytes_at()`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("🐻‍❄️");
-    /// let s = r.byte_slice(4..);
+    /// let r = Rope::from("foo bar");
+    /// let s = r.byte_slice(1..);
     ///
-    /// let mut chars = s.chars();
+    /// let (before, after) = s.bytes_at(2);
     ///
-    /// assert_eq!(Some('\u{200d}'), chars.next());
-    /// assert_eq!(Some('❄'), chars.next());
-    /// assert_eq!(Some('\u{fe0f}'), chars.next());
-    /// assert_eq!(None, chars.next());
+    /// assert_eq!(before.collect::<Vec<_>>(), b"oo");
+    /// assert_eq!(after.collect::<Vec<_>>(), b" bar");
     /// ```
+    #[track_caller]
     #[inline]
-    pub fn chars(&self) -> Chars<'a> {
-        Chars::from(self)
-    }
+    pub fn bytes_at(
+        &self,
+        byte_offset: usize,
+    ) -> (core::iter::Rev<Bytes<'a>>, Bytes<'a>) {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
 
-    /// Returns an iterator over the chunks of this `RopeSlice`.
-    #[inline]
-    pub fn chunks(&self) -> Chunks<'a> {
-        Chunks::from(self)
+        let before = self.byte_slice(..byte_offset);
+        let after = self.byte_slice(byte_offset..);
+
+        (before.bytes().rev(), after.bytes())
     }
 
-    /// Returns an iterator over the extended grapheme clusters of this
-    /// `RopeSlice`.
+    /// Returns the byte offset of the first grapheme cluster boundary at or
+    /// after the given byte offset.
+    ///
+    /// Unlike [`next_grapheme_boundary()`](Self::next_grapheme_boundary()),
+    /// this returns the byte offset itself if it's already a boundary, and
+    /// the offset doesn't have to lie on a code point boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("arg!\r\n🐻‍❄️");
-    /// let s = r.byte_slice(3..);
-    ///
-    /// let mut graphemes = s.graphemes();
+    /// let r = Rope::from("a🐻‍❄️\r\nb");
+    /// let s = r.byte_slice(..);
     ///
-    /// assert_eq!(Some("!"), graphemes.next().as_deref());
-    /// assert_eq!(Some("\r\n"), graphemes.next().as_deref());
-    /// assert_eq!(Some("🐻‍❄️"), graphemes.next().as_deref());
-    /// assert_eq!(None, graphemes.next());
+    /// assert_eq!(s.ceil_grapheme_boundary(1), 1);
+    /// assert_eq!(s.ceil_grapheme_boundary(3), 14); // inside '🐻‍❄️'
+    /// assert_eq!(s.ceil_grapheme_boundary(15), 16); // between '\r' and '\n'
     /// ```
     #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
     #[cfg(feature = "graphemes")]
+    #[track_caller]
     #[inline]
-    pub fn graphemes(&self) -> crate::iter::Graphemes<'a> {
-        crate::iter::Graphemes::from(self)
+    pub fn ceil_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        ceil_grapheme_boundary(self.byte_slice(..), byte_offset)
     }
 
-    /// Returns `true` if the given byte offset lies on a [`char`] boundary.
-    ///
-    /// # Panics
+    /// Returns an iterator over the [`char`]s of this `RopeSlice` and their
+    /// byte offsets, like [`str::char_indices()`].
     ///
-    /// Panics if the byte offset is out of bounds (i.e. greater than
-    /// [`byte_len()`](Self::byte_len())).
+    /// The offsets are relative to the start of the slice, not to the start
+    /// of the `Rope` it was taken from.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("Löwe 老虎 Léopard");
-    /// let s = r.byte_slice(3..);
+    /// let r = Rope::from("aè🐸");
+    /// let s = r.byte_slice(1..);
     ///
-    /// assert!(s.is_char_boundary(0));
-    /// assert!(s.is_char_boundary(s.byte_len()));
-    /// assert!(s.is_char_boundary(6)); // between '老' and '虎'
-    /// assert!(!s.is_char_boundary(12)); // between the 1st and 2nd byte of 'é'
+    /// let mut char_indices = s.char_indices();
+    ///
+    /// assert_eq!(Some((0, 'è')), char_indices.next());
+    /// assert_eq!(Some((2, '🐸')), char_indices.next_back());
+    /// assert_eq!(None, char_indices.next());
     /// ```
-    #[track_caller]
     #[inline]
-    pub fn is_char_boundary(&self, byte_offset: usize) -> bool {
-        if byte_offset > self.byte_len() {
-            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
-        }
-
-        let (chunk, ByteMetric(chunk_byte_offset)) =
-            self.tree_slice.leaf_at_measure(ByteMetric(byte_offset));
-
-        chunk.is_char_boundary(byte_offset - chunk_byte_offset)
+    pub fn char_indices(&self) -> crate::iter::CharIndices<'a> {
+        crate::iter::CharIndices::from(self)
     }
 
-    /// Returns `true` if the `RopeSlice`'s byte length is zero.
+    /// Returns the number of `char`s (i.e. Unicode code points) in the
+    /// `RopeSlice`.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("");
-    /// assert!(r.byte_slice(..).is_empty());
-    ///
-    /// let r = Rope::from("foo");
-    /// assert!(!r.line_slice(..).is_empty());
+    /// // The "🐸" emoji is encoded using four bytes in UTF-8.
+    /// let r = Rope::from("abc🐸");
+    /// let s = r.byte_slice(..);
+    /// assert_eq!(s.char_len(), 4);
     /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.byte_len() == 0
+    pub fn char_len(&self) -> usize {
+        self.tree_slice.summary().chars()
     }
 
-    /// Returns `true` if the given byte offset lies on a grapheme cluster
-    /// boundary.
+    /// Returns the char offset corresponding to the given byte offset.
     ///
     /// # Panics
     ///
@@ -337,297 +569,1989 @@ impl<'a> RopeSlice<'a> {
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
-    /// let s = r.line_slice(1..);
-    ///
-    /// assert!(s.is_grapheme_boundary(0));
-    /// assert!(s.is_grapheme_boundary(s.byte_len()));
-    /// assert!(!s.is_grapheme_boundary(4)); // between the 1st and 2nd code point of '🐻‍❄️'
+    /// // The "è" character is encoded using two bytes in UTF-8.
+    /// let r = Rope::from("aèb");
+    /// let s = r.byte_slice(..);
+    /// assert_eq!(s.char_of_byte(3), 2);
     /// ```
-    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
-    #[cfg(feature = "graphemes")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
     #[track_caller]
     #[inline]
-    pub fn is_grapheme_boundary(&self, byte_offset: usize) -> bool {
+    pub fn char_of_byte(&self, byte_offset: usize) -> usize {
         if byte_offset > self.byte_len() {
             panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
         }
 
-        is_grapheme_boundary(self.chunks(), self.byte_len(), byte_offset)
+        let super::metrics::CharMetric(char_offset) =
+            self.tree_slice.convert_measure(ByteMetric(byte_offset));
+
+        char_offset
     }
 
-    /// Returns the line at `line_index`, without its line terminator.
-    ///
-    /// If you want to include the line break consider taking a
-    /// [`line_slice()`](Self::line_slice()) in the
-    /// `line_index..line_index + 1` range.
+    /// Returns a sub-slice of this `RopeSlice` in the specified char range,
+    /// where the start and end of the range are interpreted as offsets.
     ///
     /// # Panics
     ///
-    /// Panics if the line index is out of bounds (i.e. greater than or equal
-    /// to [`line_len()`](Self::byte_len())).
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`char_len()`](Self::char_len())).
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("foo\nbar\r\nbaz");
-    /// let s = r.line_slice(..2);
+    /// let r = Rope::from("aè🐸d");
+    /// let s = r.byte_slice(..);
     ///
-    /// assert_eq!(s.line(0), "foo");
-    /// assert_eq!(s.line(1), "bar");
+    /// assert_eq!(s.char_slice(..2), "aè");
+    /// assert_eq!(s.char_slice(2..), "🐸d");
+    /// assert_eq!(s.char_slice(1..3), "è🐸");
     /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
     #[track_caller]
     #[inline]
-    pub fn line(self, line_index: usize) -> RopeSlice<'a> {
-        if line_index >= self.line_len() {
-            panic::line_offset_out_of_bounds(line_index, self.line_len());
-        }
+    pub fn char_slice<R>(self, char_range: R) -> RopeSlice<'a>
+    where
+        R: RangeBounds<usize>,
+    {
+        use super::metrics::CharMetric;
 
-        let tree_slice = self
-            .tree_slice
-            .slice(RawLineMetric(line_index)..RawLineMetric(line_index + 1));
+        let (start, end) =
+            range_bounds_to_start_end(char_range, 0, self.char_len());
 
-        let mut line = Self { tree_slice, has_trailing_newline: false };
+        if start > end {
+            panic::char_start_after_end(start, end);
+        }
 
-        if line.tree_slice.summary().line_breaks() == 1 {
-            line.truncate_trailing_line_break();
+        if end > self.char_len() {
+            panic::char_offset_out_of_bounds(end, self.char_len());
         }
 
-        line
+        self.tree_slice.slice(CharMetric(start)..CharMetric(end)).into()
     }
 
-    /// Returns the number of lines in the `RopeSlice`.
-    ///
-    /// The final line break is optional and doesn't count as a separate empty
-    /// line.
+    /// Returns an iterator over the [`char`]s of this `RopeSlice`.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("a\nb\r\n");
-    ///
-    /// let s = r.byte_slice(..);
-    /// assert_eq!(s.line_len(), 2);
-    ///
-    /// let s = r.byte_slice(..3);
-    /// assert_eq!(s.line_len(), 2);
-    ///
-    /// let s = r.byte_slice(..2);
-    /// assert_eq!(s.line_len(), 1);
+    /// let r = Rope::from("🐻‍❄️");
+    /// let s = r.byte_slice(4..);
     ///
-    /// let s = r.byte_slice(..1);
-    /// assert_eq!(s.line_len(), 1);
+    /// let mut chars = s.chars();
     ///
-    /// let s = r.byte_slice(..0);
-    /// assert_eq!(s.line_len(), 0);
+    /// assert_eq!(Some('\u{200d}'), chars.next());
+    /// assert_eq!(Some('❄'), chars.next());
+    /// assert_eq!(Some('\u{fe0f}'), chars.next());
+    /// assert_eq!(None, chars.next());
     /// ```
     #[inline]
-    pub fn line_len(&self) -> usize {
-        self.tree_slice.summary().line_breaks() + 1
-            - (self.has_trailing_newline as usize)
-            - (self.is_empty() as usize)
+    pub fn chars(&self) -> Chars<'a> {
+        Chars::from(self)
     }
 
-    /// Returns the line offset of the given byte.
+    /// Returns a pair of iterators over the [`char`]s before and after the
+    /// given byte offset.
+    ///
+    /// See [`Rope::chars_at()`] for more.
     ///
     /// # Panics
     ///
     /// Panics if the byte offset is out of bounds (i.e. greater than
-    /// [`byte_len()`](Self::byte_len())).
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("foo\nbar\r\nbaz");
+    /// let r = Rope::from("🐸 foo");
+    /// let s = r.byte_slice(5..);
     ///
-    /// let s = r.byte_slice(..);
-    /// assert_eq!(s.line_of_byte(0), 0);
-    /// assert_eq!(s.line_of_byte(4), 1);
+    /// let (mut before, mut after) = s.chars_at(1);
     ///
-    /// let s = r.line_slice(1..);
-    /// assert_eq!(s.line_of_byte(0), 0);
-    /// assert_eq!(s.line_of_byte(4), 0); // between the '\r' and the '\n'
-    /// assert_eq!(s.line_of_byte(s.byte_len()), 1);
+    /// assert_eq!(Some('f'), before.next());
+    /// assert_eq!(None, before.next());
+    /// assert_eq!(Some('o'), after.next());
     /// ```
     #[track_caller]
     #[inline]
-    pub fn line_of_byte(&self, byte_offset: usize) -> usize {
+    pub fn chars_at(
+        &self,
+        byte_offset: usize,
+    ) -> (core::iter::Rev<Chars<'a>>, Chars<'a>) {
         if byte_offset > self.byte_len() {
             panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
         }
 
-        let RawLineMetric(line_offset) =
-            self.tree_slice.convert_measure(ByteMetric(byte_offset));
+        let before = self.byte_slice(..byte_offset);
+        let after = self.byte_slice(byte_offset..);
 
-        line_offset
+        (before.chars().rev(), after.chars())
     }
 
-    /// Returns a sub-slice of this `RopeSlice` in the specified line range,
-    /// where the start and end of the range are interpreted as offsets.
+    /// Returns the chunk containing the start of the given line, together
+    /// with the byte offset and the line offset of the start of the chunk.
+    ///
+    /// The chunk is clamped to the slice, and both offsets are relative to
+    /// its start. See [`Rope::chunk_at_line()`] for more.
     ///
     /// # Panics
     ///
-    /// Panics if the start is greater than the end or if the end is out of
-    /// bounds (i.e. greater than [`line_len()`](Self::line_len())).
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("foo\nbar\r\nbaz\nfoobar\n");
-    /// let s = r.byte_slice(2..17);
+    /// let r = Rope::from("foo\nbar\nbaz\n");
+    /// let s = r.byte_slice(2..);
     ///
-    /// assert_eq!(s.line_slice(..1), "o\n");
-    /// assert_eq!(s.line_slice(1..3), "bar\r\nbaz\n");
-    /// assert_eq!(s.line_slice(3..4), "foob");
-    /// assert_eq!(s.line_slice(4..), "");
+    /// let (chunk, byte_offset, line_offset) = s.chunk_at_line(0);
+    ///
+    /// assert_eq!(byte_offset, 0);
+    /// assert_eq!(line_offset, 0);
+    /// assert!(chunk.starts_with('o'));
     /// ```
     #[track_caller]
     #[inline]
-    pub fn line_slice<R>(self, line_range: R) -> RopeSlice<'a>
-    where
-        R: RangeBounds<usize>,
-    {
-        let (start, end) =
-            range_bounds_to_start_end(line_range, 0, self.line_len());
+    pub fn chunk_at_line(
+        &self,
+        line_offset: usize,
+    ) -> (&'a str, usize, usize) {
+        let byte_offset = self.byte_of_line(line_offset);
 
-        if start > end {
-            panic::line_start_after_end(start, end);
-        }
+        let (chunk, chunk_start) = self.cursor(byte_offset).chunk();
 
-        if end > self.line_len() {
-            panic::line_offset_out_of_bounds(end, self.line_len());
-        }
+        (chunk, chunk_start, self.line_of_byte(chunk_start))
+    }
 
-        self.tree_slice.slice(RawLineMetric(start)..RawLineMetric(end)).into()
+    /// Returns an iterator over the chunks of this `RopeSlice`.
+    #[inline]
+    pub fn chunks(&self) -> Chunks<'a> {
+        Chunks::from(self)
     }
 
-    /// Returns an iterator over the lines of this `RopeSlice`, not including
-    /// the line terminators.
+    /// Returns an iterator over the chunks of this `RopeSlice`, together with
+    /// the byte offset at which each chunk starts.
     ///
-    /// The final line break is optional and doesn't cause the iterator to
-    /// return a final empty line.
+    /// The offsets are relative to the start of the slice, not to the start
+    /// of the `Rope` it was taken from.
     ///
-    /// If you want to include the line breaks consider using the
-    /// [`raw_lines()`](Self::raw_lines()) method instead.
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello World!");
+    /// let s = r.byte_slice(6..);
+    ///
+    /// let mut chunks = s.chunks_with_offsets();
+    /// let (offset, chunk) = chunks.next().unwrap();
+    ///
+    /// assert_eq!(offset, 0);
+    /// assert!("World!".starts_with(chunk));
+    /// ```
+    #[inline]
+    pub fn chunks_with_offsets(&self) -> crate::iter::ChunksWithOffsets<'a> {
+        crate::iter::ChunksWithOffsets::from(self)
+    }
+
+    /// Returns a [`Cursor`](crate::Cursor) positioned at the given byte
+    /// offset of the `RopeSlice`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("foo\nbar\r\nbaz\n");
-    /// let s = r.line_slice(1..);
+    /// let r = Rope::from("ƒoo");
     ///
-    /// let mut lines = s.lines();
+    /// let mut cursor = r.byte_slice(..).cursor(2);
     ///
-    /// assert_eq!("bar", lines.next().unwrap());
-    /// assert_eq!("baz", lines.next().unwrap());
-    /// assert_eq!(None, lines.next());
+    /// assert_eq!(cursor.next_char(), Some('o'));
+    /// assert_eq!(cursor.prev_char(), Some('o'));
+    /// assert_eq!(cursor.prev_char(), Some('ƒ'));
+    /// assert_eq!(cursor.prev_char(), None);
     /// ```
+    #[track_caller]
     #[inline]
-    pub fn lines(&self) -> Lines<'a> {
-        Lines::from(self)
+    pub fn cursor(&self, byte_offset: usize) -> Cursor<'a> {
+        Cursor::new(*self, byte_offset)
     }
 
-    /// Returns an iterator over the lines of this `RopeSlice`, including the
-    /// line terminators.
+    /// Counts the line endings of each style in this `RopeSlice`, which can be
+    /// used to decide which line ending to insert when the user presses
+    /// Enter.
     ///
-    /// The final line break is optional and doesn't cause the iterator to
-    /// return a final empty line.
+    /// The total number of line feeds is read from the summary of the
+    /// B-tree, so this returns immediately if there aren't any, but telling
+    /// `"\n"`s and `"\r\n"`s apart requires scanning the text.
     ///
-    /// If you don't  want to include the line breaks consider using the
-    /// [`raw_lines()`](Self::raw_lines()) method instead.
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{LineEnding, LineEndings, Rope};
+    /// #
+    /// let r = Rope::from("foo\r\nbar\nbaz\r\n");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let endings = s.detect_line_ending();
+    ///
+    /// assert_eq!(endings, LineEndings { lf: 1, crlf: 2 });
+    /// assert_eq!(endings.style(), Some(LineEnding::Mixed));
+    /// ```
+    #[inline]
+    pub fn detect_line_ending(&self) -> crate::LineEndings {
+        super::line_ending::detect_line_ending(*self)
+    }
+
+    /// Returns whether the `RopeSlice` ends with the given pattern, which can
+    /// be a `char`, a `&str`, a `RopeSlice` or a [`&Rope`](crate::Rope).
+    ///
+    /// See [`Rope::ends_with()`](crate::Rope::ends_with()) for more infos.
     ///
     /// # Examples
     ///
     /// ```
     /// # use crop::Rope;
     /// #
-    /// let r = Rope::from("foo\nbar\r\nbaz\n");
-    /// let s = r.byte_slice(..r.byte_len() - 1);
+    /// let r = Rope::from("foo bar\n");
+    /// let s = r.line(0);
     ///
-    /// let mut raw_lines = s.raw_lines();
+    /// assert!(s.ends_with("bar"));
+    /// assert!(!s.ends_with('\n'));
+    /// ```
+    #[inline]
+    pub fn ends_with<P: crate::pattern::Pattern>(
+        &self,
+        mut pattern: P,
+    ) -> bool {
+        pattern.suffix_len(*self).is_some()
+    }
+
+    /// Returns whether the text of the `RopeSlice` is equal to `other`,
+    /// ignoring differences in case.
     ///
-    /// assert_eq!("foo\n", raw_lines.next().unwrap());
-    /// assert_eq!("bar\r\n", raw_lines.next().unwrap());
-    /// assert_eq!("baz", raw_lines.next().unwrap());
-    /// assert_eq!(None, raw_lines.next());
+    /// See [`Rope::eq_ignore_case()`](crate::Rope::eq_ignore_case()) for
+    /// more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello WORLD!");
+    ///
+    /// assert!(r.byte_slice(6..).eq_ignore_case("world!"));
+    /// ```
+    #[inline]
+    pub fn eq_ignore_case(&self, other: &str) -> bool {
+        super::case::eq_ignore_case(*self, other)
+    }
+
+    /// Returns the byte offset of the first occurrence of `pattern` in the
+    /// `RopeSlice`, or `None` if it doesn't occur.
+    ///
+    /// Occurrences spanning multiple chunks are found without allocating a
+    /// `String` for the whole text.
+    ///
+    /// # Examples
     ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar foo");
     /// let s = r.byte_slice(..);
     ///
-    /// let mut raw_lines = s.raw_lines();
+    /// assert_eq!(s.find("foo"), Some(0));
+    /// assert_eq!(s.find("bar"), Some(4));
+    /// assert_eq!(s.find("baz"), None);
+    /// ```
+    #[inline]
+    pub fn find<P: crate::pattern::Pattern>(
+        &self,
+        mut pattern: P,
+    ) -> Option<usize> {
+        crate::pattern::next_match(&mut pattern, *self, 0, false)
+            .map(|range| range.start)
+    }
+
+    /// Returns the byte range of the first case-insensitive occurrence of
+    /// `pattern` in the `RopeSlice`, or `None` if it doesn't occur.
+    ///
+    /// See [`Rope::find_ignore_case()`](crate::Rope::find_ignore_case()) for
+    /// more infos.
+    ///
+    /// # Examples
     ///
-    /// assert_eq!("foo\n", raw_lines.next().unwrap());
-    /// assert_eq!("bar\r\n", raw_lines.next().unwrap());
-    /// assert_eq!("baz\n", raw_lines.next().unwrap());
-    /// assert_eq!(None, raw_lines.next());
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo BAR foo");
+    /// let s = r.byte_slice(1..);
+    ///
+    /// assert_eq!(s.find_ignore_case("bar"), Some(3..6));
     /// ```
     #[inline]
-    pub fn raw_lines(&self) -> RawLines<'a> {
-        RawLines::from(self)
+    pub fn find_ignore_case(
+        &self,
+        pattern: &str,
+    ) -> Option<core::ops::Range<usize>> {
+        self.find_iter_ignore_case(pattern).next()
     }
 
-    /// Removes the last char from the range spanned by this slice.
+    /// Returns an iterator over the byte ranges of the non-overlapping
+    /// matches of `pattern` in the `RopeSlice`, from front to back. The
+    /// pattern can be any [`Pattern`](crate::pattern::Pattern).
     ///
-    /// # Panics
+    /// Like with [`str::match_indices()`], an empty string matches at every
+    /// char boundary.
     ///
-    /// Panics if the slice is empty or if the relative offset is not on a char
-    /// boundary.
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar foo");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let mut matches = s.find_iter("foo");
+    ///
+    /// assert_eq!(matches.next(), Some(0..3));
+    /// assert_eq!(matches.next(), Some(8..11));
+    /// assert_eq!(matches.next(), None);
+    ///
+    /// let mut matches = s.find_iter(char::is_whitespace);
+    ///
+    /// assert_eq!(matches.next(), Some(3..4));
+    /// assert_eq!(matches.next(), Some(7..8));
+    /// assert_eq!(matches.next(), None);
+    /// ```
     #[inline]
-    pub(super) fn truncate_last_char(&mut self) {
-        debug_assert!(!self.is_empty());
+    pub fn find_iter<P: crate::pattern::Pattern>(
+        &self,
+        pattern: P,
+    ) -> FindIter<'a, P> {
+        FindIter::new(*self, pattern)
+    }
 
-        let slice = &mut self.tree_slice;
+    /// Returns an iterator over the byte ranges of the non-overlapping
+    /// case-insensitive occurrences of `pattern` in the `RopeSlice`, from
+    /// front to back.
+    ///
+    /// See [`Rope::find_ignore_case()`](crate::Rope::find_ignore_case()) for
+    /// more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Foo bar FOO");
+    /// let s = r.byte_slice(1..);
+    ///
+    /// let mut matches = s.find_iter_ignore_case("o");
+    ///
+    /// assert_eq!(matches.next(), Some(0..1));
+    /// assert_eq!(matches.next(), Some(1..2));
+    /// assert_eq!(matches.next(), Some(8..9));
+    /// ```
+    #[inline]
+    pub fn find_iter_ignore_case(
+        &self,
+        pattern: &str,
+    ) -> crate::iter::FindIterIgnoreCase<'a> {
+        crate::iter::FindIterIgnoreCase::new(*self, pattern)
+    }
 
-        // The last slice only contains one byte so we have to re-slice.
-        if slice.end_summary.bytes() == 1 {
-            *self = self.byte_slice(..self.byte_len() - 1);
+    /// Returns the byte offset of the last grapheme cluster boundary at or
+    /// before the given byte offset.
+    ///
+    /// Unlike [`prev_grapheme_boundary()`](Self::prev_grapheme_boundary()),
+    /// this returns the byte offset itself if it's already a boundary, and
+    /// the offset doesn't have to lie on a code point boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a🐻‍❄️\r\nb");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.floor_grapheme_boundary(1), 1);
+    /// assert_eq!(s.floor_grapheme_boundary(3), 1); // inside '🐻‍❄️'
+    /// assert_eq!(s.floor_grapheme_boundary(15), 14); // between '\r' and '\n'
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn floor_grapheme_boundary(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
         }
-        // The last slice contains more than 2 bytes so we can just mutate
-        // in place.
-        else {
-            let last = &mut slice.end_slice;
-
-            slice.summary -= slice.end_summary;
 
-            let new_end_summary = last.truncate_last_char(slice.end_summary);
+        floor_grapheme_boundary(self.byte_slice(..), byte_offset)
+    }
 
-            slice.end_summary = new_end_summary;
+    /// Calls `fun` on every chunk of the `RopeSlice` within the specified byte
+    /// range, from front to back, stopping as soon as it returns
+    /// [`ControlFlow::Break`].
+    ///
+    /// This yields the same chunks as calling [`chunks()`](Self::chunks()) on
+    /// the corresponding [`byte_slice()`](Self::byte_slice()), but it doesn't
+    /// allocate and it tends to optimize better than the iterator in tight
+    /// loops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or the end of the byte range don't lie on a code
+    /// point boundary, if the start is greater than the end or if the end is
+    /// out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use core::ops::ControlFlow;
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let find_earth = |chunk: &str| {
+    ///     if chunk.contains('🌎') {
+    ///         ControlFlow::Break(())
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// };
+    ///
+    /// assert!(s.for_each_chunk(6.., find_earth).is_break());
+    /// assert!(s.for_each_chunk(..6, find_earth).is_continue());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn for_each_chunk<R, B, F>(
+        &self,
+        byte_range: R,
+        fun: F,
+    ) -> ControlFlow<B>
+    where
+        R: RangeBounds<usize>,
+        F: FnMut(&'a str) -> ControlFlow<B>,
+    {
+        for_each_chunk(self.byte_slice(byte_range), fun)
+    }
 
-            slice.summary += slice.end_summary;
+    /// Returns the byte at `byte_index`, or `None` if the index is out of
+    /// bounds.
+    ///
+    /// This is the non-panicking version of [`byte()`](Self::byte()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("bar");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.get_byte(2), Some(b'r'));
+    /// assert_eq!(s.get_byte(3), None);
+    /// ```
+    #[inline]
+    pub fn get_byte(&self, byte_index: usize) -> Option<u8> {
+        (byte_index < self.byte_len()).then(|| self.byte(byte_index))
+    }
 
-            if slice.leaf_count() == 1 {
-                slice.start_slice = slice.end_slice;
-                slice.start_summary = slice.end_summary;
+    /// Returns the slice in the specified byte range, or `None` if the range
+    /// is invalid.
+    ///
+    /// This is the non-panicking version of
+    /// [`byte_slice()`](Self::byte_slice()). Use
+    /// [`try_byte_slice()`](Self::try_byte_slice()) to know why the range
+    /// was rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🗻∈🌏");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.get_byte_slice(4..7).unwrap(), "∈");
+    /// assert!(s.get_byte_slice(..5).is_none());
+    /// assert!(s.get_byte_slice(..12).is_none());
+    /// ```
+    #[inline]
+    pub fn get_byte_slice<R>(self, byte_range: R) -> Option<RopeSlice<'a>>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_byte_slice(byte_range).ok()
+    }
+
+    /// Returns the line at `line_index`, or `None` if the index is out of
+    /// bounds.
+    ///
+    /// This is the non-panicking version of [`line()`](Self::line()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.get_line(1).unwrap(), "bar");
+    /// assert!(s.get_line(2).is_none());
+    /// ```
+    #[inline]
+    pub fn get_line(self, line_index: usize) -> Option<RopeSlice<'a>> {
+        self.try_line(line_index).ok()
+    }
+
+    /// Returns the slice in the specified line range, or `None` if the range
+    /// is invalid.
+    ///
+    /// This is the non-panicking version of
+    /// [`line_slice()`](Self::line_slice()). Use
+    /// [`try_line_slice()`](Self::try_line_slice()) to know why the range
+    /// was rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.get_line_slice(1..).unwrap(), "bar\r\nbaz\n");
+    /// assert!(s.get_line_slice(..4).is_none());
+    /// ```
+    #[inline]
+    pub fn get_line_slice<R>(self, line_range: R) -> Option<RopeSlice<'a>>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.try_line_slice(line_range).ok()
+    }
+
+    /// Returns a sub-slice of this `RopeSlice` in the specified byte range,
+    /// widened so that it doesn't split any grapheme cluster.
+    ///
+    /// The start of the range is moved back to the
+    /// [previous](Self::floor_grapheme_boundary()) grapheme boundary and its
+    /// end forward to the [next](Self::ceil_grapheme_boundary()) one, so
+    /// neither of them has to lie on a code point boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a🐻‍❄️\r\nb");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.grapheme_slice(..1), "a");
+    /// assert_eq!(s.grapheme_slice(..3), "a🐻‍❄️");
+    /// assert_eq!(s.grapheme_slice(15..), "\r\nb");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn grapheme_slice<R>(&self, byte_range: R) -> RopeSlice<'a>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.byte_len() {
+            panic::byte_offset_out_of_bounds(end, self.byte_len());
+        }
+
+        let start = floor_grapheme_boundary(self.byte_slice(..), start);
+        let end = ceil_grapheme_boundary(self.byte_slice(..), end);
+
+        self.byte_slice(start..end)
+    }
+
+    /// Returns an iterator over the extended grapheme clusters of this
+    /// `RopeSlice`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("arg!\r\n🐻‍❄️");
+    /// let s = r.byte_slice(3..);
+    ///
+    /// let mut graphemes = s.graphemes();
+    ///
+    /// assert_eq!(Some("!"), graphemes.next().as_deref());
+    /// assert_eq!(Some("\r\n"), graphemes.next().as_deref());
+    /// assert_eq!(Some("🐻‍❄️"), graphemes.next().as_deref());
+    /// assert_eq!(None, graphemes.next());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn graphemes(&self) -> crate::iter::Graphemes<'a> {
+        crate::iter::Graphemes::from(self)
+    }
+
+    /// Returns `true` if the given byte offset lies on a [`char`] boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Löwe 老虎 Léopard");
+    /// let s = r.byte_slice(3..);
+    ///
+    /// assert!(s.is_char_boundary(0));
+    /// assert!(s.is_char_boundary(s.byte_len()));
+    /// assert!(s.is_char_boundary(6)); // between '老' and '虎'
+    /// assert!(!s.is_char_boundary(12)); // between the 1st and 2nd byte of 'é'
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn is_char_boundary(&self, byte_offset: usize) -> bool {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let (chunk, ByteMetric(chunk_byte_offset)) =
+            self.tree_slice.leaf_at_measure(ByteMetric(byte_offset));
+
+        chunk.is_char_boundary(byte_offset - chunk_byte_offset)
+    }
+
+    /// Returns `true` if the `RopeSlice`'s byte length is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("");
+    /// assert!(r.byte_slice(..).is_empty());
+    ///
+    /// let r = Rope::from("foo");
+    /// assert!(!r.line_slice(..).is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.byte_len() == 0
+    }
+
+    /// Returns `true` if the given byte offset lies on a grapheme cluster
+    /// boundary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("aargh!\r\n🐻‍❄️");
+    /// let s = r.line_slice(1..);
+    ///
+    /// assert!(s.is_grapheme_boundary(0));
+    /// assert!(s.is_grapheme_boundary(s.byte_len()));
+    /// assert!(!s.is_grapheme_boundary(4)); // between the 1st and 2nd code point of '🐻‍❄️'
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn is_grapheme_boundary(&self, byte_offset: usize) -> bool {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        is_grapheme_boundary(*self, byte_offset)
+    }
+
+    /// Returns whether the text of the `RopeSlice` is in Unicode
+    /// Normalization Form C.
+    ///
+    /// See [`Rope::is_nfc()`](crate::Rope::is_nfc()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("cafe\u{301}");
+    ///
+    /// assert!(r.byte_slice(..4).is_nfc());
+    /// assert!(!r.byte_slice(..).is_nfc());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-normalization")))]
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    pub fn is_nfc(&self) -> bool {
+        super::normalization::is_nfc(*self)
+    }
+
+    /// Returns an iterator over the summaries of the leaves of this
+    /// `RopeSlice`, together with the byte offset at which each leaf starts.
+    ///
+    /// The summaries are stored in the leaves themselves, so this doesn't
+    /// touch the text at all. It can be used to build external indexes (e.g.
+    /// of line starts) from metadata alone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!\n");
+    /// let s = r.byte_slice(6..);
+    ///
+    /// let mut bytes = 0;
+    /// let mut line_breaks = 0;
+    ///
+    /// for (offset, summary) in s.leaf_summaries() {
+    ///     assert_eq!(offset, bytes);
+    ///     bytes += summary.bytes();
+    ///     line_breaks += summary.line_breaks();
+    /// }
+    ///
+    /// assert_eq!(bytes, s.byte_len());
+    /// assert_eq!(line_breaks, 1);
+    /// ```
+    #[inline]
+    pub fn leaf_summaries(&self) -> LeafSummaries<'a> {
+        LeafSummaries::from(self)
+    }
+
+    /// Returns the line at `line_index`, without its line terminator.
+    ///
+    /// If you want to include the line break consider taking a
+    /// [`line_slice()`](Self::line_slice()) in the
+    /// `line_index..line_index + 1` range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line index is out of bounds (i.e. greater than or equal
+    /// to [`line_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz");
+    /// let s = r.line_slice(..2);
+    ///
+    /// assert_eq!(s.line(0), "foo");
+    /// assert_eq!(s.line(1), "bar");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line(self, line_index: usize) -> RopeSlice<'a> {
+        if line_index >= self.line_len() {
+            panic::line_offset_out_of_bounds(line_index, self.line_len());
+        }
+
+        let tree_slice = self
+            .tree_slice
+            .slice(RawLineMetric(line_index)..RawLineMetric(line_index + 1));
+
+        let mut line = Self { tree_slice, has_trailing_newline: false };
+
+        if line.tree_slice.summary().line_breaks() == 1 {
+            line.truncate_trailing_line_break();
+        }
+
+        line
+    }
+
+    /// Returns the number of lines in the `RopeSlice`.
+    ///
+    /// The final line break is optional and doesn't count as a separate empty
+    /// line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a\nb\r\n");
+    ///
+    /// let s = r.byte_slice(..);
+    /// assert_eq!(s.line_len(), 2);
+    ///
+    /// let s = r.byte_slice(..3);
+    /// assert_eq!(s.line_len(), 2);
+    ///
+    /// let s = r.byte_slice(..2);
+    /// assert_eq!(s.line_len(), 1);
+    ///
+    /// let s = r.byte_slice(..1);
+    /// assert_eq!(s.line_len(), 1);
+    ///
+    /// let s = r.byte_slice(..0);
+    /// assert_eq!(s.line_len(), 0);
+    /// ```
+    #[inline]
+    pub fn line_len(&self) -> usize {
+        self.tree_slice.summary().line_breaks() + 1
+            - (self.has_trailing_newline as usize)
+            - (self.is_empty() as usize)
+    }
+
+    /// Returns the line offset of the given byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz");
+    ///
+    /// let s = r.byte_slice(..);
+    /// assert_eq!(s.line_of_byte(0), 0);
+    /// assert_eq!(s.line_of_byte(4), 1);
+    ///
+    /// let s = r.line_slice(1..);
+    /// assert_eq!(s.line_of_byte(0), 0);
+    /// assert_eq!(s.line_of_byte(4), 0); // between the '\r' and the '\n'
+    /// assert_eq!(s.line_of_byte(s.byte_len()), 1);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line_of_byte(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let RawLineMetric(line_offset) =
+            self.tree_slice.convert_measure(ByteMetric(byte_offset));
+
+        line_offset
+    }
+
+    /// Returns an iterator over the byte ranges of the lines of this
+    /// `RopeSlice`, not including the line terminators.
+    ///
+    /// The ranges are relative to the start of the slice. See
+    /// [`Rope::line_ranges()`] for more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    /// let s = r.byte_slice(4..);
+    ///
+    /// let mut ranges = s.line_ranges();
+    ///
+    /// assert_eq!(ranges.next(), Some(0..3));
+    /// assert_eq!(ranges.next_back(), Some(5..8));
+    /// assert_eq!(ranges.next(), None);
+    /// ```
+    #[inline]
+    pub fn line_ranges(&self) -> crate::iter::LineRanges<'a> {
+        crate::iter::LineRanges::from(self)
+    }
+
+    /// Returns a sub-slice of this `RopeSlice` in the specified line range,
+    /// where the start and end of the range are interpreted as offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\nfoobar\n");
+    /// let s = r.byte_slice(2..17);
+    ///
+    /// assert_eq!(s.line_slice(..1), "o\n");
+    /// assert_eq!(s.line_slice(1..3), "bar\r\nbaz\n");
+    /// assert_eq!(s.line_slice(3..4), "foob");
+    /// assert_eq!(s.line_slice(4..), "");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn line_slice<R>(self, line_range: R) -> RopeSlice<'a>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(line_range, 0, self.line_len());
+
+        if start > end {
+            panic::line_start_after_end(start, end);
+        }
+
+        if end > self.line_len() {
+            panic::line_offset_out_of_bounds(end, self.line_len());
+        }
+
+        self.tree_slice.slice(RawLineMetric(start)..RawLineMetric(end)).into()
+    }
+
+    /// Returns an iterator over the lines of this `RopeSlice`, not including
+    /// the line terminators.
+    ///
+    /// The final line break is optional and doesn't cause the iterator to
+    /// return a final empty line.
+    ///
+    /// If you want to include the line breaks consider using the
+    /// [`raw_lines()`](Self::raw_lines()) method instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    /// let s = r.line_slice(1..);
+    ///
+    /// let mut lines = s.lines();
+    ///
+    /// assert_eq!("bar", lines.next().unwrap());
+    /// assert_eq!("baz", lines.next().unwrap());
+    /// assert_eq!(None, lines.next());
+    /// ```
+    #[inline]
+    pub fn lines(&self) -> Lines<'a> {
+        Lines::from(self)
+    }
+
+    /// Returns a pair of iterators over the lines before and after the
+    /// given line offset.
+    ///
+    /// See [`Rope::lines_at()`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\nbaz\nqux");
+    /// let s = r.line_slice(1..);
+    ///
+    /// let (before, after) = s.lines_at(2);
+    ///
+    /// assert_eq!(before.collect::<Vec<_>>(), ["baz", "bar"]);
+    /// assert_eq!(after.collect::<Vec<_>>(), ["qux"]);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn lines_at(
+        &self,
+        line_offset: usize,
+    ) -> (core::iter::Rev<Lines<'a>>, Lines<'a>) {
+        if line_offset > self.line_len() {
+            panic::line_offset_out_of_bounds(line_offset, self.line_len());
+        }
+
+        let before = self.line_slice(..line_offset);
+        let after = self.line_slice(line_offset..);
+
+        (before.lines().rev(), after.lines())
+    }
+
+    /// Returns an iterator over the non-overlapping matches of `pattern` in
+    /// the `RopeSlice`.
+    ///
+    /// See [`Rope::matches()`](crate::Rope::matches()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a1, b22, c333");
+    ///
+    /// let mut matches = r.byte_slice(4..).matches(char::is_numeric);
+    ///
+    /// assert_eq!(matches.next().unwrap(), "2");
+    /// assert_eq!(matches.next().unwrap(), "2");
+    /// assert_eq!(matches.count(), 3);
+    /// ```
+    #[inline]
+    pub fn matches<P: crate::pattern::Pattern>(
+        &self,
+        pattern: P,
+    ) -> crate::iter::Matches<'a, P> {
+        crate::iter::Matches::new(*self, pattern)
+    }
+
+    /// Returns the length in bytes of the longest line in the `RopeSlice`,
+    /// not counting the `"\n"` or `"\r\n"` terminating it.
+    ///
+    /// Unlike [`Rope::max_line_len()`](crate::Rope::max_line_len()) this runs
+    /// in O(log n), since the lines at the start and at the end of the slice
+    /// can be cut by its range, which means the leaves containing the start
+    /// and the end of the slice have to be rescanned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbarbaz\r\nqux");
+    ///
+    /// assert_eq!(r.byte_slice(..).max_line_len(), 6);
+    /// assert_eq!(r.byte_slice(6..).max_line_len(), 4);
+    /// assert_eq!(r.byte_slice(..6).max_line_len(), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "max-line-len")))]
+    #[cfg(feature = "max-line-len")]
+    #[inline]
+    pub fn max_line_len(&self) -> usize {
+        self.tree_slice.aggregate().line_lengths.max_line_len()
+    }
+
+    /// Returns an iterator over the lines of this `RopeSlice`, including the
+    /// line terminators.
+    ///
+    /// The final line break is optional and doesn't cause the iterator to
+    /// return a final empty line.
+    ///
+    /// If you don't  want to include the line breaks consider using the
+    /// [`raw_lines()`](Self::raw_lines()) method instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    /// let s = r.byte_slice(..r.byte_len() - 1);
+    ///
+    /// let mut raw_lines = s.raw_lines();
+    ///
+    /// assert_eq!("foo\n", raw_lines.next().unwrap());
+    /// assert_eq!("bar\r\n", raw_lines.next().unwrap());
+    /// assert_eq!("baz", raw_lines.next().unwrap());
+    /// assert_eq!(None, raw_lines.next());
+    ///
+    /// let s = r.byte_slice(..);
+    ///
+    /// let mut raw_lines = s.raw_lines();
+    ///
+    /// assert_eq!("foo\n", raw_lines.next().unwrap());
+    /// assert_eq!("bar\r\n", raw_lines.next().unwrap());
+    /// assert_eq!("baz\n", raw_lines.next().unwrap());
+    /// assert_eq!(None, raw_lines.next());
+    /// ```
+    #[inline]
+    pub fn raw_lines(&self) -> RawLines<'a> {
+        RawLines::from(self)
+    }
+
+    /// Returns a [`RopeReader`](crate::RopeReader) over the contents of the
+    /// `RopeSlice`, which implements [`Read`](std::io::Read) and
+    /// [`BufRead`](std::io::BufRead).
+    ///
+    /// This lets a `RopeSlice` be passed to any API taking a reader without
+    /// first copying its text into a contiguous buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// use std::io::Read;
+    ///
+    /// let r = Rope::from("ƒoo\nbär\r\nbaz");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let mut buf = String::new();
+    /// s.reader().read_to_string(&mut buf)?;
+    ///
+    /// assert_eq!(buf, "ƒoo\nbär\r\nbaz");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn reader(&self) -> crate::RopeReader<'a> {
+        crate::RopeReader::new(self.chunks())
+    }
+
+    /// Returns an iterator over the byte ranges of the non-overlapping
+    /// matches of `regex` in the `RopeSlice`, from front to back.
+    ///
+    /// The search runs directly over the chunks of the `RopeSlice` without
+    /// copying its text, and it has the same semantics as
+    /// [`Regex::find_iter()`](crate::Regex::find_iter()). Within a
+    /// `RopeSlice`, `^` and `$` match at its start and end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the regex's lazy DFA gives up on the search, which can only
+    /// happen if it was configured with quit bytes or with a minimum cache
+    /// clear count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Regex, Rope};
+    /// #
+    /// let r = Rope::from("foo 42, bar 1337");
+    /// let s = r.byte_slice(..);
+    /// let regex = Regex::new(r"[0-9]+").unwrap();
+    ///
+    /// let mut matches = s.regex_find_iter(&regex);
+    ///
+    /// assert_eq!(matches.next(), Some(4..6));
+    /// assert_eq!(matches.next(), Some(12..16));
+    /// assert_eq!(matches.next(), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    #[cfg(feature = "regex")]
+    #[inline]
+    pub fn regex_find_iter<'r>(
+        &self,
+        regex: &'r crate::Regex,
+    ) -> crate::iter::RegexFindIter<'a, 'r> {
+        crate::iter::RegexFindIter::new(*self, regex)
+    }
+
+    /// Returns the byte offset of the first grapheme cluster boundary after
+    /// the given byte offset, or `None` if the offset is at the end of the
+    /// `RopeSlice`.
+    ///
+    /// This can be used to move a cursor one grapheme to the right, and it
+    /// only looks at the chunks around the byte offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🐻‍❄️🐻‍❄️");
+    /// let s = r.byte_slice(13..);
+    ///
+    /// assert_eq!(s.next_grapheme_boundary(0), Some(s.byte_len()));
+    /// assert_eq!(s.next_grapheme_boundary(s.byte_len()), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn next_grapheme_boundary(&self, byte_offset: usize) -> Option<usize> {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        next_grapheme_boundary(*self, byte_offset)
+    }
+
+    /// Returns a parallel iterator over the chunks of the `RopeSlice`.
+    ///
+    /// The `RopeSlice` is recursively split in half at leaf boundaries, so the
+    /// chunks are the same ones yielded by [`chunks()`](Self::chunks()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// use rayon::prelude::*;
+    ///
+    /// let r = Rope::from("Hello\nWorld\n".repeat(1000));
+    /// let s = r.byte_slice(..);
+    ///
+    /// let line_breaks =
+    ///     s.par_chunks().map(|chunk| chunk.matches('\n').count()).sum::<usize>();
+    ///
+    /// assert_eq!(line_breaks, 2000);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_chunks(&self) -> crate::iter::ParChunks<'a> {
+        crate::iter::ParChunks::from(self)
+    }
+
+    /// Returns a parallel iterator over the lines of the `RopeSlice`.
+    ///
+    /// The `RopeSlice` is recursively split in half at line boundaries, so the
+    /// lines are the same ones yielded by [`lines()`](Self::lines()), and
+    /// collecting them preserves their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// use rayon::prelude::*;
+    ///
+    /// let r = Rope::from("foo\nbar baz\n".repeat(1000));
+    /// let s = r.byte_slice(..);
+    ///
+    /// let words = s
+    ///     .par_lines()
+    ///     .map(|line| line.chars().filter(|&ch| ch == ' ').count() + 1)
+    ///     .sum::<usize>();
+    ///
+    /// assert_eq!(words, 3000);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_lines(&self) -> crate::iter::ParLines<'a> {
+        crate::iter::ParLines::from(self)
+    }
+
+    /// Returns the [`Position`] of the given byte offset, where the column is
+    /// measured in the units of the given [`PositionEncoding`].
+    ///
+    /// This is meant to convert byte offsets into positions to be sent to a
+    /// language server client. The column is measured by scanning its line,
+    /// so this runs in O(log n + line length).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Position, PositionEncoding, Rope};
+    /// #
+    /// let r = Rope::from("foo\na𐐀bc");
+    /// let s = r.byte_slice(..);
+    ///
+    /// // "𐐀" is 4 bytes in UTF-8 and 2 code units in UTF-16.
+    /// assert_eq!(
+    ///     s.position_of_byte(10, PositionEncoding::Utf8),
+    ///     Position::new(1, 6)
+    /// );
+    /// assert_eq!(
+    ///     s.position_of_byte(10, PositionEncoding::Utf16),
+    ///     Position::new(1, 4)
+    /// );
+    /// assert_eq!(
+    ///     s.position_of_byte(10, PositionEncoding::Utf32),
+    ///     Position::new(1, 3)
+    /// );
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn position_of_byte(
+        &self,
+        byte_offset: usize,
+        encoding: PositionEncoding,
+    ) -> Position {
+        position_of_byte(*self, byte_offset, encoding)
+    }
+
+    /// Returns the byte offset of the last grapheme cluster boundary before
+    /// the given byte offset, or `None` if the offset is at the start of the
+    /// `RopeSlice`.
+    ///
+    /// This can be used to move a cursor one grapheme to the left or to
+    /// implement backspace, and it only looks at the chunks around the byte
+    /// offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🐻‍❄️🐻‍❄️");
+    /// let s = r.byte_slice(..26);
+    ///
+    /// assert_eq!(s.prev_grapheme_boundary(s.byte_len()), Some(13));
+    /// assert_eq!(s.prev_grapheme_boundary(13), Some(0));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[track_caller]
+    #[inline]
+    pub fn prev_grapheme_boundary(&self, byte_offset: usize) -> Option<usize> {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        prev_grapheme_boundary(*self, byte_offset)
+    }
+
+    /// Returns the byte offset of the last occurrence of `pattern` in the
+    /// `RopeSlice`, or `None` if it doesn't occur.
+    ///
+    /// See [`find()`](Self::find()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo bar foo");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.rfind("foo"), Some(8));
+    /// assert_eq!(s.rfind("bar"), Some(4));
+    /// assert_eq!(s.rfind("baz"), None);
+    /// ```
+    #[inline]
+    pub fn rfind<P: crate::pattern::Pattern>(
+        &self,
+        mut pattern: P,
+    ) -> Option<usize> {
+        let byte_len = self.byte_len();
+        crate::pattern::prev_match(&mut pattern, *self, byte_len, false)
+            .map(|range| range.start)
+    }
+
+    /// Returns an iterator over the sub-slices of the `RopeSlice` separated
+    /// by `pattern`, starting from the end.
+    ///
+    /// See [`Rope::rsplit()`](crate::Rope::rsplit()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a b c\nd");
+    ///
+    /// let fields = r.line(0).rsplit(" ").collect::<Vec<_>>();
+    ///
+    /// assert_eq!(fields, ["c", "b", "a"]);
+    /// ```
+    #[inline]
+    pub fn rsplit<P: crate::pattern::Pattern>(
+        &self,
+        pattern: P,
+    ) -> crate::iter::RSplit<'a, P> {
+        crate::iter::RSplit::new(*self, pattern)
+    }
+
+    /// Returns an iterator over at most `n` sub-slices of the `RopeSlice`
+    /// separated by `pattern`, starting from the end.
+    ///
+    /// See [`Rope::rsplitn()`](crate::Rope::rsplitn()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a b c\nd");
+    ///
+    /// let fields = r.line(0).rsplitn(2, " ").collect::<Vec<_>>();
+    ///
+    /// assert_eq!(fields, ["c", "a b"]);
+    /// ```
+    #[inline]
+    pub fn rsplitn<P: crate::pattern::Pattern>(
+        &self,
+        n: usize,
+        pattern: P,
+    ) -> crate::iter::RSplitN<'a, P> {
+        crate::iter::RSplitN::new(*self, n, pattern)
+    }
+
+    /// Returns an iterator over the sentences of this `RopeSlice`, as defined by
+    /// [Unicode Standard Annex #29][uax29].
+    ///
+    /// Each sentence includes the whitespace and line breaks following it, so
+    /// concatenating all the items gives back the original text.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Sentence_Boundaries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Pi is about 3.14. Is it?\nYes.");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let sentences = s.sentences().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(sentences, ["Pi is about 3.14. ", "Is it?\n", "Yes."]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn sentences(&self) -> crate::iter::Sentences<'a> {
+        crate::iter::Sentences::from(self)
+    }
+
+    /// Returns an iterator over the sub-slices of the `RopeSlice` separated
+    /// by `pattern`.
+    ///
+    /// See [`Rope::split()`](crate::Rope::split()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a b c\nd");
+    ///
+    /// let fields = r.line(0).split(" ").collect::<Vec<_>>();
+    ///
+    /// assert_eq!(fields, ["a", "b", "c"]);
+    /// ```
+    #[inline]
+    pub fn split<P: crate::pattern::Pattern>(
+        &self,
+        pattern: P,
+    ) -> crate::iter::Split<'a, P> {
+        crate::iter::Split::new(*self, pattern)
+    }
+
+    /// Returns an iterator over at most `n` sub-slices of the `RopeSlice`
+    /// separated by `pattern`.
+    ///
+    /// See [`Rope::splitn()`](crate::Rope::splitn()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a b c\nd");
+    ///
+    /// let fields = r.line(0).splitn(2, " ").collect::<Vec<_>>();
+    ///
+    /// assert_eq!(fields, ["a", "b c"]);
+    /// ```
+    #[inline]
+    pub fn splitn<P: crate::pattern::Pattern>(
+        &self,
+        n: usize,
+        pattern: P,
+    ) -> crate::iter::SplitN<'a, P> {
+        crate::iter::SplitN::new(*self, n, pattern)
+    }
+
+    /// Returns whether the `RopeSlice` starts with the given pattern, which
+    /// can be a `char`, a `&str`, a `RopeSlice` or a [`&Rope`](crate::Rope).
+    ///
+    /// See [`Rope::starts_with()`](crate::Rope::starts_with()) for more
+    /// infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar baz");
+    /// let s = r.line(1);
+    ///
+    /// assert!(s.starts_with("bar"));
+    /// assert!(s.starts_with(r.byte_slice(4..5)));
+    /// assert!(!s.starts_with('f'));
+    /// ```
+    #[inline]
+    pub fn starts_with<P: crate::pattern::Pattern>(
+        &self,
+        mut pattern: P,
+    ) -> bool {
+        pattern.prefix_len(*self).is_some()
+    }
+
+    /// Removes the last char from the range spanned by this slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice is empty or if the relative offset is not on a char
+    /// boundary.
+    #[inline]
+    pub(super) fn truncate_last_char(&mut self) {
+        debug_assert!(!self.is_empty());
+
+        let slice = &mut self.tree_slice;
+
+        // The last slice only contains one byte so we have to re-slice.
+        if slice.end_summary.bytes() == 1 {
+            *self = self.byte_slice(..self.byte_len() - 1);
+        }
+        // The last slice contains more than 2 bytes so we can just mutate
+        // in place.
+        else {
+            let last = &mut slice.end_slice;
+
+            slice.summary -= slice.end_summary;
+
+            let new_end_summary = last.truncate_last_char(slice.end_summary);
+
+            slice.end_summary = new_end_summary;
+
+            slice.summary += slice.end_summary;
+
+            if slice.leaf_count() == 1 {
+                slice.start_slice = slice.end_slice;
+                slice.start_summary = slice.end_summary;
             }
         }
     }
 
-    /// Removes the trailing line break (either LF or CRLF) from the range
-    /// spanned by this slice.
+    /// Removes the trailing line break (either LF or CRLF) from the range
+    /// spanned by this slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this slice doesn't have a trailing line break.
+    #[inline]
+    pub(super) fn truncate_trailing_line_break(&mut self) {
+        debug_assert!(
+            self.tree_slice.end_slice().last_chunk().ends_with('\n')
+        );
+
+        self.truncate_last_char();
+
+        if self.tree_slice.end_slice().last_chunk().ends_with('\r') {
+            self.truncate_last_char();
+        }
+    }
+
+    /// Returns the contents of the `RopeSlice` as a [`Cow`], which borrows
+    /// them if they're stored contiguously (see [`as_str()`](Self::as_str()))
+    /// and only allocates a new `String` if they're not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello World!");
+    ///
+    /// let (chunk, _) = r.cursor(0).chunk();
+    /// assert!(matches!(r.byte_slice(..chunk.len()).to_cow(), Cow::Borrowed(_)));
+    ///
+    /// assert_eq!(r.byte_slice(..).to_cow(), "Hello World!");
+    /// ```
+    #[inline]
+    pub fn to_cow(&self) -> Cow<'a, str> {
+        match self.as_str() {
+            Some(s) => Cow::Borrowed(s),
+            None => Cow::Owned(self.to_string()),
+        }
+    }
+
+    /// Returns a new [`Rope`](crate::Rope) with the lowercase equivalent of
+    /// the text of this `RopeSlice`.
+    ///
+    /// See [`Rope::to_lowercase()`](crate::Rope::to_lowercase()) for more
+    /// infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello WORLD!");
+    ///
+    /// assert_eq!(r.byte_slice(6..).to_lowercase(), "world!");
+    /// ```
+    #[inline]
+    pub fn to_lowercase(&self) -> Rope {
+        super::case::to_lowercase(*self)
+    }
+
+    /// Returns a new [`Rope`](crate::Rope) with the text of this `RopeSlice`
+    /// in Unicode Normalization Form C.
+    ///
+    /// See [`Rope::to_nfc()`](crate::Rope::to_nfc()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("un cafe\u{301}");
+    ///
+    /// assert_eq!(r.byte_slice(3..).to_nfc(), "caf\u{e9}");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-normalization")))]
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    pub fn to_nfc(&self) -> Rope {
+        super::normalization::to_nfc(*self)
+    }
+
+    /// Returns a new [`Rope`](crate::Rope) with the text of this `RopeSlice`
+    /// in Unicode Normalization Form D.
+    ///
+    /// See [`Rope::to_nfd()`](crate::Rope::to_nfd()) for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("un caf\u{e9}");
+    ///
+    /// assert_eq!(r.byte_slice(3..).to_nfd(), "cafe\u{301}");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-normalization")))]
+    #[cfg(feature = "unicode-normalization")]
+    #[inline]
+    pub fn to_nfd(&self) -> Rope {
+        super::normalization::to_nfd(*self)
+    }
+
+    /// Returns a new [`Rope`](crate::Rope) with the uppercase equivalent of
+    /// the text of this `RopeSlice`.
+    ///
+    /// See [`Rope::to_uppercase()`](crate::Rope::to_uppercase()) for more
+    /// infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello world!");
+    ///
+    /// assert_eq!(r.byte_slice(6..).to_uppercase(), "WORLD!");
+    /// ```
+    #[inline]
+    pub fn to_uppercase(&self) -> Rope {
+        super::case::to_uppercase(*self)
+    }
+
+    /// Returns a sub-slice of this `RopeSlice` with its leading and trailing
+    /// whitespace removed.
+    ///
+    /// Whitespace is defined as in [`char::is_whitespace()`], so this
+    /// includes line breaks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("\t foo bar \n");
+    ///
+    /// assert_eq!(r.byte_slice(..).trim(), "foo bar");
+    /// ```
+    #[inline]
+    pub fn trim(&self) -> RopeSlice<'a> {
+        self.trim_matches(char::is_whitespace)
+    }
+
+    /// Returns a sub-slice of this `RopeSlice` with its trailing whitespace
+    /// removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("\t foo bar \n");
+    ///
+    /// assert_eq!(r.byte_slice(..).trim_end(), "\t foo bar");
+    /// ```
+    #[inline]
+    pub fn trim_end(&self) -> RopeSlice<'a> {
+        self.trim_end_matches(char::is_whitespace)
+    }
+
+    /// Returns a sub-slice of this `RopeSlice` with all the suffixes matching
+    /// the pattern repeatedly removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo123barbar");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.trim_end_matches("bar"), "foo123");
+    /// assert_eq!(s.trim_end_matches(char::is_alphabetic), "foo123");
+    /// assert_eq!(s.trim_end_matches(['a', 'b', 'r']), "foo123");
+    /// ```
+    #[inline]
+    pub fn trim_end_matches<P: crate::pattern::Pattern>(
+        &self,
+        mut pattern: P,
+    ) -> RopeSlice<'a> {
+        let trimmed = pattern.repeated_suffix_len(*self);
+        self.byte_slice(..self.byte_len() - trimmed)
+    }
+
+    /// Returns a sub-slice of this `RopeSlice` with all the prefixes and
+    /// suffixes matching the pattern repeatedly removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("123foo1bar123");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.trim_matches(char::is_numeric), "foo1bar");
+    /// assert_eq!(s.trim_matches("123"), "foo1bar");
+    /// ```
+    #[inline]
+    pub fn trim_matches<P: crate::pattern::Pattern>(
+        &self,
+        mut pattern: P,
+    ) -> RopeSlice<'a> {
+        let start = pattern.repeated_prefix_len(*self);
+        let rest = self.byte_slice(start..);
+        let trimmed = pattern.repeated_suffix_len(rest);
+        rest.byte_slice(..rest.byte_len() - trimmed)
+    }
+
+    /// Returns a sub-slice of this `RopeSlice` with its leading whitespace
+    /// removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("\t foo bar \n");
+    ///
+    /// assert_eq!(r.byte_slice(..).trim_start(), "foo bar \n");
+    /// ```
+    #[inline]
+    pub fn trim_start(&self) -> RopeSlice<'a> {
+        self.trim_start_matches(char::is_whitespace)
+    }
+
+    /// Returns a sub-slice of this `RopeSlice` with all the prefixes matching
+    /// the pattern repeatedly removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foofoo123bar");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.trim_start_matches("foo"), "123bar");
+    /// assert_eq!(s.trim_start_matches('f'), "oofoo123bar");
+    /// assert_eq!(s.trim_start_matches(&['f', 'o'][..]), "123bar");
+    /// ```
+    #[inline]
+    pub fn trim_start_matches<P: crate::pattern::Pattern>(
+        &self,
+        mut pattern: P,
+    ) -> RopeSlice<'a> {
+        let trimmed = pattern.repeated_prefix_len(*self);
+        self.byte_slice(trimmed..)
+    }
+
+    /// Non-panicking version of [`byte_slice()`](Self::byte_slice()).
+    ///
+    /// Returns an [`Error`](crate::Error) if the start or the end of the byte
+    /// range don't lie on a code point boundary, if the start is greater than
+    /// the end or if the end is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Error, Rope};
+    /// #
+    /// let r = Rope::from("🗻∈🌏");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.try_byte_slice(4..7).unwrap(), "∈");
+    ///
+    /// assert_eq!(
+    ///     s.try_byte_slice(..5),
+    ///     Err(Error::ByteOffsetNotCharBoundary { byte_offset: 5 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_byte_slice<R>(
+        self,
+        byte_range: R,
+    ) -> Result<RopeSlice<'a>, Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = check_byte_range(self, byte_range)?;
+        Ok(self.byte_slice(start..end))
+    }
+
+    /// Non-panicking version of [`line()`](Self::line()).
+    ///
+    /// Returns an [`Error`](crate::Error) if the line index is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Error, Rope};
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.try_line(1).unwrap(), "bar");
+    ///
+    /// assert_eq!(
+    ///     s.try_line(2),
+    ///     Err(Error::LineIndexOutOfBounds { line_index: 2, line_len: 2 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_line(self, line_index: usize) -> Result<RopeSlice<'a>, Error> {
+        check_line_index(self, line_index)?;
+        Ok(self.line(line_index))
+    }
+
+    /// Non-panicking version of [`line_slice()`](Self::line_slice()).
+    ///
+    /// Returns an [`Error`](crate::Error) if the start is greater than the
+    /// end or if the end is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{Error, Rope};
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.try_line_slice(1..).unwrap(), "bar\r\nbaz\n");
+    ///
+    /// assert_eq!(
+    ///     s.try_line_slice(2..1),
+    ///     Err(Error::LineStartAfterEnd { line_start: 2, line_end: 1 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_line_slice<R>(
+        self,
+        line_range: R,
+    ) -> Result<RopeSlice<'a>, Error>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = check_line_range(self, line_range)?;
+        Ok(self.line_slice(start..end))
+    }
+
+    /// Returns the number of lines in the `RopeSlice` when any of the
+    /// mandatory line breaks of
+    /// [UAX #14](https://www.unicode.org/reports/tr14/) is treated as a line
+    /// terminator.
     ///
-    /// # Panics
+    /// See [`Rope::unicode_line_len()`] for more.
     ///
-    /// Panics if this slice doesn't have a trailing line break.
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\rbar\r\nbaz\u{2028}");
+    /// let s = r.byte_slice(4..);
+    ///
+    /// assert_eq!(s.unicode_line_len(), 2);
+    /// ```
     #[inline]
-    pub(super) fn truncate_trailing_line_break(&mut self) {
-        debug_assert!(
-            self.tree_slice.end_slice().last_chunk().ends_with('\n')
-        );
-
-        self.truncate_last_char();
+    pub fn unicode_line_len(&self) -> usize {
+        super::line_ending::unicode_line_len(*self)
+    }
 
-        if self.tree_slice.end_slice().last_chunk().ends_with('\r') {
-            self.truncate_last_char();
-        }
+    /// Returns an iterator over the lines of this `RopeSlice`, not including
+    /// the line terminators, when any of the mandatory line breaks of
+    /// [UAX #14](https://www.unicode.org/reports/tr14/) is treated as a line
+    /// terminator.
+    ///
+    /// See [`Rope::unicode_lines()`] for more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\rbar\r\nbaz");
+    /// let s = r.byte_slice(4..);
+    ///
+    /// let mut lines = s.unicode_lines();
+    ///
+    /// assert_eq!("bar", lines.next().unwrap());
+    /// assert_eq!("baz", lines.next().unwrap());
+    /// assert_eq!(None, lines.next());
+    /// ```
+    #[inline]
+    pub fn unicode_lines(&self) -> crate::iter::UnicodeLines<'a> {
+        crate::iter::UnicodeLines::from(self)
     }
 
     /// Returns the number of UTF-16 code units this `RopeSlice` would span if
@@ -690,8 +2614,9 @@ impl<'a> RopeSlice<'a> {
     ///
     /// # Panics
     ///
-    /// Panics if the start is greater than the end or if the end is out of
-    /// bounds (i.e. greater than [`utf16_len()`](Self::utf16_len())).
+    /// Panics if the start is greater than the end, if the end is out of
+    /// bounds (i.e. greater than [`utf16_len()`](Self::utf16_len())) or if
+    /// either of them doesn't lie on a code point boundary.
     ///
     /// # Examples
     ///
@@ -729,6 +2654,315 @@ impl<'a> RopeSlice<'a> {
 
         self.tree_slice.slice(Utf16Metric(start)..Utf16Metric(end)).into()
     }
+
+    /// Checks that all the internal invariants of the `RopeSlice` hold,
+    /// panicking if they don't.
+    ///
+    /// This checks the summaries and the gap buffers of the first and last
+    /// chunks of the slice, and the relationship between them and the root
+    /// of the slice. See [`Rope::validate()`](crate::Rope::validate()) for
+    /// validating a whole `Rope`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the invariants doesn't hold, which would always be a
+    /// bug in crop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello World!");
+    ///
+    /// r.byte_slice(3..8).validate();
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "validate")))]
+    #[cfg(feature = "validate")]
+    #[track_caller]
+    #[inline]
+    pub fn validate(&self) {
+        self.assert_invariants();
+    }
+
+    /// Returns the visual column of the given byte offset in its line, with
+    /// tabs expanding to the next multiple of `tab_width` columns.
+    ///
+    /// The width of every other char is measured with the
+    /// [`unicode-width`](https://docs.rs/unicode-width) crate. The column is
+    /// measured by scanning the line the byte offset is on, so this runs in
+    /// O(log n + line length).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\na\t日b\n");
+    /// let s = r.byte_slice(..);
+    ///
+    /// // The tab expands to the next tab stop and "日" is two columns wide.
+    /// assert_eq!(s.visual_col_of_byte(6, 4), 4);
+    /// assert_eq!(s.visual_col_of_byte(10, 4), 7);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "display-width")))]
+    #[cfg(feature = "display-width")]
+    #[track_caller]
+    #[inline]
+    pub fn visual_col_of_byte(
+        &self,
+        byte_offset: usize,
+        tab_width: usize,
+    ) -> usize {
+        super::position::visual_col_of_byte(*self, byte_offset, tab_width)
+    }
+
+    /// Returns the number of terminal columns this `RopeSlice` would take up if
+    /// it was displayed on a single row.
+    ///
+    /// This is the sum of the widths of all its chars as given by the
+    /// [`unicode-width`](https://docs.rs/unicode-width) crate, with control
+    /// characters like tabs and line breaks always having a width of zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a日b");
+    /// let s = r.byte_slice(..);
+    /// assert_eq!(s.width(), 4);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "width-metric")))]
+    #[cfg(feature = "width-metric")]
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.tree_slice.summary().width()
+    }
+
+    /// Returns the width offset corresponding to the given byte offset, i.e.
+    /// the number of terminal columns taken up by the text before it. See
+    /// [`width()`](Self::width()) for how the width is computed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("a日b");
+    /// let s = r.byte_slice(..);
+    /// assert_eq!(s.width_of_byte(4), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "width-metric")))]
+    #[cfg(feature = "width-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn width_of_byte(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let super::metrics::WidthMetric(width_offset) =
+            self.tree_slice.convert_measure(ByteMetric(byte_offset));
+
+        width_offset
+    }
+
+    /// Returns an iterator over the word boundaries of this `RopeSlice`, as
+    /// defined by [Unicode Standard Annex #29][uax29].
+    ///
+    /// Every word, run of whitespace and punctuation mark is yielded, so
+    /// concatenating all the items gives back the original text. Use
+    /// [`words()`](Self::words()) to only get the words.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Can't stop, won't stop!");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let bounds = s.word_bounds().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     bounds,
+    ///     ["Can't", " ", "stop", ",", " ", "won't", " ", "stop", "!"]
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn word_bounds(&self) -> crate::iter::WordBounds<'a> {
+        crate::iter::WordBounds::from(self)
+    }
+
+    /// Returns the number of whitespace-delimited words in the `RopeSlice`.
+    ///
+    /// A word cut by the start or the end of the slice is counted as a word.
+    /// Unlike [`Rope::word_len()`](crate::Rope::word_len()) this runs in
+    /// O(log n), otherwise see its documentation for more infos.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Can't stop, won't stop!");
+    ///
+    /// assert_eq!(r.byte_slice(..).word_len(), 4);
+    /// assert_eq!(r.byte_slice(3..14).word_len(), 3);
+    /// assert_eq!(r.byte_slice(5..6).word_len(), 0);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "word-count")))]
+    #[cfg(feature = "word-count")]
+    #[inline]
+    pub fn word_len(&self) -> usize {
+        self.tree_slice.aggregate().word_count.words()
+    }
+
+    /// Returns an iterator over the words of this `RopeSlice`, as defined by
+    /// [Unicode Standard Annex #29][uax29].
+    ///
+    /// This is the same as [`word_bounds()`](Self::word_bounds()) except
+    /// that the items not containing any alphanumeric characters, like
+    /// whitespace and punctuation, are skipped.
+    ///
+    /// [uax29]: https://www.unicode.org/reports/tr29/#Word_Boundaries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Can't stop, won't stop!");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let words = s.words().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(words, ["Can't", "stop", "won't", "stop"]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
+    #[cfg(feature = "graphemes")]
+    #[inline]
+    pub fn words(&self) -> crate::iter::Words<'a> {
+        crate::iter::Words::from(self)
+    }
+
+    /// Returns an iterator over the byte offsets where the visual rows of the
+    /// lines in the specified line range start when they're soft-wrapped to
+    /// fit in `width` columns.
+    ///
+    /// The first row of every line starts at the beginning of the line, so
+    /// the iterator yields at least one offset per line. Lines are broken
+    /// after the last whitespace that fits in a row, or in the middle of a
+    /// word if that word doesn't fit in a row on its own. Whitespace is
+    /// allowed to hang past the end of a row.
+    ///
+    /// The width of every char is measured with the
+    /// [`unicode-width`](https://docs.rs/unicode-width) crate, while tabs
+    /// expand to the next multiple of `tab_width` columns from the start of
+    /// the row they're in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero, if the start is greater than the end or if
+    /// the end is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello world, it's a long line\nshort\n");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let rows = s
+    ///     .wrap_points(.., 12, 4)
+    ///     .map(|offset| s.byte(offset) as char)
+    ///     .collect::<String>();
+    ///
+    /// assert_eq!(rows, "Hils");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "display-width")))]
+    #[cfg(feature = "display-width")]
+    #[track_caller]
+    #[inline]
+    pub fn wrap_points<R>(
+        self,
+        line_range: R,
+        width: usize,
+        tab_width: usize,
+    ) -> crate::iter::WrapPoints<'a>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(line_range, 0, self.line_len());
+
+        let lines = self.line_slice(start..end);
+
+        let byte_offset = self.byte_of_line(start);
+
+        crate::iter::WrapPoints::new(lines, byte_offset, width, tab_width)
+    }
+
+    /// Writes the contents of the `RopeSlice` to `writer`, one chunk at a time.
+    ///
+    /// Unlike going through [`to_string()`](ToString::to_string()), this
+    /// doesn't allocate.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error returned by `writer`, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("ƒoo\nbär\r\nbaz");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let mut buf = Vec::new();
+    /// s.write_to(&mut buf)?;
+    ///
+    /// assert_eq!(buf, "ƒoo\nbär\r\nbaz".as_bytes());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn write_to<W>(&self, mut writer: W) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+    {
+        for chunk in self.chunks() {
+            writer.write_all(chunk.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> From<&'a Rope> for RopeSlice<'a> {
+    #[inline]
+    fn from(rope: &'a Rope) -> Self {
+        rope.byte_slice(..)
+    }
 }
 
 impl<'a> From<TreeSlice<'a, { Rope::arity() }, RopeChunk>> for RopeSlice<'a> {
@@ -768,7 +3002,8 @@ impl core::cmp::PartialEq<RopeSlice<'_>> for RopeSlice<'_> {
     fn eq(&self, rhs: &RopeSlice<'_>) -> bool {
         (self.byte_len() == rhs.byte_len())
             && (self.line_len() == rhs.line_len())
-            && chunks_eq_chunks(self.chunks(), rhs.chunks())
+            && (self.tree_slice.ptr_eq(&rhs.tree_slice)
+                || chunks_eq_chunks(self.chunks(), rhs.chunks()))
     }
 }
 
@@ -836,3 +3071,72 @@ impl core::cmp::PartialEq<RopeSlice<'_>> for alloc::borrow::Cow<'_, str> {
 }
 
 impl core::cmp::Eq for RopeSlice<'_> {}
+
+/// Hashes the text of the `RopeSlice`, giving the same hash as a
+/// [`Rope`] with the same text.
+impl core::hash::Hash for RopeSlice<'_> {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        hash_chunks(self.chunks(), state);
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_chunks(self.chunks(), rhs.chunks()))
+    }
+}
+
+impl core::cmp::Ord for RopeSlice<'_> {
+    #[inline]
+    fn cmp(&self, rhs: &RopeSlice<'_>) -> core::cmp::Ordering {
+        chunks_cmp_chunks(self.chunks(), rhs.chunks())
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+impl core::cmp::PartialOrd<str> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &str) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_chunks(self.chunks(), core::iter::once(rhs)))
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for str {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+impl core::cmp::PartialOrd<&str> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &&str) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(*rhs)
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for &str {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for RopeSlice<'_> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_chunks(self.chunks(), serializer)
+    }
+}
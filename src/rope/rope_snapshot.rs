@@ -0,0 +1,140 @@
+use super::utils::debug_chunks;
+use super::{Rope, RopeSlice};
+
+/// An immutable snapshot of the contents of a [`Rope`], meant to be handed
+/// to other threads.
+///
+/// A `RopeSnapshot` is created by calling [`Rope::snapshot()`], and it's
+/// nothing more than a clone of the `Rope` that only gives access to its
+/// read-only methods. It's not a separate, frozen representation: it shares
+/// the whole B-tree with the `Rope` it was taken from, including the gap
+/// buffers of its leaves, so taking one is O(1). Editing that `Rope`
+/// afterwards clones the nodes and leaves on the path to the edit before
+/// modifying them, so the snapshot keeps seeing the text exactly as it was
+/// when it was taken.
+///
+/// Because the leaves keep their gaps, reading a snapshot costs the same as
+/// reading the `Rope` itself. Use [`Rope::freeze()`] to get a contiguous
+/// copy of the text instead.
+///
+/// `RopeSnapshot`s are `Send + Sync` and can only be read from, so they
+/// can be shared with background threads doing e.g. syntax highlighting or
+/// searching while the main thread keeps editing. Cloning one is also O(1).
+///
+/// All the read-only methods of [`Rope`] are available through
+/// [`Deref`](core::ops::Deref).
+///
+/// # Examples
+///
+/// ```
+/// # use crop::Rope;
+/// #
+/// let mut r = Rope::from("Hello World!");
+///
+/// let snapshot = r.snapshot();
+///
+/// let handle = std::thread::spawn(move || snapshot.line_len());
+///
+/// r.insert(5, "\n");
+///
+/// assert_eq!(handle.join().unwrap(), 1);
+/// assert_eq!(r.line_len(), 2);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct RopeSnapshot {
+    rope: Rope,
+}
+
+impl RopeSnapshot {
+    /// Returns the contents of the snapshot as a [`Rope`] reference.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello World!");
+    ///
+    /// assert_eq!(r.snapshot().as_rope(), &r);
+    /// ```
+    #[inline]
+    pub fn as_rope(&self) -> &Rope {
+        &self.rope
+    }
+
+    /// Returns a [`RopeSlice`] spanning the whole snapshot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello World!");
+    ///
+    /// assert_eq!(r.snapshot().as_slice(), "Hello World!");
+    /// ```
+    #[inline]
+    pub fn as_slice(&self) -> RopeSlice<'_> {
+        self.rope.byte_slice(..)
+    }
+
+    /// Turns the snapshot into an editable [`Rope`].
+    ///
+    /// This is O(1), and the returned `Rope` keeps sharing its contents with
+    /// any other clone of the snapshot until it's edited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello World!");
+    ///
+    /// let mut copy = r.snapshot().into_rope();
+    /// copy.delete(5..);
+    ///
+    /// assert_eq!(copy, "Hello");
+    /// assert_eq!(r, "Hello World!");
+    /// ```
+    #[inline]
+    pub fn into_rope(self) -> Rope {
+        self.rope
+    }
+
+    #[inline]
+    pub(super) fn new(rope: &Rope) -> Self {
+        Self { rope: rope.clone() }
+    }
+}
+
+impl core::ops::Deref for RopeSnapshot {
+    type Target = Rope;
+
+    #[inline]
+    fn deref(&self) -> &Rope {
+        &self.rope
+    }
+}
+
+impl core::fmt::Debug for RopeSnapshot {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("RopeSnapshot(\"")?;
+        debug_chunks(self.rope.chunks(), f)?;
+        f.write_str("\")")
+    }
+}
+
+impl core::fmt::Display for RopeSnapshot {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.rope, f)
+    }
+}
+
+impl From<RopeSnapshot> for Rope {
+    #[inline]
+    fn from(snapshot: RopeSnapshot) -> Rope {
+        snapshot.rope
+    }
+}
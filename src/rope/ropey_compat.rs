@@ -0,0 +1,677 @@
+use alloc::string::String;
+use core::ops::RangeBounds;
+
+use super::iterators::{Bytes, Chars, Chunks};
+use super::utils::panic_messages as panic;
+use crate::range_bounds_to_start_end;
+
+/// A wrapper around a [`crop::Rope`](crate::Rope) exposing the API of
+/// [`ropey::Rope`](https://docs.rs/ropey/1/ropey/struct.Rope.html).
+///
+/// All the offsets taken and returned by its methods are char indices, like
+/// in ropey, unless the method's name says otherwise. The main difference
+/// with ropey is that lines are only broken on line feeds (`'\n'`), which
+/// also covers CRLF but not the other Unicode line breaks.
+///
+/// The wrapped `Rope` can be accessed at any time with
+/// [`as_crop()`](Self::as_crop()) and [`into_crop()`](Self::into_crop()),
+/// which lets code be migrated to crop's own API one call site at a time.
+///
+/// # Examples
+///
+/// ```
+/// use crop::compat::ropey::Rope;
+///
+/// let mut r = Rope::from_str("Hello\nWörld!\n");
+///
+/// assert_eq!(r.len_chars(), 13);
+/// assert_eq!(r.len_lines(), 3);
+/// assert_eq!(r.line(1), "Wörld!\n");
+/// assert_eq!(r.char_to_line(7), 1);
+///
+/// r.insert(6, "Big ");
+/// r.remove(10..11);
+/// assert_eq!(r, "Hello\nBig örld!\n");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Rope {
+    rope: crate::Rope,
+}
+
+/// A wrapper around a [`crop::RopeSlice`](crate::RopeSlice) exposing the API
+/// of [`ropey::RopeSlice`](https://docs.rs/ropey/1/ropey/struct.RopeSlice.html).
+///
+/// See the [`Rope`] wrapper for the differences with ropey.
+#[derive(Copy, Clone)]
+pub struct RopeSlice<'a> {
+    slice: crate::RopeSlice<'a>,
+}
+
+/// An iterator over the lines of a [`Rope`] or [`RopeSlice`], including
+/// their line breaks.
+///
+/// Like in ropey, if the text ends with a line break the last line yielded is
+/// an empty one.
+#[derive(Clone)]
+pub struct Lines<'a> {
+    slice: RopeSlice<'a>,
+    start: usize,
+    end: usize,
+}
+
+impl Rope {
+    /// Appends the contents of another `Rope` to the end of this one.
+    ///
+    /// Like in Ropey, this joins the two trees in O(log n).
+    #[inline]
+    pub fn append(&mut self, other: Self) {
+        self.rope += other.rope;
+    }
+
+    /// Returns the wrapped [`crop::Rope`](crate::Rope).
+    #[inline]
+    pub fn as_crop(&self) -> &crate::Rope {
+        &self.rope
+    }
+
+    /// Returns the wrapped [`crop::Rope`](crate::Rope) mutably.
+    #[inline]
+    pub fn as_crop_mut(&mut self) -> &mut crate::Rope {
+        &mut self.rope
+    }
+
+    /// Returns a [`RopeSlice`] spanning the whole `Rope`.
+    #[inline]
+    fn as_slice(&self) -> RopeSlice<'_> {
+        RopeSlice { slice: self.rope.byte_slice(..) }
+    }
+
+    /// Returns the byte at `byte_idx`.
+    #[track_caller]
+    #[inline]
+    pub fn byte(&self, byte_idx: usize) -> u8 {
+        self.rope.byte(byte_idx)
+    }
+
+    /// Returns a slice of the `Rope` in the given byte range.
+    #[track_caller]
+    #[inline]
+    pub fn byte_slice<R>(&self, byte_range: R) -> RopeSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_slice().byte_slice(byte_range)
+    }
+
+    /// Returns the char index of the char containing the given byte.
+    #[track_caller]
+    #[inline]
+    pub fn byte_to_char(&self, byte_idx: usize) -> usize {
+        self.as_slice().byte_to_char(byte_idx)
+    }
+
+    /// Returns the index of the line containing the given byte.
+    #[track_caller]
+    #[inline]
+    pub fn byte_to_line(&self, byte_idx: usize) -> usize {
+        self.as_slice().byte_to_line(byte_idx)
+    }
+
+    /// Returns an iterator over the bytes of the `Rope`.
+    #[inline]
+    pub fn bytes(&self) -> Bytes<'_> {
+        self.rope.bytes()
+    }
+
+    /// Returns an iterator over the bytes of the `Rope`, starting at
+    /// `byte_idx`.
+    #[track_caller]
+    #[inline]
+    pub fn bytes_at(&self, byte_idx: usize) -> Bytes<'_> {
+        self.as_slice().bytes_at(byte_idx)
+    }
+
+    /// Returns the char at `char_idx`.
+    #[track_caller]
+    #[inline]
+    pub fn char(&self, char_idx: usize) -> char {
+        self.as_slice().char(char_idx)
+    }
+
+    /// Returns the byte index of the start of the given char.
+    #[track_caller]
+    #[inline]
+    pub fn char_to_byte(&self, char_idx: usize) -> usize {
+        self.rope.byte_of_char(char_idx)
+    }
+
+    /// Returns the index of the line containing the given char.
+    #[track_caller]
+    #[inline]
+    pub fn char_to_line(&self, char_idx: usize) -> usize {
+        self.as_slice().char_to_line(char_idx)
+    }
+
+    /// Returns an iterator over the chars of the `Rope`.
+    #[inline]
+    pub fn chars(&self) -> Chars<'_> {
+        self.rope.chars()
+    }
+
+    /// Returns an iterator over the chars of the `Rope`, starting at
+    /// `char_idx`.
+    #[track_caller]
+    #[inline]
+    pub fn chars_at(&self, char_idx: usize) -> Chars<'_> {
+        self.as_slice().chars_at(char_idx)
+    }
+
+    /// Returns an iterator over the chunks of the `Rope`.
+    #[inline]
+    pub fn chunks(&self) -> Chunks<'_> {
+        self.rope.chunks()
+    }
+
+    /// Creates a new `Rope` from a string slice.
+    #[allow(clippy::should_implement_trait)]
+    #[inline]
+    pub fn from_str(text: &str) -> Self {
+        Self::from(text)
+    }
+
+    /// Returns the byte at `byte_idx`, or `None` if it's out of bounds.
+    #[inline]
+    pub fn get_byte(&self, byte_idx: usize) -> Option<u8> {
+        self.rope.get_byte(byte_idx)
+    }
+
+    /// Returns the char at `char_idx`, or `None` if it's out of bounds.
+    #[inline]
+    pub fn get_char(&self, char_idx: usize) -> Option<char> {
+        self.as_slice().get_char(char_idx)
+    }
+
+    /// Returns the line at `line_idx`, or `None` if it's out of bounds.
+    #[inline]
+    pub fn get_line(&self, line_idx: usize) -> Option<RopeSlice<'_>> {
+        self.as_slice().get_line(line_idx)
+    }
+
+    /// Returns a slice of the `Rope` in the given char range, or `None` if
+    /// the range is out of bounds.
+    #[inline]
+    pub fn get_slice<R>(&self, char_range: R) -> Option<RopeSlice<'_>>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_slice().get_slice(char_range)
+    }
+
+    /// Inserts `text` at the given char index.
+    #[track_caller]
+    #[inline]
+    pub fn insert(&mut self, char_idx: usize, text: &str) {
+        self.rope.insert_at_char(char_idx, text);
+    }
+
+    /// Inserts a single char at the given char index.
+    #[track_caller]
+    #[inline]
+    pub fn insert_char(&mut self, char_idx: usize, ch: char) {
+        self.insert(char_idx, ch.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Returns the wrapped [`crop::Rope`](crate::Rope).
+    #[inline]
+    pub fn into_crop(self) -> crate::Rope {
+        self.rope
+    }
+
+    /// Returns the number of bytes in the `Rope`.
+    #[inline]
+    pub fn len_bytes(&self) -> usize {
+        self.rope.byte_len()
+    }
+
+    /// Returns the number of chars in the `Rope`.
+    #[inline]
+    pub fn len_chars(&self) -> usize {
+        self.rope.char_len()
+    }
+
+    /// Returns the number of lines in the `Rope`, which is always one more
+    /// than its number of line feeds.
+    #[inline]
+    pub fn len_lines(&self) -> usize {
+        self.as_slice().len_lines()
+    }
+
+    /// Returns the line at `line_idx`, including its line break.
+    #[track_caller]
+    #[inline]
+    pub fn line(&self, line_idx: usize) -> RopeSlice<'_> {
+        self.as_slice().line(line_idx)
+    }
+
+    /// Returns the byte index of the start of the given line.
+    #[track_caller]
+    #[inline]
+    pub fn line_to_byte(&self, line_idx: usize) -> usize {
+        self.as_slice().line_to_byte(line_idx)
+    }
+
+    /// Returns the char index of the start of the given line.
+    #[track_caller]
+    #[inline]
+    pub fn line_to_char(&self, line_idx: usize) -> usize {
+        self.as_slice().line_to_char(line_idx)
+    }
+
+    /// Returns an iterator over the lines of the `Rope`.
+    #[inline]
+    pub fn lines(&self) -> Lines<'_> {
+        self.as_slice().lines()
+    }
+
+    /// Returns an iterator over the lines of the `Rope`, starting at
+    /// `line_idx`.
+    #[track_caller]
+    #[inline]
+    pub fn lines_at(&self, line_idx: usize) -> Lines<'_> {
+        self.as_slice().lines_at(line_idx)
+    }
+
+    /// Creates a new, empty `Rope`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes the text in the given char range.
+    #[track_caller]
+    #[inline]
+    pub fn remove<R>(&mut self, char_range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.rope.delete_chars(char_range);
+    }
+
+    /// Returns a slice of the `Rope` in the given char range.
+    #[track_caller]
+    #[inline]
+    pub fn slice<R>(&self, char_range: R) -> RopeSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.as_slice().slice(char_range)
+    }
+
+    /// Splits the `Rope` at the given char index, returning the text after
+    /// it as a new `Rope`.
+    #[track_caller]
+    #[inline]
+    pub fn split_off(&mut self, char_idx: usize) -> Self {
+        let byte_idx = self.rope.byte_of_char(char_idx);
+        Self { rope: self.rope.split_off(byte_idx) }
+    }
+}
+
+impl<'a> RopeSlice<'a> {
+    /// Returns the wrapped [`crop::RopeSlice`](crate::RopeSlice).
+    #[inline]
+    pub fn as_crop(&self) -> crate::RopeSlice<'a> {
+        self.slice
+    }
+
+    /// Returns the byte at `byte_idx`.
+    #[track_caller]
+    #[inline]
+    pub fn byte(&self, byte_idx: usize) -> u8 {
+        self.slice.byte(byte_idx)
+    }
+
+    /// Returns a sub-slice in the given byte range.
+    #[track_caller]
+    #[inline]
+    pub fn byte_slice<R>(&self, byte_range: R) -> RopeSlice<'a>
+    where
+        R: RangeBounds<usize>,
+    {
+        RopeSlice { slice: self.slice.byte_slice(byte_range) }
+    }
+
+    /// Returns the char index of the char containing the given byte.
+    #[track_caller]
+    #[inline]
+    pub fn byte_to_char(&self, byte_idx: usize) -> usize {
+        // Unlike `char_of_byte()`, which rounds up, ropey returns the char
+        // the byte is part of.
+        let mut byte_idx = byte_idx.min(self.len_bytes());
+        while !self.slice.is_char_boundary(byte_idx) {
+            byte_idx -= 1;
+        }
+        self.slice.char_of_byte(byte_idx)
+    }
+
+    /// Returns the index of the line containing the given byte.
+    #[track_caller]
+    #[inline]
+    pub fn byte_to_line(&self, byte_idx: usize) -> usize {
+        self.slice.line_of_byte(byte_idx)
+    }
+
+    /// Returns an iterator over the bytes of the slice.
+    #[inline]
+    pub fn bytes(&self) -> Bytes<'a> {
+        self.slice.bytes()
+    }
+
+    /// Returns an iterator over the bytes of the slice, starting at
+    /// `byte_idx`.
+    #[track_caller]
+    #[inline]
+    pub fn bytes_at(&self, byte_idx: usize) -> Bytes<'a> {
+        self.slice.bytes_at(byte_idx).1
+    }
+
+    /// Returns the char at `char_idx`.
+    #[track_caller]
+    #[inline]
+    pub fn char(&self, char_idx: usize) -> char {
+        if char_idx >= self.len_chars() {
+            panic::char_offset_out_of_bounds(char_idx, self.len_chars());
+        }
+        self.chars_at(char_idx).next().unwrap()
+    }
+
+    /// Returns the byte index of the start of the given char.
+    #[track_caller]
+    #[inline]
+    pub fn char_to_byte(&self, char_idx: usize) -> usize {
+        self.slice.byte_of_char(char_idx)
+    }
+
+    /// Returns the index of the line containing the given char.
+    #[track_caller]
+    #[inline]
+    pub fn char_to_line(&self, char_idx: usize) -> usize {
+        self.byte_to_line(self.char_to_byte(char_idx))
+    }
+
+    /// Returns an iterator over the chars of the slice.
+    #[inline]
+    pub fn chars(&self) -> Chars<'a> {
+        self.slice.chars()
+    }
+
+    /// Returns an iterator over the chars of the slice, starting at
+    /// `char_idx`.
+    #[track_caller]
+    #[inline]
+    pub fn chars_at(&self, char_idx: usize) -> Chars<'a> {
+        self.slice.chars_at(self.char_to_byte(char_idx)).1
+    }
+
+    /// Returns an iterator over the chunks of the slice.
+    #[inline]
+    pub fn chunks(&self) -> Chunks<'a> {
+        self.slice.chunks()
+    }
+
+    /// Returns the byte at `byte_idx`, or `None` if it's out of bounds.
+    #[inline]
+    pub fn get_byte(&self, byte_idx: usize) -> Option<u8> {
+        (byte_idx < self.len_bytes()).then(|| self.byte(byte_idx))
+    }
+
+    /// Returns the char at `char_idx`, or `None` if it's out of bounds.
+    #[inline]
+    pub fn get_char(&self, char_idx: usize) -> Option<char> {
+        (char_idx < self.len_chars()).then(|| self.char(char_idx))
+    }
+
+    /// Returns the line at `line_idx`, or `None` if it's out of bounds.
+    #[inline]
+    pub fn get_line(&self, line_idx: usize) -> Option<RopeSlice<'a>> {
+        (line_idx < self.len_lines()).then(|| self.line(line_idx))
+    }
+
+    /// Returns a sub-slice in the given char range, or `None` if the range
+    /// is out of bounds.
+    #[inline]
+    pub fn get_slice<R>(&self, char_range: R) -> Option<RopeSlice<'a>>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(char_range, 0, self.len_chars());
+
+        (start <= end && end <= self.len_chars())
+            .then(|| self.slice(start..end))
+    }
+
+    /// Returns the number of bytes in the slice.
+    #[inline]
+    pub fn len_bytes(&self) -> usize {
+        self.slice.byte_len()
+    }
+
+    /// Returns the number of chars in the slice.
+    #[inline]
+    pub fn len_chars(&self) -> usize {
+        self.slice.char_len()
+    }
+
+    /// Returns the number of lines in the slice, which is always one more
+    /// than its number of line feeds.
+    #[inline]
+    pub fn len_lines(&self) -> usize {
+        self.slice.line_of_byte(self.len_bytes()) + 1
+    }
+
+    /// Returns the line at `line_idx`, including its line break.
+    #[track_caller]
+    #[inline]
+    pub fn line(&self, line_idx: usize) -> RopeSlice<'a> {
+        if line_idx >= self.len_lines() {
+            panic::line_index_out_of_bounds(line_idx, self.len_lines());
+        }
+
+        let start = self.line_to_byte(line_idx);
+        let end = self.line_to_byte(line_idx + 1);
+
+        self.byte_slice(start..end)
+    }
+
+    /// Returns the byte index of the start of the given line.
+    #[track_caller]
+    #[inline]
+    pub fn line_to_byte(&self, line_idx: usize) -> usize {
+        if line_idx > self.len_lines() {
+            panic::line_offset_out_of_bounds(line_idx, self.len_lines());
+        }
+
+        if line_idx == self.len_lines() {
+            self.len_bytes()
+        } else {
+            self.slice.byte_of_line(line_idx)
+        }
+    }
+
+    /// Returns the char index of the start of the given line.
+    #[track_caller]
+    #[inline]
+    pub fn line_to_char(&self, line_idx: usize) -> usize {
+        self.slice.char_of_byte(self.line_to_byte(line_idx))
+    }
+
+    /// Returns an iterator over the lines of the slice.
+    #[inline]
+    pub fn lines(&self) -> Lines<'a> {
+        self.lines_at(0)
+    }
+
+    /// Returns an iterator over the lines of the slice, starting at
+    /// `line_idx`.
+    #[track_caller]
+    #[inline]
+    pub fn lines_at(&self, line_idx: usize) -> Lines<'a> {
+        if line_idx > self.len_lines() {
+            panic::line_offset_out_of_bounds(line_idx, self.len_lines());
+        }
+
+        Lines { slice: *self, start: line_idx, end: self.len_lines() }
+    }
+
+    /// Returns a sub-slice in the given char range.
+    #[track_caller]
+    #[inline]
+    pub fn slice<R>(&self, char_range: R) -> RopeSlice<'a>
+    where
+        R: RangeBounds<usize>,
+    {
+        RopeSlice { slice: self.slice.char_slice(char_range) }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = RopeSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        Some(self.slice.line(self.start - 1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Lines<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(self.slice.line(self.end))
+    }
+}
+
+impl ExactSizeIterator for Lines<'_> {}
+
+impl core::iter::FusedIterator for Lines<'_> {}
+
+impl From<&str> for Rope {
+    #[inline]
+    fn from(text: &str) -> Self {
+        Self { rope: crate::Rope::from(text) }
+    }
+}
+
+impl From<String> for Rope {
+    #[inline]
+    fn from(text: String) -> Self {
+        Self { rope: crate::Rope::from(text) }
+    }
+}
+
+impl From<crate::Rope> for Rope {
+    #[inline]
+    fn from(rope: crate::Rope) -> Self {
+        Self { rope }
+    }
+}
+
+impl From<Rope> for crate::Rope {
+    #[inline]
+    fn from(rope: Rope) -> Self {
+        rope.rope
+    }
+}
+
+impl<'a> From<RopeSlice<'a>> for Rope {
+    #[inline]
+    fn from(slice: RopeSlice<'a>) -> Self {
+        Self { rope: crate::Rope::from(slice.slice) }
+    }
+}
+
+impl<'a> From<crate::RopeSlice<'a>> for RopeSlice<'a> {
+    #[inline]
+    fn from(slice: crate::RopeSlice<'a>) -> Self {
+        Self { slice }
+    }
+}
+
+impl core::fmt::Debug for Rope {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.rope, f)
+    }
+}
+
+impl core::fmt::Display for Rope {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.rope, f)
+    }
+}
+
+impl core::fmt::Debug for RopeSlice<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.slice, f)
+    }
+}
+
+impl core::fmt::Display for RopeSlice<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.slice, f)
+    }
+}
+
+impl PartialEq<str> for Rope {
+    #[inline]
+    fn eq(&self, rhs: &str) -> bool {
+        self.rope == rhs
+    }
+}
+
+impl PartialEq<&str> for Rope {
+    #[inline]
+    fn eq(&self, rhs: &&str) -> bool {
+        self.rope == *rhs
+    }
+}
+
+impl PartialEq<RopeSlice<'_>> for RopeSlice<'_> {
+    #[inline]
+    fn eq(&self, rhs: &RopeSlice<'_>) -> bool {
+        self.slice == rhs.slice
+    }
+}
+
+impl Eq for RopeSlice<'_> {}
+
+impl PartialEq<str> for RopeSlice<'_> {
+    #[inline]
+    fn eq(&self, rhs: &str) -> bool {
+        self.slice == rhs
+    }
+}
+
+impl PartialEq<&str> for RopeSlice<'_> {
+    #[inline]
+    fn eq(&self, rhs: &&str) -> bool {
+        self.slice == *rhs
+    }
+}
@@ -0,0 +1,231 @@
+//! A binary format storing the leaves of a [`Rope`] together with their
+//! summaries.
+//!
+//! A dump starts with a header made of:
+//!
+//! - the 8 bytes `b"croptree"`;
+//! - a version byte;
+//! - the maximum byte length of a leaf as a `u32`;
+//! - a byte whose bits tell which of the optional metrics are stored in the
+//!   summaries;
+//! - the number of leaves as a `u64`;
+//!
+//! followed, for every leaf, by its byte length as a `u32`, its line breaks
+//! and then its optional metrics as `u64`s, and finally its text. All the
+//! integers are little-endian.
+
+use std::io::{self, Read, Write};
+
+use super::gap_buffer::GapBuffer;
+use super::metrics::ChunkSummary;
+use super::rope::{CHUNK_MAX_BYTES, RopeChunk};
+use super::{Rope, RopeBuilder};
+use crate::tree::Tree;
+
+const MAGIC: [u8; 8] = *b"croptree";
+
+const VERSION: u8 = 1;
+
+const CHARS: u8 = 1 << 0;
+const UTF16_CODE_UNITS: u8 = 1 << 1;
+const WIDTH: u8 = 1 << 2;
+
+/// The metrics stored in the summaries of this build of the crate.
+const METRICS: u8 = {
+    let mut metrics = 0;
+    if cfg!(feature = "char-metric") {
+        metrics |= CHARS;
+    }
+    if cfg!(feature = "utf16-metric") {
+        metrics |= UTF16_CODE_UNITS;
+    }
+    if cfg!(feature = "width-metric") {
+        metrics |= WIDTH;
+    }
+    metrics
+};
+
+#[inline]
+pub(super) fn write_tree<W: Write>(
+    rope: &Rope,
+    mut writer: W,
+) -> io::Result<()> {
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&[VERSION])?;
+    writer.write_all(&(CHUNK_MAX_BYTES as u32).to_le_bytes())?;
+    writer.write_all(&[METRICS])?;
+    writer.write_all(&(rope.tree.leaf_count() as u64).to_le_bytes())?;
+
+    for (leaf, summary) in rope.tree.leaves().zip(rope.tree.leaf_summaries()) {
+        writer.write_all(&(summary.bytes as u32).to_le_bytes())?;
+        write_u64(&mut writer, summary.line_breaks)?;
+        #[cfg(feature = "char-metric")]
+        write_u64(&mut writer, summary.chars)?;
+        #[cfg(feature = "utf16-metric")]
+        write_u64(&mut writer, summary.utf16_code_units)?;
+        #[cfg(feature = "width-metric")]
+        write_u64(&mut writer, summary.width)?;
+        writer.write_all(leaf.left_chunk().as_bytes())?;
+        writer.write_all(leaf.right_chunk().as_bytes())?;
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub(super) fn read_tree<R: Read>(mut reader: R) -> io::Result<Rope> {
+    let mut magic = [0; 8];
+    reader.read_exact(&mut magic)?;
+
+    if magic != MAGIC {
+        return Err(invalid_data("not a rope tree dump"));
+    }
+
+    let [version] = read_array(&mut reader)?;
+
+    if version != VERSION {
+        return Err(invalid_data("unsupported tree dump version"));
+    }
+
+    let chunk_max_bytes = u32::from_le_bytes(read_array(&mut reader)?);
+
+    let [metrics] = read_array(&mut reader)?;
+
+    if metrics & !(CHARS | UTF16_CODE_UNITS | WIDTH) != 0 {
+        return Err(invalid_data("unknown metrics in tree dump"));
+    }
+
+    let leaf_count = read_u64(&mut reader)?;
+
+    if chunk_max_bytes as usize == CHUNK_MAX_BYTES && metrics == METRICS {
+        read_leaves(reader, leaf_count)
+    } else {
+        resummarize_leaves(reader, leaf_count, metrics)
+    }
+}
+
+/// Builds the `Rope` directly from the stored leaves, which is only possible
+/// if the dump was written with the same chunk size and metrics as this
+/// build of the crate.
+///
+/// The input can't be trusted, so every stored summary is checked against the
+/// text of its leaf. Leaf boundaries splitting a `"\r\n"` are fine, since
+/// the leaves of a `Rope` are only required to start on a char boundary.
+#[inline]
+fn read_leaves<R: Read>(mut reader: R, leaf_count: usize) -> io::Result<Rope> {
+    let mut leaves = Vec::new();
+
+    let mut text = Vec::new();
+
+    for _ in 0..leaf_count {
+        let len = read_u32(&mut reader)? as usize;
+
+        if len > CHUNK_MAX_BYTES
+            || (leaf_count > 1 && len < RopeChunk::chunk_min())
+        {
+            return Err(invalid_data("invalid leaf length in tree dump"));
+        }
+
+        let summary = ChunkSummary {
+            bytes: len,
+            line_breaks: read_u64(&mut reader)?,
+            #[cfg(feature = "char-metric")]
+            chars: read_u64(&mut reader)?,
+            #[cfg(feature = "utf16-metric")]
+            utf16_code_units: read_u64(&mut reader)?,
+            #[cfg(feature = "width-metric")]
+            width: read_u64(&mut reader)?,
+        };
+
+        let text = read_str(&mut reader, &mut text, len)?;
+
+        if ChunkSummary::from(text) != summary {
+            return Err(invalid_data("leaf summary doesn't match its text"));
+        }
+
+        leaves.push((GapBuffer::from_summarized(text, summary), summary));
+    }
+
+    let has_trailing_newline = leaves
+        .last()
+        .map(|(leaf, _)| leaf.has_trailing_newline())
+        .unwrap_or(false);
+
+    Ok(Rope {
+        tree: Tree::from_summarized_leaves(leaves),
+        has_trailing_newline,
+        has_bom: false,
+        depth_limit: None,
+    })
+}
+
+/// Builds the `Rope` from the text of the stored leaves, discarding their
+/// summaries.
+#[inline]
+fn resummarize_leaves<R: Read>(
+    mut reader: R,
+    leaf_count: usize,
+    metrics: u8,
+) -> io::Result<Rope> {
+    let mut builder = RopeBuilder::new();
+
+    let mut text = Vec::new();
+
+    for _ in 0..leaf_count {
+        let len = read_u32(&mut reader)? as usize;
+
+        for _ in 0..1 + metrics.count_ones() {
+            read_u64(&mut reader)?;
+        }
+
+        builder.append(read_str(&mut reader, &mut text, len)?);
+    }
+
+    Ok(builder.build())
+}
+
+#[inline]
+fn invalid_data(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[inline]
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Reads `len` bytes into `buf` and checks that they're valid UTF-8.
+#[inline]
+fn read_str<'a, R: Read>(
+    reader: &mut R,
+    buf: &'a mut Vec<u8>,
+    len: usize,
+) -> io::Result<&'a str> {
+    buf.clear();
+
+    let read = reader.take(len as u64).read_to_end(buf)?;
+
+    if read < len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    core::str::from_utf8(buf).map_err(|_| invalid_data("invalid UTF-8"))
+}
+
+#[inline]
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    read_array(reader).map(u32::from_le_bytes)
+}
+
+#[inline]
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<usize> {
+    let n = u64::from_le_bytes(read_array(reader)?);
+    usize::try_from(n).map_err(|_| invalid_data("integer out of range"))
+}
+
+#[inline]
+fn write_u64<W: Write>(writer: &mut W, n: usize) -> io::Result<()> {
+    writer.write_all(&(n as u64).to_le_bytes())
+}
@@ -0,0 +1,46 @@
+use super::Rope;
+use super::rope::CHUNK_MAX_BYTES;
+
+/// Statistics about the shape of the B-tree backing a [`Rope`].
+///
+/// This is returned by [`Rope::tree_stats()`](crate::Rope::tree_stats()).
+/// See its documentation for more.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TreeStats {
+    /// The number of internal nodes between the root and the leaves,
+    /// including the root itself.
+    ///
+    /// All the leaves of the B-tree are at the same depth, so this is also
+    /// the length of its deepest path. A `Rope` whose text fits in a single
+    /// leaf has a depth of zero.
+    pub depth: usize,
+
+    /// The total number of nodes in the B-tree, both internal nodes and
+    /// leaves.
+    pub node_count: usize,
+
+    /// The number of leaves in the B-tree, each one holding a chunk of
+    /// text.
+    pub leaf_count: usize,
+
+    /// The maximum number of bytes a leaf can hold.
+    pub leaf_capacity: usize,
+
+    /// The ratio between the number of bytes of text in the `Rope` and the
+    /// combined capacity of all its leaves, between `0.0` and `1.0`.
+    pub avg_leaf_fill: f64,
+}
+
+#[inline]
+pub(super) fn tree_stats(rope: &Rope) -> TreeStats {
+    let leaf_count = rope.tree.leaf_count();
+
+    TreeStats {
+        depth: rope.tree.depth(),
+        node_count: rope.tree.inode_count() + leaf_count,
+        leaf_count,
+        leaf_capacity: CHUNK_MAX_BYTES,
+        avg_leaf_fill: rope.byte_len() as f64
+            / (leaf_count * CHUNK_MAX_BYTES) as f64,
+    }
+}
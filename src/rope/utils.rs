@@ -1,6 +1,9 @@
 //! This module contains utility functions on strings and code to be shared
 //! between `Rope`s and `RopeSlice`s, `RopeChunk`s and `ChunkSlice`s.
 
+use core::ops::ControlFlow;
+
+use super::RopeSlice;
 use super::iterators::Chunks;
 
 /// Adjusts the candidate byte offset to make sure it's a char boundary for
@@ -37,6 +40,55 @@ pub(super) fn adjust_split_point<const WITH_RIGHT_BIAS: bool>(
     offset
 }
 
+/// Lexicographically compares the bytes of the chunks yielded by two
+/// iterators, regardless of where the chunk boundaries fall on either side.
+///
+/// This is used in the `PartialOrd` and `Ord` implementations of `Rope`s and
+/// `RopeSlice`s, with strings being passed as a single chunk.
+#[inline]
+pub(super) fn chunks_cmp_chunks<'a, 'b>(
+    mut lhs: impl Iterator<Item = &'a str>,
+    mut rhs: impl Iterator<Item = &'b str>,
+) -> core::cmp::Ordering {
+    use core::cmp::Ordering;
+
+    let mut left_chunk: &[u8] = &[];
+    let mut right_chunk: &[u8] = &[];
+
+    loop {
+        while left_chunk.is_empty() {
+            match lhs.next() {
+                Some(chunk) => left_chunk = chunk.as_bytes(),
+                None => break,
+            }
+        }
+
+        while right_chunk.is_empty() {
+            match rhs.next() {
+                Some(chunk) => right_chunk = chunk.as_bytes(),
+                None => break,
+            }
+        }
+
+        match (left_chunk.is_empty(), right_chunk.is_empty()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            (false, false) => {},
+        }
+
+        let len = left_chunk.len().min(right_chunk.len());
+
+        match left_chunk[..len].cmp(&right_chunk[..len]) {
+            Ordering::Equal => {},
+            ordering => return ordering,
+        }
+
+        left_chunk = &left_chunk[len..];
+        right_chunk = &right_chunk[len..];
+    }
+}
+
 /// Checks equality between the chunks yielded by iterating over two
 /// [`Chunks`].
 ///
@@ -107,6 +159,57 @@ pub(super) fn chunks_eq_str(chunks: Chunks<'_>, s: &str) -> bool {
     true
 }
 
+/// Feeds the bytes yielded by iterating over a [`Chunks`] to a hasher.
+///
+/// Hashers don't have to give the same result when the same bytes are written
+/// in different pieces, so to make the hash independent of the chunk
+/// boundaries the bytes are always written in blocks of a fixed size. Like
+/// for `str`s a `0xff` byte is written at the end, which makes the hash
+/// prefix-free.
+///
+/// This is used in the `Hash` implementation of `Rope`s and `RopeSlice`s.
+#[inline]
+pub(super) fn hash_chunks<H: core::hash::Hasher>(
+    chunks: Chunks<'_>,
+    state: &mut H,
+) {
+    const BLOCK_BYTES: usize = 256;
+
+    let mut block = [0u8; BLOCK_BYTES];
+    let mut block_len = 0;
+
+    for chunk in chunks {
+        let mut bytes = chunk.as_bytes();
+
+        if block_len > 0 {
+            let fill = bytes.len().min(BLOCK_BYTES - block_len);
+            block[block_len..block_len + fill].copy_from_slice(&bytes[..fill]);
+            block_len += fill;
+            bytes = &bytes[fill..];
+
+            if block_len < BLOCK_BYTES {
+                continue;
+            }
+
+            state.write(&block);
+        }
+
+        // The full blocks can be written straight from the chunk.
+        let mut full_blocks = bytes.chunks_exact(BLOCK_BYTES);
+
+        for full_block in &mut full_blocks {
+            state.write(full_block);
+        }
+
+        let rest = full_blocks.remainder();
+        block[..rest.len()].copy_from_slice(rest);
+        block_len = rest.len();
+    }
+
+    state.write(&block[..block_len]);
+    state.write_u8(0xff);
+}
+
 /// Iterates over the string slices yielded by [`Chunks`], writing the debug
 /// output of each chunk to a formatter.
 #[inline]
@@ -145,54 +248,68 @@ pub(super) fn debug_no_quotes(
     f.write_str(&s[written..])
 }
 
-/// Returns whether `byte_offset` is a grapheme boundary in the string
-/// constructed by concatenating the chunks yielded by `chunks`.
+/// Calls `fun` on every chunk of the slice, stopping as soon as it returns
+/// [`ControlFlow::Break`].
+#[inline]
+pub(super) fn for_each_chunk<'a, B, F>(
+    slice: RopeSlice<'a>,
+    mut fun: F,
+) -> ControlFlow<B>
+where
+    F: FnMut(&'a str) -> ControlFlow<B>,
+{
+    slice.tree_slice.for_each_leaf(|leaf| {
+        if !leaf.left_chunk().is_empty() {
+            fun(leaf.left_chunk())?;
+        }
+        if !leaf.right_chunk().is_empty() {
+            fun(leaf.right_chunk())?;
+        }
+        ControlFlow::Continue(())
+    })
+}
+
+/// Returns whether `byte_offset` is a grapheme boundary in the given slice.
+///
+/// This only looks at the chunk starting at `byte_offset` and, if the grapheme
+/// segmentation rules need more context, at the chunks right before it, so it
+/// runs in O(log n) for the common case.
 #[cfg(feature = "graphemes")]
 #[inline]
 pub(super) fn is_grapheme_boundary(
-    mut chunks: Chunks<'_>,
-    byte_len: usize,
+    slice: RopeSlice<'_>,
     byte_offset: usize,
 ) -> bool {
     use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete};
 
-    debug_assert!(byte_offset <= byte_len);
+    debug_assert!(byte_offset <= slice.byte_len());
 
-    if byte_len == 0 {
+    if byte_offset == 0 || byte_offset == slice.byte_len() {
         return true;
     }
 
-    let mut cursor = GraphemeCursor::new(0, byte_len, true);
-    cursor.set_cursor(byte_offset);
+    if !slice.is_char_boundary(byte_offset) {
+        return false;
+    }
 
-    let mut bytes_left = byte_len;
+    let mut cursor = GraphemeCursor::new(byte_offset, slice.byte_len(), true);
 
-    // Iterate from the back until we reach the chunk containing the given byte
-    // index.
-    //
-    // TODO: we need something like `Rope{Slice}::chunks_{from,up_to}_byte()`
-    // if we want to make this fast.
-    let chunk = loop {
-        let chunk = chunks.next_back().unwrap();
-        bytes_left -= chunk.len();
-        if bytes_left <= byte_offset {
-            break chunk;
-        }
-    };
+    // The chunk starting at the given byte offset.
+    let chunk = slice.byte_slice(byte_offset..).chunks().next().unwrap();
 
-    if !chunk.is_char_boundary(byte_offset - bytes_left) {
-        return false;
-    }
+    // The chunks before the given byte offset, which we'll feed to the cursor
+    // back to front if it needs more context.
+    let mut prev_chunks = slice.byte_slice(..byte_offset).chunks();
 
-    let chunk_start = bytes_left;
+    let mut bytes_left = byte_offset;
 
     loop {
-        match cursor.is_boundary(chunk, chunk_start) {
+        match cursor.is_boundary(chunk, byte_offset) {
             Ok(is_boundary) => return is_boundary,
 
             Err(GraphemeIncomplete::PreContext(offset)) => {
                 debug_assert_eq!(offset, bytes_left);
-                let prev = chunks.next_back().unwrap();
+                let prev = prev_chunks.next_back().unwrap();
                 bytes_left -= prev.len();
                 cursor.provide_context(prev, bytes_left);
             },
@@ -202,6 +319,217 @@ pub(super) fn is_grapheme_boundary(
     }
 }
 
+/// Returns the byte offset of the first grapheme cluster boundary after the
+/// given byte offset, or `None` if the offset is at the end of the slice.
+#[cfg(feature = "graphemes")]
+#[track_caller]
+#[inline]
+pub(super) fn next_grapheme_boundary(
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+) -> Option<usize> {
+    use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete};
+
+    debug_assert!(byte_offset <= slice.byte_len());
+
+    if byte_offset == slice.byte_len() {
+        return None;
+    }
+
+    let mut cursor = GraphemeCursor::new(byte_offset, slice.byte_len(), true);
+
+    // The chunks after the given byte offset, which we'll feed to the cursor
+    // front to back.
+    let mut chunks = slice.byte_slice(byte_offset..).chunks();
+
+    let mut chunk = chunks.next().unwrap();
+
+    let mut chunk_start = byte_offset;
+
+    loop {
+        match cursor.next_boundary(chunk, chunk_start) {
+            Ok(boundary) => return boundary,
+
+            Err(GraphemeIncomplete::NextChunk) => {
+                chunk_start += chunk.len();
+                chunk = chunks.next().unwrap();
+            },
+
+            Err(GraphemeIncomplete::PreContext(offset)) => {
+                let prev = slice.byte_slice(..offset).chunks().next_back();
+                let prev = prev.unwrap();
+                cursor.provide_context(prev, offset - prev.len());
+            },
+
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Returns the byte offset of the last grapheme cluster boundary before the
+/// given byte offset, or `None` if the offset is at the start of the slice.
+#[cfg(feature = "graphemes")]
+#[track_caller]
+#[inline]
+pub(super) fn prev_grapheme_boundary(
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+) -> Option<usize> {
+    use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete};
+
+    debug_assert!(byte_offset <= slice.byte_len());
+
+    if byte_offset == 0 {
+        return None;
+    }
+
+    let mut cursor = GraphemeCursor::new(byte_offset, slice.byte_len(), true);
+
+    // The chunks before the given byte offset, which we'll feed to the cursor
+    // back to front.
+    let mut chunks = slice.byte_slice(..byte_offset).chunks();
+
+    let mut chunk = chunks.next_back().unwrap();
+
+    let mut chunk_start = byte_offset - chunk.len();
+
+    loop {
+        match cursor.prev_boundary(chunk, chunk_start) {
+            Ok(boundary) => return boundary,
+
+            Err(GraphemeIncomplete::PrevChunk) => {
+                chunk = chunks.next_back().unwrap();
+                chunk_start -= chunk.len();
+            },
+
+            Err(GraphemeIncomplete::PreContext(offset)) => {
+                let prev = slice.byte_slice(..offset).chunks().next_back();
+                let prev = prev.unwrap();
+                cursor.provide_context(prev, offset - prev.len());
+            },
+
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Returns the byte offset of the last grapheme cluster boundary at or before
+/// the given byte offset, which doesn't have to lie on a code point boundary.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn floor_grapheme_boundary(
+    slice: RopeSlice<'_>,
+    mut byte_offset: usize,
+) -> usize {
+    debug_assert!(byte_offset <= slice.byte_len());
+
+    while !slice.is_char_boundary(byte_offset) {
+        byte_offset -= 1;
+    }
+
+    if is_grapheme_boundary(slice, byte_offset) {
+        byte_offset
+    } else {
+        // The start of the slice is always a boundary, so there must be one
+        // before a byte offset that isn't.
+        prev_grapheme_boundary(slice, byte_offset).unwrap()
+    }
+}
+
+/// Returns the byte offset of the first grapheme cluster boundary at or after
+/// the given byte offset, which doesn't have to lie on a code point boundary.
+#[cfg(feature = "graphemes")]
+#[inline]
+pub(super) fn ceil_grapheme_boundary(
+    slice: RopeSlice<'_>,
+    mut byte_offset: usize,
+) -> usize {
+    debug_assert!(byte_offset <= slice.byte_len());
+
+    while !slice.is_char_boundary(byte_offset) {
+        byte_offset += 1;
+    }
+
+    if is_grapheme_boundary(slice, byte_offset) {
+        byte_offset
+    } else {
+        // Same as above, the end of the slice is always a boundary.
+        next_grapheme_boundary(slice, byte_offset).unwrap()
+    }
+}
+
+/// Returns the byte offset of the last occurrence of `pattern` in the slice.
+///
+/// Matches can span any number of chunks, so while iterating over the chunks
+/// from back to front we keep the first `pattern.len() - 1` bytes of the text
+/// we've already looked at around, and check it together with the end of
+/// every new chunk.
+#[inline]
+pub(super) fn rfind(slice: RopeSlice<'_>, pattern: &str) -> Option<usize> {
+    if pattern.is_empty() {
+        return Some(slice.byte_len());
+    }
+
+    let overlap = pattern.len() - 1;
+
+    let mut window = alloc::collections::VecDeque::with_capacity(2 * overlap);
+
+    let mut end = slice.byte_len();
+
+    for chunk in slice.chunks().rev() {
+        let start = end - chunk.len();
+
+        if !window.is_empty() {
+            let tail =
+                &chunk.as_bytes()[chunk.len() - chunk.len().min(overlap)..];
+
+            for &byte in tail.iter().rev() {
+                window.push_front(byte);
+            }
+
+            let haystack = window.make_contiguous();
+
+            if let Some(idx) =
+                rfind_bytes(haystack, pattern.as_bytes(), tail.len())
+            {
+                return Some(end - tail.len() + idx);
+            }
+
+            window.drain(..tail.len());
+        }
+
+        if let Some(idx) = chunk.rfind(pattern) {
+            return Some(start + idx);
+        }
+
+        end = start;
+
+        let head = &chunk.as_bytes()[..chunk.len().min(overlap)];
+
+        for &byte in head.iter().rev() {
+            window.push_front(byte);
+        }
+
+        window.truncate(overlap);
+    }
+
+    None
+}
+
+/// Returns the index of the last occurrence of `pattern` in `haystack` that
+/// starts before `before`.
+#[inline]
+fn rfind_bytes(
+    haystack: &[u8],
+    pattern: &[u8],
+    before: usize,
+) -> Option<usize> {
+    haystack
+        .windows(pattern.len())
+        .take(before)
+        .rposition(|window| window == pattern)
+}
+
 #[inline]
 pub(super) fn split_adjusted<const WITH_RIGHT_BIAS: bool>(
     s: &str,
@@ -211,6 +539,152 @@ pub(super) fn split_adjusted<const WITH_RIGHT_BIAS: bool>(
     (&s[..split_point], &s[split_point..])
 }
 
+/// Serializes the chunks yielded by `chunks`.
+///
+/// Human-readable formats get a sequence of strings of unknown length, while
+/// compact formats get a length-prefixed sequence of byte buffers, which is
+/// what formats like `bincode` and `postcard` expect.
+#[cfg(feature = "serde")]
+#[inline]
+pub(super) fn serialize_chunks<S>(
+    chunks: Chunks<'_>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use serde::ser::SerializeSeq;
+
+    struct ChunkBytes<'a>(&'a str);
+
+    impl serde::Serialize for ChunkBytes<'_> {
+        #[inline]
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_bytes(self.0.as_bytes())
+        }
+    }
+
+    if serializer.is_human_readable() {
+        let mut seq = serializer.serialize_seq(None)?;
+        for chunk in chunks {
+            seq.serialize_element(chunk)?;
+        }
+        seq.end()
+    } else {
+        let mut seq =
+            serializer.serialize_seq(Some(chunks.clone().count()))?;
+        for chunk in chunks {
+            seq.serialize_element(&ChunkBytes(chunk))?;
+        }
+        seq.end()
+    }
+}
+
+/// A chunk of text deserialized either from a string or from a byte buffer
+/// containing valid UTF-8, depending on whether the format is
+/// human-readable.
+#[cfg(feature = "serde")]
+pub(super) struct DeChunk<'de>(pub(super) alloc::borrow::Cow<'de, str>);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DeChunk<'de> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use alloc::borrow::Cow;
+        use alloc::string::String;
+        use alloc::vec::Vec;
+
+        use serde::de::{Error, Unexpected};
+
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = DeChunk<'de>;
+
+            #[inline]
+            fn expecting(
+                &self,
+                formatter: &mut core::fmt::Formatter,
+            ) -> core::fmt::Result {
+                formatter.write_str("a string or a UTF-8 byte buffer")
+            }
+
+            #[inline]
+            fn visit_borrowed_str<E: Error>(
+                self,
+                s: &'de str,
+            ) -> Result<Self::Value, E> {
+                Ok(DeChunk(Cow::Borrowed(s)))
+            }
+
+            #[inline]
+            fn visit_str<E: Error>(self, s: &str) -> Result<Self::Value, E> {
+                Ok(DeChunk(Cow::Owned(s.into())))
+            }
+
+            #[inline]
+            fn visit_string<E: Error>(
+                self,
+                s: String,
+            ) -> Result<Self::Value, E> {
+                Ok(DeChunk(Cow::Owned(s)))
+            }
+
+            #[inline]
+            fn visit_borrowed_bytes<E: Error>(
+                self,
+                bytes: &'de [u8],
+            ) -> Result<Self::Value, E> {
+                match core::str::from_utf8(bytes) {
+                    Ok(s) => self.visit_borrowed_str(s),
+                    Err(_) => {
+                        Err(E::invalid_value(Unexpected::Bytes(bytes), &self))
+                    },
+                }
+            }
+
+            #[inline]
+            fn visit_bytes<E: Error>(
+                self,
+                bytes: &[u8],
+            ) -> Result<Self::Value, E> {
+                match core::str::from_utf8(bytes) {
+                    Ok(s) => self.visit_str(s),
+                    Err(_) => {
+                        Err(E::invalid_value(Unexpected::Bytes(bytes), &self))
+                    },
+                }
+            }
+
+            #[inline]
+            fn visit_byte_buf<E: Error>(
+                self,
+                bytes: Vec<u8>,
+            ) -> Result<Self::Value, E> {
+                match String::from_utf8(bytes) {
+                    Ok(s) => self.visit_string(s),
+                    Err(err) => Err(E::invalid_value(
+                        Unexpected::Bytes(err.as_bytes()),
+                        &self,
+                    )),
+                }
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(Visitor)
+        } else {
+            deserializer.deserialize_bytes(Visitor)
+        }
+    }
+}
+
 pub mod panic_messages {
     #[track_caller]
     #[cold]
@@ -273,6 +747,21 @@ pub mod panic_messages {
         );
     }
 
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn byte_ranges_overlap(
+        first: core::ops::Range<usize>,
+        second: core::ops::Range<usize>,
+    ) -> ! {
+        debug_assert!(first.end > second.start);
+
+        panic!(
+            "byte ranges overlap: the range {first:?} overlaps with the \
+             range {second:?}"
+        );
+    }
+
     #[track_caller]
     #[cold]
     #[inline(never)]
@@ -288,6 +777,49 @@ pub mod panic_messages {
         );
     }
 
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn column_offset_not_char_boundary(
+        column_offset: usize,
+        splitting_char: char,
+    ) -> ! {
+        panic!(
+            "column offset {column_offset} is not a char boundary: it is \
+             inside {splitting_char:?}"
+        );
+    }
+
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn column_offset_out_of_bounds(
+        column_offset: usize,
+        line_len: usize,
+    ) -> ! {
+        debug_assert!(column_offset > line_len);
+
+        panic!(
+            "column offset out of bounds: the offset is {column_offset} but \
+             the length of the line is {line_len}"
+        );
+    }
+
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn depth_limit_exceeded(
+        byte_len: usize,
+        max_byte_len: usize,
+    ) -> ! {
+        debug_assert!(byte_len > max_byte_len);
+
+        panic!(
+            "depth limit exceeded: the length would be {byte_len} but the \
+             depth limit only allows {max_byte_len}"
+        );
+    }
+
     #[track_caller]
     #[cold]
     #[inline(never)]
@@ -333,6 +865,38 @@ pub mod panic_messages {
         );
     }
 
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn char_offset_out_of_bounds(
+        char_offset: usize,
+        char_len: usize,
+    ) -> ! {
+        debug_assert!(char_offset > char_len);
+
+        panic!(
+            "char offset out of bounds: the offset is {char_offset} but the \
+             length is {char_len}"
+        );
+    }
+
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn char_start_after_end(
+        char_start: usize,
+        char_end: usize,
+    ) -> ! {
+        debug_assert!(char_start > char_end);
+
+        panic!(
+            "char offset start after end: the start is {char_start} but the \
+             end is {char_end}"
+        );
+    }
+
     #[cfg(feature = "utf16-metric")]
     #[track_caller]
     #[cold]
@@ -349,6 +913,26 @@ pub mod panic_messages {
         );
     }
 
+    #[cfg(feature = "utf16-metric")]
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn utf16_offset_not_char_boundary(
+        s: &str,
+        utf16_offset: usize,
+        byte_offset: usize,
+    ) -> ! {
+        debug_assert!(s.is_char_boundary(byte_offset));
+
+        let splitting_char = s[byte_offset..].chars().next().unwrap();
+
+        panic!(
+            "UTF-16 offset {utf16_offset} is not a char boundary: it is \
+             inside {splitting_char:?} (bytes {byte_offset}..{}) of {s:?}",
+            byte_offset + splitting_char.len_utf8()
+        );
+    }
+
     #[cfg(feature = "utf16-metric")]
     #[track_caller]
     #[cold]
@@ -364,4 +948,20 @@ pub mod panic_messages {
              the end is {utf16_end}"
         );
     }
+
+    #[cfg(feature = "width-metric")]
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn width_offset_out_of_bounds(
+        width_offset: usize,
+        width: usize,
+    ) -> ! {
+        debug_assert!(width_offset > width);
+
+        panic!(
+            "width offset out of bounds: the offset is {width_offset} but \
+             the width is {width}"
+        );
+    }
 }
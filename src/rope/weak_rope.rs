@@ -0,0 +1,79 @@
+use super::Rope;
+use super::rope::RopeChunk;
+use crate::tree::WeakTree;
+
+/// A weak handle to the contents of a [`Rope`](crate::Rope).
+///
+/// A `WeakRope` is created by calling [`Rope::downgrade()`]. Unlike cloning a
+/// `Rope`, holding a `WeakRope` doesn't prevent the underlying text from being
+/// freed, which makes it a good fit for caches that want to reuse an old
+/// version of a `Rope` if it still exists without pinning it in memory.
+#[derive(Clone)]
+pub struct WeakRope {
+    pub(super) tree: WeakTree<{ Rope::arity() }, RopeChunk>,
+    pub(super) has_trailing_newline: bool,
+    pub(super) has_bom: bool,
+    pub(super) depth_limit: Option<usize>,
+}
+
+impl WeakRope {
+    /// Returns whether the contents this handle points to are still alive,
+    /// i.e. whether [`upgrade()`](Self::upgrade()) would return `Some`.
+    ///
+    /// Note that another thread may drop or edit the last `Rope` holding the
+    /// contents right after this returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// let weak = r.downgrade();
+    /// assert!(weak.is_alive());
+    ///
+    /// drop(r);
+    /// assert!(!weak.is_alive());
+    /// ```
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        self.tree.is_alive()
+    }
+
+    /// Attempts to upgrade the handle back into a [`Rope`], returning `None`
+    /// if every `Rope` sharing its contents has since been dropped or edited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// let weak = r.downgrade();
+    ///
+    /// let snapshot = r.clone();
+    /// drop(r);
+    /// assert_eq!(weak.upgrade().unwrap(), snapshot);
+    ///
+    /// drop(snapshot);
+    /// assert!(weak.upgrade().is_none());
+    /// ```
+    #[inline]
+    pub fn upgrade(&self) -> Option<Rope> {
+        self.tree.upgrade().map(|tree| Rope {
+            tree,
+            has_trailing_newline: self.has_trailing_newline,
+            has_bom: self.has_bom,
+            depth_limit: self.depth_limit,
+        })
+    }
+}
+
+impl core::fmt::Debug for WeakRope {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("WeakRope")
+    }
+}
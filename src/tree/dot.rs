@@ -0,0 +1,91 @@
+//! This module contains the [Graphviz] DOT exporter used by
+//! `Rope::to_dot()`.
+//!
+//! [Graphviz]: https://graphviz.org
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use super::{Arc, Leaf, Node, Tree};
+
+impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
+    /// Returns a description of the nodes of this `Tree` in the DOT language,
+    /// labelling internal nodes with their summaries and leaves with their
+    /// summaries and the string returned by `leaf_label`.
+    #[inline]
+    pub fn to_dot<F>(&self, mut leaf_label: F) -> String
+    where
+        F: FnMut(L::Slice<'_>) -> String,
+    {
+        let mut dot = String::new();
+
+        dot.push_str("digraph {\n");
+        dot.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+
+        let mut next_id = 0;
+        write_node(self.root(), &mut next_id, &mut leaf_label, &mut dot);
+
+        dot.push_str("}\n");
+
+        dot
+    }
+}
+
+/// Writes the node and all its descendants, returning the id assigned to it.
+#[inline]
+fn write_node<const N: usize, L: Leaf, F>(
+    node: &Arc<Node<N, L>>,
+    next_id: &mut usize,
+    leaf_label: &mut F,
+    dot: &mut String,
+) -> usize
+where
+    F: FnMut(L::Slice<'_>) -> String,
+{
+    let id = *next_id;
+    *next_id += 1;
+
+    let label = match &**node {
+        Node::Internal(inode) => {
+            alloc::format!("depth {}\n{:?}", inode.depth(), inode.summary())
+        },
+        Node::Leaf(leaf) => {
+            alloc::format!(
+                "{:?}\n{}",
+                leaf.summary(),
+                leaf_label(leaf.as_slice())
+            )
+        },
+    };
+
+    let _ = writeln!(dot, "    n{id} [label=\"{}\"];", escape(&label));
+
+    if let Node::Internal(inode) = &**node {
+        for child in inode.children() {
+            let child_id = write_node(child, next_id, leaf_label, dot);
+            let _ = writeln!(dot, "    n{id} -> n{child_id};");
+        }
+    }
+
+    id
+}
+
+/// Escapes the label so that it can be put inside a quoted DOT string, with
+/// every newline turned into a left-justified line break.
+#[inline]
+fn escape(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+
+    for ch in label.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\l"),
+            _ => escaped.push(ch),
+        }
+    }
+
+    escaped.push_str("\\l");
+
+    escaped
+}
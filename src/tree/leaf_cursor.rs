@@ -0,0 +1,195 @@
+use alloc::vec::Vec;
+
+use super::{Inode, Leaf, Lnode, Metric, Node, Tree, TreeSlice};
+
+/// A cursor over the leaves of a `Tree` or `TreeSlice` that can move back and
+/// forth between them.
+///
+/// The cursor remembers the path from the root down to its current leaf, so
+/// moving to one of its neighbors is amortized O(1) instead of requiring a
+/// new descent from the root.
+///
+/// When created from a `TreeSlice` the cursor moves over all the leaves of
+/// its root, including the ones outside of the slice, and the offsets it
+/// reports are relative to the start of the root. It's up to the caller to
+/// clamp them to the slice.
+pub struct LeafCursor<'a, const ARITY: usize, L: Leaf> {
+    /// The root of the `Tree` or `TreeSlice` we're moving over.
+    root: &'a Node<ARITY, L>,
+
+    /// The path from the root down to (but not including) the current leaf,
+    /// together with the index of the child we descended into at each step.
+    path: Vec<(&'a Inode<ARITY, L>, usize)>,
+
+    /// The current leaf.
+    leaf: &'a Lnode<L>,
+
+    /// The base measure of all the leaves before the current one.
+    offset: L::BaseMetric,
+}
+
+impl<const ARITY: usize, L: Leaf> Clone for LeafCursor<'_, ARITY, L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { path: self.path.clone(), ..*self }
+    }
+}
+
+impl<'a, const ARITY: usize, L: Leaf> From<&'a Tree<ARITY, L>>
+    for LeafCursor<'a, ARITY, L>
+{
+    #[inline]
+    fn from(tree: &'a Tree<ARITY, L>) -> Self {
+        Self::new(tree.root())
+    }
+}
+
+impl<'a, const ARITY: usize, L: Leaf> From<&TreeSlice<'a, ARITY, L>>
+    for LeafCursor<'a, ARITY, L>
+{
+    #[inline]
+    fn from(slice: &TreeSlice<'a, ARITY, L>) -> Self {
+        Self::new(slice.root())
+    }
+}
+
+impl<'a, const ARITY: usize, L: Leaf> LeafCursor<'a, ARITY, L> {
+    /// Returns the current leaf.
+    #[inline]
+    pub fn leaf(&self) -> L::Slice<'a> {
+        self.leaf.as_slice()
+    }
+
+    /// Creates a new cursor positioned on the first leaf under `root`.
+    #[inline]
+    fn new(root: &'a Node<ARITY, L>) -> Self {
+        let mut path = Vec::with_capacity(root.depth());
+
+        let mut node = root;
+
+        let leaf = loop {
+            match node {
+                Node::Internal(inode) => {
+                    path.push((inode, 0));
+                    node = &**inode.first();
+                },
+                Node::Leaf(leaf) => break leaf,
+            }
+        };
+
+        Self { root, path, leaf, offset: L::BaseMetric::zero() }
+    }
+
+    /// Moves the cursor to the next leaf, returning `false` if the current
+    /// leaf is the last one.
+    #[inline]
+    pub fn next_leaf(&mut self) -> bool {
+        let Some(depth) = self
+            .path
+            .iter()
+            .rposition(|&(inode, child_idx)| child_idx + 1 < inode.len())
+        else {
+            return false;
+        };
+
+        self.offset += self.leaf.base_measure();
+
+        self.path.truncate(depth + 1);
+
+        let (inode, child_idx) = self.path.last_mut().unwrap();
+        *child_idx += 1;
+        let mut node = &**inode.child(*child_idx);
+
+        self.leaf = loop {
+            match node {
+                Node::Internal(inode) => {
+                    self.path.push((inode, 0));
+                    node = &**inode.first();
+                },
+                Node::Leaf(leaf) => break leaf,
+            }
+        };
+
+        true
+    }
+
+    /// Returns the base measure of all the leaves before the current one.
+    #[inline]
+    pub fn offset(&self) -> L::BaseMetric {
+        self.offset
+    }
+
+    /// Moves the cursor to the previous leaf, returning `false` if the
+    /// current leaf is the first one.
+    #[inline]
+    pub fn previous_leaf(&mut self) -> bool {
+        let Some(depth) =
+            self.path.iter().rposition(|&(_, child_idx)| child_idx > 0)
+        else {
+            return false;
+        };
+
+        self.path.truncate(depth + 1);
+
+        let (inode, child_idx) = self.path.last_mut().unwrap();
+        *child_idx -= 1;
+        let mut node = &**inode.child(*child_idx);
+
+        self.leaf = loop {
+            match node {
+                Node::Internal(inode) => {
+                    self.path.push((inode, inode.len() - 1));
+                    node = &**inode.last();
+                },
+                Node::Leaf(leaf) => break leaf,
+            }
+        };
+
+        self.offset -= self.leaf.base_measure();
+
+        true
+    }
+
+    /// Moves the cursor to the leaf containing the `measure`-th unit of the
+    /// `M`-metric, or to the last leaf if `measure` is equal to the total
+    /// `M`-measure of the root.
+    ///
+    /// When the measure falls on the boundary between two leaves, the cursor
+    /// is moved to the second one.
+    #[inline]
+    pub fn seek<M>(&mut self, mut measure: M)
+    where
+        M: Metric<L::Summary>,
+    {
+        self.path.clear();
+        self.offset = L::BaseMetric::zero();
+
+        let mut node = self.root;
+
+        self.leaf = loop {
+            match node {
+                Node::Internal(inode) => {
+                    let last_idx = inode.len() - 1;
+
+                    let mut child_idx = 0;
+
+                    for (idx, child) in inode.children().iter().enumerate() {
+                        let child_measure = child.measure::<M>();
+
+                        if measure < child_measure || idx == last_idx {
+                            child_idx = idx;
+                            break;
+                        }
+
+                        measure -= child_measure;
+                        self.offset += child.base_measure();
+                    }
+
+                    self.path.push((inode, child_idx));
+                    node = &**inode.child(child_idx);
+                },
+                Node::Leaf(leaf) => break leaf,
+            }
+        };
+    }
+}
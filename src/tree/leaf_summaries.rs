@@ -0,0 +1,190 @@
+use alloc::vec::Vec;
+
+use super::{Inode, Leaf, Metric, Node, Tree, TreeSlice};
+
+/// An iterator over the summaries of the leaves of `Tree`s and `TreeSlice`s.
+///
+/// Unlike [`Leaves`](super::Leaves), this only ever reads the summaries
+/// stored in the leaf nodes, so it never has to look at the leaves' contents.
+/// When iterating over a `TreeSlice`, the first and last summaries are the
+/// ones of the start and end slices, respectively.
+pub struct LeafSummaries<'a, const ARITY: usize, L: Leaf> {
+    /// The root of the `Tree` or `TreeSlice` we're iterating over.
+    root: &'a Node<ARITY, L>,
+
+    /// The path from the root down to (but not including) the current leaf,
+    /// together with the index of the child we took at each inode.
+    path: Vec<(&'a Inode<ARITY, L>, usize)>,
+
+    /// The summary of the first leaf, if we're iterating over a `TreeSlice`.
+    first: Option<L::Summary>,
+
+    /// The summary of the last leaf, if we're iterating over a `TreeSlice`
+    /// spanning more than one leaf.
+    last: Option<L::Summary>,
+
+    /// The base offset of the first leaf in the yielding range.
+    base_offset: L::BaseMetric,
+
+    /// The number of summaries yielded so far.
+    yielded: usize,
+
+    /// The total number of summaries this iterator will yield.
+    total: usize,
+}
+
+impl<const ARITY: usize, L: Leaf> Clone for LeafSummaries<'_, ARITY, L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            first: self.first.clone(),
+            last: self.last.clone(),
+            ..*self
+        }
+    }
+}
+
+impl<'a, const ARITY: usize, L: Leaf> From<&'a Tree<ARITY, L>>
+    for LeafSummaries<'a, ARITY, L>
+{
+    #[inline]
+    fn from(tree: &'a Tree<ARITY, L>) -> Self {
+        Self {
+            root: &**tree.root(),
+            path: Vec::with_capacity(tree.root().depth()),
+            first: None,
+            last: None,
+            base_offset: L::BaseMetric::zero(),
+            yielded: 0,
+            total: tree.leaf_count(),
+        }
+    }
+}
+
+impl<'a, const ARITY: usize, L: Leaf> From<&TreeSlice<'a, ARITY, L>>
+    for LeafSummaries<'a, ARITY, L>
+{
+    #[inline]
+    fn from(slice: &TreeSlice<'a, ARITY, L>) -> Self {
+        Self {
+            root: &**slice.root(),
+            path: Vec::with_capacity(slice.root().depth()),
+            first: Some(slice.start_summary().clone()),
+            last: (slice.leaf_count() > 1)
+                .then(|| slice.end_summary().clone()),
+            base_offset: L::BaseMetric::measure(&slice.offset),
+            yielded: 0,
+            total: slice.leaf_count(),
+        }
+    }
+}
+
+impl<'a, const ARITY: usize, L: Leaf> LeafSummaries<'a, ARITY, L> {
+    /// Descends from the root to the leaf containing the base offset,
+    /// returning its summary.
+    #[inline]
+    fn first_leaf(&mut self) -> &'a L::Summary {
+        let mut inode = match self.root {
+            Node::Internal(inode) => inode,
+            Node::Leaf(leaf) => return leaf.summary(),
+        };
+
+        let mut offset = L::BaseMetric::zero();
+
+        'outer: loop {
+            for (idx, child) in inode.children().iter().enumerate() {
+                let child_measure = child.base_measure();
+
+                if offset + child_measure > self.base_offset {
+                    self.path.push((inode, idx));
+
+                    match &**child {
+                        Node::Internal(i) => {
+                            inode = i;
+                            continue 'outer;
+                        },
+                        Node::Leaf(leaf) => return leaf.summary(),
+                    }
+                } else {
+                    offset += child_measure;
+                }
+            }
+
+            unreachable!();
+        }
+    }
+
+    /// Moves to the leaf after the current one, returning its summary.
+    #[inline]
+    fn next_leaf(&mut self) -> &'a L::Summary {
+        let mut node = loop {
+            let &mut (inode, ref mut idx) = self.path.last_mut().unwrap();
+
+            *idx += 1;
+
+            if *idx == inode.len() {
+                self.path.pop();
+            } else {
+                break &**inode.child(*idx);
+            }
+        };
+
+        loop {
+            match node {
+                Node::Internal(inode) => {
+                    self.path.push((inode, 0));
+                    node = &**inode.first();
+                },
+
+                Node::Leaf(leaf) => return leaf.summary(),
+            }
+        }
+    }
+}
+
+impl<const ARITY: usize, L: Leaf> Iterator for LeafSummaries<'_, ARITY, L> {
+    type Item = L::Summary;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.yielded == self.total {
+            return None;
+        }
+
+        self.yielded += 1;
+
+        if self.yielded == 1 {
+            let summary = self.first_leaf();
+            return Some(self.first.take().unwrap_or_else(|| summary.clone()));
+        }
+
+        if self.yielded == self.total {
+            if let Some(last) = self.last.take() {
+                return Some(last);
+            }
+        }
+
+        Some(self.next_leaf().clone())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let exact = self.len();
+        (exact, Some(exact))
+    }
+}
+
+impl<const ARITY: usize, L: Leaf> ExactSizeIterator
+    for LeafSummaries<'_, ARITY, L>
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.total - self.yielded
+    }
+}
+
+impl<const ARITY: usize, L: Leaf> core::iter::FusedIterator
+    for LeafSummaries<'_, ARITY, L>
+{
+}
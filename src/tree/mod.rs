@@ -1,3 +1,7 @@
+#[cfg(feature = "debug-viz")]
+mod dot;
+mod leaf_cursor;
+mod leaf_summaries;
 mod leaves;
 mod node;
 mod node_internal;
@@ -10,13 +14,15 @@ mod tree_slice;
 mod units;
 
 use iter_chain::ExactChain;
+pub use leaf_cursor::LeafCursor;
+pub use leaf_summaries::LeafSummaries;
 pub use leaves::Leaves;
 use node::Node;
 use node_internal::Inode;
 use node_leaf::Lnode;
 use tiny_arc::Arc;
 pub use traits::*;
-pub use tree::Tree;
+pub use tree::{Tree, WeakTree};
 pub use tree_builder::TreeBuilder;
 pub use tree_slice::TreeSlice;
 pub use units::Units;
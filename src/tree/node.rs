@@ -36,6 +36,14 @@ impl<const N: usize, L: Leaf> core::fmt::Debug for Node<N, L> {
 }
 
 impl<const N: usize, L: Leaf> Node<N, L> {
+    #[inline]
+    pub(super) fn aggregate(&self) -> &L::Aggregate {
+        match self {
+            Node::Internal(inode) => inode.aggregate(),
+            Node::Leaf(leaf) => leaf.aggregate(),
+        }
+    }
+
     /// Asserts the invariants of this node, then if it's an inode it calls
     /// itself recursively on all of its children.
     pub(super) fn assert_invariants(&self) {
@@ -10,6 +10,7 @@ use crate::range_bounds_to_start_end;
 pub(super) struct Inode<const N: usize, L: Leaf> {
     children: Vec<Arc<Node<N, L>>>,
     summary: L::Summary,
+    aggregate: L::Aggregate,
     depth: usize,
     leaf_count: usize,
 }
@@ -21,6 +22,7 @@ impl<const N: usize, L: Leaf> core::fmt::Debug for Inode<N, L> {
             f.debug_struct("Inode")
                 .field("children", &self.children)
                 .field("summary", &self.summary)
+                .field("aggregate", &self.aggregate)
                 .field("depth", &self.depth)
                 .field("leaf_count", &self.leaf_count)
                 .finish()
@@ -31,6 +33,11 @@ impl<const N: usize, L: Leaf> core::fmt::Debug for Inode<N, L> {
 }
 
 impl<const N: usize, L: Leaf> Inode<N, L> {
+    #[inline]
+    pub(super) fn aggregate(&self) -> &L::Aggregate {
+        &self.aggregate
+    }
+
     /// Appends the node at the right depth.
     ///
     /// If all the nodes on the right side of the subtree up to the one to
@@ -118,6 +125,14 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
             actual_leaves
         );
 
+        let mut actual_aggregate = L::Aggregate::default();
+
+        for child in self.children() {
+            actual_aggregate.append(child.aggregate());
+        }
+
+        assert_eq!(self.aggregate, actual_aggregate);
+
         for child in self.children() {
             assert_eq!(
                 self.depth(),
@@ -301,6 +316,7 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
                     first.children.append(&mut second.children);
                     first.leaf_count += second.leaf_count;
                     first.summary += second.summary();
+                    first.aggregate.append(second.aggregate());
                     self.children.remove(1);
                 }
                 // Move the minimum number of children from the second child
@@ -371,6 +387,7 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
                     penultimate.children.append(&mut last.children);
                     penultimate.leaf_count += last.leaf_count;
                     penultimate.summary += last.summary();
+                    penultimate.aggregate.append(last.aggregate());
                     self.children.remove(last_idx);
                 }
                 // Move the minimum number of children from the penultimate
@@ -418,6 +435,12 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         &self.children
     }
 
+    /// Returns the number of children this inode has allocated space for.
+    #[inline]
+    pub(super) fn children_capacity(&self) -> usize {
+        self.children.capacity()
+    }
+
     /// Returns the index of the child at the given measure together
     /// with the combined `M`-offset of the other children up to but not
     /// including that child.
@@ -520,6 +543,15 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
             self.leaf_count -= child.leaf_count();
         }
 
+        let mut aggregate = L::Aggregate::default();
+
+        for child in self.children[..start].iter().chain(&self.children[end..])
+        {
+            aggregate.append(child.aggregate());
+        }
+
+        self.aggregate = aggregate;
+
         self.children.drain(start..end)
     }
 
@@ -530,6 +562,7 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
             depth: 1,
             leaf_count: 0,
             summary: Default::default(),
+            aggregate: Default::default(),
         }
     }
 
@@ -561,13 +594,15 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
 
         let mut leaf_count = children[0].leaf_count();
         let mut summary = children[0].summary().clone();
+        let mut aggregate = children[0].aggregate().clone();
 
         for child in &children[1..] {
             leaf_count += child.leaf_count();
             summary += child.summary();
+            aggregate.append(child.aggregate());
         }
 
-        Self { children, depth, leaf_count, summary }
+        Self { children, depth, leaf_count, summary, aggregate }
     }
 
     /// Constructs a new inode from an arbitrarily long sequence of nodes.
@@ -640,6 +675,7 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         self.leaf_count += child.leaf_count();
         self.summary += child.summary();
         self.children.insert(child_offset, child);
+        self.update_aggregate();
     }
 
     /// Inserts a node shallower than this inode's children at the right depth
@@ -886,6 +922,7 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
 
         self.leaf_count += child.leaf_count();
         self.summary += child.summary();
+        self.aggregate.append(child.aggregate());
         self.children.push(child);
     }
 
@@ -900,6 +937,7 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         let child = self.children.remove(child_idx);
         self.leaf_count -= child.leaf_count();
         self.summary -= child.summary();
+        self.update_aggregate();
         child
     }
 
@@ -930,6 +968,7 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         self.summary += new_child.summary();
         self.leaf_count += new_child.leaf_count();
         self.children[child_idx] = new_child;
+        self.update_aggregate();
     }
 
     /// Returns mutable references to the child nodes at `first_idx` and
@@ -953,6 +992,22 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         (&mut first[first_idx], &mut second[second_idx - split_at])
     }
 
+    /// Recomputes the aggregate of this inode from the ones of its children.
+    ///
+    /// Unlike the summary, the aggregate can't be updated by subtracting the
+    /// aggregate of the children that were removed or changed, so this is
+    /// done after every change to the children except for pushes.
+    #[inline]
+    fn update_aggregate(&mut self) {
+        let mut aggregate = L::Aggregate::default();
+
+        for child in &self.children {
+            aggregate.append(child.aggregate());
+        }
+
+        self.aggregate = aggregate;
+    }
+
     /// Calls a function taking a mutable reference to the child at `child_idx`
     /// making sure this inode's summary and leaf count are updated correctly
     /// in case that child's summary or leaf count change as a result of
@@ -975,6 +1030,7 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
 
         self.summary += child.summary();
         self.leaf_count += child.leaf_count();
+        self.update_aggregate();
 
         ret
     }
@@ -6,6 +6,7 @@ use super::traits::{BalancedLeaf, Leaf, Metric, ReplaceableLeaf};
 pub(super) struct Lnode<L: Leaf> {
     value: L,
     summary: L::Summary,
+    aggregate: L::Aggregate,
 }
 
 impl<L: Leaf> core::fmt::Debug for Lnode<L> {
@@ -25,20 +26,27 @@ impl<L: Leaf> core::fmt::Debug for Lnode<L> {
 impl<L: Leaf> From<L> for Lnode<L> {
     #[inline]
     fn from(value: L) -> Self {
-        Self { summary: value.summarize(), value }
+        let summary = value.summarize();
+        Self::new(value, summary)
     }
 }
 
 impl<L: Leaf> From<(L, L::Summary)> for Lnode<L> {
     #[inline]
     fn from((value, summary): (L, L::Summary)) -> Self {
-        Self { value, summary }
+        Self::new(value, summary)
     }
 }
 
 impl<L: Leaf> Lnode<L> {
+    #[inline]
+    pub(super) fn aggregate(&self) -> &L::Aggregate {
+        &self.aggregate
+    }
+
     pub(super) fn assert_invariants(&self) {
         assert_eq!(self.summary, self.value.summarize());
+        assert_eq!(self.aggregate, L::aggregate(self.value.as_slice()));
     }
 
     #[inline]
@@ -54,7 +62,9 @@ impl<L: Leaf> Lnode<L> {
         L::balance_leaves(
             (&mut self.value, &mut self.summary),
             (&mut other.value, &mut other.summary),
-        )
+        );
+        self.update_aggregate();
+        other.update_aggregate();
     }
 
     #[inline]
@@ -82,7 +92,8 @@ impl<L: Leaf> Lnode<L> {
 
     #[inline]
     pub(super) fn new(value: L, summary: L::Summary) -> Self {
-        Self { value, summary }
+        let aggregate = L::aggregate(value.as_slice());
+        Self { value, summary, aggregate }
     }
 
     #[inline]
@@ -92,6 +103,7 @@ impl<L: Leaf> Lnode<L> {
         L: ReplaceableLeaf<M>,
     {
         self.value.remove_up_to(&mut self.summary, up_to);
+        self.update_aggregate();
     }
 
     #[track_caller]
@@ -106,13 +118,20 @@ impl<L: Leaf> Lnode<L> {
         R: RangeBounds<M>,
         L: ReplaceableLeaf<M>,
     {
-        self.value
-            .replace(&mut self.summary, range, replace_with)
-            .map(|extra_leaves| extra_leaves.map(Self::from))
+        let extra_leaves =
+            self.value.replace(&mut self.summary, range, replace_with);
+        self.update_aggregate();
+        extra_leaves.map(|extra_leaves| extra_leaves.map(Self::from))
     }
 
     #[inline]
     pub(super) fn summary(&self) -> &L::Summary {
         &self.summary
     }
+
+    /// Recomputes the aggregate of the leaf after its value has changed.
+    #[inline]
+    fn update_aggregate(&mut self) {
+        self.aggregate = L::aggregate(self.value.as_slice());
+    }
 }
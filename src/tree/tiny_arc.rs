@@ -26,6 +26,13 @@ unsafe impl<T: Sync + Send> Send for ArcInner<T> {}
 unsafe impl<T: Sync + Send> Sync for ArcInner<T> {}
 
 impl<T> Arc<T> {
+    /// Returns the size in bytes of the heap allocation backing an `Arc<T>`,
+    /// including its counter.
+    #[inline]
+    pub(super) const fn allocation_size() -> usize {
+        core::mem::size_of::<ArcInner<T>>()
+    }
+
     #[inline]
     pub(super) fn get_mut(this: &mut Self) -> Option<&mut T> {
         if this.is_unique() {
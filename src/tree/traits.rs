@@ -26,9 +26,36 @@ pub trait AsSlice: Summarize {
     fn as_slice(&self) -> Self::Slice<'_>;
 }
 
-pub trait Leaf: Summarize + BaseMeasured + AsSlice {}
+/// Some data about the contents of a node that, unlike its summary, can't be
+/// subtracted out once it's been added in (e.g. the length of its longest
+/// line).
+///
+/// Instead of being updated incrementally, the aggregate of a node is
+/// recomputed by appending the aggregates of its children from left to right
+/// every time the node is changed.
+pub trait Aggregate: Debug + Default + Clone + PartialEq {
+    /// Appends the aggregate of the contents that come right after the ones
+    /// of `self`.
+    ///
+    /// This should be associative, and the default value should be the
+    /// aggregate of empty contents and act as its identity element.
+    fn append(&mut self, next: &Self);
+}
+
+impl Aggregate for () {
+    #[inline]
+    fn append(&mut self, _: &Self) {}
+}
+
+pub trait Aggregated: AsSlice {
+    type Aggregate: Aggregate;
+
+    fn aggregate(slice: Self::Slice<'_>) -> Self::Aggregate;
+}
+
+pub trait Leaf: Summarize + BaseMeasured + AsSlice + Aggregated {}
 
-impl<T: Summarize + BaseMeasured + AsSlice> Leaf for T {}
+impl<T: Summarize + BaseMeasured + AsSlice + Aggregated> Leaf for T {}
 
 pub trait BalancedLeaf: Leaf + for<'a> From<Self::Slice<'a>> {
     /// Returns whether the leaf node is too small to be on its own and should
@@ -1,4 +1,6 @@
+use alloc::sync;
 use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::ops::Range;
 
 use super::*;
@@ -6,13 +8,47 @@ use super::*;
 /// A self-balancing tree with metadata stored in each node.
 #[derive(Default)]
 pub struct Tree<const ARITY: usize, L: Leaf> {
-    pub(super) root: Arc<Node<ARITY, L>>,
+    /// The root of the tree, behind a handle shared by all the clones of this
+    /// `Tree`.
+    ///
+    /// The handle is the only thing a [`WeakTree`] points to, which keeps
+    /// weak references out of the nodes themselves. It's detached on the
+    /// first edit (see [`root_mut()`](Self::root_mut())), so a `WeakTree`
+    /// stays alive exactly as long as an unedited clone of its `Tree` does.
+    root: sync::Arc<Arc<Node<ARITY, L>>>,
 }
 
 impl<const ARITY: usize, L: Leaf> Clone for Tree<ARITY, L> {
     #[inline]
     fn clone(&self) -> Self {
-        Tree { root: Arc::clone(&self.root) }
+        Tree { root: sync::Arc::clone(&self.root) }
+    }
+}
+
+/// A weak reference to a [`Tree`] that doesn't keep its nodes alive.
+pub struct WeakTree<const ARITY: usize, L: Leaf> {
+    root: sync::Weak<Arc<Node<ARITY, L>>>,
+}
+
+impl<const ARITY: usize, L: Leaf> Clone for WeakTree<ARITY, L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        WeakTree { root: sync::Weak::clone(&self.root) }
+    }
+}
+
+impl<const ARITY: usize, L: Leaf> WeakTree<ARITY, L> {
+    /// Returns whether the `Tree` this was created from is still alive.
+    #[inline]
+    pub fn is_alive(&self) -> bool {
+        self.root.strong_count() > 0
+    }
+
+    /// Attempts to upgrade this to a `Tree`, returning `None` if all the
+    /// `Tree`s sharing its root have since been dropped or edited.
+    #[inline]
+    pub fn upgrade(&self) -> Option<Tree<ARITY, L>> {
+        self.root.upgrade().map(|root| Tree { root })
     }
 }
 
@@ -20,9 +56,9 @@ impl<const ARITY: usize, L: Leaf> core::fmt::Debug for Tree<ARITY, L> {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         if !f.alternate() {
-            f.debug_struct("Tree").field("root", &self.root).finish()
+            f.debug_struct("Tree").field("root", self.root()).finish()
         } else {
-            write!(f, "{:#?}", self.root)
+            write!(f, "{:#?}", self.root())
         }
     }
 }
@@ -64,14 +100,20 @@ impl<const ARITY: usize, L: BalancedLeaf + Clone> From<TreeSlice<'_, ARITY, L>>
             from_treeslice::into_tree_root(slice)
         };
 
-        Tree { root }
+        Tree::from_root(root)
     }
 }
 
 impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
+    /// Returns the aggregate of all the leaves of the tree.
+    #[inline]
+    pub fn aggregate(&self) -> &L::Aggregate {
+        self.root.aggregate()
+    }
+
     #[doc(hidden)]
     pub fn assert_invariants(&self) {
-        match &*self.root {
+        match &**self.root() {
             Node::Internal(root) => {
                 // The root is the only inode that can have as few as 2
                 // children.
@@ -86,11 +128,87 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         }
     }
 
+    /// Appends `other` at the end of this `Tree`.
+    ///
+    /// The root of the shallower tree is attached to the side of the deeper
+    /// one at its own depth, and only the nodes along the seam are
+    /// rebalanced, so this runs in O(log n) and every other node of both
+    /// trees is reused as is.
+    #[inline]
+    pub fn append(&mut self, other: Self)
+    where
+        L: BalancedLeaf + Clone,
+    {
+        if other.base_measure() == L::BaseMetric::zero() {
+            return;
+        }
+
+        if self.base_measure() == L::BaseMetric::zero() {
+            *self = other;
+            return;
+        }
+
+        match self.depth().cmp(&other.depth()) {
+            Ordering::Greater => {
+                let root = self.root_mut();
+
+                let inode = Arc::make_mut(root).get_internal_mut();
+
+                if let Some(extra) = inode.append_at_depth(other.into_root()) {
+                    let extra = Arc::new(Node::Internal(extra));
+                    let inode =
+                        Inode::from_children([Arc::clone(root), extra]);
+                    *root = Arc::new(Node::Internal(inode));
+                }
+            },
+
+            Ordering::Less => {
+                let root = self.root_mut();
+
+                let left = core::mem::replace(root, other.into_root());
+
+                let inode = Arc::make_mut(root).get_internal_mut();
+
+                if let Some(extra) = inode.prepend_at_depth(left) {
+                    let extra = Arc::new(Node::Internal(extra));
+                    let inode =
+                        Inode::from_children([extra, Arc::clone(root)]);
+                    *root = Arc::new(Node::Internal(inode));
+                }
+            },
+
+            Ordering::Equal => {
+                let root = self.root_mut();
+
+                let mut other = other.into_root();
+
+                Arc::make_mut(root).balance(Arc::make_mut(&mut other));
+
+                if !other.is_empty() {
+                    let inode =
+                        Inode::from_children([Arc::clone(root), other]);
+                    *root = Arc::new(Node::Internal(inode));
+                }
+            },
+        }
+    }
+
     #[inline]
     pub fn base_measure(&self) -> L::BaseMetric {
         self.measure::<L::BaseMetric>()
     }
 
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.root.depth()
+    }
+
+    /// Returns a [`WeakTree`] pointing to the root of this `Tree`.
+    #[inline]
+    pub fn downgrade(&self) -> WeakTree<ARITY, L> {
+        WeakTree { root: sync::Arc::downgrade(&self.root) }
+    }
+
     /// Returns the `M2`-measure of all the leaves before `up_to` plus the
     /// `M2`-measure of the left sub-slice of the leaf at `up_to`.
     #[track_caller]
@@ -114,12 +232,38 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         I: IntoIterator<Item = L>,
         L: Default,
     {
-        let mut leaves =
-            leaves.into_iter().map(Lnode::from).map(Node::Leaf).map(Arc::new);
+        Self::from_lnodes(leaves.into_iter().map(Lnode::from))
+    }
+
+    /// Creates a new `Tree` from a sequence of leaves and their summaries,
+    /// without summarizing the leaves again.
+    ///
+    /// It's up to the caller to make sure that each summary matches its
+    /// leaf. If the iterator doesn't yield any items the `Tree` will contain
+    /// a single leaf with its default value.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_summarized_leaves<I>(leaves: I) -> Self
+    where
+        I: IntoIterator<Item = (L, L::Summary)>,
+        L: Default,
+    {
+        Self::from_lnodes(leaves.into_iter().map(Lnode::from))
+    }
+
+    #[inline]
+    fn from_lnodes<I>(lnodes: I) -> Self
+    where
+        I: Iterator<Item = Lnode<L>>,
+        L: Default,
+    {
+        let mut leaves = lnodes.map(Node::Leaf).map(Arc::new);
 
         let Some(first) = leaves.next() else { return Self::default() };
 
-        let Some(second) = leaves.next() else { return Self { root: first } };
+        let Some(second) = leaves.next() else {
+            return Self::from_root(first);
+        };
 
         let leaves = {
             let (lo, hi) = leaves.size_hint();
@@ -132,7 +276,17 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
 
         let root = Inode::from_nodes(leaves);
 
-        Self { root: Arc::new(Node::Internal(root)) }
+        Self::from_root(Arc::new(Node::Internal(root)))
+    }
+
+    #[inline]
+    pub(super) fn from_root(root: Arc<Node<ARITY, L>>) -> Self {
+        Self { root: sync::Arc::new(root) }
+    }
+
+    #[inline]
+    fn into_root(self) -> Arc<Node<ARITY, L>> {
+        sync::Arc::unwrap_or_clone(self.root)
     }
 
     /// Returns the leaf containing the `measure`-th unit of the `M`-metric,
@@ -147,17 +301,36 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         self.root.leaf_at_measure(measure)
     }
 
+    /// Returns the number of internal nodes of this `Tree`.
+    #[inline]
+    pub fn inode_count(&self) -> usize {
+        inode_count(self.root())
+    }
+
     #[inline]
     pub fn leaf_count(&self) -> usize {
         self.root.leaf_count()
     }
 
+    /// Returns an iterator over the summaries of the leaves of this `Tree`.
+    #[inline]
+    pub fn leaf_summaries(&self) -> LeafSummaries<'_, ARITY, L> {
+        LeafSummaries::from(self)
+    }
+
     /// Returns an iterator over the leaves of this `Tree`.
     #[inline]
     pub fn leaves(&self) -> Leaves<'_, ARITY, L> {
         Leaves::from(self)
     }
 
+    /// Returns the minimum number of children of every internal node except
+    /// the root.
+    #[inline]
+    pub const fn min_children() -> usize {
+        Inode::<ARITY, L>::min_children()
+    }
+
     /// Returns the `M`-measure of this `Tree` obtaining by summing up the
     /// `M`-measures of all its leaves.
     #[inline]
@@ -168,6 +341,16 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         M::measure(self.summary())
     }
 
+    /// Returns the number of bytes allocated on the heap for the nodes of
+    /// this `Tree`, i.e. for the node allocations themselves and for the
+    /// children vectors of the internal nodes.
+    ///
+    /// Any heap memory owned by the leaves is not included.
+    #[inline]
+    pub fn node_bytes(&self) -> usize {
+        node_bytes(self.root())
+    }
+
     /// Replaces a range of the `Tree` with the given replacement.
     #[track_caller]
     #[inline]
@@ -179,15 +362,14 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         M: Metric<L::Summary>,
         L: ReplaceableLeaf<M> + Clone,
     {
-        if let Some(extras) =
-            tree_replace::replace(&mut self.root, range, replace_with)
+        let root = self.root_mut();
+
+        if let Some(extras) = tree_replace::replace(root, range, replace_with)
         {
-            debug_assert!(
-                extras.iter().all(|n| n.depth() == self.root.depth())
-            );
+            debug_assert!(extras.iter().all(|n| n.depth() == root.depth()));
 
-            self.root = Arc::new(Node::Internal(Inode::from_nodes(
-                core::iter::once(Arc::clone(self.root())).exact_chain(extras),
+            *root = Arc::new(Node::Internal(Inode::from_nodes(
+                core::iter::once(Arc::clone(root)).exact_chain(extras),
             )));
         }
     }
@@ -197,6 +379,37 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         &self.root
     }
 
+    /// Returns a mutable reference to the root of this `Tree`, to be called
+    /// before every edit.
+    ///
+    /// If the root handle is shared with other `Tree`s or is pointed to by a
+    /// `WeakTree`, this `Tree` first gets a handle of its own, so the edit is
+    /// invisible to them. The nodes themselves are still shared, and are only
+    /// cloned by `Arc::make_mut()` along the path being edited.
+    #[inline]
+    fn root_mut(&mut self) -> &mut Arc<Node<ARITY, L>> {
+        sync::Arc::make_mut(&mut self.root)
+    }
+
+    /// Returns the base measure of the longest prefix of this `Tree` made up
+    /// of nodes shared with `other`, i.e. of nodes pointing to the same
+    /// allocations as the ones at the same position in `other`.
+    ///
+    /// This runs in O(log n) for `Tree`s derived from each other, since at
+    /// each level it only needs to descend into the first child they don't
+    /// share.
+    #[inline]
+    pub fn shared_prefix(&self, other: &Self) -> L::BaseMetric {
+        shared_prefix(self.root(), other.root())
+    }
+
+    /// Same as [`shared_prefix()`](Self::shared_prefix()), but for the
+    /// longest suffix.
+    #[inline]
+    pub fn shared_suffix(&self, other: &Self) -> L::BaseMetric {
+        shared_suffix(self.root(), other.root())
+    }
+
     /// Returns a slice of the `Tree` in the range of the given metric.
     #[track_caller]
     #[inline]
@@ -210,7 +423,7 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         debug_assert!(range.start <= range.end);
         debug_assert!(range.end <= self.measure::<M>() + M::one());
 
-        TreeSlice::from_range_in_root(&self.root, range)
+        TreeSlice::from_range_in_root(self.root(), range)
     }
 
     #[inline]
@@ -229,6 +442,119 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
     }
 }
 
+#[inline]
+fn inode_count<const N: usize, L: Leaf>(node: &Arc<Node<N, L>>) -> usize {
+    match &**node {
+        // The children of an inode at depth 1 are all leaves.
+        Node::Internal(inode) if inode.depth() == 1 => 1,
+        Node::Internal(inode) => {
+            1 + inode.children().iter().map(inode_count).sum::<usize>()
+        },
+        Node::Leaf(_) => 0,
+    }
+}
+
+#[inline]
+fn node_bytes<const N: usize, L: Leaf>(node: &Arc<Node<N, L>>) -> usize {
+    let allocation = Arc::<Node<N, L>>::allocation_size();
+
+    match &**node {
+        Node::Internal(inode) => {
+            allocation
+                + inode.children_capacity()
+                    * core::mem::size_of::<Arc<Node<N, L>>>()
+                + inode.children().iter().map(node_bytes).sum::<usize>()
+        },
+        Node::Leaf(_) => allocation,
+    }
+}
+
+#[inline]
+fn shared_prefix<const N: usize, L: Leaf>(
+    left: &Arc<Node<N, L>>,
+    right: &Arc<Node<N, L>>,
+) -> L::BaseMetric {
+    if Arc::ptr_eq(left, right) {
+        return left.base_measure();
+    }
+
+    match left.depth().cmp(&right.depth()) {
+        Ordering::Greater => shared_prefix(left.get_internal().first(), right),
+
+        Ordering::Less => shared_prefix(left, right.get_internal().first()),
+
+        Ordering::Equal => {
+            let (Node::Internal(left), Node::Internal(right)) =
+                (&**left, &**right)
+            else {
+                return L::BaseMetric::zero();
+            };
+
+            let mut measure = L::BaseMetric::zero();
+
+            for (left, right) in left.children().iter().zip(right.children()) {
+                let shared = shared_prefix(left, right);
+
+                measure += shared;
+
+                // Keep going only if the children are identical, even if
+                // they're not the same allocation.
+                if shared != left.base_measure()
+                    || shared != right.base_measure()
+                {
+                    break;
+                }
+            }
+
+            measure
+        },
+    }
+}
+
+#[inline]
+fn shared_suffix<const N: usize, L: Leaf>(
+    left: &Arc<Node<N, L>>,
+    right: &Arc<Node<N, L>>,
+) -> L::BaseMetric {
+    if Arc::ptr_eq(left, right) {
+        return left.base_measure();
+    }
+
+    match left.depth().cmp(&right.depth()) {
+        Ordering::Greater => shared_suffix(left.get_internal().last(), right),
+
+        Ordering::Less => shared_suffix(left, right.get_internal().last()),
+
+        Ordering::Equal => {
+            let (Node::Internal(left), Node::Internal(right)) =
+                (&**left, &**right)
+            else {
+                return L::BaseMetric::zero();
+            };
+
+            let mut measure = L::BaseMetric::zero();
+
+            for (left, right) in
+                left.children().iter().rev().zip(right.children().iter().rev())
+            {
+                let shared = shared_suffix(left, right);
+
+                measure += shared;
+
+                // Keep going only if the children are identical, even if
+                // they're not the same allocation.
+                if shared != left.base_measure()
+                    || shared != right.base_measure()
+                {
+                    break;
+                }
+            }
+
+            measure
+        },
+    }
+}
+
 mod from_treeslice {
     //! This module handles the logic used to convert `TreeSlice`s into
     //! `Tree`s.
@@ -1647,6 +1973,12 @@ mod tests {
         }
     }
 
+    impl Aggregated for usize {
+        type Aggregate = ();
+
+        fn aggregate(_: UsizeSlice<'_>) {}
+    }
+
     impl Metric<Count> for LeavesMetric {
         fn zero() -> Self {
             0
@@ -101,6 +101,24 @@ impl<const ARITY: usize, L: Leaf> TreeBuilder<ARITY, L> {
         }
     }
 
+    /// Creates a new `TreeBuilder` whose stack is already big enough to build
+    /// a `Tree` with `leaf_count` leaves without reallocating.
+    #[inline]
+    pub fn with_capacity(leaf_count: usize) -> Self {
+        let mut levels = 0;
+        let mut nodes = leaf_count / ARITY;
+
+        while nodes > 0 {
+            levels += 1;
+            nodes /= ARITY;
+        }
+
+        Self {
+            stack: Vec::with_capacity(levels),
+            leaves: Vec::with_capacity(ARITY),
+        }
+    }
+
     /// Completes the build and outputs the final `Tree`, consuming `self`.
     #[inline]
     pub fn build(mut self) -> Tree<ARITY, L>
@@ -114,7 +132,9 @@ impl<const ARITY: usize, L: Leaf> TreeBuilder<ARITY, L> {
                 // empty Tree. This is why we need the `Default` bound on `L`.
                 return Tree::default();
             } else if self.leaves.len() == 1 {
-                return Tree { root: self.leaves.into_iter().next().unwrap() };
+                return Tree::from_root(
+                    self.leaves.into_iter().next().unwrap(),
+                );
             }
         }
 
@@ -167,7 +187,7 @@ impl<const ARITY: usize, L: Leaf> TreeBuilder<ARITY, L> {
 
         Node::replace_with_single_child(&mut root);
 
-        Tree { root }
+        Tree::from_root(root)
     }
 
     #[allow(dead_code)]
@@ -1,4 +1,4 @@
-use core::ops::Range;
+use core::ops::{ControlFlow, Range};
 
 use super::*;
 
@@ -56,6 +56,31 @@ impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L> {
       Public methods
     */
 
+    /// Returns the aggregate of the slice.
+    ///
+    /// This appends the aggregates of the start and end slices to the ones
+    /// the nodes fully contained in the slice already store, so it runs in
+    /// `O(log n)` time without having to visit every leaf.
+    #[inline]
+    pub fn aggregate(&self) -> L::Aggregate {
+        let mut visitor = LeafVisitor {
+            base_offset: L::BaseMetric::measure(&self.offset),
+            offset: L::BaseMetric::zero(),
+            first: self.start_slice,
+            last: self.end_slice,
+            visited: 0,
+            total: self.leaf_count,
+        };
+
+        let mut aggregate = L::Aggregate::default();
+
+        if visitor.total > 0 {
+            visitor.aggregate(self.root, &mut aggregate);
+        }
+
+        aggregate
+    }
+
     #[doc(hidden)]
     pub fn assert_invariants(&self) {
         match &**self.root {
@@ -121,6 +146,13 @@ impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L> {
         self.measure::<L::BaseMetric>()
     }
 
+    /// Returns the base measure of the part of the slice's root that comes
+    /// before the start of the slice.
+    #[inline]
+    pub fn base_offset(&self) -> L::BaseMetric {
+        L::BaseMetric::measure(&self.offset)
+    }
+
     /// Returns the `M2`-measure of all the leaves before `up_to` plus the
     /// `M2`-measure of the left sub-slice of the leaf at `up_to`.
     #[inline]
@@ -150,6 +182,32 @@ impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L> {
         &self.end_summary
     }
 
+    /// Calls `fun` on every leaf of this `TreeSlice`, from front to back,
+    /// stopping as soon as it returns [`ControlFlow::Break`].
+    ///
+    /// Unlike iterating over [`leaves()`](Self::leaves()) this doesn't
+    /// allocate.
+    #[inline]
+    pub fn for_each_leaf<B, F>(&self, mut fun: F) -> ControlFlow<B>
+    where
+        F: FnMut(L::Slice<'a>) -> ControlFlow<B>,
+    {
+        let mut visitor = LeafVisitor {
+            base_offset: L::BaseMetric::measure(&self.offset),
+            offset: L::BaseMetric::zero(),
+            first: self.start_slice,
+            last: self.end_slice,
+            visited: 0,
+            total: self.leaf_count,
+        };
+
+        if visitor.total == 0 {
+            return ControlFlow::Continue(());
+        }
+
+        visitor.visit(self.root, &mut fun)
+    }
+
     /// Returns the leaf containing the `measure`-th unit of the `M`-metric,
     /// plus the `M`-measure of all the leaves before it.
     #[inline]
@@ -182,6 +240,13 @@ impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L> {
         self.leaf_count
     }
 
+    /// Returns an iterator over the summaries of the leaves of this
+    /// `TreeSlice`.
+    #[inline]
+    pub fn leaf_summaries(&self) -> LeafSummaries<'a, ARITY, L> {
+        LeafSummaries::from(self)
+    }
+
     #[inline]
     pub fn leaves(&self) -> Leaves<'a, ARITY, L> {
         Leaves::from(self)
@@ -195,6 +260,16 @@ impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L> {
         M::measure(self.summary())
     }
 
+    /// Returns `true` if both slices cover the same range of the same root,
+    /// i.e. of the same allocation. If this returns `true` the slices are
+    /// guaranteed to be equal, but not vice versa.
+    #[inline]
+    pub fn ptr_eq(&self, other: &TreeSlice<'_, ARITY, L>) -> bool {
+        Arc::ptr_eq(self.root, other.root)
+            && self.base_offset() == other.base_offset()
+            && self.base_measure() == other.base_measure()
+    }
+
     #[inline]
     pub(super) fn root(&self) -> &'a Arc<Node<ARITY, L>> {
         self.root
@@ -368,6 +443,135 @@ where
     }
 }
 
+/// The state of [`TreeSlice::for_each_leaf()`] and
+/// [`TreeSlice::aggregate()`].
+struct LeafVisitor<'a, L: Leaf + 'a> {
+    /// The base offset of the start of the slice in its root.
+    base_offset: L::BaseMetric,
+
+    /// The base measure of the leaves skipped so far while looking for the
+    /// first leaf of the slice.
+    offset: L::BaseMetric,
+
+    /// The slice's start slice.
+    first: L::Slice<'a>,
+
+    /// The slice's end slice.
+    last: L::Slice<'a>,
+
+    /// The number of leaves visited so far.
+    visited: usize,
+
+    /// The number of leaves spanned by the slice.
+    total: usize,
+}
+
+impl<'a, L: Leaf + 'a> LeafVisitor<'a, L> {
+    /// Like [`visit()`](Self::visit()), but instead of calling a function on
+    /// every leaf it appends the aggregates of the leaves to `aggregate`,
+    /// using the ones stored in the nodes that are fully contained in the
+    /// slice instead of descending into them.
+    #[inline]
+    fn aggregate<const N: usize>(
+        &mut self,
+        node: &'a Node<N, L>,
+        aggregate: &mut L::Aggregate,
+    ) {
+        match node {
+            Node::Internal(inode) => {
+                for child in inode.children() {
+                    if self.visited == 0 {
+                        let child_measure = child.base_measure();
+
+                        if self.offset + child_measure <= self.base_offset {
+                            self.offset += child_measure;
+                            continue;
+                        }
+                    }
+
+                    let leaf_count = child.leaf_count();
+
+                    // The child contains neither the first nor the last leaf
+                    // of the slice.
+                    if self.visited > 0
+                        && self.visited + leaf_count < self.total
+                    {
+                        aggregate.append(child.aggregate());
+                        self.visited += leaf_count;
+                    } else {
+                        self.aggregate(child, aggregate);
+                    }
+
+                    if self.visited == self.total {
+                        break;
+                    }
+                }
+            },
+
+            Node::Leaf(leaf) => {
+                let leaf_aggregate = if self.visited == 0 {
+                    L::aggregate(self.first)
+                } else if self.visited + 1 == self.total {
+                    L::aggregate(self.last)
+                } else {
+                    leaf.aggregate().clone()
+                };
+
+                self.visited += 1;
+
+                aggregate.append(&leaf_aggregate);
+            },
+        }
+    }
+
+    #[inline]
+    fn visit<const N: usize, B, F>(
+        &mut self,
+        node: &'a Node<N, L>,
+        fun: &mut F,
+    ) -> ControlFlow<B>
+    where
+        F: FnMut(L::Slice<'a>) -> ControlFlow<B>,
+    {
+        match node {
+            Node::Internal(inode) => {
+                for child in inode.children() {
+                    if self.visited == 0 {
+                        let child_measure = child.base_measure();
+
+                        if self.offset + child_measure <= self.base_offset {
+                            self.offset += child_measure;
+                            continue;
+                        }
+                    }
+
+                    self.visit(child, fun)?;
+
+                    if self.visited == self.total {
+                        break;
+                    }
+                }
+
+                ControlFlow::Continue(())
+            },
+
+            Node::Leaf(leaf) => {
+                let slice = if self.visited == 0 {
+                    self.first
+                } else if self.visited + 1 == self.total {
+                    self.last
+                } else {
+                    leaf.as_slice()
+                };
+
+                self.visited += 1;
+
+                fun(slice)
+            },
+        }
+    }
+}
+
 /// Returns the deepest node under `nodes`'s subtree that fully contains the
 /// range between `start` and `end`, together with the `S` and `E` offsets with
 /// respect to that node.
@@ -0,0 +1,52 @@
+mod common;
+
+#[cfg(feature = "arbitrary")]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+    use crop::Rope;
+    use crop::arbitrary::EditOp;
+    use rand::{Rng, rng};
+
+    #[test]
+    fn arbitrary_rope_empty() {
+        let mut u = Unstructured::new(&[]);
+        let r = Rope::arbitrary(&mut u).unwrap();
+        r.assert_invariants();
+        assert_eq!(r, "");
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn arbitrary_rope_and_edits_random() {
+        let mut rng = rng();
+
+        for _ in 0..100 {
+            let len = rng.random_range(0..2048);
+            let bytes = (0..len).map(|_| rng.random()).collect::<Vec<u8>>();
+
+            let mut u = Unstructured::new(&bytes);
+
+            let mut r = Rope::arbitrary(&mut u).unwrap();
+            r.assert_invariants();
+
+            let mut s = r.to_string();
+
+            for op in u.arbitrary_iter::<EditOp>().unwrap() {
+                let op = op.unwrap();
+
+                let change = op.apply(&mut r);
+                r.assert_invariants();
+
+                let text = match &op {
+                    EditOp::Insert { text, .. }
+                    | EditOp::Replace { text, .. } => text.as_str(),
+                    EditOp::Delete { .. } => "",
+                };
+
+                assert_eq!(change.inserted_len, text.len());
+                s.replace_range(change.byte_range, text);
+                assert_eq!(r, s);
+            }
+        }
+    }
+}
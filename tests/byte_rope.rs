@@ -0,0 +1,131 @@
+use crop::ByteRope;
+use rand::Rng;
+
+mod common;
+
+use common::LARGE;
+
+fn random_bytes(rng: &mut impl Rng, len: usize) -> Vec<u8> {
+    (0..len).map(|_| rng.random()).collect()
+}
+
+#[test]
+fn byte_rope_empty() {
+    let r = ByteRope::new();
+    assert!(r.is_empty());
+    assert_eq!(r.chunks().count(), 0);
+    assert_eq!(r.bytes().count(), 0);
+    assert!(r.to_vec().is_empty());
+    r.assert_invariants();
+}
+
+#[test]
+fn byte_rope_non_utf8() {
+    let bytes = [0xff, 0xfe, 0x80, 0x00, 0xc3];
+
+    let mut r = ByteRope::from(&bytes[..]);
+    assert_eq!(r, bytes);
+
+    // None of these offsets would be valid in a `Rope`.
+    r.insert(1, [0xe2, 0x82]);
+    r.delete(4..5);
+    assert_eq!(r, [0xff, 0xe2, 0x82, 0xfe, 0x00, 0xc3]);
+    r.assert_invariants();
+}
+
+#[test]
+fn byte_rope_debug() {
+    let r = ByteRope::from(&[1, 2, 3][..]);
+    assert_eq!(format!("{r:?}"), "ByteRope([1, 2, 3])");
+    assert_eq!(format!("{:?}", r.byte_slice(1..)), "ByteRopeSlice([2, 3])");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn byte_rope_from_large() {
+    let r = ByteRope::from(LARGE.as_bytes());
+    assert_eq!(r, LARGE.as_bytes());
+    assert_eq!(r.len(), LARGE.len());
+    assert!(r.bytes().eq(LARGE.bytes()));
+    assert!(r.bytes().rev().eq(LARGE.bytes().rev()));
+    r.assert_invariants();
+}
+
+/// Applies the same random edits to a `ByteRope` and to a `Vec<u8>`, checking
+/// that they always have the same contents.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn byte_rope_random_edits() {
+    let mut rng = rand::rng();
+
+    let mut vec = random_bytes(&mut rng, 10_000);
+    let mut r = ByteRope::from(&*vec);
+
+    for _ in 0..1000 {
+        let start = rng.random_range(0..=vec.len());
+        let end = rng.random_range(start..=(start + 100).min(vec.len()));
+        let len = rng.random_range(0..100);
+        let bytes = random_bytes(&mut rng, len);
+
+        r.replace(start..end, &bytes);
+        vec.splice(start..end, bytes);
+
+        assert_eq!(r, vec);
+        r.assert_invariants();
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn byte_rope_random_slices() {
+    let mut rng = rand::rng();
+
+    let vec = random_bytes(&mut rng, 10_000);
+    let r = ByteRope::from(&*vec);
+
+    for _ in 0..1000 {
+        let start = rng.random_range(0..=vec.len());
+        let end = rng.random_range(start..=vec.len());
+
+        let s = r.byte_slice(start..end);
+        assert_eq!(s, vec[start..end]);
+        assert_eq!(s.len(), end - start);
+
+        if !s.is_empty() {
+            let idx = rng.random_range(0..s.len());
+            assert_eq!(s.byte(idx), vec[start + idx]);
+        }
+
+        let mut bytes = s.bytes();
+        let mid = rng.random_range(0..=s.len());
+        let front = bytes.by_ref().take(mid).collect::<Vec<_>>();
+        let back = bytes.rev().collect::<Vec<_>>();
+        assert_eq!(front, vec[start..start + mid]);
+        assert!(
+            back.into_iter().rev().eq(vec[start + mid..end].iter().copied())
+        );
+
+        let r = ByteRope::from(s);
+        assert_eq!(r, vec[start..end]);
+        r.assert_invariants();
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn byte_rope_split_off() {
+    let mut rng = rand::rng();
+
+    let vec = random_bytes(&mut rng, 10_000);
+
+    for _ in 0..100 {
+        let mut r = ByteRope::from(&*vec);
+        let at = rng.random_range(0..=vec.len());
+
+        let rest = r.split_off(at);
+        assert_eq!(r, vec[..at]);
+        assert_eq!(rest, vec[at..]);
+        r.assert_invariants();
+        rest.assert_invariants();
+    }
+}
@@ -0,0 +1,159 @@
+use std::ops::Range;
+
+use crop::Rope;
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, TEXT_EMOJI};
+
+/// Greek text whose final sigmas are lowercased differently from the other
+/// ones, mixed with chars whose uppercase equivalent is longer than them.
+const GREEK: &str = "ὈΔΥΣΣΕΎΣ ΣΑΣ. Σ 'ΣΑΣ' ΑΣ\nstraße ﬁve ǰ ΐ ΑΣ\t ΑΣ.ΣΑ";
+
+#[test]
+fn to_lowercase_final_sigma() {
+    // Repeating the text makes some of the chunk boundaries fall within the
+    // context of a sigma.
+    for s in [GREEK, &GREEK.repeat(200)] {
+        let lower = Rope::from(s).to_lowercase();
+        assert_eq!(lower, s.to_lowercase());
+        lower.assert_invariants();
+    }
+
+    assert_eq!(Rope::from("ΌΣΟΣ").to_lowercase(), "όσος");
+    assert_eq!(Rope::new().to_lowercase(), "");
+}
+
+#[test]
+fn to_uppercase_multi_char() {
+    for s in [GREEK, &GREEK.repeat(200)] {
+        let upper = Rope::from(s).to_uppercase();
+        assert_eq!(upper, s.to_uppercase());
+        assert!(upper.byte_len() > s.len());
+        upper.assert_invariants();
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn case_conversion_random_slices() {
+    let mut rng = rand::rng();
+
+    let text = [GREEK, TEXT_EMOJI, CURSED_LIPSUM, MEDIUM].join(GREEK);
+
+    for s in [text.as_str(), LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let mut start = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+            let mut end = rng.random_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+            assert_eq!(slice.to_lowercase(), s[start..end].to_lowercase());
+            assert_eq!(slice.to_uppercase(), s[start..end].to_uppercase());
+        }
+    }
+}
+
+#[test]
+fn eq_ignore_case() {
+    let r = Rope::from(GREEK);
+
+    assert!(r.eq_ignore_case(&GREEK.to_lowercase()));
+    assert!(r.eq_ignore_case(&GREEK.to_lowercase().replace('ς', "σ")));
+    assert!(!r.eq_ignore_case(&GREEK.to_uppercase()));
+    assert!(!r.eq_ignore_case(GREEK.strip_suffix('Α').unwrap()));
+
+    assert!(Rope::from("\u{212A}ELVIN ſ").eq_ignore_case("kelvin s"));
+    assert!(!Rope::from("ı").eq_ignore_case("I"));
+    assert!(Rope::new().eq_ignore_case(""));
+
+    let s = format!("{MEDIUM}{GREEK}");
+    let r = Rope::from(s.as_str());
+    assert!(r.eq_ignore_case(&s.to_lowercase()));
+    assert!(r.byte_slice(1..).eq_ignore_case(&s[1..].to_lowercase()));
+}
+
+#[test]
+fn find_ignore_case() {
+    let r = Rope::from("Ὀδυσσεύς, ΟΔΥΣΣΕΎΣ \u{212A}");
+
+    let matches = r.find_iter_ignore_case("σσ").collect::<Vec<_>>();
+    assert_eq!(matches, [7..11, 25..29]);
+
+    assert_eq!(r.find_ignore_case("k"), Some(36..39));
+    assert_eq!(r.find_ignore_case("ΟΔΥΣΣΕΎΣ k!"), None);
+
+    // Overlapping occurrences are skipped, and a partial match is retried
+    // from the longest suffix matching a prefix of the pattern.
+    let r = Rope::from("AaAaAb aAB");
+    let matches = r.find_iter_ignore_case("aab").collect::<Vec<_>>();
+    assert_eq!(matches, [3..6, 7..10]);
+
+    let matches = r.find_iter_ignore_case("aa").collect::<Vec<_>>();
+    assert_eq!(matches, [0..2, 2..4, 7..9]);
+
+    let r = Rope::from("ab");
+    let matches = r.find_iter_ignore_case("").collect::<Vec<_>>();
+    assert_eq!(matches, [0..0, 1..1, 2..2]);
+}
+
+/// A quadratic reference implementation of `find_iter_ignore_case()`, which
+/// compares every run of chars as long as the pattern against it.
+fn find_ignore_case_naive(s: &str, pattern: &str) -> Vec<Range<usize>> {
+    let pattern_len = pattern.chars().count();
+    let chars = s.char_indices().collect::<Vec<_>>();
+
+    let mut matches = Vec::new();
+    let mut idx = 0;
+
+    while idx + pattern_len <= chars.len() {
+        let candidate = &chars[idx..idx + pattern_len];
+
+        let (start, _) = candidate[0];
+        let (last, ch) = candidate[pattern_len - 1];
+        let end = last + ch.len_utf8();
+
+        if Rope::from(&s[start..end]).eq_ignore_case(pattern) {
+            matches.push(start..end);
+            idx += pattern_len;
+        } else {
+            idx += 1;
+        }
+    }
+
+    matches
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn find_ignore_case_random() {
+    let mut rng = rand::rng();
+
+    let text = [GREEK, TEXT_EMOJI, CURSED_LIPSUM].join(GREEK).repeat(20);
+
+    let r = Rope::from(text.as_str());
+
+    let chars = text.char_indices().map(|(idx, _)| idx).collect::<Vec<_>>();
+
+    for _ in 0..50 {
+        let start = chars[rng.random_range(0..chars.len())];
+        let len = rng.random_range(1..8);
+        let pattern = text[start..].chars().take(len).collect::<String>();
+        let pattern = if rng.random() {
+            pattern.to_uppercase()
+        } else {
+            pattern.to_lowercase()
+        };
+
+        let matches = r.find_iter_ignore_case(&pattern).collect::<Vec<_>>();
+        assert_eq!(matches, find_ignore_case_naive(&text, &pattern));
+    }
+}
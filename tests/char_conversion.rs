@@ -0,0 +1,145 @@
+mod common;
+
+#[cfg(feature = "char-metric")]
+mod tests {
+    use crop::Rope;
+    use rand::{Rng, rng};
+
+    use crate::common::{CURSED_LIPSUM, LARGE, TEXT_EMOJI};
+
+    #[test]
+    fn char_len_0() {
+        let r = Rope::new();
+        assert_eq!(r.char_len(), 0);
+        assert_eq!(r.byte_slice(..).char_len(), 0);
+    }
+
+    #[test]
+    fn char_len_1() {
+        let r = Rope::from(TEXT_EMOJI);
+        assert_eq!(r.char_len(), TEXT_EMOJI.chars().count());
+
+        let s = r.byte_slice(16..39);
+        assert_eq!(s.char_len(), TEXT_EMOJI[16..39].chars().count());
+    }
+
+    #[test]
+    fn char_byte_round_trip() {
+        let r = Rope::from(TEXT_EMOJI);
+        let s = r.byte_slice(..);
+
+        for (char_offset, (byte_offset, _)) in
+            TEXT_EMOJI.char_indices().enumerate()
+        {
+            assert_eq!(r.byte_of_char(char_offset), byte_offset);
+            assert_eq!(r.char_of_byte(byte_offset), char_offset);
+            assert_eq!(s.byte_of_char(char_offset), byte_offset);
+            assert_eq!(s.char_of_byte(byte_offset), char_offset);
+        }
+
+        assert_eq!(r.byte_of_char(r.char_len()), r.byte_len());
+        assert_eq!(r.char_of_byte(r.byte_len()), r.char_len());
+    }
+
+    #[should_panic]
+    #[test]
+    fn byte_of_char_out_of_bounds() {
+        let r = Rope::from("🐸");
+        let _ = r.byte_of_char(2);
+    }
+
+    #[should_panic]
+    #[test]
+    fn char_of_byte_out_of_bounds() {
+        let r = Rope::from("🐸");
+        let _ = r.byte_slice(..).char_of_byte(5);
+    }
+
+    #[test]
+    fn insert_and_delete_chars() {
+        let mut r = Rope::from("ƒoo bär");
+
+        r.insert_at_char(4, "bàz ");
+        assert_eq!(r, "ƒoo bàz bär");
+
+        r.delete_chars(..4);
+        assert_eq!(r, "bàz bär");
+
+        r.delete_chars(2..=4);
+        assert_eq!(r, "bàär");
+        assert_eq!(r.char_len(), 4);
+    }
+
+    #[test]
+    fn char_slice() {
+        let r = Rope::from(CURSED_LIPSUM);
+        let chars = CURSED_LIPSUM.char_indices().collect::<Vec<_>>();
+
+        let byte_of = |char_offset: usize| {
+            chars.get(char_offset).map_or(CURSED_LIPSUM.len(), |&(i, _)| i)
+        };
+
+        for start in (0..=chars.len()).step_by(7) {
+            for end in (start..=chars.len()).step_by(5) {
+                let expected = &CURSED_LIPSUM[byte_of(start)..byte_of(end)];
+                assert_eq!(r.char_slice(start..end), expected);
+                assert_eq!(r.byte_slice(..).char_slice(start..end), expected);
+            }
+        }
+
+        let s = r.char_slice(10..60);
+        assert_eq!(s.char_slice(5..20), r.char_slice(15..30));
+        assert_eq!(s.char_slice(..), r.char_slice(10..60));
+    }
+
+    #[should_panic]
+    #[test]
+    fn char_slice_out_of_bounds() {
+        let r = Rope::from("🐸");
+        let _ = r.char_slice(..2);
+    }
+
+    #[should_panic]
+    #[test]
+    fn char_slice_start_after_end() {
+        let r = Rope::from("abc");
+        #[allow(clippy::reversed_empty_ranges)]
+        let _ = r.byte_slice(..).char_slice(2..1);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn char_metric_after_random_edits() {
+        let mut rng = rng();
+
+        let mut r = Rope::from(LARGE);
+        let mut s = String::from(LARGE);
+
+        for _ in 0..200 {
+            let char_len = s.chars().count();
+            let start = rng.random_range(0..=char_len);
+            let end = rng.random_range(start..=char_len.min(start + 50));
+
+            let byte_start =
+                s.char_indices().nth(start).map_or(s.len(), |(i, _)| i);
+            let byte_end =
+                s.char_indices().nth(end).map_or(s.len(), |(i, _)| i);
+
+            let text = &CURSED_LIPSUM[..CURSED_LIPSUM
+                .char_indices()
+                .nth(rng.random_range(0..20))
+                .unwrap()
+                .0];
+
+            r.delete_chars(start..end);
+            r.insert_at_char(start, text);
+
+            s.replace_range(byte_start..byte_end, text);
+
+            assert_eq!(r.char_len(), s.chars().count());
+        }
+
+        r.assert_invariants();
+        assert_eq!(r, s);
+    }
+}
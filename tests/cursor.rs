@@ -0,0 +1,150 @@
+use crop::{Rope, RopeSlice};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TEXT_EMOJI, TINY};
+
+/// Checks that walking a cursor over the slice forward and backward yields
+/// the same chars as `s`, and that its chunk always contains it.
+fn assert_walk(slice: RopeSlice<'_>, s: &str) {
+    let mut cursor = slice.cursor(0);
+
+    let mut offset = 0;
+
+    for expected in s.chars() {
+        assert_chunk(slice, &cursor);
+        assert_eq!(cursor.next_char(), Some(expected));
+        offset += expected.len_utf8();
+        assert_eq!(cursor.byte_offset(), offset);
+    }
+
+    assert_eq!(cursor.next_char(), None);
+    assert_eq!(cursor.byte_offset(), s.len());
+
+    for expected in s.chars().rev() {
+        assert_chunk(slice, &cursor);
+        assert_eq!(cursor.prev_char(), Some(expected));
+        offset -= expected.len_utf8();
+        assert_eq!(cursor.byte_offset(), offset);
+    }
+
+    assert_eq!(cursor.prev_char(), None);
+    assert_eq!(cursor.byte_offset(), 0);
+}
+
+fn assert_chunk(slice: RopeSlice<'_>, cursor: &crop::Cursor<'_>) {
+    let (chunk, chunk_start) = cursor.chunk();
+    let chunk_end = chunk_start + chunk.len();
+    assert!((chunk_start..=chunk_end).contains(&cursor.byte_offset()));
+    assert_eq!(slice.byte_slice(chunk_start..chunk_end), chunk);
+}
+
+#[test]
+fn cursor_empty() {
+    let r = Rope::new();
+
+    let mut cursor = r.cursor(0);
+
+    assert_eq!(cursor.next_char(), None);
+    assert_eq!(cursor.prev_char(), None);
+    assert_eq!(cursor.chunk(), ("", 0));
+
+    let r = Rope::from("foo");
+
+    let mut cursor = r.byte_slice(1..1).cursor(0);
+
+    assert_eq!(cursor.next_char(), None);
+    assert_eq!(cursor.prev_char(), None);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn cursor_walk() {
+    for s in [TINY, SMALL, CURSED_LIPSUM, TEXT_EMOJI] {
+        let r = Rope::from(s);
+        assert_walk(r.byte_slice(..), s);
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn cursor_walk_edited() {
+    let mut r = Rope::from(SMALL);
+    let mut s = SMALL.to_owned();
+
+    // Insert some text in the middle of the leaves to move their gaps around.
+    for offset in (0..s.len()).step_by(97).rev() {
+        if s.is_char_boundary(offset) {
+            r.insert(offset, "🐸");
+            s.insert(offset, '🐸');
+        }
+    }
+
+    assert_walk(r.byte_slice(..), &s);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn cursor_walk_slices() {
+    let mut rng = rand::rng();
+
+    for s in [SMALL, CURSED_LIPSUM, TEXT_EMOJI] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let mut start = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end = rng.random_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            assert_walk(r.byte_slice(start..end), &s[start..end]);
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn cursor_seek() {
+    let mut rng = rand::rng();
+
+    for s in [MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        let mut cursor = r.cursor(0);
+
+        for _ in 0..100 {
+            let mut offset = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(offset) {
+                offset += 1;
+            }
+
+            cursor.seek(offset);
+            assert_eq!(cursor.byte_offset(), offset);
+            assert_chunk(r.byte_slice(..), &cursor);
+
+            assert_eq!(cursor.next_char(), s[offset..].chars().next());
+            cursor.seek(offset);
+            assert_eq!(cursor.prev_char(), s[..offset].chars().next_back());
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn cursor_seek_out_of_bounds() {
+    let r = Rope::from("foo");
+    let _ = r.cursor(4);
+}
+
+#[test]
+#[should_panic]
+fn cursor_seek_not_char_boundary() {
+    let r = Rope::from("ƒoo");
+    let _ = r.cursor(1);
+}
@@ -0,0 +1,57 @@
+mod common;
+
+#[cfg(feature = "debug-viz")]
+mod tests {
+    use crop::Rope;
+
+    use super::common::{LARGE, TINY};
+
+    #[test]
+    fn to_dot_empty() {
+        let dot = Rope::new().to_dot();
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot.matches("[label=").count(), 1);
+        assert_eq!(dot.matches(" -> ").count(), 0);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn to_dot_0() {
+        for s in [TINY, LARGE] {
+            let r = Rope::from(s);
+
+            let stats = r.tree_stats();
+
+            let dot = r.to_dot();
+
+            // Every node but the root has exactly one incoming edge.
+            assert_eq!(dot.matches("[label=").count(), stats.node_count);
+            assert_eq!(dot.matches(" -> ").count(), stats.node_count - 1);
+        }
+    }
+
+    #[test]
+    fn to_dot_escapes_labels() {
+        let r = Rope::from("\"quoted\" \\ back\nslash");
+
+        let dot = r.to_dot();
+
+        for line in dot.lines().filter(|line| line.contains("[label=")) {
+            let label = line
+                .split_once("[label=\"")
+                .and_then(|(_, rest)| rest.strip_suffix("\"];"))
+                .unwrap();
+
+            // All the quotes in the label are escaped.
+            let mut chars = label.chars();
+            while let Some(ch) = chars.next() {
+                match ch {
+                    '\\' => assert!(chars.next().is_some()),
+                    '"' => panic!("unescaped quote in {label:?}"),
+                    _ => {},
+                }
+            }
+        }
+    }
+}
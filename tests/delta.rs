@@ -0,0 +1,376 @@
+use crop::{Delta, Edit, Rope};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+#[test]
+fn delta_empty() {
+    let delta = Delta::new();
+    assert!(delta.is_empty());
+
+    let mut r = Rope::from("foo");
+    r.apply_delta(&delta);
+    assert_eq!(r, "foo");
+}
+
+#[test]
+fn delta_merges_touching_edits() {
+    let mut delta = Delta::new();
+
+    // Typing a word one character at a time results in a single edit.
+    for (idx, ch) in "ƒoo".char_indices() {
+        delta.insert(3 + idx, ch.encode_utf8(&mut [0; 4]));
+    }
+
+    assert_eq!(
+        delta.edits(),
+        [Edit { byte_range: 3..3, text: "ƒoo".to_owned() }]
+    );
+
+    // Deleting what was just inserted cancels it out.
+    delta.delete(3..7);
+    assert!(delta.is_empty());
+}
+
+#[test]
+fn delta_overlapping_edits() {
+    let mut delta = Delta::new();
+
+    delta.replace(2..4, "aaaa").replace(10..12, "bbbb");
+
+    // The new edit spans the end of the first edit, some untouched text and
+    // the start of the second one.
+    delta.replace(4..12, "c");
+
+    assert_eq!(
+        delta.edits(),
+        [Edit { byte_range: 2..10, text: "aacbb".to_owned() }]
+    );
+
+    let mut r = Rope::from("0123456789ab");
+    r.apply_delta(&delta);
+    assert_eq!(r, "01aacbbab");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn delta_random() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, CURSED_LIPSUM] {
+        let mut delta = Delta::new();
+        let mut string = s.to_owned();
+
+        for _ in 0..100 {
+            let mut start = rng.random_range(0..=string.len());
+            while !string.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end =
+                rng.random_range(start..=string.len().min(start + 8));
+            while !string.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let text = ["", "a", "ƒoo", "\r\n", "🐸"][rng.random_range(0..5)];
+
+            delta.replace(start..end, text);
+            string.replace_range(start..end, text);
+
+            for pair in delta.edits().windows(2) {
+                assert!(pair[0].byte_range.end < pair[1].byte_range.start);
+            }
+
+            let mut r = Rope::from(s);
+            r.apply_delta(&delta);
+            r.assert_invariants();
+            assert_eq!(r, string);
+        }
+    }
+}
+
+/// Returns a `Delta` made of `edits` random edits to `s`, together with the
+/// result of applying it.
+fn random_delta(rng: &mut impl Rng, s: &str, edits: usize) -> (Delta, String) {
+    let mut delta = Delta::new();
+    let mut string = s.to_owned();
+
+    for _ in 0..edits {
+        let mut start = rng.random_range(0..=string.len());
+        while !string.is_char_boundary(start) {
+            start += 1;
+        }
+
+        let mut end = rng.random_range(start..=string.len().min(start + 8));
+        while !string.is_char_boundary(end) {
+            end += 1;
+        }
+
+        let text = ["", "a", "ƒoo", "\r\n", "🐸"][rng.random_range(0..5)];
+
+        delta.replace(start..end, text);
+        string.replace_range(start..end, text);
+    }
+
+    (delta, string)
+}
+
+#[test]
+fn delta_compose() {
+    let mut first = Delta::new();
+    first.replace(2..4, "aaaa").insert(10, "ƒoo");
+
+    let mut second = Delta::new();
+    second.delete(..3).insert(7, "b").replace(10..13, "c");
+
+    let mut r = Rope::from("0123456789");
+    r.apply_delta(&first.compose(&second));
+    assert_eq!(r, "aaa4567bƒc9");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn delta_compose_random() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, CURSED_LIPSUM] {
+        for _ in 0..20 {
+            let (first, after_first) = random_delta(&mut rng, s, 10);
+            let (second, after_second) =
+                random_delta(&mut rng, &after_first, 10);
+
+            let mut r = Rope::from(s);
+            r.apply_delta(&first.compose(&second));
+            assert_eq!(r, after_second);
+        }
+    }
+}
+
+#[test]
+fn delta_transform_same_offset() {
+    let mut ours = Delta::new();
+    ours.insert(3, "a");
+
+    let mut theirs = Delta::new();
+    theirs.insert(3, "b");
+
+    let (ours_rebased, theirs_rebased) = ours.transform(&theirs);
+
+    let mut r = Rope::from("foobar");
+    r.apply_delta(&ours);
+    r.apply_delta(&theirs_rebased);
+    assert_eq!(r, "fooabbar");
+
+    let mut r = Rope::from("foobar");
+    r.apply_delta(&theirs);
+    r.apply_delta(&ours_rebased);
+    assert_eq!(r, "fooabbar");
+}
+
+#[test]
+fn delta_transform_overlapping_deletions() {
+    let mut ours = Delta::new();
+    ours.delete(2..6);
+
+    let mut theirs = Delta::new();
+    theirs.delete(4..8).insert(1, "ƒ");
+
+    let (ours_rebased, theirs_rebased) = ours.transform(&theirs);
+
+    let mut r = Rope::from("0123456789");
+    r.apply_delta(&ours);
+    r.apply_delta(&theirs_rebased);
+    assert_eq!(r, "0ƒ189");
+
+    let mut r = Rope::from("0123456789");
+    r.apply_delta(&theirs);
+    r.apply_delta(&ours_rebased);
+    assert_eq!(r, "0ƒ189");
+}
+
+#[test]
+fn delta_transform_insert_in_deleted_range() {
+    let mut ours = Delta::new();
+    ours.delete(2..8);
+
+    let mut theirs = Delta::new();
+    theirs.insert(5, "🐸");
+
+    let (ours_rebased, theirs_rebased) = ours.transform(&theirs);
+
+    assert_eq!(
+        ours_rebased.edits(),
+        [
+            Edit { byte_range: 2..5, text: String::new() },
+            Edit { byte_range: 9..12, text: String::new() },
+        ]
+    );
+
+    let mut r = Rope::from("0123456789");
+    r.apply_delta(&ours);
+    r.apply_delta(&theirs_rebased);
+    assert_eq!(r, "01🐸89");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn delta_transform_random() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, CURSED_LIPSUM] {
+        for _ in 0..50 {
+            let ours_edits = rng.random_range(0..10);
+            let theirs_edits = rng.random_range(0..10);
+
+            let (ours, _) = random_delta(&mut rng, s, ours_edits);
+            let (theirs, _) = random_delta(&mut rng, s, theirs_edits);
+
+            let (ours_rebased, theirs_rebased) = ours.transform(&theirs);
+
+            let mut a = Rope::from(s);
+            a.apply_delta(&ours);
+            a.apply_delta(&theirs_rebased);
+
+            let mut b = Rope::from(s);
+            b.apply_delta(&theirs);
+            b.apply_delta(&ours_rebased);
+
+            a.assert_invariants();
+            assert_eq!(a, b);
+            assert_eq!(a.to_string(), b.to_string());
+
+            // Composing either side gives the same edit.
+            let mut c = Rope::from(s);
+            c.apply_delta(&ours.compose(&theirs_rebased));
+            assert_eq!(c, a);
+        }
+    }
+}
+
+/// Asserts that applying `old.diff(new)` to `old` results in `new`, returning
+/// the delta.
+fn assert_diff(old: &Rope, new: &Rope) -> Delta {
+    let delta = old.diff(new);
+
+    for pair in delta.edits().windows(2) {
+        assert!(pair[0].byte_range.end < pair[1].byte_range.start);
+    }
+
+    let mut r = old.clone();
+    r.apply_delta(&delta);
+    r.assert_invariants();
+    assert_eq!(r, *new);
+
+    delta
+}
+
+#[test]
+fn diff_identical() {
+    let r = Rope::from(MEDIUM);
+    assert!(r.diff(&r.clone()).is_empty());
+    assert!(r.diff(&Rope::from(MEDIUM)).is_empty());
+    assert!(Rope::new().diff(&Rope::new()).is_empty());
+}
+
+#[test]
+fn diff_is_minimal() {
+    // The example from Myers' paper, which needs 5 insertions and deletions.
+    let old = Rope::from("abcabba");
+    let new = Rope::from("cbabac");
+
+    let delta = assert_diff(&old, &new);
+
+    let changed = delta
+        .edits()
+        .iter()
+        .map(|edit| edit.byte_range.len() + edit.text.len())
+        .sum::<usize>();
+
+    assert_eq!(changed, 5);
+}
+
+#[test]
+fn diff_multibyte() {
+    assert_diff(&Rope::from("é"), &Rope::from("è"));
+    assert_diff(&Rope::from("a🐸b"), &Rope::from("a🐻b"));
+    assert_diff(&Rope::from(CURSED_LIPSUM), &Rope::from(TINY));
+    assert_diff(&Rope::new(), &Rope::from(CURSED_LIPSUM));
+    assert_diff(&Rope::from(CURSED_LIPSUM), &Rope::new());
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn diff_random() {
+    let mut rng = rand::rng();
+
+    for _ in 0..200 {
+        let old = (0..rng.random_range(0..40))
+            .map(|_| ['a', 'b', 'ƒ', '\n'][rng.random_range(0..4)])
+            .collect::<String>();
+
+        let new = (0..rng.random_range(0..40))
+            .map(|_| ['a', 'b', 'ƒ', '\n'][rng.random_range(0..4)])
+            .collect::<String>();
+
+        assert_diff(&Rope::from(old), &Rope::from(new));
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn diff_edited_clone() {
+    let mut rng = rand::rng();
+
+    for s in [SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let old = Rope::from(s);
+        let mut new = old.clone();
+
+        let mut changed = 0;
+
+        for _ in 0..5 {
+            let mut start = rng.random_range(0..=new.byte_len());
+            while !new.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end =
+                rng.random_range(start..=new.byte_len().min(start + 64));
+            while !new.is_char_boundary(end) {
+                end += 1;
+            }
+
+            changed += new.byte_slice(start..end).chars().count() + 3;
+            new.replace(start..end, "ƒoo");
+        }
+
+        let delta = assert_diff(&old, &new);
+
+        // The diff can't change more chars than the edits did.
+        let diff_changed = delta
+            .edits()
+            .iter()
+            .map(|edit| {
+                old.byte_slice(edit.byte_range.clone()).chars().count()
+                    + edit.text.chars().count()
+            })
+            .sum::<usize>();
+
+        assert!(diff_changed <= changed);
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn diff_large_changes() {
+    let old = Rope::from(MEDIUM);
+
+    let mut new = old.clone();
+    new.delete(1000..60000);
+    new.insert(100000, &LARGE[..5000]);
+
+    assert_diff(&old, &new);
+    assert_diff(&new, &old);
+}
@@ -0,0 +1,147 @@
+mod common;
+
+#[cfg(feature = "encoding")]
+mod tests {
+    use crop::Rope;
+    use crop::encoding::Encoding;
+
+    use crate::common::{CURSED_LIPSUM, LARGE, TEXT_EMOJI};
+
+    fn encode_utf16(s: &str, encoding: Encoding, bom: bool) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        if bom {
+            bytes.extend_from_slice(encoding.bom());
+        }
+
+        for unit in s.encode_utf16() {
+            match encoding {
+                Encoding::Utf16Le => bytes.extend(unit.to_le_bytes()),
+                Encoding::Utf16Be => bytes.extend(unit.to_be_bytes()),
+                _ => unreachable!(),
+            }
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn detect() {
+        assert_eq!(Encoding::detect(b""), Encoding::Utf8);
+        assert_eq!(Encoding::detect(b"\xEF\xBB\xBFfoo"), Encoding::Utf8);
+        assert_eq!(Encoding::detect(b"\xFF\xFE"), Encoding::Utf16Le);
+        assert_eq!(Encoding::detect(b"\xFE\xFF"), Encoding::Utf16Be);
+        assert_eq!(Encoding::from_bom(b"\xFF"), None);
+    }
+
+    #[test]
+    fn utf8() {
+        let mut bytes = Encoding::Utf8.bom().to_vec();
+        bytes.extend_from_slice(TEXT_EMOJI.as_bytes());
+
+        let r =
+            Rope::from_bytes_with_encoding(&bytes, Encoding::Utf8).unwrap();
+        assert_eq!(r, TEXT_EMOJI);
+        assert!(r.has_bom());
+
+        let r = Rope::from_bytes_with_encoding(&bytes[3..], Encoding::Utf8)
+            .unwrap();
+        assert!(!r.has_bom());
+
+        bytes.insert(10, 0xFF);
+
+        let err = Rope::from_bytes_with_encoding(&bytes, Encoding::Utf8)
+            .unwrap_err();
+        assert_eq!(err.byte_offset(), 10);
+        assert_eq!(err.encoding(), Encoding::Utf8);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn utf16_round_trip() {
+        for s in [TEXT_EMOJI, CURSED_LIPSUM, LARGE] {
+            for encoding in [Encoding::Utf16Le, Encoding::Utf16Be] {
+                for bom in [false, true] {
+                    let bytes = encode_utf16(s, encoding, bom);
+                    assert_eq!(Encoding::detect(&bytes) == encoding, bom);
+
+                    let r = Rope::from_bytes_with_encoding(&bytes, encoding)
+                        .unwrap();
+                    assert_eq!(r, s);
+                    r.assert_invariants();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn utf16_unpaired_surrogate() {
+        let mut bytes = encode_utf16("foo", Encoding::Utf16Be, true);
+        bytes.extend([0xD8, 0x3D, 0x00, 0x61]);
+
+        let err = Rope::from_bytes_with_encoding(&bytes, Encoding::Utf16Be)
+            .unwrap_err();
+
+        assert_eq!(err.byte_offset(), 8);
+        assert_eq!(err.to_string(), "invalid UTF-16BE at byte 8");
+    }
+
+    #[test]
+    fn latin1() {
+        let bytes = (0..=255).collect::<Vec<u8>>();
+
+        let r =
+            Rope::from_bytes_with_encoding(&bytes, Encoding::Latin1).unwrap();
+
+        assert_eq!(
+            r,
+            bytes.iter().map(|&b| char::from(b)).collect::<String>()
+        );
+        assert_eq!(r.byte_len(), 128 + 2 * 128);
+        r.assert_invariants();
+    }
+
+    #[test]
+    fn windows_1252() {
+        let bytes = b"\x93caf\xE9\x94 \x80 5 \x85 \x81\x9D";
+
+        let r = Rope::from_bytes_with_encoding(bytes, Encoding::Windows1252)
+            .unwrap();
+
+        assert_eq!(
+            r,
+            "\u{201C}caf\u{E9}\u{201D} \u{20AC} 5 \u{2026} \u{81}\u{9D}"
+        );
+        assert_eq!(Encoding::detect(bytes), Encoding::Utf8);
+        assert_eq!(Encoding::Windows1252.to_string(), "Windows-1252");
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn windows_1252_large() {
+        let bytes = LARGE
+            .chars()
+            .map(|ch| if ch.is_ascii() { ch as u8 } else { 0x80 })
+            .collect::<Vec<u8>>();
+
+        let r = Rope::from_bytes_with_encoding(&bytes, Encoding::Windows1252)
+            .unwrap();
+
+        assert_eq!(r.chars().count(), bytes.len());
+        assert!(r.chars().zip(LARGE.chars()).all(|(decoded, ch)| {
+            if ch.is_ascii() { decoded == ch } else { decoded == '\u{20AC}' }
+        }));
+        r.assert_invariants();
+    }
+
+    #[test]
+    fn utf16_odd_length() {
+        let mut bytes = encode_utf16("foo", Encoding::Utf16Le, false);
+        bytes.push(0x61);
+
+        let err = Rope::from_bytes_with_encoding(&bytes, Encoding::Utf16Le)
+            .unwrap_err();
+
+        assert_eq!(err.byte_offset(), 6);
+    }
+}
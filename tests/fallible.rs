@@ -0,0 +1,209 @@
+use crop::{Error, Rope};
+use rand::{Rng, rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, TEXT_EMOJI};
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn try_byte_slice_random() {
+    let mut rng = rng();
+
+    for s in [CURSED_LIPSUM, TEXT_EMOJI, LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..100 {
+            let start = rng.random_range(0..=s.len() + 1);
+            let end = rng.random_range(0..=s.len() + 1);
+
+            match s.get(start..end) {
+                Some(slice) => {
+                    assert_eq!(r.try_byte_slice(start..end).unwrap(), slice);
+
+                    let rs = r.byte_slice(..);
+                    assert_eq!(rs.try_byte_slice(start..end).unwrap(), slice);
+                },
+
+                None => {
+                    assert!(r.try_byte_slice(start..end).is_err());
+                    assert!(
+                        r.byte_slice(..).try_byte_slice(start..end).is_err()
+                    );
+                },
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn try_replace_random() {
+    let mut rng = rng();
+
+    let mut r = Rope::from(CURSED_LIPSUM);
+    let mut s = String::from(CURSED_LIPSUM);
+
+    for _ in 0..100 {
+        let start = rng.random_range(0..=s.len() + 1);
+        let end = rng.random_range(0..=s.len() + 1);
+
+        let is_valid = s.get(start..end).is_some();
+
+        assert_eq!(r.try_replace(start..end, "ƒoo").is_ok(), is_valid);
+
+        if is_valid {
+            s.replace_range(start..end, "ƒoo");
+        }
+
+        assert_eq!(r, s);
+    }
+}
+
+#[allow(clippy::reversed_empty_ranges)]
+#[test]
+fn try_byte_slice_errors() {
+    let r = Rope::from("🗻∈🌏");
+
+    assert_eq!(
+        r.try_byte_slice(4..3),
+        Err(Error::ByteStartAfterEnd { byte_start: 4, byte_end: 3 })
+    );
+
+    assert_eq!(
+        r.try_byte_slice(..12),
+        Err(Error::ByteOffsetOutOfBounds { byte_offset: 12, byte_len: 11 })
+    );
+
+    assert_eq!(
+        r.try_byte_slice(1..),
+        Err(Error::ByteOffsetNotCharBoundary { byte_offset: 1 })
+    );
+}
+
+#[test]
+fn try_insert_errors() {
+    let mut r = Rope::from("🗻∈🌏");
+
+    assert_eq!(
+        r.try_insert(12, "foo"),
+        Err(Error::ByteOffsetOutOfBounds { byte_offset: 12, byte_len: 11 })
+    );
+
+    assert_eq!(
+        r.try_insert(2, "foo"),
+        Err(Error::ByteOffsetNotCharBoundary { byte_offset: 2 })
+    );
+
+    assert_eq!(r, "🗻∈🌏");
+
+    assert!(r.try_insert(11, "foo").is_ok());
+    assert_eq!(r, "🗻∈🌏foo");
+}
+
+#[test]
+fn try_line_errors() {
+    let r = Rope::from("foo\nbar\n");
+
+    assert_eq!(r.try_line(0).unwrap(), "foo");
+    assert_eq!(r.try_line(1).unwrap(), "bar");
+
+    assert_eq!(
+        r.try_line(2),
+        Err(Error::LineIndexOutOfBounds { line_index: 2, line_len: 2 })
+    );
+
+    assert_eq!(
+        Rope::new().try_line(0),
+        Err(Error::LineIndexOutOfBounds { line_index: 0, line_len: 0 })
+    );
+}
+
+#[allow(clippy::reversed_empty_ranges)]
+#[test]
+fn try_line_slice_errors() {
+    let r = Rope::from("foo\nbar\n");
+    let s = r.byte_slice(4..);
+
+    assert_eq!(r.try_line_slice(2..).unwrap(), "");
+    assert_eq!(s.try_line_slice(..1).unwrap(), "bar\n");
+
+    assert_eq!(
+        r.try_line_slice(..3),
+        Err(Error::LineOffsetOutOfBounds { line_offset: 3, line_len: 2 })
+    );
+
+    assert_eq!(
+        s.try_line_slice(1..0),
+        Err(Error::LineStartAfterEnd { line_start: 1, line_end: 0 })
+    );
+}
+
+#[test]
+fn error_display() {
+    let err = Error::ByteOffsetOutOfBounds { byte_offset: 12, byte_len: 11 };
+
+    assert_eq!(
+        err.to_string(),
+        "byte offset out of bounds: the offset is 12 but the length is 11"
+    );
+}
+
+#[test]
+fn get_byte() {
+    let r = Rope::from(TEXT_EMOJI);
+    let s = r.byte_slice(3..20);
+
+    for idx in 0..=TEXT_EMOJI.len() {
+        assert_eq!(r.get_byte(idx), TEXT_EMOJI.as_bytes().get(idx).copied());
+    }
+
+    for idx in 0..=s.byte_len() {
+        assert_eq!(
+            s.get_byte(idx),
+            TEXT_EMOJI.as_bytes()[3..20].get(idx).copied()
+        );
+    }
+}
+
+#[test]
+fn get_lines() {
+    let r = Rope::from("foo\nbar\r\nbaz\n");
+    let s = r.byte_slice(4..);
+
+    assert_eq!(r.get_line(2).unwrap(), "baz");
+    assert!(r.get_line(3).is_none());
+    assert!(s.get_line(2).is_none());
+
+    assert_eq!(r.get_line_slice(1..3).unwrap(), "bar\r\nbaz\n");
+    assert_eq!(s.get_line_slice(1..).unwrap(), "baz\n");
+    assert!(s.get_line_slice(..3).is_none());
+}
+
+#[test]
+fn try_from_utf8() {
+    let r = Rope::try_from(TEXT_EMOJI.as_bytes()).unwrap();
+    assert_eq!(r, TEXT_EMOJI);
+
+    let r = Rope::try_from_utf8(LARGE.as_bytes().to_vec()).unwrap();
+    assert_eq!(r, LARGE);
+
+    let mut bytes = TEXT_EMOJI.as_bytes().to_vec();
+    bytes.insert(10, 0xFF);
+
+    let err = Rope::try_from(&*bytes).unwrap_err();
+    assert_eq!(err.byte_offset(), 10);
+    assert_eq!(err.invalid_len(), Some(1));
+    assert_eq!(err, Rope::try_from_utf8(bytes).unwrap_err());
+
+    // The input ends in the middle of a 4-byte code point.
+    let bytes = &"foo🌎".as_bytes()[..5];
+
+    let err = Rope::try_from(bytes).unwrap_err();
+    assert_eq!(err.byte_offset(), 3);
+    assert_eq!(err.invalid_len(), None);
+    assert_eq!(
+        err.to_string(),
+        "invalid UTF-8 at byte 3: input ends in the middle of a code point"
+    );
+}
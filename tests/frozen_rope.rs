@@ -0,0 +1,90 @@
+use crop::{FrozenRope, Rope};
+use rand::{Rng, rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+fn check_frozen(frozen: &FrozenRope, r: &Rope) {
+    assert_eq!(frozen.as_str(), r.to_string());
+    assert_eq!(frozen.byte_len(), r.byte_len());
+    assert_eq!(frozen.line_len(), r.line_len());
+    assert_eq!(frozen.is_empty(), r.is_empty());
+
+    for line_offset in 0..=r.line_len() {
+        assert_eq!(
+            frozen.byte_of_line(line_offset),
+            r.byte_of_line(line_offset)
+        );
+    }
+
+    for line_index in 0..r.line_len() {
+        assert_eq!(frozen.line(line_index), r.line(line_index));
+    }
+
+    let text = frozen.as_str();
+
+    for (byte_offset, _) in text.char_indices().step_by(31) {
+        assert_eq!(
+            frozen.line_of_byte(byte_offset),
+            r.line_of_byte(byte_offset)
+        );
+    }
+
+    assert_eq!(frozen.line_of_byte(text.len()), r.line_of_byte(text.len()));
+
+    assert_eq!(&frozen.thaw(), r);
+}
+
+#[test]
+fn frozen_empty() {
+    let r = Rope::new();
+    check_frozen(&r.freeze(), &r);
+}
+
+#[test]
+fn frozen_crlf() {
+    let r = Rope::from("foo\r\nbar\r\n\r\nbaz\r");
+    let frozen = r.freeze();
+    check_frozen(&frozen, &r);
+    assert_eq!(frozen.line(2), "");
+    assert_eq!(frozen.line(3), "baz\r");
+}
+
+#[test]
+#[should_panic]
+fn frozen_line_out_of_bounds() {
+    let _ = Rope::from("foo\nbar\n").freeze().line(2);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn frozen_test_vectors() {
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+        check_frozen(&r.freeze(), &r);
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn frozen_random_slices() {
+    let mut rng = rng();
+
+    for s in [MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..10 {
+            let mut start = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+            let mut end = rng.random_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+            let slice = r.byte_slice(start..end);
+            check_frozen(&FrozenRope::from(slice), &Rope::from(slice));
+        }
+    }
+}
@@ -144,3 +144,299 @@ fn graphemes_is_boundary_out_of_bounds() {
 fn graphemes_is_boundary_empty_rope() {
     assert!(Rope::new().is_grapheme_boundary(0));
 }
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn graphemes_is_boundary_matches_str() {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let s = "🇬🇧🇯🇵👨‍👩‍👧e\u{301}\r\n🇺🇸a🇫🇷🇷🇺".repeat(16);
+
+    let r = Rope::from(s.as_str());
+
+    for (start, end) in [(0, s.len()), (8, s.len() - 8)] {
+        let slice = r.byte_slice(start..end);
+
+        let boundaries = s[start..end]
+            .grapheme_indices(true)
+            .map(|(offset, _)| offset)
+            .chain([end - start])
+            .collect::<Vec<_>>();
+
+        for offset in 0..=slice.byte_len() {
+            assert_eq!(
+                slice.is_grapheme_boundary(offset),
+                boundaries.contains(&offset),
+                "{offset}"
+            );
+        }
+    }
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn graphemes_next_prev_boundary_empty_rope() {
+    let r = Rope::new();
+    assert_eq!(r.next_grapheme_boundary(0), None);
+    assert_eq!(r.prev_grapheme_boundary(0), None);
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn graphemes_next_prev_boundary_matches_str() {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let s = "🇬🇧🇯🇵👨‍👩‍👧e\u{301}\r\n🇺🇸a🇫🇷🇷🇺".repeat(16);
+
+    let r = Rope::from(s.as_str());
+
+    for (start, end) in [(0, s.len()), (8, s.len() - 8)] {
+        let slice = r.byte_slice(start..end);
+
+        let boundaries = s[start..end]
+            .grapheme_indices(true)
+            .map(|(offset, _)| offset)
+            .chain([end - start])
+            .collect::<Vec<_>>();
+
+        for offset in 0..=slice.byte_len() {
+            if !s.is_char_boundary(start + offset) {
+                continue;
+            }
+
+            let next = boundaries.iter().copied().find(|&b| b > offset);
+            let prev = boundaries.iter().copied().rfind(|&b| b < offset);
+
+            assert_eq!(slice.next_grapheme_boundary(offset), next, "{offset}");
+            assert_eq!(slice.prev_grapheme_boundary(offset), prev, "{offset}");
+        }
+    }
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn graphemes_next_prev_boundary_walk() {
+    let r = Rope::from("🇷🇸🇮🇴");
+
+    assert_eq!(r.next_grapheme_boundary(0), Some(8));
+    assert_eq!(r.next_grapheme_boundary(8), Some(16));
+    assert_eq!(r.next_grapheme_boundary(16), None);
+
+    assert_eq!(r.prev_grapheme_boundary(16), Some(8));
+    assert_eq!(r.prev_grapheme_boundary(8), Some(0));
+    assert_eq!(r.prev_grapheme_boundary(0), None);
+
+    // An offset in the middle of a grapheme moves to the boundaries around
+    // it.
+    assert_eq!(r.next_grapheme_boundary(4), Some(8));
+    assert_eq!(r.prev_grapheme_boundary(4), Some(0));
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn graphemes_floor_ceil_boundary_matches_str() {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let s = "🇬🇧🇯🇵👨‍👩‍👧e\u{301}\r\n🇺🇸a🇫🇷🇷🇺".repeat(16);
+
+    let r = Rope::from(s.as_str());
+
+    for (start, end) in [(0, s.len()), (8, s.len() - 8)] {
+        let slice = r.byte_slice(start..end);
+
+        let boundaries = s[start..end]
+            .grapheme_indices(true)
+            .map(|(offset, _)| offset)
+            .chain([end - start])
+            .collect::<Vec<_>>();
+
+        for offset in 0..=slice.byte_len() {
+            let floor = boundaries.iter().copied().rfind(|&b| b <= offset);
+            let ceil = boundaries.iter().copied().find(|&b| b >= offset);
+
+            assert_eq!(
+                slice.floor_grapheme_boundary(offset),
+                floor.unwrap(),
+                "{offset}"
+            );
+            assert_eq!(
+                slice.ceil_grapheme_boundary(offset),
+                ceil.unwrap(),
+                "{offset}"
+            );
+        }
+    }
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn graphemes_floor_ceil_boundary_empty_rope() {
+    let r = Rope::new();
+    assert_eq!(r.floor_grapheme_boundary(0), 0);
+    assert_eq!(r.ceil_grapheme_boundary(0), 0);
+    assert_eq!(r.grapheme_slice(..), "");
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn grapheme_slice() {
+    let r = Rope::from("🇷🇸🇮🇴e\u{301}");
+
+    assert_eq!(r.grapheme_slice(0..8), "🇷🇸");
+    assert_eq!(r.grapheme_slice(3..9), "🇷🇸🇮🇴");
+    assert_eq!(r.grapheme_slice(16..17), "e\u{301}");
+    assert_eq!(r.grapheme_slice(8..8), "");
+    assert_eq!(r.grapheme_slice(4..4), "🇷🇸");
+
+    let s = r.byte_slice(8..);
+    assert_eq!(s.grapheme_slice(1..), "🇮🇴e\u{301}");
+    assert_eq!(s.grapheme_slice(..9), "🇮🇴e\u{301}");
+}
+
+#[cfg(feature = "graphemes")]
+#[should_panic]
+#[test]
+fn grapheme_slice_out_of_bounds() {
+    let r = Rope::from("🇷🇸");
+    let _ = r.grapheme_slice(..9);
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn word_bounds_empty_rope() {
+    let r = Rope::new();
+    assert_eq!(r.word_bounds().next(), None);
+    assert_eq!(r.words().next(), None);
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn word_bounds_matches_str() {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let s = "Can't stop\r\n3.14  won't 🇷🇸🇮🇴 e\u{301}tude, \u{3000} 日本語 👨‍👩‍👧 \
+             a\u{200D}b \"quoted\"\n"
+        .repeat(64);
+
+    let r = Rope::from(s.as_str());
+
+    for (start, end) in [(0, s.len()), (3, s.len() - 5)] {
+        let slice = r.byte_slice(start..end);
+
+        let bounds = slice.word_bounds().collect::<Vec<_>>();
+        assert_eq!(
+            bounds,
+            s[start..end].split_word_bounds().collect::<Vec<_>>()
+        );
+
+        let words = slice.words().collect::<Vec<_>>();
+        assert_eq!(words, s[start..end].unicode_words().collect::<Vec<_>>());
+    }
+}
+
+#[cfg(feature = "graphemes")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn word_bounds_random() {
+    use rand::Rng;
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let pieces = [
+        "a", "Z", "'", ".", ",", "1", " ", "  ", "\u{3000}", "\n", "\r\n",
+        "\u{301}", "\u{200D}", "🇷", "👨", "日", "\"", "_", "\t",
+    ];
+
+    let mut rng = rand::rng();
+
+    for _ in 0..20 {
+        let len = rng.random_range(0..5000);
+
+        let s = (0..len)
+            .map(|_| pieces[rng.random_range(0..pieces.len())])
+            .collect::<String>();
+
+        // Build the rope with random insertions so that the chunk boundaries
+        // end up in different places.
+        let mut r = Rope::new();
+        let mut offset = 0;
+        while offset < s.len() {
+            let mut end = (offset + rng.random_range(1..300)).min(s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+            r.insert(r.byte_len(), &s[offset..end]);
+            offset = end;
+        }
+
+        assert_eq!(
+            r.word_bounds().collect::<Vec<_>>(),
+            s.split_word_bounds().collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            r.words().collect::<Vec<_>>(),
+            s.unicode_words().collect::<Vec<_>>()
+        );
+    }
+}
+
+#[cfg(feature = "graphemes")]
+#[test]
+fn sentences_matches_str() {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let s = "Mr. Fox jumped. \"Really?\" she asked.\r\nIt was 3.14 a.m. \
+             etc.\n\nNew paragraph! 日本語。Ok\u{2029}Done"
+        .repeat(64);
+
+    let r = Rope::from(s.as_str());
+
+    for (start, end) in [(0, s.len()), (3, s.len() - 2)] {
+        let sentences =
+            r.byte_slice(start..end).sentences().collect::<Vec<_>>();
+
+        assert_eq!(
+            sentences,
+            s[start..end].split_sentence_bounds().collect::<Vec<_>>()
+        );
+    }
+}
+
+#[cfg(feature = "graphemes")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn sentences_random() {
+    use rand::Rng;
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let pieces = [
+        "a", "Z", ".", "?", "!", ",", "1", " ", "\n", "\r\n", "\"", ")",
+        "\u{2029}", "\u{301}", "日", "。",
+    ];
+
+    let mut rng = rand::rng();
+
+    for _ in 0..20 {
+        let len = rng.random_range(0..5000);
+
+        let s = (0..len)
+            .map(|_| pieces[rng.random_range(0..pieces.len())])
+            .collect::<String>();
+
+        let mut r = Rope::new();
+        let mut offset = 0;
+        while offset < s.len() {
+            let mut end = (offset + rng.random_range(1..300)).min(s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+            r.insert(r.byte_len(), &s[offset..end]);
+            offset = end;
+        }
+
+        assert_eq!(
+            r.sentences().collect::<Vec<_>>(),
+            s.split_sentence_bounds().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use crop::{History, Rope};
+use rand::Rng;
+
+mod common;
+
+use common::{LARGE, MEDIUM, SMALL};
+
+#[test]
+fn history_empty() {
+    let mut history = History::new(Rope::from("foo"));
+
+    assert!(!history.can_undo());
+    assert!(!history.can_redo());
+    assert!(!history.undo());
+    assert!(!history.redo());
+    assert_eq!(history.rope(), "foo");
+}
+
+#[test]
+fn history_redo_cleared_by_edit() {
+    let mut history = History::new(Rope::new());
+
+    history.insert(0, "foo");
+    history.insert(3, "bar");
+
+    assert!(history.undo());
+    assert_eq!(history.rope(), "foo");
+    assert!(history.can_redo());
+
+    history.insert(0, "baz");
+    assert!(!history.can_redo());
+    assert!(!history.redo());
+    assert_eq!(history.rope(), "bazfoo");
+
+    assert!(history.undo());
+    assert!(history.undo());
+    assert!(!history.undo());
+    assert_eq!(history.rope(), "");
+}
+
+#[test]
+fn history_transactions() {
+    let mut history = History::new(Rope::from("foo\n"));
+
+    history.begin_transaction();
+    history.insert(4, "bar\n");
+    history.delete(..4);
+    assert!(history.can_undo());
+
+    // Undoing commits the transaction in progress.
+    assert!(history.undo());
+    assert_eq!(history.rope(), "foo\n");
+
+    assert!(history.redo());
+    assert_eq!(history.rope(), "bar\n");
+
+    // A transaction without edits doesn't add anything to the history.
+    history.begin_transaction();
+    history.commit_transaction();
+    assert!(history.undo());
+    assert!(!history.undo());
+    assert_eq!(history.rope(), "foo\n");
+}
+
+#[test]
+fn history_clear() {
+    let mut history = History::new(Rope::from("foo"));
+
+    history.insert(3, "bar");
+    history.begin_transaction();
+    history.replace(..3, "baz");
+    history.clear();
+
+    assert!(!history.can_undo());
+
+    history.insert(6, "!");
+    history.commit_transaction();
+
+    assert!(history.undo());
+    assert_eq!(history.rope(), "bazbar");
+    assert!(!history.undo());
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn history_random() {
+    let mut rng = rand::rng();
+
+    for s in [SMALL, MEDIUM, LARGE] {
+        let mut history = History::new(Rope::from(s));
+        let mut states = vec![s.to_owned()];
+
+        for _ in 0..50 {
+            let mut string = states.last().unwrap().clone();
+
+            let start = rng.random_range(0..=string.len());
+            let end = rng.random_range(start..=string.len());
+            let text = &s[..rng.random_range(0..=16)];
+
+            history.replace(start..end, text);
+            string.replace_range(start..end, text);
+
+            assert_eq!(*history.rope(), string);
+            states.push(string);
+        }
+
+        for state in states.iter().rev().skip(1) {
+            assert!(history.undo());
+            history.rope().assert_invariants();
+            assert_eq!(history.rope(), state);
+        }
+
+        for state in &states[1..] {
+            assert!(history.redo());
+            assert_eq!(history.rope(), state);
+        }
+
+        assert_eq!(history.into_rope(), *states.last().unwrap());
+    }
+}
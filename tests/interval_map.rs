@@ -0,0 +1,282 @@
+use crop::{Delta, IntervalMap, Rope};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, SMALL};
+
+#[test]
+fn interval_map_insert() {
+    let mut map = IntervalMap::new();
+    assert!(map.is_empty());
+
+    map.insert(4..8, 'b');
+    map.insert(0..2, 'a');
+    map.insert(4..6, 'c');
+    map.insert(10..10, 'd');
+
+    assert_eq!(map.len(), 4);
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        [(0..2, &'a'), (4..8, &'b'), (4..6, &'c'), (10..10, &'d')]
+    );
+
+    assert_eq!(map.iter().next_back(), Some((10..10, &'d')));
+
+    map.retain(|range, _| range.start != 4);
+    assert_eq!(map.iter().collect::<Vec<_>>(), [(0..2, &'a'), (10..10, &'d')]);
+
+    map.clear();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn interval_map_from_iter() {
+    let map = [(6..8, 'c'), (0..4, 'a'), (2..6, 'b')]
+        .into_iter()
+        .collect::<IntervalMap<_>>();
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        [(0..4, &'a'), (2..6, &'b'), (6..8, &'c')]
+    );
+}
+
+#[test]
+fn interval_map_insertions() {
+    let mut map = IntervalMap::new();
+
+    map.insert(2..4, ());
+    map.insert(4..4, ());
+
+    // Inserting at the start of an interval shifts it.
+    map.apply_edit(2..2, 1);
+    assert_eq!(map.iter().map(|(r, _)| r).collect::<Vec<_>>(), [3..5, 5..5]);
+
+    // Inserting inside of an interval extends it.
+    map.apply_edit(4..4, 1);
+    assert_eq!(map.iter().map(|(r, _)| r).collect::<Vec<_>>(), [3..6, 6..6]);
+
+    // Inserting at the end of an interval doesn't extend it, and empty
+    // intervals stay before the inserted text.
+    map.apply_edit(6..6, 1);
+    assert_eq!(map.iter().map(|(r, _)| r).collect::<Vec<_>>(), [3..6, 6..6]);
+}
+
+#[test]
+fn interval_map_deletions() {
+    let mut map = IntervalMap::new();
+
+    map.insert(2..4, 'a');
+    map.insert(3..8, 'b');
+    map.insert(5..6, 'c');
+    map.insert(6..6, 'd');
+
+    map.apply_edit(3..7, 0);
+
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        [(2..3, &'a'), (3..4, &'b'), (3..3, &'d')]
+    );
+
+    // Replacing all the text of an interval removes it, but empty intervals
+    // are kept.
+    map.apply_edit(2..4, 3);
+
+    assert_eq!(map.iter().collect::<Vec<_>>(), [(2..2, &'d')]);
+}
+
+#[test]
+fn interval_map_reorder() {
+    let mut map = IntervalMap::new();
+
+    map.insert(1..5, 'a');
+    map.insert(2..2, 'b');
+
+    map.apply_edit(0..3, 1);
+
+    assert_eq!(map.iter().collect::<Vec<_>>(), [(0..0, &'b'), (1..3, &'a')]);
+}
+
+#[test]
+fn interval_map_overlapping() {
+    let map = [(0..4, 'a'), (2..6, 'b'), (4..4, 'c'), (8..10, 'd')]
+        .into_iter()
+        .collect::<IntervalMap<_>>();
+
+    let values =
+        |range| map.overlapping(range).map(|(_, &v)| v).collect::<String>();
+
+    assert_eq!(values(0..10), "abcd");
+    assert_eq!(values(3..3), "ab");
+    assert_eq!(values(4..5), "bc");
+    assert_eq!(values(6..8), "");
+    assert_eq!(values(9..20), "d");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn interval_map_overlapping_random() {
+    let mut rng = rand::rng();
+
+    for len in [0, 1, 2, 7, 64, 300] {
+        let mut map = IntervalMap::new();
+
+        for idx in 0..len {
+            let start = rng.random_range(0..1000);
+            let end = start + rng.random_range(0..[0, 5, 200][idx % 3] + 1);
+            map.insert(start..end, idx);
+        }
+
+        if len > 1 {
+            map.apply_edit(100..300, rng.random_range(0..400));
+        }
+
+        for _ in 0..100 {
+            let start = rng.random_range(0..1200);
+            let end = start + rng.random_range(0..50);
+
+            // The intervals overlapping the range, found by checking all of
+            // them.
+            let expected = map
+                .iter()
+                .filter(|(range, _)| {
+                    range.start < end.max(start + 1)
+                        && start < range.end.max(range.start + 1)
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                map.overlapping(start..end).collect::<Vec<_>>(),
+                expected,
+                "{start}..{end}"
+            );
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn interval_map_overlapping_after_insert() {
+    let mut rng = rand::rng();
+
+    let mut map = IntervalMap::new();
+
+    for idx in 0..200 {
+        // Mix in-order insertions, which only update the path to the new
+        // interval, with insertions in the middle of the map.
+        let start =
+            if idx % 2 == 0 { idx * 5 } else { rng.random_range(0..1000) };
+        let end = start + rng.random_range(0..[0, 5, 200][idx % 3] + 1);
+        map.insert(start..end, idx);
+
+        for _ in 0..10 {
+            let start = rng.random_range(0..1200);
+            let end = start + rng.random_range(0..50);
+
+            let expected = map
+                .iter()
+                .filter(|(range, _)| {
+                    range.start < end.max(start + 1)
+                        && start < range.end.max(range.start + 1)
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                map.overlapping(start..end).collect::<Vec<_>>(),
+                expected,
+                "{start}..{end}"
+            );
+        }
+    }
+}
+
+#[test]
+fn interval_map_delta() {
+    let mut r = Rope::from("foo bar baz");
+
+    let mut map = IntervalMap::new();
+    map.insert(4..7, "bar");
+    map.insert(8..11, "baz");
+
+    let mut delta = Delta::new();
+    delta.replace(0..3, "ƒ").insert(2, "🐸").delete(10..14);
+
+    r.apply_delta(&delta);
+    map.apply_delta(&delta);
+
+    assert_eq!(r, "ƒ🐸 bar");
+
+    let (range, &value) = map.iter().next().unwrap();
+    assert_eq!(map.len(), 1);
+    assert_eq!(r.byte_slice(range), value);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn interval_map_random() {
+    let mut rng = rand::rng();
+
+    for s in [SMALL, CURSED_LIPSUM] {
+        let mut r = Rope::from(s);
+
+        let mut map = IntervalMap::new();
+
+        let boundaries = (0..=s.len())
+            .filter(|&offset| s.is_char_boundary(offset))
+            .collect::<Vec<_>>();
+
+        for _ in 0..100 {
+            let start = rng.random_range(0..boundaries.len());
+            let end =
+                rng.random_range(start..boundaries.len().min(start + 20));
+            let range = boundaries[start]..boundaries[end];
+            let text = s[range.clone()].to_owned();
+            map.insert(range, (text, false));
+        }
+
+        for _ in 0..50 {
+            let mut start = rng.random_range(0..=r.byte_len());
+            while !r.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end =
+                rng.random_range(start..=r.byte_len().min(start + 8));
+            while !r.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let text = ["", "a", "ƒoo", "\r\n", "🐸"][rng.random_range(0..5)];
+
+            map.retain(|range, (_, touched)| {
+                *touched |= range.start <= end && start <= range.end;
+                true
+            });
+
+            r.replace(start..end, text);
+            map.apply_edit(start..end, text.len());
+
+            let ranges =
+                map.iter().map(|(range, _)| range).collect::<Vec<_>>();
+
+            assert!(ranges.is_sorted_by_key(|range| range.start));
+
+            for range in ranges {
+                assert!(range.start <= range.end);
+                assert!(range.end <= r.byte_len());
+                assert!(r.is_char_boundary(range.start));
+                assert!(r.is_char_boundary(range.end));
+            }
+        }
+
+        // The intervals that were never touched by an edit still contain the
+        // same text.
+        for (range, (text, touched)) in map.iter() {
+            if !touched {
+                assert_eq!(r.byte_slice(range), text.as_str());
+            }
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use std::io::{BufRead, Read, Write};
+
+use crop::Rope;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM};
+
+/// A writer that fails after accepting a given number of bytes.
+struct FailingWriter {
+    accepted: Vec<u8>,
+    capacity: usize,
+}
+
+impl Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(self.capacity - self.accepted.len());
+        if len == 0 {
+            return Err(std::io::ErrorKind::WriteZero.into());
+        }
+        self.accepted.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn write_to() {
+    for s in ["", CURSED_LIPSUM, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        let mut buf = Vec::new();
+        r.write_to(&mut buf).unwrap();
+        assert_eq!(buf, s.as_bytes());
+
+        let mut half = s.len() / 2;
+        while !s.is_char_boundary(half) {
+            half += 1;
+        }
+
+        let mut buf = Vec::new();
+        r.byte_slice(half..).write_to(&mut buf).unwrap();
+        assert_eq!(buf, &s.as_bytes()[half..]);
+    }
+}
+
+#[test]
+fn write_to_bom() {
+    let mut r = Rope::from(MEDIUM);
+    r.set_bom(true);
+
+    r.insert(0, "ƒoo");
+    r.delete(..100);
+    r.shrink_to_fit();
+    r += Rope::from(LARGE);
+
+    let clone = r.clone();
+    assert!(clone.has_bom());
+
+    let mut buf = Vec::new();
+    r.write_to(&mut buf).unwrap();
+    assert_eq!(&buf[..3], b"\xEF\xBB\xBF");
+    assert_eq!(buf[3..], *r.to_string().as_bytes());
+
+    // Slices and split off ropes don't have the BOM.
+    let mut buf = Vec::new();
+    r.byte_slice(..).write_to(&mut buf).unwrap();
+    assert_eq!(buf, r.to_string().as_bytes());
+    assert!(!r.split_off(10).has_bom());
+    assert!(r.has_bom());
+
+    r.set_bom(false);
+    let mut buf = Vec::new();
+    r.write_to(&mut buf).unwrap();
+    assert_eq!(buf, r.to_string().as_bytes());
+}
+
+#[test]
+fn write_to_error() {
+    let r = Rope::from(MEDIUM);
+
+    let mut writer = FailingWriter { accepted: Vec::new(), capacity: 100 };
+
+    let err = r.write_to(&mut writer).unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    assert_eq!(writer.accepted, MEDIUM.as_bytes()[..100]);
+}
+
+#[test]
+fn reader() {
+    for s in ["", CURSED_LIPSUM, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        let mut buf = Vec::new();
+        r.reader().read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, s.as_bytes());
+
+        let mut half = s.len() / 2;
+        while !s.is_char_boundary(half) {
+            half += 1;
+        }
+
+        let mut buf = String::new();
+        r.byte_slice(..half).reader().read_to_string(&mut buf).unwrap();
+        assert_eq!(buf, s[..half]);
+    }
+}
+
+#[test]
+fn reader_small_reads() {
+    let r = Rope::from(CURSED_LIPSUM);
+
+    let mut reader = r.reader();
+
+    let mut buf = Vec::new();
+    let mut read_buf = [0; 7];
+
+    loop {
+        match reader.read(&mut read_buf).unwrap() {
+            0 => break,
+            n => buf.extend_from_slice(&read_buf[..n]),
+        }
+    }
+
+    assert_eq!(buf, CURSED_LIPSUM.as_bytes());
+    assert_eq!(reader.read(&mut read_buf).unwrap(), 0);
+}
+
+#[test]
+fn reader_lines() {
+    let r = Rope::from(CURSED_LIPSUM);
+
+    let lines = r.reader().lines().collect::<Result<Vec<_>, _>>().unwrap();
+
+    let expected =
+        CURSED_LIPSUM.lines().map(str::to_owned).collect::<Vec<_>>();
+
+    assert_eq!(lines, expected);
+}
+
+/// Byte offsets of some header fields of a dump.
+const DUMP_CHUNK_MAX_BYTES: std::ops::Range<usize> = 9..13;
+const DUMP_METRICS: usize = 13;
+
+#[test]
+fn tree_dump_round_trip() {
+    for s in ["", "\n", CURSED_LIPSUM, MEDIUM, LARGE] {
+        let mut r = Rope::from(s);
+
+        // Move the gaps around so that not all the text is before them.
+        if !r.is_empty() {
+            r.insert(0, "a");
+            r.delete(0..1);
+        }
+
+        let mut dump = Vec::new();
+        r.write_tree(&mut dump).unwrap();
+
+        let loaded = Rope::read_tree(dump.as_slice()).unwrap();
+        loaded.assert_invariants();
+
+        assert_eq!(loaded, r);
+        assert_eq!(loaded.line_len(), r.line_len());
+        assert_eq!(loaded.tree_stats().leaf_count, r.tree_stats().leaf_count);
+        assert!(loaded.leaf_summaries().eq(r.leaf_summaries()));
+    }
+}
+
+#[test]
+fn tree_dump_edit_after_load() {
+    let r = Rope::from(MEDIUM);
+
+    let mut dump = Vec::new();
+    r.write_tree(&mut dump).unwrap();
+
+    let mut loaded = Rope::read_tree(dump.as_slice()).unwrap();
+    let mut s = MEDIUM.to_owned();
+
+    loaded.insert(0, "Hello\n");
+    s.insert_str(0, "Hello\n");
+
+    let end = loaded.byte_len();
+    loaded.delete(end - 1..end);
+    s.pop();
+
+    loaded.assert_invariants();
+    assert_eq!(loaded, s);
+}
+
+#[test]
+fn tree_dump_resummarizes_mismatched_chunk_size() {
+    let r = Rope::from(CURSED_LIPSUM);
+
+    let mut dump = Vec::new();
+    r.write_tree(&mut dump).unwrap();
+    dump[DUMP_CHUNK_MAX_BYTES].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    let loaded = Rope::read_tree(dump.as_slice()).unwrap();
+    loaded.assert_invariants();
+    assert_eq!(loaded, r);
+}
+
+#[test]
+fn tree_dump_resummarizes_mismatched_metrics() {
+    let r = Rope::from(MEDIUM);
+
+    let mut dump = Vec::new();
+    r.write_tree(&mut dump).unwrap();
+
+    // Pretend the dump was written with none of the optional metrics by
+    // dropping them from every leaf.
+    let metrics = dump[DUMP_METRICS];
+    let skipped = 8 * metrics.count_ones() as usize;
+
+    let mut stripped = dump[..22].to_vec();
+    stripped[DUMP_METRICS] = 0;
+
+    let mut rest = &dump[22..];
+    while !rest.is_empty() {
+        let len = u32::from_le_bytes(rest[..4].try_into().unwrap()) as usize;
+        stripped.extend_from_slice(&rest[..12]);
+        rest = &rest[12 + skipped..];
+        stripped.extend_from_slice(&rest[..len]);
+        rest = &rest[len..];
+    }
+
+    let loaded = Rope::read_tree(stripped.as_slice()).unwrap();
+    loaded.assert_invariants();
+    assert_eq!(loaded, r);
+}
+
+#[test]
+fn tree_dump_invalid() {
+    let r = Rope::from(CURSED_LIPSUM);
+
+    let mut dump = Vec::new();
+    r.write_tree(&mut dump).unwrap();
+
+    let err = Rope::read_tree(&b"not a tree dump"[..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let truncated = &dump[..dump.len() - 1];
+    let err = Rope::read_tree(truncated).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    // Replace the second byte of the "Ḽ" at the start of the first leaf.
+    let metrics = dump[DUMP_METRICS].count_ones() as usize;
+    let text_start = 22 + 4 + 8 * (1 + metrics);
+    let mut invalid = dump.clone();
+    invalid[text_start + 1] = b'a';
+    let err = Rope::read_tree(invalid.as_slice()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn tree_dump_corrupted_summary() {
+    let r = Rope::from(MEDIUM);
+
+    let mut dump = Vec::new();
+    r.write_tree(&mut dump).unwrap();
+
+    // The line breaks of the first leaf come right after its byte length.
+    let line_breaks = 22 + 4;
+
+    for corrupted in [0, 1, 200] {
+        let mut invalid = dump.clone();
+        let stored = u64::from_le_bytes(
+            invalid[line_breaks..line_breaks + 8].try_into().unwrap(),
+        );
+        if stored == corrupted {
+            continue;
+        }
+        invalid[line_breaks..line_breaks + 8]
+            .copy_from_slice(&corrupted.to_le_bytes());
+        let err = Rope::read_tree(invalid.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn tree_dump_split_crlf() {
+    let first_leaf_len =
+        |r: &Rope| r.leaf_summaries().next().unwrap().1.bytes();
+
+    let mut s = "a".repeat(LARGE.len());
+    let split = first_leaf_len(&Rope::from(s.as_str()));
+    s.replace_range(split - 1..split + 1, "\r\n");
+
+    // The "\r\n" is split between the first two leaves, which is a valid
+    // state for a `Rope` and has to survive a round trip.
+    let r = Rope::from(s.as_str());
+    assert_eq!(first_leaf_len(&r), split);
+
+    let mut dump = Vec::new();
+    r.write_tree(&mut dump).unwrap();
+
+    let loaded = Rope::read_tree(dump.as_slice()).unwrap();
+    loaded.assert_invariants();
+    assert_eq!(loaded, r);
+    assert_eq!(loaded.line_len(), 2);
+    assert_eq!(loaded.line(0).byte_len(), split - 1);
+}
@@ -426,6 +426,25 @@ fn iter_lines_forward_backward() {
     assert_eq!(None, backward.next());
 }
 
+#[test]
+fn iter_lines_meet_in_the_middle() {
+    let r = Rope::from("foo\nbar\nbaz\n");
+
+    let mut lines = r.lines();
+    assert_eq!("foo", lines.next().unwrap());
+    assert_eq!("baz", lines.next_back().unwrap());
+    assert_eq!("bar", lines.next().unwrap());
+    assert_eq!(None, lines.next());
+    assert_eq!(None, lines.next_back());
+
+    let mut raw_lines = r.raw_lines();
+    assert_eq!("baz\n", raw_lines.next_back().unwrap());
+    assert_eq!("foo\n", raw_lines.next().unwrap());
+    assert_eq!("bar\n", raw_lines.next_back().unwrap());
+    assert_eq!(None, raw_lines.next_back());
+    assert_eq!(None, raw_lines.next());
+}
+
 #[cfg_attr(miri, ignore)]
 #[test]
 fn iter_lines_over_random_slices() {
@@ -612,3 +631,371 @@ fn iter_raw_lines_over_random_slices() {
         }
     }
 }
+
+/// The byte ranges of the lines of `s`, as yielded by `str::lines()`.
+fn str_line_ranges(s: &str) -> Vec<std::ops::Range<usize>> {
+    s.lines()
+        .map(|line| {
+            let start = line.as_ptr() as usize - s.as_ptr() as usize;
+            start..start + line.len()
+        })
+        .collect()
+}
+
+#[test]
+fn iter_line_ranges_0() {
+    let r = Rope::from("\nfoo\r\nbar\n\nbaz");
+
+    let ranges = r.line_ranges().collect::<Vec<_>>();
+    assert_eq!(ranges, [0..0, 1..4, 6..9, 10..10, 11..14]);
+
+    let mut ranges = r.line_ranges();
+    assert_eq!(ranges.len(), 5);
+    assert_eq!(ranges.next_back(), Some(11..14));
+    assert_eq!(ranges.next_back(), Some(10..10));
+    assert_eq!(ranges.next(), Some(0..0));
+    assert_eq!(ranges.next_back(), Some(6..9));
+    assert_eq!(ranges.next(), Some(1..4));
+    assert_eq!(ranges.len(), 0);
+    assert_eq!(ranges.next(), None);
+    assert_eq!(ranges.next_back(), None);
+
+    assert_eq!(Rope::new().line_ranges().count(), 0);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn iter_line_ranges_over_random_slices() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM] {
+        let rope = Rope::from(s);
+
+        assert_eq!(rope.line_ranges().collect::<Vec<_>>(), str_line_ranges(s));
+
+        for _ in 0..20 {
+            let start = rng.random_range(0..=rope.byte_len());
+            let end = rng.random_range(start..=rope.byte_len());
+
+            let rope_slice = rope.byte_slice(start..end);
+            let str_ranges = str_line_ranges(&s[start..end]);
+
+            assert_eq!(
+                rope_slice.line_ranges().collect::<Vec<_>>(),
+                str_ranges
+            );
+
+            assert!(
+                rope_slice
+                    .line_ranges()
+                    .rev()
+                    .eq(str_ranges.into_iter().rev())
+            );
+        }
+    }
+}
+
+/// Checks that the size hint of the iterator is correct after every call to
+/// `next()` and `next_back()`, alternating between the two.
+fn check_size_hint<I: DoubleEndedIterator + Clone>(mut iter: I) {
+    let mut left = iter.clone().count();
+
+    loop {
+        let (lower, upper) = iter.size_hint();
+        assert!(lower <= left, "{lower} > {left}");
+        assert!(upper.is_none_or(|upper| left <= upper), "{upper:?} < {left}");
+
+        let item = if left % 2 == 0 { iter.next() } else { iter.next_back() };
+
+        if item.is_none() {
+            assert_eq!(left, 0);
+            break;
+        }
+
+        left -= 1;
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn iter_size_hints() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        let mut slices = vec![r.byte_slice(..)];
+
+        for _ in 0..10 {
+            let mut start = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start -= 1;
+            }
+            let mut end = rng.random_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            slices.push(r.byte_slice(start..end));
+        }
+
+        check_size_hint(r.bytes());
+        check_size_hint(r.chars());
+        check_size_hint(r.chunks());
+        check_size_hint(r.lines());
+
+        for slice in slices {
+            check_size_hint(slice.bytes());
+            check_size_hint(slice.chars());
+            check_size_hint(slice.char_indices());
+            check_size_hint(slice.chunks());
+            check_size_hint(slice.lines());
+            check_size_hint(slice.raw_lines());
+            check_size_hint(slice.line_ranges());
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn iter_at_random_offsets() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let mut offset = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(offset) {
+                offset -= 1;
+            }
+
+            let (before, after) = r.bytes_at(offset);
+            assert!(before.eq(s[..offset].bytes().rev()));
+            assert!(after.eq(s[offset..].bytes()));
+
+            let (before, after) = r.chars_at(offset);
+            assert!(before.eq(s[..offset].chars().rev()));
+            assert!(after.eq(s[offset..].chars()));
+
+            let lines = s.lines().collect::<Vec<_>>();
+            let line_offset = rng.random_range(0..=r.line_len());
+
+            let (before, after) = r.lines_at(line_offset);
+            assert!(before.eq(lines[..line_offset].iter().rev().copied()));
+            assert!(after.eq(lines[line_offset..].iter().copied()));
+
+            let slice = r.byte_slice(offset..);
+            let str_slice = &s[offset..];
+
+            let mut slice_offset = rng.random_range(0..=str_slice.len());
+            while !str_slice.is_char_boundary(slice_offset) {
+                slice_offset -= 1;
+            }
+
+            let (before, after) = slice.chars_at(slice_offset);
+            assert!(before.eq(str_slice[..slice_offset].chars().rev()));
+            assert!(after.eq(str_slice[slice_offset..].chars()));
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn iter_lines_at_out_of_bounds() {
+    let r = Rope::from("foo\nbar\n");
+    let _ = r.lines_at(3);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn iter_chunks_with_offsets_over_random_slices() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let start = rng.random_range(0..=r.byte_len());
+            let end = rng.random_range(start..=r.byte_len());
+
+            let slice = r.byte_slice(start..end);
+            let str_slice = &s[start..end];
+
+            let offsets = slice.chunks_with_offsets().collect::<Vec<_>>();
+
+            let mut expected_offset = 0;
+            for (&(offset, chunk), slice_chunk) in
+                offsets.iter().zip(slice.chunks())
+            {
+                assert_eq!(offset, expected_offset);
+                assert_eq!(chunk, slice_chunk);
+                assert_eq!(&str_slice[offset..offset + chunk.len()], chunk);
+                expected_offset += chunk.len();
+            }
+            assert_eq!(expected_offset, str_slice.len());
+
+            let mut backward =
+                slice.chunks_with_offsets().rev().collect::<Vec<_>>();
+            backward.reverse();
+            assert_eq!(backward, offsets);
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn iter_char_indices_over_random_slices() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        assert!(r.char_indices().eq(s.char_indices()));
+
+        for _ in 0..20 {
+            let mut start = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start -= 1;
+            }
+            let mut end = rng.random_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+            let str_slice = &s[start..end];
+
+            assert!(slice.char_indices().eq(str_slice.char_indices()));
+            assert!(
+                slice.char_indices().rev().eq(str_slice.char_indices().rev())
+            );
+        }
+    }
+}
+
+#[test]
+fn iter_leaf_summaries_empty() {
+    let r = Rope::new();
+    assert_eq!(r.leaf_summaries().next(), None);
+    assert_eq!(r.byte_slice(..).leaf_summaries().next(), None);
+}
+
+/// Checks that the summaries yielded by `leaf_summaries()` cover the whole
+/// slice and match the text they summarize.
+fn check_leaf_summaries(slice: crop::RopeSlice<'_>) {
+    let mut next_offset = 0;
+
+    for (offset, summary) in slice.leaf_summaries() {
+        assert_eq!(offset, next_offset);
+        assert!(summary.bytes() > 0);
+
+        let leaf = slice.byte_slice(offset..offset + summary.bytes());
+
+        assert_eq!(
+            summary.line_breaks(),
+            leaf.bytes().filter(|&b| b == b'\n').count()
+        );
+
+        next_offset += summary.bytes();
+    }
+
+    assert_eq!(next_offset, slice.byte_len());
+}
+
+#[test]
+fn iter_leaf_summaries_rope() {
+    for s in [CURSED_LIPSUM, TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+        check_leaf_summaries(r.byte_slice(..));
+
+        let (total_bytes, total_breaks) =
+            r.leaf_summaries().fold((0, 0), |(b, l), (_, s)| {
+                (b + s.bytes(), l + s.line_breaks())
+            });
+
+        assert_eq!(total_bytes, s.len());
+        assert_eq!(total_breaks, s.matches('\n').count());
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn iter_leaf_summaries_random_slices() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let start = rng.random_range(0..=r.byte_len());
+            let end = rng.random_range(start..=r.byte_len());
+            check_leaf_summaries(r.byte_slice(start..end));
+        }
+    }
+}
+
+fn check_for_each_chunk(slice: crop::RopeSlice<'_>) {
+    let mut chunks = Vec::new();
+
+    let flow = slice.for_each_chunk(.., |chunk| {
+        chunks.push(chunk);
+        core::ops::ControlFlow::<()>::Continue(())
+    });
+
+    assert!(flow.is_continue());
+    assert_eq!(chunks, slice.chunks().collect::<Vec<_>>());
+}
+
+#[test]
+fn for_each_chunk_empty() {
+    let r = Rope::new();
+    let flow = r.for_each_chunk(.., |_| core::ops::ControlFlow::Break(()));
+    assert!(flow.is_continue());
+}
+
+#[test]
+fn for_each_chunk_early_exit() {
+    let r = Rope::from(LARGE);
+
+    let mut visited = 0;
+
+    let flow = r.for_each_chunk(.., |chunk| {
+        visited += 1;
+        if visited == 3 {
+            core::ops::ControlFlow::Break(chunk)
+        } else {
+            core::ops::ControlFlow::Continue(())
+        }
+    });
+
+    assert_eq!(visited, 3);
+    assert_eq!(flow.break_value(), r.chunks().nth(2));
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn for_each_chunk_random_slices() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        check_for_each_chunk(r.byte_slice(..));
+
+        for _ in 0..20 {
+            let start = rng.random_range(0..=r.byte_len());
+            let end = rng.random_range(start..=r.byte_len());
+            check_for_each_chunk(r.byte_slice(start..end));
+
+            let mut chunks = Vec::new();
+            let _ = r.for_each_chunk(start..end, |chunk| {
+                chunks.push(chunk);
+                core::ops::ControlFlow::<()>::Continue(())
+            });
+            assert_eq!(
+                chunks,
+                r.byte_slice(start..end).chunks().collect::<Vec<_>>()
+            );
+        }
+    }
+}
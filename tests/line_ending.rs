@@ -0,0 +1,310 @@
+use crop::{LineEnding, LineEndings, Rope, RopeBuilder};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, TEXT};
+
+/// The line endings of `s`, computed by scanning the string.
+fn line_endings(s: &str) -> LineEndings {
+    let crlf = s.matches("\r\n").count();
+    LineEndings { lf: s.matches('\n').count() - crlf, crlf }
+}
+
+#[test]
+fn line_ending_none() {
+    for r in [Rope::new(), Rope::from(TEXT), Rope::from("foo\r")] {
+        let endings = r.detect_line_ending();
+        assert_eq!(endings, LineEndings::default());
+        assert_eq!(endings.style(), None);
+    }
+}
+
+#[test]
+fn line_ending_styles() {
+    let r = Rope::from("foo\nbar\n");
+    assert_eq!(r.detect_line_ending().style(), Some(LineEnding::Lf));
+
+    let r = Rope::from("foo\r\nbar\r\n");
+    assert_eq!(r.detect_line_ending().style(), Some(LineEnding::Crlf));
+
+    let r = Rope::from("foo\r\nbar\n\r");
+    assert_eq!(r.detect_line_ending(), LineEndings { lf: 1, crlf: 1 });
+    assert_eq!(r.detect_line_ending().style(), Some(LineEnding::Mixed));
+}
+
+#[test]
+fn line_ending_slice() {
+    let r = Rope::from("foo\r\nbar\nbaz\r\n");
+
+    // The slice starts between the CR and the LF of the first CRLF.
+    let s = r.byte_slice(4..);
+    assert_eq!(s.detect_line_ending(), LineEndings { lf: 2, crlf: 1 });
+
+    let s = r.byte_slice(..4);
+    assert_eq!(s.detect_line_ending(), LineEndings::default());
+}
+
+#[test]
+fn line_ending_crlf_across_chunks() {
+    let mut builder = RopeBuilder::new();
+    builder.append("foo\r").append("\nbar\r").append("").append("\n");
+    let r = builder.build();
+
+    assert_eq!(r.detect_line_ending(), LineEndings { lf: 0, crlf: 2 });
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn line_ending_random() {
+    let mut rng = rand::rng();
+
+    for s in [CURSED_LIPSUM, LARGE] {
+        let mut r = Rope::from(s);
+        let mut s = String::from(s);
+
+        for _ in 0..20 {
+            let mut offset = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(offset) {
+                offset -= 1;
+            }
+
+            let text = ["\r", "\n", "\r\n"][rng.random_range(0..3)];
+
+            r.insert(offset, text);
+            s.insert_str(offset, text);
+
+            assert_eq!(r.detect_line_ending(), line_endings(&s));
+
+            let mut start = offset.saturating_sub(100);
+            while !s.is_char_boundary(start) {
+                start -= 1;
+            }
+
+            let mut end = (offset + 100).min(s.len());
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            assert_eq!(
+                r.byte_slice(start..end).detect_line_ending(),
+                line_endings(&s[start..end])
+            );
+        }
+    }
+}
+
+/// `s` with its line endings normalized to `line_ending`, leaving lone CRs
+/// alone.
+fn normalized(s: &str, line_ending: LineEnding) -> String {
+    let mut normalized = String::with_capacity(s.len());
+    for ch in s.chars() {
+        if ch == '\n' {
+            match line_ending {
+                LineEnding::Lf => {
+                    while normalized.ends_with('\r') {
+                        normalized.pop();
+                    }
+                },
+                _ => {
+                    if !normalized.ends_with('\r') {
+                        normalized.push('\r');
+                    }
+                },
+            }
+        }
+        normalized.push(ch);
+    }
+    normalized
+}
+
+#[test]
+fn normalize_line_endings_basic() {
+    let mut r = Rope::from("foo\r\nbar\n\rbaz\r\r\n");
+
+    r.normalize_line_endings(LineEnding::Lf);
+    assert_eq!(r, "foo\nbar\n\rbaz\n");
+    r.assert_invariants();
+
+    r.normalize_line_endings(LineEnding::Crlf);
+    assert_eq!(r, "foo\r\nbar\r\n\rbaz\r\n");
+    r.assert_invariants();
+
+    let mut r = Rope::from(TEXT);
+    r.normalize_line_endings(LineEnding::Crlf);
+    assert_eq!(r, TEXT);
+}
+
+#[test]
+fn normalize_line_endings_across_chunks() {
+    let mut builder = RopeBuilder::new();
+    builder.append("foo\r").append("\nbar\r").append("\r").append("\n\r");
+    let r = builder.build();
+
+    let mut lf = r.clone();
+    lf.normalize_line_endings(LineEnding::Lf);
+    assert_eq!(lf, "foo\nbar\n\r");
+    lf.assert_invariants();
+
+    let mut crlf = r.clone();
+    crlf.normalize_line_endings(LineEnding::Crlf);
+    assert_eq!(crlf, r);
+}
+
+#[test]
+#[should_panic]
+fn normalize_line_endings_mixed() {
+    let mut r = Rope::from("foo\n");
+    r.normalize_line_endings(LineEnding::Mixed);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn normalize_line_endings_random() {
+    let mut rng = rand::rng();
+
+    for s in [CURSED_LIPSUM, LARGE] {
+        let mut r = Rope::from(s);
+        let mut s = String::from(s);
+
+        for _ in 0..50 {
+            let mut offset = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(offset) {
+                offset -= 1;
+            }
+
+            let text = ["\r", "\n", "\r\n"][rng.random_range(0..3)];
+
+            r.insert(offset, text);
+            s.insert_str(offset, text);
+        }
+
+        for line_ending in [LineEnding::Crlf, LineEnding::Lf] {
+            let original = r.clone();
+
+            r.normalize_line_endings(line_ending);
+            s = normalized(&s, line_ending);
+
+            r.assert_invariants();
+            assert_eq!(r, s);
+
+            let endings = r.detect_line_ending();
+            assert_eq!(endings.style(), Some(line_ending));
+
+            // Normalizing doesn't affect the clones of the `Rope`.
+            assert_ne!(original, r);
+        }
+    }
+}
+
+/// The lines of `s` when any of the Unicode line breaks can terminate them.
+fn unicode_lines(s: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((idx, ch)) = chars.next() {
+        if !matches!(
+            ch,
+            '\n' | '\u{000B}'
+                | '\u{000C}'
+                | '\r'
+                | '\u{0085}'
+                | '\u{2028}'
+                | '\u{2029}'
+        ) {
+            continue;
+        }
+
+        lines.push(&s[line_start..idx]);
+        line_start = idx + ch.len_utf8();
+
+        if ch == '\r' && chars.peek().map(|&(_, ch)| ch) == Some('\n') {
+            chars.next();
+            line_start += 1;
+        }
+    }
+
+    if line_start < s.len() {
+        lines.push(&s[line_start..]);
+    }
+
+    lines
+}
+
+#[test]
+fn unicode_lines_empty() {
+    let r = Rope::new();
+    assert_eq!(r.unicode_lines().count(), 0);
+    assert_eq!(r.unicode_line_len(), 0);
+}
+
+#[test]
+fn unicode_lines_terminators() {
+    let s = "a\nb\u{000B}c\u{000C}d\re\r\nf\u{0085}g\u{2028}h\u{2029}i\r\r\n";
+    let r = Rope::from(s);
+
+    let lines = r.unicode_lines().collect::<Vec<_>>();
+    assert_eq!(lines, ["a", "b", "c", "d", "e", "f", "g", "h", "i", ""]);
+    assert_eq!(r.unicode_line_len(), 10);
+
+    let r = Rope::from("\r\n");
+    assert_eq!(r.unicode_lines().collect::<Vec<_>>(), [""]);
+    assert_eq!(r.unicode_line_len(), 1);
+}
+
+#[test]
+fn unicode_lines_crlf_across_chunks() {
+    let mut builder = RopeBuilder::new();
+    builder.append("foo\r").append("\nbar\r").append("").append("\n\r");
+    let r = builder.build();
+
+    assert_eq!(r.unicode_lines().collect::<Vec<_>>(), ["foo", "bar", ""]);
+    assert_eq!(r.unicode_line_len(), 3);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn unicode_lines_random() {
+    let mut rng = rand::rng();
+
+    for s in [CURSED_LIPSUM, LARGE] {
+        let mut r = Rope::from(s);
+        let mut s = String::from(s);
+
+        for _ in 0..50 {
+            let mut offset = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(offset) {
+                offset -= 1;
+            }
+
+            let text =
+                ["\r", "\n", "\r\n", "\u{000C}", "\u{2028}", "\u{0085}"]
+                    [rng.random_range(0..6)];
+
+            r.insert(offset, text);
+            s.insert_str(offset, text);
+        }
+
+        let lines = unicode_lines(&s);
+        assert_eq!(r.unicode_line_len(), lines.len());
+        assert!(r.unicode_lines().eq(lines.iter().copied()));
+
+        for _ in 0..10 {
+            let mut start = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start -= 1;
+            }
+
+            let mut end = (start + 1000).min(s.len());
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+            let lines = unicode_lines(&s[start..end]);
+            assert_eq!(slice.unicode_line_len(), lines.len());
+            assert!(slice.unicode_lines().eq(lines.iter().copied()));
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crop::{Delta, Gravity, MarkerSet, Rope};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, SMALL};
+
+#[test]
+fn markers_add_remove() {
+    let mut markers = MarkerSet::new();
+    assert!(markers.is_empty());
+
+    let foo = markers.add(1, Gravity::Left);
+    let bar = markers.add(2, Gravity::Right);
+    assert_eq!(markers.len(), 2);
+
+    assert_eq!(markers.remove(foo), Some(1));
+    assert_eq!(markers.remove(foo), None);
+    assert_eq!(markers.get(foo), None);
+    assert_eq!(markers.set(foo, 3), None);
+    assert_eq!(markers.len(), 1);
+
+    assert_eq!(markers.set(bar, 5), Some(2));
+    assert_eq!(markers.iter().collect::<Vec<_>>(), [(bar, 5)]);
+
+    // The handle of a removed marker stays stale after its slot is reused.
+    let baz = markers.add(0, Gravity::Left);
+    assert_ne!(baz, foo);
+    assert_eq!(markers.get(foo), None);
+    assert_eq!(markers.set(foo, 3), None);
+    assert_eq!(markers.remove(foo), None);
+    assert_eq!(markers.get(baz), Some(0));
+    assert_eq!(markers.iter().len(), 2);
+    assert!(markers.iter().any(|(id, offset)| id == baz && offset == 0));
+}
+
+#[test]
+fn markers_gravity() {
+    let mut markers = MarkerSet::new();
+
+    let left = markers.add(3, Gravity::Left);
+    let right = markers.add(3, Gravity::Right);
+    let before = markers.add(2, Gravity::Right);
+    let after = markers.add(4, Gravity::Left);
+
+    markers.apply_edit(3..3, 2);
+
+    assert_eq!(markers.get(left), Some(3));
+    assert_eq!(markers.get(right), Some(5));
+    assert_eq!(markers.get(before), Some(2));
+    assert_eq!(markers.get(after), Some(6));
+
+    // Deleting the text around a marker collapses it to the start of the
+    // deleted range, whatever its gravity.
+    markers.apply_edit(1..6, 0);
+
+    for id in [left, right, before, after] {
+        assert_eq!(markers.get(id), Some(1));
+    }
+}
+
+#[test]
+fn markers_delta() {
+    let r = Rope::from("foo bar baz");
+
+    let mut markers = MarkerSet::new();
+    let bar = markers.add(4, Gravity::Left);
+    let baz = markers.add(8, Gravity::Left);
+
+    let mut delta = Delta::new();
+    delta.replace(0..3, "ƒ").insert(2, "🐸").delete(10..14);
+
+    let mut edited = r.clone();
+    edited.apply_delta(&delta);
+    markers.apply_delta(&delta);
+
+    assert_eq!(edited, "ƒ🐸 bar");
+    assert_eq!(edited.byte_slice(markers.get(bar).unwrap()..), "bar");
+    assert_eq!(markers.get(baz), Some(edited.byte_len()));
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn markers_random() {
+    let mut rng = rand::rng();
+
+    for s in [SMALL, CURSED_LIPSUM] {
+        let mut r = Rope::from(s);
+
+        let mut markers = MarkerSet::new();
+
+        for (offset, _) in s.char_indices() {
+            let gravity = if rng.random_bool(0.5) {
+                Gravity::Left
+            } else {
+                Gravity::Right
+            };
+            markers.add(offset, gravity);
+        }
+
+        for _ in 0..50 {
+            let mut start = rng.random_range(0..=r.byte_len());
+            while !r.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end =
+                rng.random_range(start..=r.byte_len().min(start + 8));
+            while !r.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let text = ["", "a", "ƒoo", "\r\n", "🐸"][rng.random_range(0..5)];
+
+            let before = markers.iter().collect::<Vec<_>>();
+            let old = r.clone();
+
+            r.replace(start..end, text);
+            markers.apply_edit(start..end, text.len());
+
+            for (id, old_offset) in before {
+                let offset = markers.get(id).unwrap();
+
+                if old_offset < start {
+                    assert_eq!(offset, old_offset);
+                } else if old_offset > end {
+                    assert_eq!(
+                        offset,
+                        old_offset + text.len() - (end - start)
+                    );
+                    if old_offset < old.byte_len() {
+                        assert_eq!(r.byte(offset), old.byte(old_offset));
+                    }
+                } else {
+                    assert!(offset == start || offset == start + text.len());
+                }
+
+                assert!(r.is_char_boundary(offset));
+            }
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn markers_random_add_remove() {
+    let mut rng = rand::rng();
+
+    let mut markers = MarkerSet::new();
+
+    // The markers that should be in the set, with their offsets and gravity.
+    let mut expected = Vec::new();
+
+    // The handles of the removed markers, which must stay stale.
+    let mut removed = Vec::new();
+
+    for _ in 0..500 {
+        match rng.random_range(0..4) {
+            0 | 1 => {
+                let offset = rng.random_range(0..100);
+                let gravity = if rng.random_bool(0.5) {
+                    Gravity::Left
+                } else {
+                    Gravity::Right
+                };
+                expected.push((markers.add(offset, gravity), offset, gravity));
+            },
+
+            2 if !expected.is_empty() => {
+                let idx = rng.random_range(0..expected.len());
+                let (id, offset, _) = expected.swap_remove(idx);
+                assert_eq!(markers.remove(id), Some(offset));
+                removed.push(id);
+            },
+
+            _ if !expected.is_empty() => {
+                let idx = rng.random_range(0..expected.len());
+                let new_offset = rng.random_range(0..100);
+                let (id, offset, _) = &mut expected[idx];
+                assert_eq!(markers.set(*id, new_offset), Some(*offset));
+                *offset = new_offset;
+            },
+
+            _ => {},
+        }
+
+        let start = rng.random_range(0..100);
+        let end = rng.random_range(start..start + 10);
+        let inserted_len = rng.random_range(0..10);
+
+        markers.apply_edit(start..end, inserted_len);
+
+        for (_, offset, gravity) in &mut expected {
+            *offset = if *offset < start {
+                *offset
+            } else if *offset > end {
+                *offset - (end - start) + inserted_len
+            } else if *gravity == Gravity::Left {
+                start
+            } else {
+                start + inserted_len
+            };
+        }
+
+        assert_eq!(markers.len(), expected.len());
+
+        for &(id, offset, _) in &expected {
+            assert_eq!(markers.get(id), Some(offset));
+        }
+
+        for &id in &removed {
+            assert_eq!(markers.get(id), None);
+        }
+    }
+}
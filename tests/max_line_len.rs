@@ -0,0 +1,130 @@
+mod common;
+
+#[cfg(feature = "max-line-len")]
+mod tests {
+    use crop::{Rope, RopeSlice};
+    use rand::Rng;
+
+    use crate::common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+    /// The length of the longest line of `s` computed by iterating over its
+    /// lines.
+    fn max_line_len(s: RopeSlice<'_>) -> usize {
+        s.lines().map(|line| line.byte_len()).max().unwrap_or(0)
+    }
+
+    #[test]
+    fn max_line_len_0() {
+        let r = Rope::new();
+        assert_eq!(r.max_line_len(), 0);
+        assert_eq!(r.byte_slice(..).max_line_len(), 0);
+    }
+
+    #[test]
+    fn max_line_len_1() {
+        let r = Rope::from("foo\n\nbarbaz\r\n\r\nqux\n");
+        assert_eq!(r.max_line_len(), 6);
+        assert_eq!(r.byte_slice(6..).max_line_len(), 5);
+        assert_eq!(r.byte_slice(..7).max_line_len(), 3);
+        assert_eq!(r.byte_slice(4..5).max_line_len(), 0);
+    }
+
+    #[test]
+    fn max_line_len_crlf_across_chunks() {
+        // Build the rope a piece at a time so that the "\r\n"s are split
+        // between two chunks.
+        let mut r = Rope::new();
+
+        for _ in 0..256 {
+            r.insert(r.byte_len(), "a\r");
+            r.insert(r.byte_len(), "\nbb\r");
+            r.insert(r.byte_len(), "\n");
+        }
+
+        r.assert_invariants();
+
+        assert_eq!(r.max_line_len(), 2);
+        assert_eq!(r.max_line_len(), max_line_len(r.byte_slice(..)));
+
+        // A carriage return not followed by a line feed is part of the line.
+        r.insert(1, "ccc\r");
+        assert_eq!(r.max_line_len(), 5);
+        assert_eq!(r.max_line_len(), max_line_len(r.byte_slice(..)));
+
+        // Slicing between a carriage return and its line feed.
+        let s = r.byte_slice(..r.byte_len() - 1);
+        assert_eq!(s.max_line_len(), max_line_len(s));
+    }
+
+    #[test]
+    fn max_line_len_matches_lines() {
+        for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+            let r = Rope::from(s);
+            assert_eq!(r.max_line_len(), max_line_len(r.byte_slice(..)));
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn max_line_len_random_slices() {
+        let mut rng = rand::rng();
+
+        let r = Rope::from(MEDIUM);
+
+        for _ in 0..100 {
+            let start = rng.random_range(0..=r.byte_len());
+            let end = rng.random_range(start..=r.byte_len());
+
+            let s = r.byte_slice(start..end);
+            assert_eq!(s.max_line_len(), max_line_len(s), "{start}..{end}");
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn max_line_len_random_edits() {
+        let mut rng = rand::rng();
+
+        let mut r = Rope::from(MEDIUM);
+
+        for _ in 0..100 {
+            let start = rng.random_range(0..=r.byte_len());
+            let end = rng.random_range(start..=r.byte_len().min(start + 200));
+
+            let text = match rng.random_range(0..4) {
+                0 => "",
+                1 => "\n",
+                2 => "\r\nfoo bar baz qux\r",
+                _ => "a long line without any line break in it",
+            };
+
+            r.replace(start..end, text);
+            r.assert_invariants();
+
+            assert_eq!(r.max_line_len(), max_line_len(r.byte_slice(..)));
+
+            let s = r.byte_slice(start..);
+            assert_eq!(s.max_line_len(), max_line_len(s));
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn max_line_len_concat() {
+        let mut rng = rand::rng();
+
+        for s in [TINY, SMALL, MEDIUM, LARGE] {
+            for _ in 0..10 {
+                let mut at = rng.random_range(0..=s.len());
+                while !s.is_char_boundary(at) {
+                    at += 1;
+                }
+
+                let r = Rope::from(&s[..at]) + Rope::from(&s[at..]);
+                r.assert_invariants();
+
+                assert_eq!(r.max_line_len(), max_line_len(r.byte_slice(..)));
+            }
+        }
+    }
+}
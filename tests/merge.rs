@@ -0,0 +1,122 @@
+use crop::Rope;
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM};
+
+/// Applies a few random edits to `s`, using only chars from `alphabet`.
+fn random_edits(s: &mut String, alphabet: &[char], rng: &mut impl Rng) {
+    for _ in 0..rng.random_range(0..4) {
+        let mut start = rng.random_range(0..=s.len());
+        while !s.is_char_boundary(start) {
+            start += 1;
+        }
+
+        let mut end = rng.random_range(start..=s.len().min(start + 4));
+        while !s.is_char_boundary(end) {
+            end += 1;
+        }
+
+        let text = (0..rng.random_range(0..4))
+            .map(|_| alphabet[rng.random_range(0..alphabet.len())])
+            .collect::<String>();
+
+        s.replace_range(start..end, &text);
+    }
+}
+
+#[test]
+fn merge_one_side() {
+    let base = Rope::from(CURSED_LIPSUM);
+
+    let mut edited = base.clone();
+    edited.replace(3..13, "ƒoo\r\n");
+    edited.insert(0, "bar");
+
+    let (merged, conflicts) = Rope::merge(&base, &edited, &base);
+    assert_eq!(merged, edited);
+    assert!(conflicts.is_empty());
+
+    let (merged, conflicts) = Rope::merge(&base, &base, &edited);
+    assert_eq!(merged, edited);
+    assert!(conflicts.is_empty());
+
+    let (merged, conflicts) = Rope::merge(&base, &edited, &edited);
+    assert_eq!(merged, edited);
+    assert!(conflicts.is_empty());
+}
+
+#[test]
+fn merge_conflict() {
+    let base = Rope::from("foo\nbar\nbaz\n");
+    let ours = Rope::from("foo\nbär\nqux\n");
+    let theirs = Rope::from("foo!\nbaz\n");
+
+    let (merged, conflicts) = Rope::merge(&base, &ours, &theirs);
+
+    assert_eq!(merged, "foo\nbär\nqux\n");
+    assert_eq!(conflicts.len(), 1);
+
+    let conflict = &conflicts[0];
+
+    assert_eq!(base.byte_slice(conflict.base_byte_range.clone()), "\nbar");
+    assert_eq!(merged.byte_slice(conflict.byte_range.clone()), "\nbär");
+    assert_eq!(theirs.byte_slice(conflict.their_byte_range.clone()), "!");
+}
+
+#[test]
+fn merge_conflict_offsets() {
+    // The conflict comes after clean changes which shift it in both the
+    // merged `Rope` and in theirs.
+    let base = Rope::from("aaa bbb ccc ddd");
+    let ours = Rope::from("aaaaaa bbb XXX ddd");
+    let theirs = Rope::from("aaa bbbbbb YY ddd");
+
+    let (merged, conflicts) = Rope::merge(&base, &ours, &theirs);
+
+    assert_eq!(merged, "aaaaaa bbbbbb XXX ddd");
+    assert_eq!(conflicts.len(), 1);
+
+    let conflict = &conflicts[0];
+
+    assert_eq!(merged.byte_slice(conflict.byte_range.clone()), "XXX");
+    assert_eq!(theirs.byte_slice(conflict.their_byte_range.clone()), "YY");
+    assert_eq!(base.byte_slice(conflict.base_byte_range.clone()), "ccc");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn merge_random_disjoint() {
+    let mut rng = rand::rng();
+
+    let alphabet = ['a', 'b', 'ƒ', '\n'];
+
+    for _ in 0..100 {
+        let mut first = MEDIUM[..rng.random_range(0..64)].to_owned();
+        let mut second = MEDIUM[..rng.random_range(0..64)].to_owned();
+
+        let base = format!("{first}\n#####\n{second}");
+
+        let old_first = first.clone();
+        let old_second = second.clone();
+
+        // Ours only edits the first half and theirs only the second one, with
+        // a separator neither of them can touch in between.
+        random_edits(&mut first, &alphabet, &mut rng);
+        random_edits(&mut second, &alphabet, &mut rng);
+
+        let ours = format!("{first}\n#####\n{old_second}");
+        let theirs = format!("{old_first}\n#####\n{second}");
+
+        let (merged, conflicts) = Rope::merge(
+            &Rope::from(base.as_str()),
+            &Rope::from(ours),
+            &Rope::from(theirs),
+        );
+
+        merged.assert_invariants();
+        assert!(conflicts.is_empty());
+        assert_eq!(merged, format!("{first}\n#####\n{second}"));
+    }
+}
@@ -0,0 +1,66 @@
+mod common;
+
+#[cfg(feature = "unicode-normalization")]
+mod tests {
+    use crop::Rope;
+    use unicode_normalization::UnicodeNormalization;
+
+    use crate::common::{CURSED_LIPSUM, LARGE, TEXT_EMOJI};
+
+    /// Text mixing composed and decomposed forms, including sequences of
+    /// combining marks that have to be reordered.
+    const MIXED: &str = "cafe\u{301} caf\u{e9} A\u{30a} \u{212b} \
+                         q\u{307}\u{323} \u{1e0b}\u{323} 한국어 \
+                         \u{1100}\u{1161}";
+
+    #[test]
+    fn normalization_forms() {
+        let r = Rope::from(MIXED);
+
+        let nfc = r.to_nfc();
+        assert_eq!(nfc, MIXED.nfc().collect::<String>());
+        assert!(nfc.is_nfc());
+        assert!(!r.is_nfc());
+        nfc.assert_invariants();
+
+        let nfd = r.to_nfd();
+        assert_eq!(nfd, MIXED.nfd().collect::<String>());
+        assert!(!nfd.is_nfc());
+        assert_eq!(nfd.to_nfc(), nfc);
+        nfd.assert_invariants();
+
+        // Both forms now compare equal.
+        assert_eq!(Rope::from("cafe\u{301}").to_nfc(), "caf\u{e9}");
+        assert_eq!(Rope::from("caf\u{e9}").to_nfd(), "cafe\u{301}");
+    }
+
+    #[test]
+    fn normalization_empty() {
+        let r = Rope::new();
+        assert!(r.is_nfc());
+        assert_eq!(r.to_nfc(), "");
+        assert_eq!(r.to_nfd(), "");
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn normalization_large() {
+        let s = [MIXED, TEXT_EMOJI, CURSED_LIPSUM, LARGE].join(MIXED);
+
+        let r = Rope::from(s.as_str());
+
+        let nfc = s.nfc().collect::<String>();
+        let nfd = s.nfd().collect::<String>();
+
+        assert_eq!(r.to_nfc(), nfc);
+        assert_eq!(r.to_nfd(), nfd);
+        assert_eq!(r.is_nfc(), unicode_normalization::is_nfc(&s));
+        assert!(Rope::from(nfc.as_str()).is_nfc());
+
+        let slice = r.byte_slice(MIXED.len()..s.len() - MIXED.len());
+        let sub = &s[MIXED.len()..s.len() - MIXED.len()];
+        assert_eq!(slice.to_nfc(), sub.nfc().collect::<String>());
+        assert_eq!(slice.to_nfd(), sub.nfd().collect::<String>());
+        assert_eq!(slice.is_nfc(), unicode_normalization::is_nfc(sub));
+    }
+}
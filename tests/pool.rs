@@ -0,0 +1,93 @@
+mod common;
+
+#[cfg(feature = "pool")]
+mod tests {
+    use crop::{GapBuffer, Rope};
+
+    use super::common::LARGE;
+
+    #[test]
+    fn pool_editing_session() {
+        crop::pool::set_capacity(64);
+
+        let mut r = Rope::from(LARGE);
+        let mut s = String::from(LARGE);
+
+        let text = LARGE[..LARGE.len() / 8].to_owned();
+
+        for i in 0..16 {
+            let start = [0, r.byte_len() / 3, r.byte_len() / 2][i % 3];
+            let start = (start..=r.byte_len())
+                .find(|&o| r.is_char_boundary(o))
+                .unwrap();
+            let end = (start + text.len()).min(r.byte_len());
+            let end =
+                (end..=r.byte_len()).find(|&o| r.is_char_boundary(o)).unwrap();
+            r.delete(start..end);
+            s.replace_range(start..end, "");
+            r.insert(start, &text);
+            s.insert_str(start, &text);
+        }
+
+        r.assert_invariants();
+        assert_eq!(r, s);
+
+        let stats = crop::pool::stats();
+        assert!(stats.hits > 0);
+        assert!(stats.recycled > 0);
+        assert!(stats.pooled <= 64);
+    }
+
+    #[test]
+    fn pool_two_chunk_sizes() {
+        crop::pool::set_capacity(64);
+        crop::pool::clear();
+
+        drop(GapBuffer::<16>::from("Hello"));
+        drop(GapBuffer::<32>::from("Earth"));
+        assert_eq!(crop::pool::stats().pooled, 2);
+
+        // Each size is served from its own free list, and taking a buffer of
+        // one size doesn't throw away the buffer of the other.
+        let before = crop::pool::stats();
+        let small = GapBuffer::<16>::from("🌎");
+        let large = GapBuffer::<32>::from("🌎");
+        let after = crop::pool::stats();
+
+        assert_eq!(after.hits, before.hits + 2);
+        assert_eq!(after.misses, before.misses);
+        assert_eq!(after.pooled, 0);
+
+        // Since every size has its own free list, a full list doesn't prevent
+        // buffers of another size from being pooled.
+        crop::pool::set_capacity(1);
+        drop(GapBuffer::<16>::from("a"));
+        drop(small);
+        drop(large);
+
+        let stats = crop::pool::stats();
+        assert_eq!(stats.pooled, 2);
+        assert_eq!(stats.discarded, after.discarded + 1);
+    }
+
+    #[test]
+    fn pool_zero_capacity() {
+        crop::pool::set_capacity(0);
+
+        let before = crop::pool::stats();
+        assert_eq!(before.pooled, 0);
+
+        let r = Rope::from(LARGE);
+        let mut clone = r.clone();
+        clone.delete(..LARGE.len() / 2);
+        drop(r);
+
+        let after = crop::pool::stats();
+        assert_eq!(after.pooled, 0);
+        assert_eq!(after.hits, before.hits);
+        assert_eq!(after.recycled, before.recycled);
+        assert!(after.discarded > before.discarded);
+
+        assert_eq!(clone, &LARGE[LARGE.len() / 2..]);
+    }
+}
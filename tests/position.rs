@@ -0,0 +1,211 @@
+use crop::{Position, PositionEncoding, Rope};
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM, TEXT_EMOJI};
+
+const ENCODINGS: [PositionEncoding; 3] =
+    [PositionEncoding::Utf8, PositionEncoding::Utf16, PositionEncoding::Utf32];
+
+/// The position of `byte_offset` in `s`, computed by scanning the string
+/// from the start.
+fn position_of_byte(
+    s: &str,
+    byte_offset: usize,
+    encoding: PositionEncoding,
+) -> Position {
+    let prefix = &s[..byte_offset];
+
+    let line = prefix.matches('\n').count();
+
+    let line_start = prefix.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+
+    let line_prefix = &prefix[line_start..];
+
+    let column = match encoding {
+        PositionEncoding::Utf8 => line_prefix.len(),
+        PositionEncoding::Utf16 => line_prefix.encode_utf16().count(),
+        PositionEncoding::Utf32 => line_prefix.chars().count(),
+    };
+
+    Position::new(line, column)
+}
+
+#[test]
+fn position_empty() {
+    let r = Rope::new();
+
+    for encoding in ENCODINGS {
+        assert_eq!(r.position_of_byte(0, encoding), Position::default());
+        assert_eq!(r.byte_of_position(Position::default(), encoding), 0);
+    }
+}
+
+#[test]
+fn position_trailing_newline() {
+    let r = Rope::from("foo\nbar\n");
+
+    for encoding in ENCODINGS {
+        assert_eq!(r.position_of_byte(8, encoding), Position::new(2, 0));
+        assert_eq!(r.byte_of_position(Position::new(2, 0), encoding), 8);
+        assert_eq!(r.byte_of_position(Position::new(1, 3), encoding), 7);
+    }
+}
+
+#[test]
+fn position_crlf() {
+    let r = Rope::from("foo\r\nbar");
+
+    for encoding in ENCODINGS {
+        assert_eq!(r.position_of_byte(3, encoding), Position::new(0, 3));
+        assert_eq!(r.position_of_byte(5, encoding), Position::new(1, 0));
+        assert_eq!(r.byte_of_position(Position::new(0, 3), encoding), 3);
+    }
+}
+
+#[test]
+fn position_round_trip() {
+    for s in [CURSED_LIPSUM, MEDIUM, TEXT_EMOJI] {
+        let r = Rope::from(s);
+
+        let half = s.char_indices().nth(s.chars().count() / 2).unwrap().0;
+
+        for slice in [r.byte_slice(..), r.byte_slice(..half)] {
+            let text = slice.to_string();
+
+            for (byte_offset, _) in
+                text.char_indices().step_by(13).chain([(text.len(), ' ')])
+            {
+                for encoding in ENCODINGS {
+                    let position =
+                        slice.position_of_byte(byte_offset, encoding);
+
+                    assert_eq!(
+                        position,
+                        position_of_byte(&text, byte_offset, encoding)
+                    );
+
+                    // Offsets between a CR and a LF can't be expressed as
+                    // positions within the line.
+                    if text[byte_offset..].starts_with('\n')
+                        && text[..byte_offset].ends_with('\r')
+                    {
+                        continue;
+                    }
+
+                    assert_eq!(
+                        slice.byte_of_position(position, encoding),
+                        byte_offset
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn position_line_out_of_bounds() {
+    let r = Rope::from("foo\nbar");
+    let _ = r.byte_of_position(Position::new(3, 0), PositionEncoding::Utf8);
+}
+
+#[test]
+#[should_panic]
+fn position_column_out_of_bounds() {
+    let r = Rope::from("foo\nbar");
+    let _ = r.byte_of_position(Position::new(0, 4), PositionEncoding::Utf8);
+}
+
+#[test]
+#[should_panic]
+fn position_column_inside_surrogate_pair() {
+    let r = Rope::from("a𐐀b");
+    let _ = r.byte_of_position(Position::new(0, 2), PositionEncoding::Utf16);
+}
+
+#[cfg(feature = "display-width")]
+#[test]
+fn visual_col_tabs() {
+    let r = Rope::from("\tab\t日\n  \tx\n");
+
+    let cols = [0, 4, 5, 6, 8, 10];
+    let offsets = [0, 1, 2, 3, 4, 7];
+
+    for (col, offset) in cols.into_iter().zip(offsets) {
+        assert_eq!(r.visual_col_of_byte(offset, 4), col, "{offset}");
+    }
+
+    assert_eq!(r.visual_col_of_byte(10, 4), 2);
+    assert_eq!(r.visual_col_of_byte(11, 4), 4);
+    assert_eq!(r.visual_col_of_byte(11, 8), 8);
+    assert_eq!(r.visual_col_of_byte(11, 0), 2);
+
+    // Columns inside a tab or a wide char round down to its start.
+    assert_eq!(r.byte_of_visual_col(0, 2, 4), 0);
+    assert_eq!(r.byte_of_visual_col(0, 9, 4), 4);
+    assert_eq!(r.byte_of_visual_col(1, 3, 4), 10);
+    assert_eq!(r.byte_of_visual_col(1, 4, 4), 11);
+
+    // Columns past the end of a line stop before its line break.
+    assert_eq!(r.byte_of_visual_col(0, 100, 4), 7);
+    assert_eq!(r.byte_of_visual_col(2, 100, 4), r.byte_len());
+}
+
+#[cfg(feature = "display-width")]
+#[test]
+fn visual_col_zero_width() {
+    let r = Rope::from("e\u{301}x");
+
+    assert_eq!(r.visual_col_of_byte(1, 4), 1);
+    assert_eq!(r.visual_col_of_byte(3, 4), 1);
+
+    // The column is placed after the zero-width chars that precede it.
+    assert_eq!(r.byte_of_visual_col(0, 1, 4), 3);
+}
+
+#[cfg(feature = "display-width")]
+#[test]
+fn visual_col_round_trip() {
+    let s = CURSED_LIPSUM.replace(' ', "\t").repeat(8) + "日本\tx";
+
+    let r = Rope::from(s.as_str());
+
+    for slice in [r.byte_slice(..), r.byte_slice(3..)] {
+        let text = slice.to_string();
+
+        for (byte_offset, _) in text.char_indices().chain([(text.len(), ' ')])
+        {
+            // Offsets between a CR and a LF can't be reached by a column.
+            if text[byte_offset..].starts_with('\n')
+                && text[..byte_offset].ends_with('\r')
+            {
+                continue;
+            }
+
+            let line = slice.line_of_byte(byte_offset);
+            let col = slice.visual_col_of_byte(byte_offset, 4);
+
+            let found = slice.byte_of_visual_col(line, col, 4);
+
+            // Zero-width chars share the column of the char after them.
+            assert!(found >= byte_offset);
+            assert_eq!(slice.visual_col_of_byte(found, 4), col);
+            assert!(
+                slice
+                    .byte_slice(byte_offset..found)
+                    .chars()
+                    .all(|ch| matches!(ch, '\u{300}'..='\u{36F}')),
+                "{byte_offset} {found}"
+            );
+        }
+    }
+}
+
+#[cfg(feature = "display-width")]
+#[test]
+#[should_panic]
+fn visual_col_line_out_of_bounds() {
+    let r = Rope::from("foo\nbar");
+    let _ = r.byte_of_visual_col(3, 0, 4);
+}
@@ -0,0 +1,30 @@
+mod common;
+
+#[cfg(feature = "proptest")]
+mod tests {
+    use crop::Rope;
+    use proptest::prelude::*;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[cfg_attr(miri, ignore)]
+        #[test]
+        fn rope_is_valid(r in crop::proptest::rope()) {
+            r.assert_invariants();
+            prop_assert_eq!(r.lines().count(), r.line_len());
+        }
+
+        #[cfg_attr(miri, ignore)]
+        #[test]
+        fn rope_from_keeps_text(
+            (r, s) in "[a-z\r\n]{0,512}".prop_flat_map(|s| {
+                (crop::proptest::rope_from(Just(s.clone())), Just(s))
+            })
+        ) {
+            r.assert_invariants();
+            prop_assert_eq!(&r, s.as_str());
+            prop_assert_eq!(r.line_len(), Rope::from(s.as_str()).line_len());
+        }
+    }
+}
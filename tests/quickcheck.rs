@@ -0,0 +1,51 @@
+mod common;
+
+#[cfg(feature = "quickcheck")]
+mod tests {
+    use crop::Rope;
+    use quickcheck::{Arbitrary, Gen, QuickCheck};
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn arbitrary_rope_is_valid() {
+        fn prop(r: Rope) -> bool {
+            r.assert_invariants();
+            r.lines().count() == r.line_len()
+        }
+
+        QuickCheck::new().tests(64).quickcheck(prop as fn(Rope) -> bool);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn shrink_repacks_first() {
+        let mut g = Gen::new(256);
+
+        for _ in 0..16 {
+            let r = Rope::arbitrary(&mut g);
+
+            let mut shrunk = r.shrink();
+
+            let Some(first) = shrunk.next() else {
+                assert_eq!(r, "");
+                continue;
+            };
+
+            first.assert_invariants();
+
+            if first == r {
+                // The first candidate is the same text in fewer chunks.
+                assert!(
+                    first.tree_stats().leaf_count < r.tree_stats().leaf_count
+                );
+            } else {
+                assert!(first.byte_len() < r.byte_len());
+            }
+
+            for candidate in shrunk.take(8) {
+                candidate.assert_invariants();
+                assert!(candidate.byte_len() <= r.byte_len());
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+mod common;
+
+#[cfg(feature = "rayon")]
+mod tests {
+    use crop::Rope;
+    use rand::{Rng, rng};
+    use rayon::prelude::*;
+
+    use super::common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+    #[test]
+    fn par_chunks_empty() {
+        let r = Rope::new();
+        assert_eq!(r.par_chunks().count(), 0);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn par_chunks_random() {
+        let mut rng = rng();
+
+        for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+            let r = Rope::from(s);
+
+            let chunks = r.par_chunks().collect::<Vec<_>>();
+            assert_eq!(chunks, r.chunks().collect::<Vec<_>>());
+
+            for _ in 0..10 {
+                let mut start = rng.random_range(0..=s.len());
+                while !s.is_char_boundary(start) {
+                    start += 1;
+                }
+
+                let mut end = rng.random_range(start..=s.len());
+                while !s.is_char_boundary(end) {
+                    end += 1;
+                }
+
+                let slice = r.byte_slice(start..end);
+
+                let chunks = slice.par_chunks().collect::<Vec<_>>();
+                assert_eq!(chunks, slice.chunks().collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn par_lines_trailing_line_break() {
+        let r = Rope::from("foo\r\nbar\n\nbaz\n");
+        let lines = r.par_lines().collect::<Vec<_>>();
+        assert_eq!(lines, ["foo", "bar", "", "baz"]);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn par_lines_random() {
+        let mut rng = rng();
+
+        for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+            let r = Rope::from(s);
+
+            let lines = r.par_lines().collect::<Vec<_>>();
+            assert_eq!(lines, r.lines().collect::<Vec<_>>());
+
+            for _ in 0..10 {
+                let mut start = rng.random_range(0..=s.len());
+                while !s.is_char_boundary(start) {
+                    start += 1;
+                }
+
+                let mut end = rng.random_range(start..=s.len());
+                while !s.is_char_boundary(end) {
+                    end += 1;
+                }
+
+                let slice = r.byte_slice(start..end);
+
+                let lines = slice.par_lines().collect::<Vec<_>>();
+                assert_eq!(lines, slice.lines().collect::<Vec<_>>());
+            }
+        }
+    }
+}
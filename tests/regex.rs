@@ -0,0 +1,190 @@
+mod common;
+
+#[cfg(feature = "regex")]
+mod tests {
+    use crop::{Regex, Rope};
+    use rand::{Rng, rng};
+
+    use super::common::{CURSED_LIPSUM, MEDIUM, SMALL, TEXT_EMOJI};
+
+    const PATTERNS: &[&str] = &[
+        "",
+        "a",
+        "[0-9]+",
+        r"\w+",
+        r"\s+",
+        "(?m)^",
+        "(?m)$",
+        "(?m)^.+$",
+        "^",
+        "$",
+        "a*",
+        "(?i)lorem",
+        r"[\p{Greek}ṁṕ]+",
+        "🐸",
+        r"\r?\n",
+        "e.{1,10}e",
+        "x*|o",
+    ];
+
+    /// The byte ranges of the matches of `regex` in `s`.
+    fn str_matches(regex: &Regex, s: &str) -> Vec<std::ops::Range<usize>> {
+        let mut cache = regex.create_cache();
+        regex.find_iter(&mut cache, s).map(|m| m.range()).collect()
+    }
+
+    #[test]
+    fn regex_find_iter_patterns() {
+        for s in [CURSED_LIPSUM, TEXT_EMOJI, SMALL, "", "aaba\n\n"] {
+            let r = Rope::from(s);
+
+            for pattern in PATTERNS {
+                let regex = Regex::new(pattern).unwrap();
+
+                assert_eq!(
+                    r.regex_find_iter(&regex).collect::<Vec<_>>(),
+                    str_matches(&regex, s),
+                    "{pattern:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn regex_find_iter_random_slices() {
+        let mut rng = rng();
+
+        for s in [CURSED_LIPSUM, TEXT_EMOJI, MEDIUM] {
+            let r = Rope::from(s);
+
+            for _ in 0..10 {
+                let mut start = rng.random_range(0..=s.len());
+                while !s.is_char_boundary(start) {
+                    start += 1;
+                }
+
+                let mut end = rng.random_range(start..=s.len());
+                while !s.is_char_boundary(end) {
+                    end += 1;
+                }
+
+                let slice = r.byte_slice(start..end);
+
+                let pattern = PATTERNS[rng.random_range(0..PATTERNS.len())];
+
+                let regex = Regex::new(pattern).unwrap();
+
+                assert_eq!(
+                    slice.regex_find_iter(&regex).collect::<Vec<_>>(),
+                    str_matches(&regex, &s[start..end]),
+                    "{pattern:?}"
+                );
+            }
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn regex_pattern() {
+        for s in [CURSED_LIPSUM, SMALL, TEXT_EMOJI] {
+            let r = Rope::from(s);
+
+            for pattern in PATTERNS {
+                let regex = Regex::new(pattern).unwrap();
+
+                let matches = str_matches(&regex, s);
+
+                assert_eq!(
+                    r.find(&regex),
+                    matches.first().map(|range| range.start),
+                    "{pattern:?}"
+                );
+
+                assert_eq!(
+                    r.matches(&regex).collect::<Vec<_>>(),
+                    matches.iter().map(|m| &s[m.clone()]).collect::<Vec<_>>(),
+                    "{pattern:?}"
+                );
+
+                let mut split = Vec::new();
+                let mut start = 0;
+                for range in &matches {
+                    split.push(&s[start..range.start]);
+                    start = range.end;
+                }
+                split.push(&s[start..]);
+
+                assert_eq!(
+                    r.split(&regex).collect::<Vec<_>>(),
+                    split,
+                    "{pattern:?}"
+                );
+
+                assert_eq!(
+                    r.starts_with(&regex),
+                    matches.first().is_some_and(|range| range.start == 0),
+                    "{pattern:?}"
+                );
+
+                assert_eq!(r.find_iter(&regex).collect::<Vec<_>>(), matches);
+
+                // A suffix match can overlap the last forward match, so
+                // check against a regex anchored at the end instead.
+                let anchored =
+                    Regex::new(&format!(r"(?:{pattern})\z")).unwrap();
+
+                assert_eq!(
+                    r.ends_with(&regex),
+                    !str_matches(&anchored, s).is_empty(),
+                    "{pattern:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn regex_pattern_rev() {
+        let r = Rope::from("foo 42, bar 1337");
+        let regex = Regex::new(r"[0-9]+").unwrap();
+
+        assert_eq!(r.rfind(&regex), Some(12));
+        assert!(r.ends_with(&regex));
+        assert_eq!(
+            r.rsplit(&regex).collect::<Vec<_>>(),
+            ["", ", bar ", "foo "]
+        );
+        assert_eq!(
+            r.byte_slice(..14).trim_end_matches(&regex),
+            "foo 42, bar "
+        );
+        assert_eq!(r.byte_slice(4..).trim_start_matches(&regex), ", bar 1337");
+    }
+
+    #[test]
+    fn regex_pattern_overlapping_suffix() {
+        let r = Rope::from("aaa");
+        let regex = Regex::new("aa").unwrap();
+
+        assert_eq!(r.find(&regex), Some(0));
+        assert_eq!(r.rfind(&regex), Some(1));
+        assert!(r.ends_with(&regex));
+        assert_eq!(r.byte_slice(..).trim_end_matches(&regex), "a");
+        assert_eq!(r.rsplit(&regex).collect::<Vec<_>>(), ["", "a"]);
+
+        let r = Rope::from("foo 42 1337");
+        let regex = Regex::new(r"\d+ \d+").unwrap();
+
+        assert_eq!(r.find_iter(&regex).next(), Some(4..11));
+        assert_eq!(r.find_iter(&regex).count(), 1);
+        assert_eq!(r.rfind(&regex), Some(4));
+        assert_eq!(r.byte_slice(..).trim_end_matches(&regex), "foo ");
+    }
+
+    #[test]
+    fn regex_replace_all() {
+        let mut r = Rope::from("foo 42, bar 1337");
+        r.replace_all(&Regex::new(r"[0-9]+").unwrap(), "N");
+        assert_eq!(r, "foo N, bar N");
+    }
+}
@@ -0,0 +1,58 @@
+mod common;
+
+#[cfg(feature = "rkyv")]
+mod tests {
+    use crop::{ArchivedRope, Rope};
+    use rkyv::rancor::Error;
+
+    use super::common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+    #[test]
+    fn rkyv_empty() {
+        let r = Rope::new();
+
+        let bytes = rkyv::to_bytes::<Error>(&r).unwrap();
+        let archived = rkyv::access::<ArchivedRope, Error>(&bytes).unwrap();
+
+        assert!(archived.is_empty());
+        assert_eq!(archived.byte_len(), 0);
+        assert_eq!(rkyv::deserialize::<Rope, Error>(archived).unwrap(), r);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn rkyv_round_trip() {
+        for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+            let mut r = Rope::from(s);
+            r.insert(0, "Hello\r\nWorld");
+
+            let bytes = rkyv::to_bytes::<Error>(&r).unwrap();
+            let archived =
+                rkyv::access::<ArchivedRope, Error>(&bytes).unwrap();
+
+            assert_eq!(archived.byte_len(), r.byte_len());
+            assert!(archived.chunks().eq(r.chunks()));
+
+            let deserialized =
+                rkyv::from_bytes::<Rope, Error>(&bytes).unwrap();
+            deserialized.assert_invariants();
+            assert_eq!(deserialized, r);
+        }
+    }
+
+    #[test]
+    fn rkyv_invalid_utf8() {
+        let r = Rope::from("Hello 🌎!");
+
+        let mut bytes = rkyv::to_bytes::<Error>(&r).unwrap().to_vec();
+
+        // Replace the second byte of the globe emoji with an ASCII char.
+        let idx = bytes.windows(4).position(|w| w == "🌎".as_bytes()).unwrap();
+        bytes[idx + 1] = b'a';
+
+        let mut aligned = rkyv::util::AlignedVec::<16>::new();
+        aligned.extend_from_slice(&bytes);
+
+        assert!(rkyv::access::<ArchivedRope, Error>(&aligned).is_err());
+    }
+}
@@ -1,7 +1,8 @@
 mod common;
 
-use common::LARGE;
+use common::{LARGE, MEDIUM, SMALL, TINY};
 use crop::{Rope, RopeBuilder};
+use rand::Rng;
 
 #[test]
 fn builder_line_len() {
@@ -59,3 +60,221 @@ fn builder_crlf_1() {
     r.assert_invariants();
     assert_eq!(r, "aaa\r\nbbb");
 }
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn builder_with_capacity() {
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        for capacity in [0, s.len() / 2, s.len(), s.len() * 2] {
+            let mut b = RopeBuilder::with_capacity(capacity);
+            for line in s.split_inclusive('\n') {
+                b.append(line);
+            }
+            let r = b.build();
+            r.assert_invariants();
+            assert_eq!(r, s);
+        }
+    }
+}
+
+/// A reader yielding its bytes a few at a time, which splits most of the
+/// multi-byte chars across reads.
+struct SlowReader<'a> {
+    bytes: &'a [u8],
+    reads: usize,
+}
+
+impl std::io::Read for SlowReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reads += 1;
+
+        // Sprinkle some interruptions, which should be retried.
+        if self.reads % 7 == 0 {
+            return Err(std::io::ErrorKind::Interrupted.into());
+        }
+
+        let len = (1 + self.reads % 4).min(buf.len()).min(self.bytes.len());
+        buf[..len].copy_from_slice(&self.bytes[..len]);
+        self.bytes = &self.bytes[len..];
+        Ok(len)
+    }
+}
+
+#[test]
+fn builder_from_reader() {
+    for s in ["", "ƒoo\nbär\r\nbaz🐸", common::CURSED_LIPSUM, MEDIUM] {
+        let r = Rope::from_reader(s.as_bytes()).unwrap();
+        assert_eq!(r, s);
+        r.assert_invariants();
+
+        let r =
+            Rope::from_reader(SlowReader { bytes: s.as_bytes(), reads: 0 })
+                .unwrap();
+        assert_eq!(r, s);
+        r.assert_invariants();
+    }
+}
+
+#[test]
+fn builder_from_reader_invalid_utf8() {
+    let err = Rope::from_reader(&b"foo\xFFbar"[..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    // A truncated char at the very end.
+    let err = Rope::from_reader(&"ƒoo🐸".as_bytes()[..6]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn builder_strip_bom() {
+    let bytes = "\u{FEFF}ƒoo\n\u{FEFF}bär".as_bytes();
+
+    // The BOM is split across the first two reads.
+    let mut builder = RopeBuilder::new();
+    builder.strip_bom().append_reader(SlowReader { bytes, reads: 0 }).unwrap();
+    let r = builder.build();
+    assert_eq!(r, "ƒoo\n\u{FEFF}bär");
+    assert!(r.has_bom());
+    r.assert_invariants();
+
+    // Without `strip_bom()` the BOM is kept as text.
+    let r = Rope::from_reader(bytes).unwrap();
+    assert_eq!(r, "\u{FEFF}ƒoo\n\u{FEFF}bär");
+    assert!(!r.has_bom());
+
+    // It's too late to strip a BOM once some text has been appended.
+    let mut builder = RopeBuilder::new();
+    builder.append("").append("ƒoo").strip_bom().append("\u{FEFF}");
+    let r = builder.build();
+    assert_eq!(r, "ƒoo\u{FEFF}");
+    assert!(!r.has_bom());
+
+    let mut builder = RopeBuilder::new();
+    builder.strip_bom().append("ƒoo");
+    assert!(!builder.build().has_bom());
+}
+
+#[test]
+fn builder_append_reader_invalid_utf8() {
+    let mut builder = RopeBuilder::new();
+    builder.append("ƒoo ");
+    assert!(builder.append_reader(&b"bar\xFF"[..]).is_err());
+    builder.append(" baz");
+    assert_eq!(builder.build(), "ƒoo bar baz");
+}
+
+#[test]
+fn rope_from_iter() {
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let r = s.split_inclusive(' ').collect::<Rope>();
+        r.assert_invariants();
+        assert_eq!(r, s);
+
+        let r = s.chars().collect::<Rope>();
+        r.assert_invariants();
+        assert_eq!(r, s);
+    }
+
+    assert!(core::iter::empty::<char>().collect::<Rope>().is_empty());
+}
+
+#[test]
+fn rope_extend() {
+    for s in [TINY, SMALL, MEDIUM] {
+        let (left, right) = s.split_at(s.len() / 2);
+
+        let mut r = Rope::from(left);
+        r.extend(right.split_inclusive('\n'));
+        r.assert_invariants();
+        assert_eq!(r, s);
+
+        let mut r = Rope::from(left);
+        r.extend(right.chars());
+        r.assert_invariants();
+        assert_eq!(r, s);
+    }
+}
+
+#[test]
+fn rope_add() {
+    for (lhs, rhs) in [(TINY, SMALL), (MEDIUM, TINY), ("", SMALL), (SMALL, "")]
+    {
+        let expected = [lhs, rhs].concat();
+
+        let r = Rope::from(lhs) + Rope::from(rhs);
+        r.assert_invariants();
+        assert_eq!(r, expected);
+
+        let r = Rope::from(lhs) + &Rope::from(rhs);
+        r.assert_invariants();
+        assert_eq!(r, expected);
+
+        let r = Rope::from(lhs) + rhs;
+        r.assert_invariants();
+        assert_eq!(r, expected);
+
+        let mut r = Rope::from(lhs);
+        r += Rope::from(rhs);
+        r.assert_invariants();
+        assert_eq!(r, expected);
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn rope_add_random() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        for _ in 0..20 {
+            // Pick the split point close to either end once in a while, so
+            // that the two trees have very different depths.
+            let mut at = match rng.random_range(0..3) {
+                0 => rng.random_range(0..=s.len().min(16)),
+                1 => s.len() - rng.random_range(0..=s.len().min(16)),
+                _ => rng.random_range(0..=s.len()),
+            };
+            while !s.is_char_boundary(at) {
+                at += 1;
+            }
+
+            let (left, right) = s.split_at(at);
+
+            let lhs = Rope::from(left);
+            let rhs = Rope::from(right);
+
+            let mut r = lhs.clone();
+            r += &rhs;
+            r.assert_invariants();
+            assert_eq!(r, s);
+            assert_eq!(r.line_len(), Rope::from(s).line_len());
+
+            // The originals are left untouched.
+            assert_eq!(lhs, left);
+            assert_eq!(rhs, right);
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn rope_add_shares_nodes() {
+    let large = Rope::from(LARGE);
+    let small = Rope::from(SMALL);
+
+    // All the nodes of the larger tree are reused, except for the ones
+    // along the seam. The shared prefix and suffix stop at the first node
+    // whose children were split between two new parents, so they can only
+    // give a lower bound.
+    let r = large.clone() + &small;
+    r.assert_invariants();
+    assert!(r.shared_byte_len(&large) >= LARGE.len() / 2);
+
+    let r = small.clone() + &large;
+    r.assert_invariants();
+    assert!(r.shared_byte_len(&large) >= LARGE.len() / 2);
+
+    let r = large.clone() + &large;
+    r.assert_invariants();
+    assert_eq!(r.byte_len(), 2 * LARGE.len());
+}
@@ -0,0 +1,114 @@
+use crop::{Rope, RopeChain};
+use rand::{Rng, rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+/// Splits `s` at random char boundaries into a bunch of ropes, including some
+/// empty ones.
+fn random_pieces(s: &str, rng: &mut impl Rng) -> Vec<Rope> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+
+    while start < s.len() {
+        let mut end = rng.random_range(start..=s.len().min(start + 500));
+        while !s.is_char_boundary(end) {
+            end += 1;
+        }
+        pieces.push(Rope::from(&s[start..end]));
+        start = end;
+    }
+
+    pieces
+}
+
+#[test]
+fn chain_empty() {
+    let chain = RopeChain::new();
+    assert!(chain.is_empty());
+    assert_eq!(chain.byte_len(), 0);
+    assert_eq!(chain.line_len(), 0);
+    assert_eq!(chain.byte_of_line(0), 0);
+    assert_eq!(chain.line_of_byte(0), 0);
+    assert_eq!(chain.chunks().count(), 0);
+    assert_eq!(chain, "");
+}
+
+#[test]
+fn chain_line_spanning_members() {
+    let (a, b, c) = (Rope::from("foo"), Rope::new(), Rope::from("bar\nbaz"));
+
+    let chain = RopeChain::from_iter([&a, &b, &c]);
+
+    assert_eq!(chain.line_len(), 2);
+    assert_eq!(chain.line(0), "foobar");
+    assert_eq!(chain.line(1), "baz");
+    assert_eq!(chain.line(0).members().len(), 2);
+}
+
+#[test]
+#[should_panic]
+fn chain_byte_out_of_bounds() {
+    let r = Rope::from("foo");
+    let chain = RopeChain::from_iter([&r, &r]);
+    let _ = chain.byte(6);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn chain_matches_concatenation() {
+    let mut rng = rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let pieces = random_pieces(s, &mut rng);
+
+        let chain = pieces.iter().collect::<RopeChain>();
+
+        let r = Rope::from(s);
+
+        assert_eq!(chain, s);
+        assert_eq!(Rope::from(&chain), r);
+        assert_eq!(chain.byte_len(), r.byte_len());
+        assert_eq!(chain.line_len(), r.line_len());
+        assert_eq!(chain.to_string(), s);
+        assert!(chain.bytes().eq(s.bytes()));
+        assert!(chain.chars().eq(s.chars()));
+
+        for line_offset in 0..=r.line_len() {
+            assert_eq!(
+                chain.byte_of_line(line_offset),
+                r.byte_of_line(line_offset)
+            );
+        }
+
+        for line_index in 0..r.line_len() {
+            assert_eq!(chain.line(line_index).to_string(), r.line(line_index));
+        }
+
+        for _ in 0..100 {
+            let mut byte_offset = rng.random_range(0..=r.byte_len());
+            while !s.is_char_boundary(byte_offset) {
+                byte_offset += 1;
+            }
+            assert_eq!(
+                chain.line_of_byte(byte_offset),
+                r.line_of_byte(byte_offset)
+            );
+        }
+
+        for _ in 0..20 {
+            let mut start = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+            let mut end = rng.random_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+            let slice = chain.byte_slice(start..end);
+            assert_eq!(slice, &s[start..end]);
+            assert_eq!(slice.line_len(), r.byte_slice(start..end).line_len());
+        }
+    }
+}
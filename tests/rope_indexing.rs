@@ -1,4 +1,5 @@
 use crop::Rope;
+use rand::Rng;
 
 mod common;
 
@@ -99,3 +100,129 @@ fn rope_line_3() {
     let l = r.line(2);
     assert_eq!("", l);
 }
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn rope_chunk_at_line() {
+    let mut rng = rand::rng();
+
+    for s in ["", "\n", "foo\nbar", TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        let slice_start = rng.random_range(0..=s.len());
+        let slice = r.byte_slice(slice_start..);
+
+        for _ in 0..20 {
+            let line_offset = rng.random_range(0..=r.line_len());
+            let (chunk, byte_offset, chunk_line) =
+                r.chunk_at_line(line_offset);
+
+            let line_start = r.byte_of_line(line_offset);
+            assert!(byte_offset <= line_start);
+            assert!(line_start <= byte_offset + chunk.len());
+            assert_eq!(
+                r.byte_slice(byte_offset..byte_offset + chunk.len()),
+                chunk
+            );
+            assert_eq!(chunk_line, r.line_of_byte(byte_offset));
+
+            let line_offset = rng.random_range(0..=slice.line_len());
+            let (chunk, byte_offset, chunk_line) =
+                slice.chunk_at_line(line_offset);
+
+            let line_start = slice.byte_of_line(line_offset);
+            assert!(byte_offset <= line_start);
+            assert!(line_start <= byte_offset + chunk.len());
+            assert_eq!(
+                slice.byte_slice(byte_offset..byte_offset + chunk.len()),
+                chunk
+            );
+            assert_eq!(chunk_line, slice.line_of_byte(byte_offset));
+        }
+    }
+}
+
+#[test]
+fn mem_usage_empty() {
+    let r = Rope::new();
+    let mem_usage = r.mem_usage();
+    assert_eq!(mem_usage.text_bytes, 0);
+    assert!(mem_usage.gap_bytes > 0);
+    assert!(mem_usage.node_bytes > 0);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn mem_usage_0() {
+    for s in ["", "Hi", "Hello", TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        let mem_usage = r.mem_usage();
+
+        assert_eq!(mem_usage.text_bytes, s.len());
+
+        assert_eq!(
+            mem_usage.total_bytes(),
+            mem_usage.text_bytes + mem_usage.gap_bytes + mem_usage.node_bytes
+        );
+
+        assert_eq!(r.clone().mem_usage(), mem_usage);
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn mem_usage_shrinks_after_deletion() {
+    let mut r = Rope::from(LARGE);
+
+    let before = r.mem_usage();
+
+    r.delete(LARGE.len() / 4..);
+
+    let after = r.mem_usage();
+
+    assert_eq!(after.text_bytes, LARGE.len() / 4);
+    assert!(after.node_bytes < before.node_bytes);
+    assert!(after.total_bytes() < before.total_bytes());
+}
+
+#[test]
+fn tree_stats_empty() {
+    let stats = Rope::new().tree_stats();
+    assert_eq!(stats.depth, 0);
+    assert_eq!(stats.node_count, 1);
+    assert_eq!(stats.leaf_count, 1);
+    assert_eq!(stats.avg_leaf_fill, 0.0);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn tree_stats_0() {
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let mut r = Rope::from(s);
+
+        let stats = r.tree_stats();
+
+        assert!(stats.leaf_count * stats.leaf_capacity >= s.len());
+        assert!(stats.avg_leaf_fill > 0.0 && stats.avg_leaf_fill <= 1.0);
+
+        if stats.depth == 0 {
+            assert_eq!(stats.node_count, 1);
+        } else {
+            assert!(stats.node_count > stats.leaf_count + stats.depth - 1);
+        }
+
+        let mut half = s.len() / 2;
+        while !s.is_char_boundary(half) {
+            half += 1;
+        }
+
+        r.delete(..half);
+        r.shrink_to_fit();
+
+        let shrunk = r.tree_stats();
+
+        assert!(shrunk.leaf_count <= stats.leaf_count);
+        assert!(shrunk.depth <= stats.depth);
+    }
+}
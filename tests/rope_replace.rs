@@ -1,4 +1,6 @@
-use crop::Rope;
+use std::panic;
+
+use crop::{Change, Delta, Error, LineEnding, Rope};
 use rand::Rng;
 
 mod common;
@@ -156,6 +158,25 @@ fn insert_small_random() {
     assert_eq!(string, rope);
 }
 
+#[test]
+fn write_fmt() {
+    use core::fmt::Write;
+
+    let mut r = Rope::from("foo\r");
+    let mut s = String::from("foo\r");
+
+    for i in 0..100 {
+        write!(r, "\n{i}: {:?}\r", "ƒoo").unwrap();
+        write!(s, "\n{i}: {:?}\r", "ƒoo").unwrap();
+    }
+
+    writeln!(r).unwrap();
+    writeln!(s).unwrap();
+
+    r.assert_invariants();
+    assert_eq!(r, s);
+}
+
 #[cfg_attr(miri, ignore)]
 #[test]
 fn insert_random() {
@@ -403,3 +424,427 @@ fn fix_crlf_4() {
 
     assert_eq!(r, "\r\n\r\n\r\n\r\n\r\n\r\n\r\n\r\n\r\n\r\n\r\n");
 }
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn depth_stays_within_bound() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let mut r = Rope::from(s);
+
+        assert!(r.depth() <= Rope::max_depth(r.byte_len()));
+
+        for _ in 0..50 {
+            let start = rng.random_range(0..=r.byte_len());
+            let end = rng.random_range(start..=r.byte_len());
+
+            if rng.random_bool(0.5) {
+                r.delete(start..end);
+            } else {
+                let insert = r.byte_slice(start..end).to_string();
+                r.insert(start, insert);
+            }
+
+            assert!(r.depth() <= Rope::max_depth(r.byte_len()));
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn depth_limit_is_maintained() {
+    let mut rng = rand::rng();
+
+    for depth_limit in 0..3 {
+        let max_byte_len = Rope::max_byte_len(depth_limit);
+
+        let mut r = Rope::new();
+        r.set_depth_limit(Some(depth_limit));
+
+        for _ in 0..100 {
+            let start = rng.random_range(0..=r.byte_len());
+            let end = rng.random_range(start..=r.byte_len());
+            let text = "a".repeat(rng.random_range(0..=max_byte_len / 4 + 1));
+
+            let new_len = r.byte_len() - (end - start) + text.len();
+
+            let res = r.try_replace(start..end, &text);
+
+            if new_len > max_byte_len {
+                assert_eq!(
+                    res,
+                    Err(Error::DepthLimitExceeded {
+                        byte_len: new_len,
+                        max_byte_len
+                    })
+                );
+            } else {
+                assert!(res.is_ok());
+            }
+
+            r.assert_invariants();
+            assert!(r.byte_len() <= max_byte_len);
+            assert!(r.depth() <= depth_limit);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn depth_limit_exceeded() {
+    let mut r = Rope::from("a".repeat(Rope::max_byte_len(1)));
+    r.set_depth_limit(Some(1));
+    r.insert(0, "a");
+}
+
+#[test]
+fn depth_limit_exceeded_leaves_rope_untouched() {
+    let max_byte_len = Rope::max_byte_len(1);
+
+    let text = "a\n".repeat(max_byte_len / 2) + &"a".repeat(max_byte_len % 2);
+    let mut r = Rope::from(text.as_str());
+    r.set_depth_limit(Some(1));
+
+    // The deletion at the end fits, but the insertion at the start would
+    // then make the `Rope` too long.
+    let mut delta = Delta::new();
+    delta.insert(0, "bb").delete(text.len() - 1..text.len());
+
+    let edits = [(0..0, "bb"), (text.len() - 1..text.len(), "")];
+
+    let edit_fns: [&dyn Fn(&mut Rope); 4] = [
+        &|r| r.apply_delta(&delta),
+        &|r| r.apply_edits(&edits),
+        &|r| r.replace_all('\n', "\r\n"),
+        &|r| r.normalize_line_endings(LineEnding::Crlf),
+    ];
+
+    for edit in edit_fns {
+        let res =
+            panic::catch_unwind(panic::AssertUnwindSafe(|| edit(&mut r)));
+        assert!(res.is_err());
+        assert_eq!(r, text);
+    }
+}
+
+#[test]
+fn replace_all_0() {
+    let mut r = Rope::from("aaaa");
+    r.replace_all("aa", "b");
+    assert_eq!(r, "bb");
+
+    let mut r = Rope::from("ƒoo");
+    r.replace_all("", "-");
+    assert_eq!(r, "-ƒ-o-o-");
+
+    let mut r = Rope::from("foo\nbar\n");
+    r.replace_all("\n", "\r\n");
+    assert_eq!(r, "foo\r\nbar\r\n");
+    r.assert_invariants();
+}
+
+#[test]
+fn replace_all_keeps_settings() {
+    let mut r = Rope::from("a b c");
+    r.set_bom(true);
+    r.set_depth_limit(Some(2));
+
+    r.replace_all(' ', "");
+    assert_eq!(r, "abc");
+    assert!(r.has_bom());
+    assert_eq!(r.depth_limit(), Some(2));
+
+    // No matches leaves the `Rope` untouched.
+    r.replace_all(char::is_whitespace, "-");
+    assert_eq!(r, "abc");
+    r.assert_invariants();
+}
+
+#[test]
+#[should_panic]
+fn replace_all_depth_limit_exceeded() {
+    let mut r = Rope::from("a".repeat(Rope::max_byte_len(1)));
+    r.set_depth_limit(Some(1));
+    r.replace_all("a", "aa");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn replace_all_shares_untouched_nodes() {
+    let half = LARGE.len() / 2;
+    let r = Rope::from(format!("{}🦀{}", &LARGE[..half], &LARGE[half..]));
+
+    let mut replaced = r.clone();
+    replaced.replace_all('🦀', "🐸");
+    assert_eq!(replaced, format!("{}🐸{}", &LARGE[..half], &LARGE[half..]));
+
+    // Only the leaves around the match are copied, everything else is still
+    // shared with the original.
+    assert!(replaced.shared_byte_len(&r) >= LARGE.len() - 4096);
+    assert_eq!(r.shared_byte_len(&r), r.byte_len());
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn replace_all_random() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..5 {
+            let start = rng.random_range(0..s.len() - 4);
+            let pattern = &s[start..start + rng.random_range(1..=4)];
+            let replacement =
+                ["", "ƒoo", "\n", pattern][rng.random_range(0..4)];
+
+            let mut replaced = r.clone();
+            replaced.replace_all(pattern, replacement);
+
+            assert_eq!(replaced, s.replace(pattern, replacement));
+            replaced.assert_invariants();
+
+            // The original is left untouched.
+            assert_eq!(r, s);
+        }
+    }
+}
+
+#[test]
+fn edits_return_change() {
+    let mut r = Rope::from("Hello World!");
+
+    let change = r.insert(5, ",");
+    assert_eq!(change, Change { byte_range: 5..5, inserted_len: 1 });
+
+    let change = r.replace(7..12, "Earth 🌎");
+    assert_eq!(change, Change { byte_range: 7..12, inserted_len: 10 });
+    assert_eq!(r.byte_slice(change.inserted_byte_range()), "Earth 🌎");
+
+    let change = r.delete(7..);
+    assert_eq!(change, Change { byte_range: 7..18, inserted_len: 0 });
+    assert_eq!(r, "Hello, ");
+
+    assert_eq!(
+        r.try_insert(7, "🐸"),
+        Ok(Change { byte_range: 7..7, inserted_len: 4 })
+    );
+    assert!(r.try_delete(8..).is_err());
+}
+
+#[test]
+fn apply_edits_in_any_order() {
+    let mut r = Rope::from("Hello World!");
+
+    r.apply_edits(&[
+        (12..12, "!"),
+        (5..5, ","),
+        (0..5, "Hi"),
+        (5..5, " there"),
+        (6..11, "Earth"),
+    ]);
+
+    r.assert_invariants();
+    assert_eq!(r, "Hi, there Earth!!");
+}
+
+#[test]
+#[should_panic]
+fn apply_edits_overlapping() {
+    let mut r = Rope::from("Hello World!");
+    r.apply_edits(&[(0..6, ""), (5..7, "")]);
+}
+
+#[test]
+fn apply_edits_keeps_settings() {
+    let mut r = Rope::from("foo\nbar");
+    r.set_bom(true);
+    r.set_depth_limit(Some(4));
+
+    r.apply_edits(&[(4..7, "baz\n"), (0..0, "")]);
+    assert_eq!(r, "foo\nbaz\n");
+    assert_eq!(r.line_len(), 2);
+    assert!(r.has_bom());
+    assert_eq!(r.depth_limit(), Some(4));
+
+    r.apply_edits::<&str>(&[]);
+    assert_eq!(r, "foo\nbaz\n");
+    r.assert_invariants();
+}
+
+#[test]
+#[should_panic]
+fn apply_edits_depth_limit_exceeded() {
+    let mut r = Rope::from("a".repeat(Rope::max_byte_len(1)));
+    r.set_depth_limit(Some(1));
+    r.apply_edits(&[(0..0, "a")]);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn apply_edits_shares_untouched_nodes() {
+    let r = Rope::from(LARGE);
+
+    let half = LARGE.len() / 2;
+    let mut edited = r.clone();
+    edited.apply_edits(&[(half + 8..half + 16, "🦀"), (half..half, "🐸")]);
+    assert_eq!(edited.byte_len(), LARGE.len() + 2 * "🦀".len() - 8);
+
+    // Only the leaves around the edits are copied, everything else is still
+    // shared with the original.
+    assert!(edited.shared_byte_len(&r) >= LARGE.len() * 9 / 10);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn apply_edits_random() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM] {
+        let mut edits = Vec::new();
+
+        let mut offset = 0;
+
+        while offset < s.len() {
+            let mut start =
+                rng.random_range(offset..=s.len().min(offset + 64));
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end = rng.random_range(start..=s.len().min(start + 8));
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let text = ["", "a", "ƒoo", "\r\n", "🐸"][rng.random_range(0..5)];
+
+            edits.push((start..end, text));
+
+            offset = end + 1;
+        }
+
+        let mut string = s.to_owned();
+
+        for (byte_range, text) in edits.iter().rev() {
+            string.replace_range(byte_range.clone(), text);
+        }
+
+        // The order of the edits shouldn't matter.
+        for idx in (1..edits.len()).rev() {
+            edits.swap(idx, rng.random_range(0..=idx));
+        }
+
+        let mut r = Rope::from(s);
+        r.apply_edits(&edits);
+        r.assert_invariants();
+        assert_eq!(r, string);
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn split_off_random() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        for _ in 0..5 {
+            let mut at = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(at) {
+                at += 1;
+            }
+
+            let mut r = Rope::from(s);
+            let split = r.split_off(at);
+
+            r.assert_invariants();
+            split.assert_invariants();
+
+            assert_eq!(r, s[..at]);
+            assert_eq!(split, s[at..]);
+        }
+    }
+}
+
+#[test]
+fn split_off_at_ends() {
+    let mut r = Rope::from("foo\n");
+
+    let split = r.split_off(4);
+    assert_eq!(r, "foo\n");
+    assert!(split.is_empty());
+
+    let split = r.split_off(0);
+    assert!(r.is_empty());
+    assert_eq!(split, "foo\n");
+    assert_eq!(split.line_len(), 1);
+}
+
+#[test]
+fn truncate_0() {
+    let mut r = Rope::from("Hello\r\nWorld!");
+
+    r.truncate(7);
+    r.assert_invariants();
+    assert_eq!(r, "Hello\r\n");
+
+    r.truncate(5);
+    r.assert_invariants();
+    assert_eq!(r, "Hello");
+    assert_eq!(r.line_len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn truncate_out_of_bounds() {
+    let mut r = Rope::from("foo");
+    r.truncate(4);
+}
+
+#[test]
+fn shrink_to_fit_empty() {
+    let mut r = Rope::from(LARGE);
+    r.delete(..);
+    r.shrink_to_fit();
+    r.assert_invariants();
+    assert_eq!(r, "");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn shrink_to_fit_random() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let mut r = Rope::from(s);
+        let mut s = String::from(s);
+
+        for _ in 0..s.len().min(500) {
+            let start = rng.random_range(0..=s.len());
+            let end = (start + rng.random_range(0..=4)).min(s.len());
+
+            if !s.is_char_boundary(start) || !s.is_char_boundary(end) {
+                continue;
+            }
+
+            r.delete(start..end);
+            s.replace_range(start..end, "");
+        }
+
+        let before = r.mem_usage();
+
+        r.shrink_to_fit();
+        r.assert_invariants();
+
+        let after = r.mem_usage();
+
+        assert_eq!(r, s);
+        assert_eq!(r.line_len(), Rope::from(s.as_str()).line_len());
+        assert!(after.total_bytes() <= before.total_bytes());
+
+        // Shrinking a `Rope` that's already been shrunk is a no-op.
+        r.shrink_to_fit();
+        assert_eq!(r.mem_usage(), after);
+    }
+}
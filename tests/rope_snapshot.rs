@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use crop::{Rope, RopeSnapshot};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn snapshot_is_send_and_sync() {
+    assert_send_sync::<RopeSnapshot>();
+}
+
+#[test]
+fn snapshot_unaffected_by_edits() {
+    let mut r = Rope::from(LARGE);
+
+    let snapshot = r.snapshot();
+
+    r.insert(0, CURSED_LIPSUM);
+    r.delete(r.byte_len() / 2..r.byte_len() / 2 + 1);
+    r.replace(..10, "");
+
+    snapshot.assert_invariants();
+    assert_eq!(snapshot.as_slice(), LARGE);
+    assert_eq!(snapshot.into_rope(), LARGE);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn snapshot_read_from_threads_while_editing() {
+    let mut r = Rope::from(LARGE);
+
+    let snapshot = Arc::new(r.snapshot());
+
+    let handles = (0..4)
+        .map(|_| {
+            let snapshot = Arc::clone(&snapshot);
+            std::thread::spawn(move || {
+                snapshot.lines().map(|line| line.byte_len()).sum::<usize>()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut rng = rand::rng();
+
+    for _ in 0..100 {
+        let offset = rng.random_range(0..=r.byte_len());
+        r.insert(offset, "a");
+    }
+
+    let expected = LARGE.lines().map(str::len).sum::<usize>();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+
+    assert_eq!(*snapshot.as_rope(), LARGE);
+}
@@ -0,0 +1,163 @@
+mod common;
+
+#[cfg(feature = "ropey-compat")]
+mod tests {
+    use crop::compat::ropey::Rope;
+    use rand::Rng;
+
+    use crate::common::{LARGE, MEDIUM, SMALL, TINY};
+
+    #[test]
+    fn ropey_compat_empty() {
+        let r = Rope::new();
+        assert_eq!(r.len_bytes(), 0);
+        assert_eq!(r.len_chars(), 0);
+        assert_eq!(r.len_lines(), 1);
+        assert_eq!(r.line(0), "");
+        assert_eq!(r.lines().count(), 1);
+        assert_eq!(r.get_line(1), None);
+        assert_eq!(r.get_char(0), None);
+    }
+
+    #[test]
+    fn ropey_compat_lines() {
+        let r = Rope::from_str("aaa\nƒƒƒ\r\nccc\n");
+
+        assert_eq!(r.len_lines(), 4);
+
+        let lines = r.lines().collect::<Vec<_>>();
+        assert_eq!(lines, ["aaa\n", "ƒƒƒ\r\n", "ccc\n", ""]);
+
+        let lines = r.lines_at(2).collect::<Vec<_>>();
+        assert_eq!(lines, ["ccc\n", ""]);
+
+        let lines = r.lines().rev().collect::<Vec<_>>();
+        assert_eq!(lines, ["", "ccc\n", "ƒƒƒ\r\n", "aaa\n"]);
+
+        assert_eq!(r.lines_at(4).count(), 0);
+    }
+
+    #[test]
+    fn ropey_compat_byte_to_char_mid_char() {
+        let r = Rope::from_str("aƒb");
+        assert_eq!(r.byte_to_char(1), 1);
+        assert_eq!(r.byte_to_char(2), 1);
+        assert_eq!(r.byte_to_char(3), 2);
+    }
+
+    #[allow(clippy::reversed_empty_ranges)]
+    #[test]
+    fn ropey_compat_get_slice() {
+        let r = Rope::from_str("Hello Wörld");
+        assert_eq!(r.get_slice(6..).unwrap(), "Wörld");
+        assert_eq!(r.get_slice(6..12), None);
+        assert_eq!(r.get_slice(7..6), None);
+    }
+
+    /// Tests the methods of `crop::compat::ropey::Rope` against their
+    /// counterparts on `ropey::Rope`.
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn ropey_compat_random() {
+        let mut rng = rand::rng();
+
+        for s in [TINY, SMALL, MEDIUM, LARGE] {
+            let crop = Rope::from_str(s);
+            let ropey = ropey::Rope::from_str(s);
+
+            assert_eq!(crop.len_bytes(), ropey.len_bytes());
+            assert_eq!(crop.len_chars(), ropey.len_chars());
+            assert_eq!(crop.len_lines(), ropey.len_lines());
+
+            for _ in 0..100 {
+                let char_idx = rng.random_range(0..=ropey.len_chars());
+                assert_eq!(
+                    crop.char_to_byte(char_idx),
+                    ropey.char_to_byte(char_idx)
+                );
+                assert_eq!(
+                    crop.char_to_line(char_idx),
+                    ropey.char_to_line(char_idx)
+                );
+
+                let byte_idx = rng.random_range(0..=ropey.len_bytes());
+                assert_eq!(
+                    crop.byte_to_char(byte_idx),
+                    ropey.byte_to_char(byte_idx)
+                );
+                assert_eq!(
+                    crop.byte_to_line(byte_idx),
+                    ropey.byte_to_line(byte_idx)
+                );
+
+                let line_idx = rng.random_range(0..ropey.len_lines());
+                assert_eq!(
+                    crop.line_to_byte(line_idx),
+                    ropey.line_to_byte(line_idx)
+                );
+                assert_eq!(
+                    crop.line_to_char(line_idx),
+                    ropey.line_to_char(line_idx)
+                );
+                assert_eq!(
+                    crop.line(line_idx),
+                    ropey.line(line_idx).to_string().as_str()
+                );
+
+                let start = rng.random_range(0..=ropey.len_chars());
+                let end = rng.random_range(
+                    start..=(start + 1000).min(ropey.len_chars()),
+                );
+                let crop_slice = crop.slice(start..end);
+                let ropey_slice = ropey.slice(start..end);
+                assert_eq!(crop_slice, ropey_slice.to_string().as_str());
+                assert_eq!(crop_slice.len_lines(), ropey_slice.len_lines());
+
+                let lines = crop_slice.lines().map(|l| l.to_string());
+                assert!(lines.eq(ropey_slice.lines().map(|l| l.to_string())));
+            }
+        }
+    }
+
+    /// Applies the same random edits to a `crop::compat::ropey::Rope` and to
+    /// a `ropey::Rope`, checking that they always have the same contents.
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn ropey_compat_random_edits() {
+        let mut rng = rand::rng();
+
+        let mut crop = Rope::from_str(MEDIUM);
+        let mut ropey = ropey::Rope::from_str(MEDIUM);
+
+        for _ in 0..100 {
+            let start = rng.random_range(0..=ropey.len_chars());
+            let end =
+                rng.random_range(start..=(start + 50).min(ropey.len_chars()));
+            crop.remove(start..end);
+            ropey.remove(start..end);
+
+            let char_idx = rng.random_range(0..=ropey.len_chars());
+            crop.insert(char_idx, "ƒoo\n");
+            ropey.insert(char_idx, "ƒoo\n");
+
+            let char_idx = rng.random_range(0..=ropey.len_chars());
+            crop.insert_char(char_idx, 'ö');
+            ropey.insert_char(char_idx, 'ö');
+
+            assert_eq!(crop, ropey.to_string().as_str());
+            assert_eq!(crop.len_lines(), ropey.len_lines());
+        }
+
+        let char_idx = rng.random_range(0..=ropey.len_chars());
+        let crop_right = crop.split_off(char_idx);
+        let ropey_right = ropey.split_off(char_idx);
+        assert_eq!(crop, ropey.to_string().as_str());
+        assert_eq!(crop_right, ropey_right.to_string().as_str());
+
+        crop.append(crop_right);
+        ropey.append(ropey_right);
+        assert_eq!(crop, ropey.to_string().as_str());
+
+        crop.as_crop().assert_invariants();
+    }
+}
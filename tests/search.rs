@@ -0,0 +1,432 @@
+use crop::Rope;
+use rand::{Rng, rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+/// Returns a random, non-empty substring of `s` about `max_len` bytes long at
+/// most.
+fn random_pattern<'a>(
+    s: &'a str,
+    max_len: usize,
+    rng: &mut impl Rng,
+) -> &'a str {
+    let boundaries = s.char_indices().map(|(idx, _)| idx).collect::<Vec<_>>();
+    let start = boundaries[rng.random_range(0..boundaries.len())];
+    let mut end = (start + rng.random_range(1..=max_len)).min(s.len());
+    while !s.is_char_boundary(end) {
+        end += 1;
+    }
+    &s[start..end]
+}
+
+#[test]
+fn find_empty() {
+    let r = Rope::from("foo");
+    assert_eq!(r.find(""), Some(0));
+    assert_eq!(r.rfind(""), Some(3));
+    assert_eq!(Rope::new().find("foo"), None);
+    assert_eq!(Rope::new().rfind("foo"), None);
+}
+
+#[test]
+fn find_overlapping() {
+    let s = "aaaabaaaab";
+    let r = Rope::from(s);
+
+    for pattern in ["a", "aa", "aaa", "aab", "aaaab", "baaaab", "ab", "b"] {
+        assert_eq!(r.find(pattern), s.find(pattern), "{pattern:?}");
+        assert_eq!(r.rfind(pattern), s.rfind(pattern), "{pattern:?}");
+    }
+}
+
+#[test]
+fn find_longer_than_rope() {
+    let r = Rope::from("foo");
+    assert_eq!(r.find("foobar"), None);
+    assert_eq!(r.rfind("foobar"), None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn find_random() {
+    let mut rng = rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let pattern = random_pattern(s, 64, &mut rng);
+            assert_eq!(r.find(pattern), s.find(pattern), "{pattern:?}");
+            assert_eq!(r.rfind(pattern), s.rfind(pattern), "{pattern:?}");
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn find_random_slices() {
+    let mut rng = rng();
+
+    for s in [TINY, SMALL, MEDIUM, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let mut start = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end = rng.random_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+            let str_slice = &s[start..end];
+
+            // Use patterns from the whole text, so that some of them only
+            // occur outside of the slice.
+            let pattern = random_pattern(s, 32, &mut rng);
+
+            assert_eq!(slice.find(pattern), str_slice.find(pattern));
+            assert_eq!(slice.rfind(pattern), str_slice.rfind(pattern));
+        }
+    }
+}
+
+/// The byte ranges of the matches of `pattern` in `s`.
+fn match_ranges(s: &str, pattern: &str) -> Vec<std::ops::Range<usize>> {
+    s.match_indices(pattern).map(|(idx, m)| idx..idx + m.len()).collect()
+}
+
+#[test]
+fn find_iter_empty_pattern() {
+    for s in ["", "a", "ƒoo", "🐸\r\n"] {
+        let r = Rope::from(s);
+        assert_eq!(r.find_iter("").collect::<Vec<_>>(), match_ranges(s, ""));
+    }
+}
+
+#[test]
+fn find_iter_overlapping() {
+    let s = "aaaaabaaaab";
+    let r = Rope::from(s);
+
+    for pattern in ["a", "aa", "aaa", "aab", "aaaab", "baaaab", "ab", "b"] {
+        assert_eq!(
+            r.find_iter(pattern).collect::<Vec<_>>(),
+            match_ranges(s, pattern),
+            "{pattern:?}"
+        );
+    }
+}
+
+#[test]
+fn find_iter_char_patterns() {
+    let s = "a ƒoo,\tb🐸r ";
+    let r = Rope::from(s);
+
+    assert_eq!(
+        r.find_iter('o').collect::<Vec<_>>(),
+        s.match_indices('o')
+            .map(|(idx, m)| idx..idx + m.len())
+            .collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        r.find_iter(char::is_whitespace).collect::<Vec<_>>(),
+        s.match_indices(char::is_whitespace)
+            .map(|(idx, m)| idx..idx + m.len())
+            .collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        r.find_iter(['ƒ', '🐸']).collect::<Vec<_>>(),
+        s.match_indices(['ƒ', '🐸'])
+            .map(|(idx, m)| idx..idx + m.len())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn find_iter_random() {
+    let mut rng = rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let pattern = random_pattern(s, 8, &mut rng);
+
+            assert_eq!(
+                r.find_iter(pattern).collect::<Vec<_>>(),
+                match_ranges(s, pattern),
+                "{pattern:?}"
+            );
+
+            let half = s.len() / 2;
+            let mut start = rng.random_range(0..=half);
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let slice = r.byte_slice(start..);
+
+            assert_eq!(
+                slice.find_iter(pattern).collect::<Vec<_>>(),
+                match_ranges(&s[start..], pattern),
+                "{pattern:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn starts_ends_with() {
+    let r = Rope::from("ƒoo bär 🐸");
+
+    assert!(r.starts_with(""));
+    assert!(r.ends_with(""));
+    assert!(r.starts_with('ƒ'));
+    assert!(r.ends_with('🐸'));
+    assert!(!r.starts_with('f'));
+    assert!(r.starts_with(&String::from("ƒoo")));
+    assert!(r.ends_with(&Rope::from("bär 🐸")));
+    assert!(!r.ends_with("ƒoo bär 🐸!"));
+    assert!(!r.starts_with("!ƒoo bär 🐸"));
+
+    let s = r.byte_slice(2..4);
+    assert!(s.starts_with("oo"));
+    assert!(!s.starts_with("oo "));
+    assert!(Rope::new().starts_with(""));
+    assert!(!Rope::new().ends_with('a'));
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn starts_ends_with_random() {
+    let mut rng = rng();
+
+    for s in [CURSED_LIPSUM, TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..50 {
+            let pattern = random_pattern(s, 1000, &mut rng);
+
+            // A pattern stored in a rope with a different chunk layout.
+            let pattern_rope = Rope::from(pattern);
+
+            let start = s.find(pattern).unwrap();
+            let end = start + pattern.len();
+
+            for (slice, text) in [
+                (r.byte_slice(start..), &s[start..]),
+                (r.byte_slice(..end), &s[..end]),
+                (r.byte_slice(..), s),
+            ] {
+                assert_eq!(
+                    slice.starts_with(pattern),
+                    text.starts_with(pattern)
+                );
+                assert_eq!(slice.ends_with(pattern), text.ends_with(pattern));
+                assert_eq!(
+                    slice.starts_with(&pattern_rope),
+                    text.starts_with(pattern)
+                );
+                assert_eq!(
+                    slice.ends_with(pattern_rope.byte_slice(..)),
+                    text.ends_with(pattern)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn trim() {
+    let r = Rope::from(" \t\u{3000}ƒoo bär\u{2029}\r\n");
+    let s = r.byte_slice(..);
+
+    assert_eq!(s.trim(), "ƒoo bär");
+    assert_eq!(s.trim_start(), "ƒoo bär\u{2029}\r\n");
+    assert_eq!(s.trim_end(), " \t\u{3000}ƒoo bär");
+
+    let r = Rope::from("ababfooab");
+    let s = r.byte_slice(..);
+
+    assert_eq!(s.trim_matches("ab"), "foo");
+    assert_eq!(s.trim_start_matches("aba"), "bfooab");
+    assert_eq!(s.trim_end_matches('b'), "ababfooa");
+    assert_eq!(s.trim_matches(['a', 'b']), "foo");
+    assert_eq!(s.trim_matches(|ch| ch != 'o'), "oo");
+    assert_eq!(s.trim_matches(""), "ababfooab");
+    assert_eq!(s.trim_matches(char::is_alphabetic), "");
+
+    assert_eq!(Rope::new().byte_slice(..).trim(), "");
+    assert_eq!(Rope::from("  \n ").byte_slice(..).trim_start(), "");
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn trim_random() {
+    let mut rng = rng();
+
+    for s in [CURSED_LIPSUM, TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        for (line, line_str) in r.raw_lines().zip(s.split_inclusive('\n')) {
+            assert_eq!(line.trim(), line_str.trim());
+            assert_eq!(line.trim_start(), line_str.trim_start());
+            assert_eq!(line.trim_end(), line_str.trim_end());
+        }
+
+        for _ in 0..50 {
+            let pattern = random_pattern(s, 10, &mut rng);
+            let start = s.find(pattern).unwrap();
+            let end = start + pattern.len();
+
+            let slice = r.byte_slice(start..);
+            let text = &s[start..];
+            assert_eq!(
+                slice.trim_start_matches(pattern),
+                text.trim_start_matches(pattern)
+            );
+
+            let slice = r.byte_slice(..end);
+            let text = &s[..end];
+            assert_eq!(
+                slice.trim_end_matches(pattern),
+                text.trim_end_matches(pattern)
+            );
+
+            let first = pattern.chars().next().unwrap();
+            assert_eq!(slice.trim_matches(first), text.trim_matches(first));
+        }
+    }
+}
+
+/// Checks that all the split iterators of `slice` yield the same sub-slices
+/// as the ones of `text`.
+#[track_caller]
+fn assert_splits_eq(slice: crop::RopeSlice<'_>, text: &str, pattern: &str) {
+    let split = slice.split(pattern).collect::<Vec<_>>();
+    assert_eq!(split, text.split(pattern).collect::<Vec<_>>());
+
+    let rsplit = slice.rsplit(pattern).collect::<Vec<_>>();
+    assert_eq!(rsplit, text.rsplit(pattern).collect::<Vec<_>>());
+
+    for n in [0, 1, 2, 5] {
+        let splitn = slice.splitn(n, pattern).collect::<Vec<_>>();
+        assert_eq!(splitn, text.splitn(n, pattern).collect::<Vec<_>>());
+
+        let rsplitn = slice.rsplitn(n, pattern).collect::<Vec<_>>();
+        assert_eq!(rsplitn, text.rsplitn(n, pattern).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn split() {
+    for (text, pattern) in [
+        ("foo, bar, , baz", ", "),
+        (", foo, ", ", "),
+        ("aaa", "aa"),
+        ("ƒoo bär", ""),
+        ("", ""),
+        ("", "foo"),
+        ("foo", "foo"),
+        ("foo", "bar"),
+    ] {
+        let r = Rope::from(text);
+        assert_splits_eq(r.byte_slice(..), text, pattern);
+    }
+
+    let r = Rope::from("a=b=c");
+    let mut splitn = r.splitn(2, "=");
+    assert_eq!(splitn.next().unwrap(), "a");
+    assert_eq!(splitn.next().unwrap(), "b=c");
+    assert_eq!(splitn.next(), None);
+    assert_eq!(r.rsplitn(2, "=").collect::<Vec<_>>(), ["c", "a=b"]);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn split_random() {
+    let mut rng = rng();
+
+    for s in [CURSED_LIPSUM, TINY, SMALL, MEDIUM] {
+        let r = Rope::from(s);
+
+        assert_splits_eq(r.byte_slice(..), s, "\n");
+
+        for _ in 0..20 {
+            let pattern = random_pattern(s, 5, &mut rng);
+            assert_splits_eq(r.byte_slice(..), s, pattern);
+        }
+    }
+}
+
+#[test]
+fn generic_patterns() {
+    let r = Rope::from("ƒoo, bär; baz");
+    let s = "ƒoo, bär; baz";
+
+    assert_eq!(r.find('ä'), s.find('ä'));
+    assert_eq!(r.rfind('o'), s.rfind('o'));
+    assert_eq!(r.find([',', ';']), s.find([',', ';']));
+    assert_eq!(r.rfind(&[',', ';'][..]), s.rfind(&[',', ';'][..]));
+    assert_eq!(r.find(char::is_whitespace), s.find(char::is_whitespace));
+    assert_eq!(r.rfind(|ch| ch == 'b'), s.rfind('b'));
+    assert_eq!(r.find(&Rope::from("bär")), Some(6));
+    assert_eq!(r.rfind(r.byte_slice(0..2)), Some(0));
+    assert_eq!(r.find('x'), None);
+
+    assert_eq!(
+        r.split([',', ';']).collect::<Vec<_>>(),
+        s.split([',', ';']).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        r.rsplit(char::is_whitespace).collect::<Vec<_>>(),
+        s.rsplit(char::is_whitespace).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        r.matches(char::is_alphabetic).collect::<Vec<_>>(),
+        s.matches(char::is_alphabetic).collect::<Vec<_>>()
+    );
+    assert_eq!(r.matches("").count(), s.matches("").count());
+    assert_eq!(
+        r.matches("a").collect::<Vec<_>>(),
+        s.matches("a").collect::<Vec<_>>()
+    );
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn generic_patterns_random() {
+    let mut rng = rng();
+
+    for s in [CURSED_LIPSUM, TINY, SMALL, MEDIUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let pattern = random_pattern(s, 5, &mut rng);
+            let ch = pattern.chars().next().unwrap();
+
+            assert_eq!(r.find(ch), s.find(ch));
+            assert_eq!(r.rfind(ch), s.rfind(ch));
+
+            assert_eq!(r.matches(pattern).count(), s.matches(pattern).count());
+
+            assert_eq!(
+                r.split(ch).collect::<Vec<_>>(),
+                s.split(ch).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                r.rsplitn(3, |c| c == ch).collect::<Vec<_>>(),
+                s.rsplitn(3, ch).collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -1,13 +1,14 @@
 #[cfg(feature = "serde")]
 mod tests {
     use crop::Rope;
+    use serde_test::Configure;
 
     #[test]
     fn ser_de_empty() {
         let rope = Rope::new();
 
         serde_test::assert_tokens(
-            &rope,
+            &rope.readable(),
             &[serde_test::Token::Seq { len: None }, serde_test::Token::SeqEnd],
         );
     }
@@ -20,7 +21,7 @@ mod tests {
         rope.insert(6, "ipsum");
 
         serde_test::assert_tokens(
-            &rope,
+            &rope.readable(),
             &[
                 serde_test::Token::Seq { len: None },
                 serde_test::Token::Str("lorem ipsum"),
@@ -38,7 +39,7 @@ mod tests {
         rope.delete(11..12);
 
         serde_test::assert_tokens(
-            &rope,
+            &rope.readable(),
             &[
                 serde_test::Token::Seq { len: None },
                 serde_test::Token::Str("lorem ipsum"),
@@ -56,7 +57,7 @@ mod tests {
         rope.insert(6, "ipsum");
 
         serde_test::assert_tokens(
-            &rope,
+            &rope.readable(),
             &[
                 serde_test::Token::Seq { len: None },
                 serde_test::Token::Str("lorem\nipsum"),
@@ -73,7 +74,7 @@ mod tests {
         rope.insert(7, "ipsum");
 
         serde_test::assert_tokens(
-            &rope,
+            &rope.readable(),
             &[
                 serde_test::Token::Seq { len: None },
                 serde_test::Token::Str("lorem\r\nipsum"),
@@ -81,4 +82,146 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn ser_empty_slice() {
+        let rope = Rope::from("lorem ipsum");
+
+        serde_test::assert_ser_tokens(
+            &rope.byte_slice(3..3).readable(),
+            &[serde_test::Token::Seq { len: None }, serde_test::Token::SeqEnd],
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "small_chunks", ignore)]
+    fn ser_slice_multiple_chunks() {
+        let mut rope = Rope::new();
+        rope.insert(0, "lorem dolor");
+        rope.insert(6, "ipsuma ");
+        rope.delete(11..12);
+
+        serde_test::assert_ser_tokens(
+            &rope.byte_slice(2..14).readable(),
+            &[
+                serde_test::Token::Seq { len: None },
+                serde_test::Token::Str("rem ipsum"),
+                serde_test::Token::Str(" do"),
+                serde_test::Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn ser_slice_matches_rope() {
+        let rope = Rope::from("lorem ipsum\ndolor sit amet\n".repeat(64));
+
+        for (start, end) in [(0, rope.byte_len()), (3, 500), (700, 1000)] {
+            let slice = rope.byte_slice(start..end);
+
+            let json = serde_json::to_string(&slice).unwrap();
+            assert_eq!(
+                json,
+                serde_json::to_string(&Rope::from(slice)).unwrap()
+            );
+
+            let rope: Rope = serde_json::from_str(&json).unwrap();
+            assert_eq!(rope, slice);
+        }
+    }
+
+    #[test]
+    fn de_readable_from_string() {
+        let rope = Rope::from("lorem ipsum");
+
+        for token in [
+            serde_test::Token::Str("lorem ipsum"),
+            serde_test::Token::BorrowedStr("lorem ipsum"),
+            serde_test::Token::String("lorem ipsum"),
+        ] {
+            serde_test::assert_de_tokens(&rope.clone().readable(), &[token]);
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn de_json_string() {
+        let text = "Hello\r\nWorld!\n".repeat(1000);
+
+        let json = serde_json::to_string(&text).unwrap();
+
+        let rope: Rope = serde_json::from_str(&json).unwrap();
+        rope.assert_invariants();
+        assert_eq!(rope, text);
+    }
+
+    #[test]
+    fn ser_de_compact_empty() {
+        let rope = Rope::new();
+
+        serde_test::assert_tokens(
+            &rope.clone().compact(),
+            &[
+                serde_test::Token::Seq { len: Some(0) },
+                serde_test::Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "small_chunks", ignore)]
+    fn ser_de_compact_multiple_chunks() {
+        let mut rope = Rope::new();
+        rope.insert(0, "lorem dolor");
+        rope.insert(6, "ipsuma ");
+        rope.delete(11..12);
+
+        serde_test::assert_tokens(
+            &rope.clone().compact(),
+            &[
+                serde_test::Token::Seq { len: Some(2) },
+                serde_test::Token::Bytes(b"lorem ipsum"),
+                serde_test::Token::Bytes(b" dolor"),
+                serde_test::Token::SeqEnd,
+            ],
+        );
+
+        serde_test::assert_ser_tokens(
+            &rope.byte_slice(2..14).compact(),
+            &[
+                serde_test::Token::Seq { len: Some(2) },
+                serde_test::Token::Bytes(b"rem ipsum"),
+                serde_test::Token::Bytes(b" do"),
+                serde_test::Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn de_compact_borrowed_and_owned() {
+        let rope = Rope::from("lorem ipsum dolor");
+
+        serde_test::assert_de_tokens(
+            &rope.clone().compact(),
+            &[
+                serde_test::Token::Seq { len: Some(3) },
+                serde_test::Token::BorrowedBytes(b"lorem "),
+                serde_test::Token::ByteBuf(b"ipsum "),
+                serde_test::Token::BorrowedStr("dolor"),
+                serde_test::Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn de_compact_invalid_utf8() {
+        serde_test::assert_de_tokens_error::<serde_test::Compact<Rope>>(
+            &[
+                serde_test::Token::Seq { len: Some(1) },
+                serde_test::Token::Bytes(&[0xF0, 0x9F, 0x8C]),
+            ],
+            "invalid value: byte array, expected a string or a UTF-8 byte \
+             buffer",
+        );
+    }
 }
@@ -138,3 +138,45 @@ fn byte_of_line_random() {
         }
     }
 }
+
+/// Tests `RopeSlice::as_str()` and `RopeSlice::to_cow()` on a bunch of random
+/// RopeSlices over different texts.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn as_str_random() {
+    use std::borrow::Cow;
+
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..100 {
+            let mut start = rng.random_range(0..=r.byte_len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end = rng.random_range(start..=start + 8).min(s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+
+            let chunks = slice.chunks().filter(|c| !c.is_empty()).count();
+
+            match slice.as_str() {
+                Some(str) => {
+                    assert!(chunks <= 1);
+                    assert_eq!(str, &s[start..end]);
+                },
+                None => assert!(chunks > 1),
+            }
+
+            let cow = slice.to_cow();
+            assert_eq!(cow, &s[start..end]);
+            assert_eq!(matches!(cow, Cow::Borrowed(_)), chunks <= 1);
+        }
+    }
+}
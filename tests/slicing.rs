@@ -220,3 +220,162 @@ fn rope_from_slice() {
         }
     }
 }
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn byte_slices_cmp_random() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..100 {
+            let mut ranges = [0, 1].map(|_| {
+                let mut start = rng.random_range(0..=s.len());
+                while !s.is_char_boundary(start) {
+                    start += 1;
+                }
+                let mut end = rng.random_range(start..=s.len());
+                while !s.is_char_boundary(end) {
+                    end += 1;
+                }
+                start..end
+            });
+
+            // Make the slices share a prefix once in a while.
+            if rng.random_bool(0.5) {
+                ranges[1].start = ranges[0].start.min(ranges[1].end);
+            }
+
+            let [left, right] = ranges;
+
+            let (left_str, right_str) = (&s[left.clone()], &s[right.clone()]);
+            let (left_slice, right_slice) =
+                (r.byte_slice(left), r.byte_slice(right));
+
+            let expected = left_str.cmp(right_str);
+
+            assert_eq!(left_slice.cmp(&right_slice), expected);
+            assert_eq!(left_slice.partial_cmp(right_str), Some(expected));
+            assert_eq!(left_str.partial_cmp(&right_slice), Some(expected));
+
+            let left_rope = Rope::from(left_slice);
+            let right_rope = Rope::from(right_str);
+
+            assert_eq!(left_rope.cmp(&right_rope), expected);
+            assert_eq!(left_rope.partial_cmp(&right_slice), Some(expected));
+            assert_eq!(left_slice.partial_cmp(&right_rope), Some(expected));
+        }
+    }
+}
+
+/// A hasher recording every call to `write()`, so that two values only hash
+/// the same if they write the same bytes in the same pieces.
+#[derive(Default)]
+struct RecordingHasher(Vec<Vec<u8>>);
+
+impl core::hash::Hasher for RecordingHasher {
+    fn finish(&self) -> u64 {
+        unimplemented!()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.push(bytes.to_owned());
+    }
+}
+
+fn recorded_hash(value: impl core::hash::Hash) -> Vec<Vec<u8>> {
+    let mut hasher = RecordingHasher::default();
+    value.hash(&mut hasher);
+    hasher.0
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn hash_is_independent_of_chunks() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let mut start = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end = rng.random_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+            let expected = recorded_hash(Rope::from(&s[start..end]));
+
+            assert_eq!(recorded_hash(slice), expected);
+
+            // Build the same text out of chunks of different sizes by
+            // inserting it piece by piece from the back.
+            let mut edited = Rope::new();
+            let mut piece_end = end;
+            while piece_end > start {
+                let mut piece_start = piece_end
+                    - rng.random_range(1..=512).min(piece_end - start);
+                while !s.is_char_boundary(piece_start) {
+                    piece_start -= 1;
+                }
+                edited.insert(0, &s[piece_start..piece_end]);
+                piece_end = piece_start;
+            }
+
+            assert_eq!(recorded_hash(&edited), expected);
+        }
+    }
+
+    assert_ne!(
+        recorded_hash(Rope::from("ab")),
+        recorded_hash(Rope::from("a"))
+    );
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn eq_edited_clone() {
+    let mut rng = rand::rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let mut start = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end = rng.random_range(start..=s.len().min(start + 64));
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            // Replacing some text with itself unshares the nodes around it
+            // without changing the text.
+            let mut edited = r.clone();
+            edited.replace(start..end, &s[start..end]);
+            assert_eq!(edited, r);
+            assert_eq!(r, edited);
+            assert_eq!(edited.byte_slice(..), r.byte_slice(..));
+
+            let text = ["", "a", "\n", "🐸"][rng.random_range(0..4)];
+
+            let mut edited = r.clone();
+            edited.replace(start..end, text);
+
+            let expected = s[start..end] == *text;
+            assert_eq!(edited == r, expected);
+            assert_eq!(r.byte_slice(..) == edited, expected);
+        }
+
+        let slice = r.byte_slice(s.len() / 3..s.len() / 2);
+        assert_eq!(slice, r.byte_slice(s.len() / 3..s.len() / 2));
+    }
+}
@@ -191,10 +191,6 @@ mod tests {
         assert_eq!(143, s.byte_of_utf16_code_unit(111));
     }
 
-    // TODO: we should panic the given UTF-16 offset doesn't lie on a char
-    // boundary. Right now we just return the byte offset up to the previous
-    // char boundary.
-    #[ignore]
     #[should_panic]
     #[test]
     fn utf16_to_byte_5() {
@@ -202,8 +198,6 @@ mod tests {
         let _ = r.byte_of_utf16_code_unit(13);
     }
 
-    // TODO: see above.
-    #[ignore]
     #[should_panic]
     #[test]
     fn utf16_to_byte_6() {
@@ -211,4 +205,11 @@ mod tests {
         let s = r.byte_slice(..);
         let _ = s.byte_of_utf16_code_unit(13);
     }
+
+    #[should_panic]
+    #[test]
+    fn utf16_slice_not_char_boundary() {
+        let r = Rope::from("a𐐀b");
+        let _ = r.utf16_slice(..2);
+    }
 }
@@ -0,0 +1,39 @@
+mod common;
+
+#[cfg(feature = "validate")]
+mod tests {
+    use crop::Rope;
+    use rand::{Rng, rng};
+
+    use super::common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+    #[test]
+    fn validate_empty() {
+        let r = Rope::new();
+        r.validate();
+        r.byte_slice(..).validate();
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn validate_random_edits() {
+        let mut rng = rng();
+
+        for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+            let mut r = Rope::from(s);
+
+            for _ in 0..32 {
+                let start = rng.random_range(0..=r.byte_len());
+                let end = (start + rng.random_range(0..=32)).min(r.byte_len());
+
+                if !r.is_char_boundary(start) || !r.is_char_boundary(end) {
+                    continue;
+                }
+
+                r.replace(start..end, "Hello\r\nWorld");
+                r.validate();
+                r.byte_slice(start..start + "Hello\r\nWorld".len()).validate();
+            }
+        }
+    }
+}
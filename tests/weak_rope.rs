@@ -0,0 +1,88 @@
+mod common;
+
+use common::LARGE;
+use crop::Rope;
+
+#[test]
+fn weak_upgrade_while_alive() {
+    let r = Rope::from(LARGE);
+    let weak = r.downgrade();
+    let upgraded = weak.upgrade().unwrap();
+    upgraded.assert_invariants();
+    assert_eq!(upgraded, r);
+    assert_eq!(upgraded.line_len(), r.line_len());
+}
+
+#[test]
+fn weak_upgrade_after_drop() {
+    let r = Rope::from(LARGE);
+    let weak = r.downgrade();
+    let clone = weak.clone();
+    drop(r);
+    assert!(weak.upgrade().is_none());
+    assert!(!clone.is_alive());
+}
+
+#[test]
+fn weak_upgrade_after_edit() {
+    let mut r = Rope::from(LARGE);
+    let weak = r.downgrade();
+    r.insert(0, "Hello");
+    r.assert_invariants();
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn weak_doesnt_see_edits() {
+    let mut r = Rope::from(LARGE);
+    let snapshot = r.clone();
+    let weak = r.downgrade();
+
+    r.insert(0, "Hello");
+    r.delete(LARGE.len() / 2..LARGE.len());
+
+    let upgraded = weak.upgrade().unwrap();
+    upgraded.assert_invariants();
+    assert_eq!(upgraded, LARGE);
+
+    drop(snapshot);
+    drop(upgraded);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn weak_edit_upgraded() {
+    let r = Rope::from(LARGE);
+    let weak = r.downgrade();
+
+    let mut upgraded = weak.upgrade().unwrap();
+    upgraded.replace(..LARGE.len() / 2, "Hello");
+    upgraded.assert_invariants();
+
+    assert_eq!(r, LARGE);
+    assert_eq!(weak.upgrade().unwrap(), LARGE);
+}
+
+#[test]
+fn weak_across_threads() {
+    let r = Rope::from(LARGE);
+    let weak = r.downgrade();
+
+    let handles = (0..4)
+        .map(|_| {
+            let weak = weak.clone();
+            std::thread::spawn(move || {
+                let mut r = weak.upgrade().unwrap();
+                r.insert(0, "Hello");
+                r.byte_len()
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), LARGE.len() + 5);
+    }
+
+    drop(r);
+    assert!(weak.upgrade().is_none());
+}
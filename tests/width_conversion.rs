@@ -0,0 +1,138 @@
+mod common;
+
+#[cfg(feature = "width-metric")]
+mod tests {
+    use crop::Rope;
+    use rand::Rng;
+    use unicode_width::UnicodeWidthChar;
+
+    use crate::common::{CURSED_LIPSUM, MEDIUM, TEXT_EMOJI};
+
+    /// The width of `s` computed by summing the widths of its chars.
+    fn width(s: &str) -> usize {
+        s.chars().map(|ch| ch.width().unwrap_or(0)).sum()
+    }
+
+    #[test]
+    fn width_0() {
+        let r = Rope::new();
+        assert_eq!(r.width(), 0);
+        assert_eq!(r.width_of_byte(0), 0);
+        assert_eq!(r.byte_of_width(0), 0);
+    }
+
+    #[test]
+    fn width_1() {
+        let r = Rope::from("日本\tgo\r\n👨‍👩‍👧e\u{301}");
+
+        assert_eq!(r.width(), 4 + 2 + 6 + 1);
+
+        let s = r.byte_slice(3..);
+        assert_eq!(s.width(), 2 + 2 + 6 + 1);
+    }
+
+    #[test]
+    fn width_matches_str() {
+        for s in [CURSED_LIPSUM, TEXT_EMOJI, MEDIUM] {
+            let r = Rope::from(s);
+            assert_eq!(r.width(), width(s));
+
+            let mut width_offset = 0;
+
+            for (offset, ch) in s.char_indices() {
+                if offset % 7 == 0 {
+                    assert_eq!(r.width_of_byte(offset), width_offset);
+                }
+                width_offset += ch.width().unwrap_or(0);
+            }
+        }
+    }
+
+    #[test]
+    fn byte_of_width_0() {
+        let r = Rope::from("a日b");
+
+        assert_eq!(r.byte_of_width(0), 0);
+        assert_eq!(r.byte_of_width(1), 1);
+        assert_eq!(r.byte_of_width(2), 4);
+        assert_eq!(r.byte_of_width(3), 4);
+        assert_eq!(r.byte_of_width(4), 5);
+    }
+
+    #[test]
+    fn byte_of_width_1() {
+        // Zero-width chars after a column stay after the returned offset.
+        let r = Rope::from("ab\ncd");
+
+        assert_eq!(r.byte_of_width(2), 2);
+        assert_eq!(r.byte_of_width(3), 4);
+
+        let s = r.byte_slice(1..);
+        assert_eq!(s.byte_of_width(1), 1);
+        assert_eq!(s.byte_of_width(2), 3);
+    }
+
+    #[should_panic]
+    #[test]
+    fn byte_of_width_2() {
+        let r = Rope::from("a日b");
+        let _ = r.byte_of_width(5);
+    }
+
+    #[should_panic]
+    #[test]
+    fn width_of_byte_0() {
+        let r = Rope::from("a日b");
+        let _ = r.width_of_byte(6);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn width_random() {
+        let mut rng = rand::rng();
+
+        let mut r = Rope::from(CURSED_LIPSUM);
+        let mut s = String::from(CURSED_LIPSUM);
+
+        for _ in 0..50 {
+            let mut start = rng.random_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start -= 1;
+            }
+
+            let mut end = rng.random_range(start..=s.len().min(start + 40));
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            r.replace(start..end, "日\tx");
+            s.replace_range(start..end, "日\tx");
+
+            assert_eq!(r.width(), width(&s));
+
+            let slice = r.byte_slice(start..);
+            let slice_str = &s[start..];
+            assert_eq!(slice.width(), width(slice_str));
+
+            for width_offset in 0..=slice.width().min(64) {
+                let byte_offset = slice.byte_of_width(width_offset);
+
+                // The shortest prefix with at least `width_offset` columns.
+                assert!(width(&slice_str[..byte_offset]) >= width_offset);
+
+                if let Some(ch) = slice_str[..byte_offset].chars().next_back()
+                {
+                    assert!(
+                        width(&slice_str[..byte_offset - ch.len_utf8()])
+                            < width_offset
+                    );
+                }
+
+                assert_eq!(
+                    slice.width_of_byte(byte_offset),
+                    width(&slice_str[..byte_offset])
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+mod common;
+
+#[cfg(feature = "word-count")]
+mod tests {
+    use crop::{Rope, RopeSlice};
+    use rand::Rng;
+
+    use crate::common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+    /// The number of words in `s` computed by splitting its text on
+    /// whitespace.
+    fn word_len(s: RopeSlice<'_>) -> usize {
+        s.to_string().split_whitespace().count()
+    }
+
+    #[test]
+    fn word_len_0() {
+        let r = Rope::new();
+        assert_eq!(r.word_len(), 0);
+        assert_eq!(r.byte_slice(..).word_len(), 0);
+
+        let r = Rope::from(" \t\r\n\u{3000} ");
+        assert_eq!(r.word_len(), 0);
+    }
+
+    #[test]
+    fn word_len_1() {
+        let r = Rope::from("  foo bar\u{3000}baz\n\nqux  ");
+        assert_eq!(r.word_len(), 4);
+        assert_eq!(r.byte_slice(3..).word_len(), 4);
+        assert_eq!(r.byte_slice(5..15).word_len(), 2);
+        assert_eq!(r.byte_slice(5..6).word_len(), 0);
+    }
+
+    #[test]
+    fn word_len_across_chunks() {
+        // Build the rope a piece at a time so that most words are split
+        // between two chunks.
+        let mut r = Rope::new();
+
+        for _ in 0..512 {
+            r.insert(r.byte_len(), "lo");
+            r.insert(r.byte_len(), "rem ip");
+            r.insert(r.byte_len(), "sum ");
+        }
+
+        r.assert_invariants();
+
+        assert_eq!(r.word_len(), 1024);
+        assert_eq!(r.word_len(), word_len(r.byte_slice(..)));
+
+        // Joining two words by deleting the space between them.
+        r.delete(5..6);
+        assert_eq!(r.word_len(), 1023);
+    }
+
+    #[test]
+    fn word_len_matches_split_whitespace() {
+        for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+            let r = Rope::from(s);
+            assert_eq!(r.word_len(), s.split_whitespace().count());
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn word_len_random_slices() {
+        let mut rng = rand::rng();
+
+        let r = Rope::from(MEDIUM);
+
+        for _ in 0..100 {
+            let start = rng.random_range(0..=r.byte_len());
+            let end = rng.random_range(start..=r.byte_len());
+
+            let s = r.byte_slice(start..end);
+            assert_eq!(s.word_len(), word_len(s), "{start}..{end}");
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn word_len_random_edits() {
+        let mut rng = rand::rng();
+
+        let mut r = Rope::from(MEDIUM);
+
+        for _ in 0..100 {
+            let start = rng.random_range(0..=r.byte_len());
+            let end = rng.random_range(start..=r.byte_len().min(start + 200));
+
+            let text = match rng.random_range(0..4) {
+                0 => "",
+                1 => " ",
+                2 => "foo",
+                _ => "consectetur adipiscing\telit\n",
+            };
+
+            r.replace(start..end, text);
+            r.assert_invariants();
+
+            assert_eq!(r.word_len(), word_len(r.byte_slice(..)));
+
+            let s = r.byte_slice(start..);
+            assert_eq!(s.word_len(), word_len(s));
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn word_len_concat() {
+        let mut rng = rand::rng();
+
+        for s in [TINY, SMALL, MEDIUM, LARGE] {
+            for _ in 0..10 {
+                let mut at = rng.random_range(0..=s.len());
+                while !s.is_char_boundary(at) {
+                    at += 1;
+                }
+
+                let r = Rope::from(&s[..at]) + Rope::from(&s[at..]);
+                r.assert_invariants();
+
+                assert_eq!(r.word_len(), word_len(r.byte_slice(..)));
+            }
+        }
+    }
+}
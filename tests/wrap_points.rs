@@ -0,0 +1,145 @@
+mod common;
+
+#[cfg(feature = "display-width")]
+mod tests {
+    use crop::Rope;
+    use rand::{Rng, rng};
+
+    use super::common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+    fn rows(s: &str, width: usize, tab_width: usize) -> Vec<&str> {
+        let r = Rope::from(s);
+        let mut offsets =
+            r.wrap_points(.., width, tab_width).collect::<Vec<_>>();
+        offsets.push(s.len());
+        offsets.windows(2).map(|w| &s[w[0]..w[1]]).collect()
+    }
+
+    #[test]
+    fn wrap_points_empty() {
+        let r = Rope::new();
+        assert_eq!(r.wrap_points(.., 10, 4).count(), 0);
+    }
+
+    #[test]
+    fn wrap_points_at_whitespace() {
+        assert_eq!(
+            rows("lorem ipsum dolor sit amet", 11, 4),
+            ["lorem ipsum ", "dolor sit ", "amet"]
+        );
+    }
+
+    #[test]
+    fn wrap_points_hanging_whitespace() {
+        assert_eq!(rows("lorem      ipsum", 5, 4), ["lorem      ", "ipsum"]);
+    }
+
+    #[test]
+    fn wrap_points_long_word() {
+        assert_eq!(rows("abcdefghij", 4, 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(rows("ab cdefghij", 4, 4), ["ab ", "cdef", "ghij"]);
+    }
+
+    #[test]
+    fn wrap_points_after_line_breaks() {
+        assert_eq!(
+            rows("aaa bbb\n\nccc\r\nddd", 3, 4),
+            ["aaa ", "bbb\n", "\n", "ccc\r\n", "ddd"]
+        );
+    }
+
+    #[test]
+    fn wrap_points_wide_chars() {
+        assert_eq!(
+            rows("日本語のテキスト", 5, 4),
+            ["日本", "語の", "テキ", "スト"]
+        );
+        assert_eq!(
+            rows("e\u{301}e\u{301}e\u{301}", 2, 4),
+            ["e\u{301}e\u{301}", "e\u{301}"]
+        );
+    }
+
+    #[test]
+    fn wrap_points_tabs() {
+        assert_eq!(rows("a\tbb\tc", 8, 4), ["a\tbb\t", "c"]);
+        assert_eq!(rows("\tabcd", 6, 4), ["\t", "abcd"]);
+        assert_eq!(rows("\tabcd", 6, 0), ["\tabcd"]);
+    }
+
+    #[test]
+    fn wrap_points_line_range() {
+        let r = Rope::from("aaa bbb\nccc ddd\neee fff\n");
+
+        assert_eq!(r.wrap_points(1..2, 4, 4).collect::<Vec<_>>(), [8, 12]);
+
+        let s = r.byte_slice(4..);
+
+        assert_eq!(
+            s.wrap_points(1.., 4, 4).collect::<Vec<_>>(),
+            [4, 8, 12, 16]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrap_points_zero_width() {
+        let _ = Rope::from("foo").wrap_points(.., 0, 4);
+    }
+
+    /// Checks that every row fits in `width` columns, except for hanging
+    /// whitespace and single chars wider than `width`, and that the offsets
+    /// don't depend on how the text is chunked.
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn wrap_points_random() {
+        use unicode_width::UnicodeWidthChar;
+
+        let mut rng = rng();
+
+        for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+            let r = Rope::from(s);
+
+            for _ in 0..10 {
+                let width = rng.random_range(1..100);
+
+                let offsets = r.wrap_points(.., width, 4).collect::<Vec<_>>();
+
+                let mut lines = 0;
+
+                for (idx, &start) in offsets.iter().enumerate() {
+                    let end = offsets.get(idx + 1).copied().unwrap_or(s.len());
+                    let row = &s[start..end];
+
+                    if start == 0 || s.as_bytes()[start - 1] == b'\n' {
+                        lines += 1;
+                    }
+
+                    let row_width = row
+                        .trim_end()
+                        .chars()
+                        .map(|ch| ch.width().unwrap_or(0))
+                        .sum::<usize>();
+
+                    assert!(
+                        row_width <= width
+                            || row.trim_end().chars().count() == 1,
+                        "{row:?} is wider than {width}"
+                    );
+                }
+
+                assert_eq!(lines, r.line_len());
+
+                let mut other = Rope::new();
+                for word in s.split_inclusive(' ') {
+                    other.insert(other.byte_len(), word);
+                }
+
+                assert_eq!(
+                    other.wrap_points(.., width, 4).collect::<Vec<_>>(),
+                    offsets
+                );
+            }
+        }
+    }
+}